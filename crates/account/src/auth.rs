@@ -28,6 +28,10 @@ pub struct AccountPublicKeysMap {
     pub pk_to_idx: HashMap<common::PublicKey, u8>,
     /// Hashmap from index key to public key
     pub idx_to_pk: HashMap<u8, common::PublicKey>,
+    /// Hashmap from index key to the weight of the public key. Keys without
+    /// an entry have a weight of one.
+    #[serde(default)]
+    pub idx_to_weight: HashMap<u8, u8>,
 }
 
 impl FromIterator<common::PublicKey> for AccountPublicKeysMap {
@@ -56,6 +60,7 @@ impl FromIterator<common::PublicKey> for AccountPublicKeysMap {
         Self {
             pk_to_idx,
             idx_to_pk,
+            idx_to_weight: HashMap::new(),
         }
     }
 }
@@ -77,6 +82,31 @@ impl AccountPublicKeysMap {
         self.pk_to_idx.get(public_key).cloned()
     }
 
+    /// Set the weights of the public keys by their index
+    pub fn with_weights(
+        mut self,
+        weights: impl IntoIterator<Item = (u8, u8)>,
+    ) -> Self {
+        self.idx_to_weight = weights.into_iter().collect();
+        self
+    }
+
+    /// Retrieve the weight of the public key at the given index
+    pub fn get_weight_from_index(&self, index: u8) -> u8 {
+        self.idx_to_weight.get(&index).copied().unwrap_or(1)
+    }
+
+    /// Sum up the weights of the public keys at the given indices
+    pub fn total_weight<'a>(
+        &self,
+        indices: impl IntoIterator<Item = &'a u8>,
+    ) -> u32 {
+        indices
+            .into_iter()
+            .map(|index| u32::from(self.get_weight_from_index(*index)))
+            .sum()
+    }
+
     /// Index the given set of secret keys
     pub fn index_secret_keys(
         &self,
@@ -20,9 +20,11 @@
 )]
 
 mod auth;
+mod policy;
 mod storage;
 mod storage_key;
 mod types;
+mod vesting;
 
 pub use auth::AccountPublicKeysMap;
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -33,10 +35,12 @@ pub use namada_core::storage::Key;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
+pub use policy::*;
 use serde::{Deserialize, Serialize};
 pub use storage::*;
 pub use storage_key::*;
 pub use types::*;
+pub use vesting::*;
 
 #[derive(
     Debug,
@@ -0,0 +1,363 @@
+//! Signing policies of an account: weights of its public keys, signature
+//! thresholds of specific actions and token debit limits.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::{self, DbKeySeg, KeySeg};
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use serde::{Deserialize, Serialize};
+
+/// An action on an account that can be given its own signature threshold
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccountAction {
+    /// Bonding tokens to a validator
+    Bond,
+    /// Unbonding tokens from a validator
+    Unbond,
+    /// Withdrawing unbonded tokens
+    Withdraw,
+    /// Redelegating bonded tokens
+    Redelegate,
+    /// Claiming PoS rewards
+    ClaimRewards,
+    /// Becoming a validator and managing its state, commission, metadata
+    /// and consensus key
+    ValidatorManagement,
+    /// Governance proposals and votes
+    Governance,
+    /// PGF stewardship
+    Pgf,
+    /// Authorizing shielded transactions
+    Masp,
+    /// Changing the account's VP, public keys, threshold or policy
+    UpdateAccount,
+    /// Debiting tokens from the account
+    Debit,
+}
+
+impl AccountAction {
+    /// All the account actions
+    pub const ALL: [AccountAction; 11] = [
+        Self::Bond,
+        Self::Unbond,
+        Self::Withdraw,
+        Self::Redelegate,
+        Self::ClaimRewards,
+        Self::ValidatorManagement,
+        Self::Governance,
+        Self::Pgf,
+        Self::Masp,
+        Self::UpdateAccount,
+        Self::Debit,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bond => "bond",
+            Self::Unbond => "unbond",
+            Self::Withdraw => "withdraw",
+            Self::Redelegate => "redelegate",
+            Self::ClaimRewards => "claim-rewards",
+            Self::ValidatorManagement => "validator-management",
+            Self::Governance => "governance",
+            Self::Pgf => "pgf",
+            Self::Masp => "masp",
+            Self::UpdateAccount => "update-account",
+            Self::Debit => "debit",
+        }
+    }
+}
+
+impl Display for AccountAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AccountAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| format!("Unknown account action: {s}"))
+    }
+}
+
+impl KeySeg for AccountAction {
+    fn parse(string: String) -> storage::Result<Self> {
+        Self::from_str(&string).map_err(storage::Error::ParseKeySeg)
+    }
+
+    fn raw(&self) -> String {
+        self.to_string()
+    }
+
+    fn to_db_key(&self) -> DbKeySeg {
+        DbKeySeg::StringSeg(self.raw())
+    }
+}
+
+/// A limit above which debits of a token require a higher threshold
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct DebitLimit {
+    /// The largest amount that can be debited in a single transaction with
+    /// the regular [`AccountAction::Debit`] threshold
+    pub amount: token::Amount,
+    /// The threshold required to debit more than the `amount`
+    pub threshold: u8,
+}
+
+/// The signing policy of an account
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct AccountPolicy {
+    /// The weights of the account's public keys by their index. Keys without
+    /// a weight count as one.
+    pub key_weights: BTreeMap<u8, u8>,
+    /// The thresholds of specific actions. Actions without a threshold use
+    /// the account's threshold.
+    pub action_thresholds: BTreeMap<AccountAction, u8>,
+    /// Debit limits by token
+    pub debit_limits: BTreeMap<Address, DebitLimit>,
+}
+
+/// Account policy validation errors
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    UnknownKeyIndex(u8),
+    ZeroThreshold,
+    UnreachableThreshold { threshold: u8, total_weight: u32 },
+}
+
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownKeyIndex(index) => {
+                write!(f, "There is no public key with index {index}")
+            }
+            Self::ZeroThreshold => write!(
+                f,
+                "The action and debit limit thresholds must be greater than \
+                 zero"
+            ),
+            Self::UnreachableThreshold {
+                threshold,
+                total_weight,
+            } => write!(
+                f,
+                "The threshold {threshold} is greater than the total weight \
+                 {total_weight} of the account's public keys"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl AccountPolicy {
+    /// Check if the policy doesn't set anything
+    pub fn is_empty(&self) -> bool {
+        self.key_weights.is_empty()
+            && self.action_thresholds.is_empty()
+            && self.debit_limits.is_empty()
+    }
+
+    /// The total weight of an account's public keys under this policy
+    pub fn total_weight(&self, num_public_keys: usize) -> u32 {
+        (0..num_public_keys)
+            .map(|index| {
+                u8::try_from(index)
+                    .ok()
+                    .and_then(|index| self.key_weights.get(&index))
+                    .copied()
+                    .map(u32::from)
+                    .unwrap_or(1)
+            })
+            .sum()
+    }
+
+    /// The threshold of the given action under this policy. Falls back to
+    /// the account's `threshold` if the action doesn't have its own.
+    pub fn action_threshold(&self, action: AccountAction, threshold: u8) -> u8 {
+        self.action_thresholds
+            .get(&action)
+            .copied()
+            .unwrap_or(threshold)
+    }
+
+    /// The threshold of debiting the given amount of a token under this
+    /// policy, given the account's `threshold`
+    pub fn debit_threshold(
+        &self,
+        token: &Address,
+        amount: token::Amount,
+        threshold: u8,
+    ) -> u8 {
+        let threshold = self.action_threshold(AccountAction::Debit, threshold);
+        match self.debit_limits.get(token) {
+            Some(limit) if amount > limit.amount => {
+                std::cmp::max(threshold, limit.threshold)
+            }
+            _ => threshold,
+        }
+    }
+
+    /// Validate the policy against the number of the account's public keys
+    /// and its threshold
+    pub fn validate(
+        &self,
+        num_public_keys: usize,
+        threshold: u8,
+    ) -> Result<(), PolicyError> {
+        if let Some(index) = self
+            .key_weights
+            .keys()
+            .find(|index| usize::from(**index) >= num_public_keys)
+        {
+            return Err(PolicyError::UnknownKeyIndex(*index));
+        }
+        let policy_thresholds = self
+            .action_thresholds
+            .values()
+            .copied()
+            .chain(self.debit_limits.values().map(|limit| limit.threshold));
+        if policy_thresholds.clone().any(|threshold| threshold == 0) {
+            return Err(PolicyError::ZeroThreshold);
+        }
+        let total_weight = self.total_weight(num_public_keys);
+        for threshold in std::iter::once(threshold).chain(policy_thresholds) {
+            if u32::from(threshold) > total_weight {
+                return Err(PolicyError::UnreachableThreshold {
+                    threshold,
+                    total_weight,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::address::testing::nam;
+
+    use super::*;
+
+    #[test]
+    fn test_account_action_key_seg_roundtrip() {
+        for action in AccountAction::ALL {
+            assert_eq!(AccountAction::parse(action.raw()).unwrap(), action);
+        }
+        assert!(AccountAction::parse("transfer".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let policy = AccountPolicy {
+            key_weights: BTreeMap::from([(0, 3)]),
+            action_thresholds: BTreeMap::from([(
+                AccountAction::ClaimRewards,
+                1,
+            )]),
+            debit_limits: BTreeMap::from([(
+                nam(),
+                DebitLimit {
+                    amount: token::Amount::native_whole(100),
+                    threshold: 5,
+                },
+            )]),
+        };
+        assert_eq!(policy.total_weight(3), 5);
+        assert!(policy.validate(3, 4).is_ok());
+        assert_eq!(
+            policy.validate(2, 4),
+            Err(PolicyError::UnreachableThreshold {
+                threshold: 5,
+                total_weight: 4
+            })
+        );
+        assert_eq!(policy.validate(0, 0), Err(PolicyError::UnknownKeyIndex(0)));
+
+        let zero_threshold = AccountPolicy {
+            action_thresholds: BTreeMap::from([(AccountAction::Bond, 0)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            zero_threshold.validate(1, 1),
+            Err(PolicyError::ZeroThreshold)
+        );
+    }
+
+    #[test]
+    fn test_policy_thresholds() {
+        let limit = DebitLimit {
+            amount: token::Amount::native_whole(100),
+            threshold: 3,
+        };
+        let policy = AccountPolicy {
+            action_thresholds: BTreeMap::from([
+                (AccountAction::ClaimRewards, 1),
+                (AccountAction::Debit, 2),
+            ]),
+            debit_limits: BTreeMap::from([(nam(), limit)]),
+            ..Default::default()
+        };
+        assert_eq!(policy.action_threshold(AccountAction::ClaimRewards, 2), 1);
+        assert_eq!(policy.action_threshold(AccountAction::Bond, 2), 2);
+        assert_eq!(policy.debit_threshold(&nam(), limit.amount, 1), 2);
+        assert_eq!(
+            policy.debit_threshold(&nam(), token::Amount::native_whole(101), 1),
+            3
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Cryptographic signature keys storage API
 
-use namada_core::storage;
+use namada_core::chain::Epoch;
+use namada_core::{storage, token};
 use namada_storage::{Result, ResultExt, StorageRead, StorageWrite};
 
 use super::*;
@@ -75,8 +76,11 @@ where
     S: StorageRead,
 {
     let public_keys = public_keys(storage, owner)?;
+    let weights = key_weights_handle(owner)
+        .iter(storage)?
+        .collect::<Result<Vec<_>>>()?;
 
-    Ok(AccountPublicKeysMap::from_iter(public_keys))
+    Ok(AccountPublicKeysMap::from_iter(public_keys).with_weights(weights))
 }
 
 /// Check if a user account exists in storage
@@ -120,3 +124,192 @@ where
     }
     Ok(())
 }
+
+/// Write the vesting schedules of a new account. A schedule of a token that
+/// appears more than once overrides the previous ones.
+pub fn init_vesting_schedules<S>(
+    storage: &mut S,
+    owner: &Address,
+    schedules: &[VestingSchedule],
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    for schedule in schedules {
+        schedule.validate().into_storage_result()?;
+        vesting_handle(owner).insert(
+            storage,
+            schedule.token.clone(),
+            schedule.clone(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Get the vesting schedules associated with an account
+pub fn vesting_schedules<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Vec<VestingSchedule>>
+where
+    S: StorageRead,
+{
+    vesting_handle(owner)
+        .iter(storage)?
+        .map(|res| res.map(|(_token, schedule)| schedule))
+        .collect()
+}
+
+/// Get the amount of the given token that is locked by the account's vesting
+/// schedule at the given epoch. Zero if the account has no schedule for the
+/// token.
+pub fn locked_amount<S>(
+    storage: &S,
+    owner: &Address,
+    token: &Address,
+    epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead,
+{
+    Ok(vesting_handle(owner)
+        .get(storage, token)?
+        .map(|schedule| schedule.locked_at(epoch))
+        .unwrap_or_default())
+}
+
+/// Replace the signing policy of an account. The policy must be valid for the
+/// account's public keys and threshold, so these must be written first.
+pub fn write_account_policy<S>(
+    storage: &mut S,
+    owner: &Address,
+    policy: &AccountPolicy,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    validate_account_policy(storage, owner, policy)?;
+    clear_account_policy(storage, owner)?;
+    for (index, weight) in &policy.key_weights {
+        key_weights_handle(owner).insert(storage, *index, *weight)?;
+    }
+    for (action, threshold) in &policy.action_thresholds {
+        action_thresholds_handle(owner).insert(storage, *action, *threshold)?;
+    }
+    for (token, limit) in &policy.debit_limits {
+        debit_limits_handle(owner).insert(storage, token.clone(), *limit)?;
+    }
+    Ok(())
+}
+
+/// Check that a signing policy is valid for the public keys and the threshold
+/// of an account
+pub fn validate_account_policy<S>(
+    storage: &S,
+    owner: &Address,
+    policy: &AccountPolicy,
+) -> Result<()>
+where
+    S: StorageRead,
+{
+    let num_public_keys = pks_handle(owner).len(storage)?;
+    let num_public_keys =
+        usize::try_from(num_public_keys).into_storage_result()?;
+    let threshold = threshold(storage, owner)?.unwrap_or(1);
+    policy
+        .validate(num_public_keys, threshold)
+        .into_storage_result()
+}
+
+/// Clear the signing policy of an account
+pub fn clear_account_policy<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    clear_key_weights(storage, owner)?;
+    let actions = action_thresholds_handle(owner)
+        .iter(storage)?
+        .map(|res| res.map(|(action, _threshold)| action))
+        .collect::<Result<Vec<_>>>()?;
+    for action in actions {
+        action_thresholds_handle(owner).remove(storage, &action)?;
+    }
+    let tokens = debit_limits_handle(owner)
+        .iter(storage)?
+        .map(|res| res.map(|(token, _limit)| token))
+        .collect::<Result<Vec<_>>>()?;
+    for token in tokens {
+        debit_limits_handle(owner).remove(storage, &token)?;
+    }
+    Ok(())
+}
+
+/// Clear the weights of an account's public keys
+pub fn clear_key_weights<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let indices = key_weights_handle(owner)
+        .iter(storage)?
+        .map(|res| res.map(|(index, _weight)| index))
+        .collect::<Result<Vec<_>>>()?;
+    for index in indices {
+        key_weights_handle(owner).remove(storage, &index)?;
+    }
+    Ok(())
+}
+
+/// Get the signing policy associated with an account
+pub fn account_policy<S>(storage: &S, owner: &Address) -> Result<AccountPolicy>
+where
+    S: StorageRead,
+{
+    Ok(AccountPolicy {
+        key_weights: key_weights_handle(owner)
+            .iter(storage)?
+            .collect::<Result<_>>()?,
+        action_thresholds: action_thresholds_handle(owner)
+            .iter(storage)?
+            .collect::<Result<_>>()?,
+        debit_limits: debit_limits_handle(owner)
+            .iter(storage)?
+            .collect::<Result<_>>()?,
+    })
+}
+
+/// Get the signature threshold required to authorize the given action on an
+/// account. Falls back to the account's threshold if the action doesn't have
+/// its own.
+pub fn action_threshold<S>(
+    storage: &S,
+    owner: &Address,
+    action: AccountAction,
+) -> Result<u8>
+where
+    S: StorageRead,
+{
+    match action_thresholds_handle(owner).get(storage, &action)? {
+        Some(threshold) => Ok(threshold),
+        None => Ok(threshold(storage, owner)?.unwrap_or(1)),
+    }
+}
+
+/// Get the signature threshold required to debit the given amount of a token
+/// from an account
+pub fn debit_threshold<S>(
+    storage: &S,
+    owner: &Address,
+    token: &Address,
+    amount: token::Amount,
+) -> Result<u8>
+where
+    S: StorageRead,
+{
+    let threshold = action_threshold(storage, owner, AccountAction::Debit)?;
+    Ok(match debit_limits_handle(owner).get(storage, token)? {
+        Some(limit) if amount > limit.amount => {
+            std::cmp::max(threshold, limit.threshold)
+        }
+        _ => threshold,
+    })
+}
@@ -5,12 +5,18 @@ use namada_macros::StorageKeys;
 use namada_storage::collections::lazy_map::LazyMap;
 use namada_storage::collections::{lazy_map, LazyCollection};
 
+use crate::{AccountAction, DebitLimit, VestingSchedule};
+
 /// Storage keys for account.
 #[derive(StorageKeys)]
 struct Keys {
     public_keys: &'static str,
     threshold: &'static str,
     protocol_public_keys: &'static str,
+    vesting: &'static str,
+    key_weights: &'static str,
+    action_thresholds: &'static str,
+    debit_limits: &'static str,
 }
 
 /// Obtain a storage key for user's public key.
@@ -89,3 +95,73 @@ pub fn is_protocol_pk_key(key: &storage::Key) -> Option<&Address> {
         _ => None,
     }
 }
+
+/// Obtain the storage key prefix for the vesting schedules of an account.
+pub fn vesting_key_prefix(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.vesting.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the vesting schedules of an account, keyed by token
+pub fn vesting_handle(owner: &Address) -> LazyMap<Address, VestingSchedule> {
+    LazyMap::open(vesting_key_prefix(owner))
+}
+
+/// Check if the given storage key is a part of an account's vesting schedules.
+/// If it is, returns the owner.
+pub fn is_vesting_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(prefix), ..]
+            if prefix.as_str() == Keys::VALUES.vesting =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+fn owner_key_prefix(owner: &Address, prefix: &str) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(prefix.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the weights of an account's public keys, keyed by the
+/// index of the public key
+pub fn key_weights_handle(owner: &Address) -> LazyMap<u8, u8> {
+    LazyMap::open(owner_key_prefix(owner, Keys::VALUES.key_weights))
+}
+
+/// LazyMap handler for the signature thresholds of specific actions on an
+/// account
+pub fn action_thresholds_handle(owner: &Address) -> LazyMap<AccountAction, u8> {
+    LazyMap::open(owner_key_prefix(owner, Keys::VALUES.action_thresholds))
+}
+
+/// LazyMap handler for the debit limits of an account, keyed by token
+pub fn debit_limits_handle(owner: &Address) -> LazyMap<Address, DebitLimit> {
+    LazyMap::open(owner_key_prefix(owner, Keys::VALUES.debit_limits))
+}
+
+/// Check if the given storage key is a part of an account's signing policy
+/// (key weights, action thresholds or debit limits). If it is, returns the
+/// owner.
+pub fn is_policy_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(prefix), ..]
+            if prefix.as_str() == Keys::VALUES.key_weights
+                || prefix.as_str() == Keys::VALUES.action_thresholds
+                || prefix.as_str() == Keys::VALUES.debit_limits =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
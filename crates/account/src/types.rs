@@ -7,6 +7,8 @@ use namada_macros::BorshDeserializer;
 use namada_migrations::*;
 use serde::{Deserialize, Serialize};
 
+use crate::{AccountPolicy, VestingSchedule};
+
 /// A tx data type to initialize a new established account
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
@@ -29,6 +31,11 @@ pub struct InitAccount {
     pub vp_code_hash: Hash,
     /// The account signature threshold
     pub threshold: u8,
+    /// Vesting schedules locking a part of the account's balances. These can
+    /// only be set when the account is created.
+    pub vesting: Vec<VestingSchedule>,
+    /// The signing policy of the account
+    pub policy: Option<AccountPolicy>,
 }
 
 /// A tx data type to update an account's validity predicate
@@ -55,6 +62,9 @@ pub struct UpdateAccount {
     pub public_keys: Vec<common::PublicKey>,
     /// The account signature threshold
     pub threshold: Option<u8>,
+    /// The new signing policy of the account. If the public keys are updated
+    /// without a new policy, the weights of the previous keys are cleared.
+    pub policy: Option<AccountPolicy>,
 }
 
 #[allow(clippy::cast_possible_truncation)]
@@ -82,6 +92,8 @@ pub mod tests {
                 public_keys,
                 vp_code_hash,
                 threshold,
+                vesting: vec![],
+                policy: None,
             }
         }
     }
@@ -101,6 +113,7 @@ pub mod tests {
                 vp_code_hash,
                 public_keys,
                 threshold,
+                policy: None,
             }
         }
     }
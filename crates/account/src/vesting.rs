@@ -0,0 +1,246 @@
+//! Vesting schedules that keep a part of an account's balance locked until it
+//! gets released over time.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::chain::Epoch;
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use serde::{Deserialize, Serialize};
+
+/// The way in which the amount of a [`VestingSchedule`] gets released.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum VestingKind {
+    /// The whole amount is released at once at the `unlock` epoch
+    Cliff {
+        /// The first epoch in which the amount is spendable
+        unlock: Epoch,
+    },
+    /// The amount is released linearly between the `start` and `end` epochs.
+    /// Nothing is spendable before the `cliff` epoch, at which point
+    /// everything vested since `start` gets released.
+    Linear {
+        /// The epoch from which the amount starts vesting
+        start: Epoch,
+        /// The first epoch in which the vested amount is spendable
+        cliff: Epoch,
+        /// The epoch from which the whole amount is spendable
+        end: Epoch,
+    },
+    /// A fixed amount is released in every epoch starting from `start`
+    PerEpoch {
+        /// The first epoch in which an amount is released
+        start: Epoch,
+        /// The amount released in every epoch
+        amount: token::Amount,
+    },
+}
+
+/// A vesting schedule of a single token held by an account
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VestingSchedule {
+    /// The vested token
+    pub token: Address,
+    /// The total amount subject to vesting
+    pub amount: token::Amount,
+    /// How the amount gets released
+    pub kind: VestingKind,
+}
+
+/// Vesting schedule validation errors
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VestingError {
+    ZeroAmount,
+    InvalidLinearBounds,
+    ZeroAmountPerEpoch,
+}
+
+impl std::fmt::Display for VestingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroAmount => {
+                write!(f, "The vested amount must be greater than zero")
+            }
+            Self::InvalidLinearBounds => write!(
+                f,
+                "A linear vesting schedule requires start <= cliff <= end and \
+                 start < end"
+            ),
+            Self::ZeroAmountPerEpoch => write!(
+                f,
+                "The amount released per epoch must be greater than zero"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VestingError {}
+
+impl VestingSchedule {
+    /// Check that the schedule is well-formed
+    pub fn validate(&self) -> Result<(), VestingError> {
+        if self.amount.is_zero() {
+            return Err(VestingError::ZeroAmount);
+        }
+        match &self.kind {
+            VestingKind::Cliff { .. } => Ok(()),
+            VestingKind::Linear { start, cliff, end } => {
+                if start <= cliff && cliff <= end && start < end {
+                    Ok(())
+                } else {
+                    Err(VestingError::InvalidLinearBounds)
+                }
+            }
+            VestingKind::PerEpoch { amount, .. } => {
+                if amount.is_zero() {
+                    Err(VestingError::ZeroAmountPerEpoch)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Get the amount that is still locked at the given epoch
+    pub fn locked_at(&self, epoch: Epoch) -> token::Amount {
+        self.amount
+            .checked_sub(self.released_at(epoch))
+            .unwrap_or_default()
+    }
+
+    /// Get the amount that has been released by the given epoch. On overflow,
+    /// nothing is considered released.
+    pub fn released_at(&self, epoch: Epoch) -> token::Amount {
+        match &self.kind {
+            VestingKind::Cliff { unlock } => {
+                if epoch >= *unlock {
+                    self.amount
+                } else {
+                    token::Amount::zero()
+                }
+            }
+            VestingKind::Linear { start, cliff, end } => {
+                if epoch < *cliff {
+                    token::Amount::zero()
+                } else if epoch >= *end {
+                    self.amount
+                } else {
+                    let elapsed = epoch.0.saturating_sub(start.0);
+                    let duration = end.0.saturating_sub(start.0);
+                    self.amount
+                        .checked_mul(token::Amount::from_u64(elapsed))
+                        .and_then(|vested| vested.checked_div_u64(duration))
+                        .unwrap_or_default()
+                }
+            }
+            VestingKind::PerEpoch { start, amount } => {
+                if epoch < *start {
+                    token::Amount::zero()
+                } else {
+                    let periods =
+                        epoch.0.saturating_sub(start.0).saturating_add(1);
+                    amount
+                        .checked_mul(token::Amount::from_u64(periods))
+                        .map(|released| std::cmp::min(released, self.amount))
+                        .unwrap_or_default()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::address::testing::nam;
+
+    use super::*;
+
+    fn schedule(kind: VestingKind) -> VestingSchedule {
+        VestingSchedule {
+            token: nam(),
+            amount: token::Amount::native_whole(100),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_cliff_vesting() {
+        let schedule = schedule(VestingKind::Cliff { unlock: Epoch(10) });
+        assert!(schedule.validate().is_ok());
+        assert_eq!(schedule.locked_at(Epoch(0)), schedule.amount);
+        assert_eq!(schedule.locked_at(Epoch(9)), schedule.amount);
+        assert_eq!(schedule.locked_at(Epoch(10)), token::Amount::zero());
+    }
+
+    #[test]
+    fn test_linear_vesting() {
+        let schedule = schedule(VestingKind::Linear {
+            start: Epoch(10),
+            cliff: Epoch(12),
+            end: Epoch(20),
+        });
+        assert!(schedule.validate().is_ok());
+        assert_eq!(schedule.locked_at(Epoch(5)), schedule.amount);
+        assert_eq!(schedule.locked_at(Epoch(11)), schedule.amount);
+        assert_eq!(
+            schedule.locked_at(Epoch(12)),
+            token::Amount::native_whole(80)
+        );
+        assert_eq!(
+            schedule.locked_at(Epoch(15)),
+            token::Amount::native_whole(50)
+        );
+        assert_eq!(schedule.locked_at(Epoch(20)), token::Amount::zero());
+
+        let invalid = self::schedule(VestingKind::Linear {
+            start: Epoch(10),
+            cliff: Epoch(5),
+            end: Epoch(20),
+        });
+        assert_eq!(invalid.validate(), Err(VestingError::InvalidLinearBounds));
+    }
+
+    #[test]
+    fn test_per_epoch_vesting() {
+        let schedule = schedule(VestingKind::PerEpoch {
+            start: Epoch(3),
+            amount: token::Amount::native_whole(30),
+        });
+        assert!(schedule.validate().is_ok());
+        assert_eq!(schedule.locked_at(Epoch(2)), schedule.amount);
+        assert_eq!(
+            schedule.locked_at(Epoch(3)),
+            token::Amount::native_whole(70)
+        );
+        assert_eq!(
+            schedule.locked_at(Epoch(5)),
+            token::Amount::native_whole(10)
+        );
+        assert_eq!(schedule.locked_at(Epoch(6)), token::Amount::zero());
+    }
+}
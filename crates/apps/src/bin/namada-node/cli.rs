@@ -58,6 +58,11 @@ pub fn main() -> Result<()> {
                 node::rollback(chain_ctx.config.ledger)
                     .wrap_err("Failed to rollback the Namada node")?;
             }
+            cmds::Ledger::PruneArchive(cmds::LedgerPruneArchive(args)) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                node::prune_archive(chain_ctx.config.ledger, args)
+                    .wrap_err("Failed to prune the Namada node archive")?;
+            }
             cmds::Ledger::UpdateDB(cmds::LedgerUpdateDB(args)) => {
                 #[cfg(not(feature = "migrations"))]
                 {
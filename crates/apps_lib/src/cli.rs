@@ -258,6 +258,7 @@ pub mod cmds {
                 .subcommand(ClaimRewards::def().display_order(2))
                 .subcommand(TxCommissionRateChange::def().display_order(2))
                 .subcommand(TxChangeConsensusKey::def().display_order(2))
+                .subcommand(TxChangeValidatorKeys::def().display_order(2))
                 .subcommand(TxMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
@@ -276,6 +277,7 @@ pub mod cmds {
                 .subcommand(QueryBonds::def().display_order(5))
                 .subcommand(QueryBondedStake::def().display_order(5))
                 .subcommand(QuerySlashes::def().display_order(5))
+                .subcommand(QuerySlashProjection::def().display_order(5))
                 .subcommand(QueryDelegations::def().display_order(5))
                 .subcommand(QueryFindValidator::def().display_order(5))
                 .subcommand(QueryResult::def().display_order(5))
@@ -295,6 +297,8 @@ pub mod cmds {
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
                 .subcommand(ShieldedSync::def().display_order(6))
+                .subcommand(ShieldedHistory::def().display_order(6))
+                .subcommand(ShieldedConsolidate::def().display_order(6))
                 .subcommand(GenIbcShieldingTransfer::def().display_order(6))
                 // Utils
                 .subcommand(ClientUtils::def().display_order(7))
@@ -338,6 +342,8 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, TxCommissionRateChange);
             let tx_change_consensus_key =
                 Self::parse_with_ctx(matches, TxChangeConsensusKey);
+            let tx_change_validator_keys =
+                Self::parse_with_ctx(matches, TxChangeValidatorKeys);
             let tx_change_metadata =
                 Self::parse_with_ctx(matches, TxMetadataChange);
             let bond = Self::parse_with_ctx(matches, Bond);
@@ -360,6 +366,8 @@ pub mod cmds {
             let query_bonded_stake =
                 Self::parse_with_ctx(matches, QueryBondedStake);
             let query_slashes = Self::parse_with_ctx(matches, QuerySlashes);
+            let query_slash_projection =
+                Self::parse_with_ctx(matches, QuerySlashProjection);
             let query_rewards = Self::parse_with_ctx(matches, QueryRewards);
             let query_delegations =
                 Self::parse_with_ctx(matches, QueryDelegations);
@@ -390,6 +398,10 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
             let shielded_sync = Self::parse_with_ctx(matches, ShieldedSync);
+            let shielded_history =
+                Self::parse_with_ctx(matches, ShieldedHistory);
+            let shielded_consolidate =
+                Self::parse_with_ctx(matches, ShieldedConsolidate);
             let gen_ibc_shielding =
                 Self::parse_with_ctx(matches, GenIbcShieldingTransfer);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
@@ -408,6 +420,7 @@ pub mod cmds {
                 .or(tx_init_validator)
                 .or(tx_commission_rate_change)
                 .or(tx_change_consensus_key)
+                .or(tx_change_validator_keys)
                 .or(tx_change_metadata)
                 .or(tx_unjail_validator)
                 .or(tx_deactivate_validator)
@@ -430,6 +443,7 @@ pub mod cmds {
                 .or(query_bonds)
                 .or(query_bonded_stake)
                 .or(query_slashes)
+                .or(query_slash_projection)
                 .or(query_rewards)
                 .or(query_delegations)
                 .or(query_find_validator)
@@ -449,6 +463,8 @@ pub mod cmds {
                 .or(query_account)
                 .or(sign_tx)
                 .or(shielded_sync)
+                .or(shielded_history)
+                .or(shielded_consolidate)
                 .or(gen_ibc_shielding)
                 .or(utils)
         }
@@ -500,6 +516,7 @@ pub mod cmds {
         TxInitValidator(TxInitValidator),
         TxCommissionRateChange(TxCommissionRateChange),
         TxChangeConsensusKey(TxChangeConsensusKey),
+        TxChangeValidatorKeys(TxChangeValidatorKeys),
         TxMetadataChange(TxMetadataChange),
         TxUnjailValidator(TxUnjailValidator),
         TxDeactivateValidator(TxDeactivateValidator),
@@ -528,6 +545,7 @@ pub mod cmds {
         QueryCommissionRate(QueryCommissionRate),
         QueryMetaData(QueryMetaData),
         QuerySlashes(QuerySlashes),
+        QuerySlashProjection(QuerySlashProjection),
         QueryDelegations(QueryDelegations),
         QueryTotalSupply(QueryTotalSupply),
         QueryEffNativeSupply(QueryEffNativeSupply),
@@ -543,6 +561,8 @@ pub mod cmds {
         QueryRewards(QueryRewards),
         SignTx(SignTx),
         ShieldedSync(ShieldedSync),
+        ShieldedHistory(ShieldedHistory),
+        ShieldedConsolidate(ShieldedConsolidate),
         GenIbcShieldingTransfer(GenIbcShieldingTransfer),
     }
 
@@ -891,6 +911,7 @@ pub mod cmds {
         UpdateDB(LedgerUpdateDB),
         QueryDB(LedgerQueryDB),
         RollBack(LedgerRollBack),
+        PruneArchive(LedgerPruneArchive),
     }
 
     impl SubCmd for Ledger {
@@ -904,12 +925,15 @@ pub mod cmds {
                 let update_db = SubCmd::parse(matches).map(Self::UpdateDB);
                 let query_db = SubCmd::parse(matches).map(Self::QueryDB);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
+                let prune_archive =
+                    SubCmd::parse(matches).map(Self::PruneArchive);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
                 run.or(reset)
                     .or(dump_db)
                     .or(update_db)
                     .or(query_db)
                     .or(rollback)
+                    .or(prune_archive)
                     .or(run_until)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
@@ -934,6 +958,7 @@ pub mod cmds {
                 .subcommand(LedgerUpdateDB::def())
                 .subcommand(LedgerQueryDB::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerPruneArchive::def())
         }
     }
 
@@ -1082,6 +1107,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerPruneArchive(pub args::LedgerPruneArchive);
+
+    impl SubCmd for LedgerPruneArchive {
+        const CMD: &'static str = "prune-archive";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerPruneArchive::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Prune the storage values archived by a node in archival \
+                     mode that are older than a given height, while the \
+                     ledger is not running."
+                ))
+                .add_args::<args::LedgerPruneArchive>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1612,6 +1660,55 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ShieldedHistory(pub args::ShieldedHistory<args::CliTypes>);
+
+    impl SubCmd for ShieldedHistory {
+        const CMD: &'static str = "shielded-history";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                ShieldedHistory(args::ShieldedHistory::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Show the history of the shielded txs of a viewing key, \
+                     as recorded by the local shielded context. The context \
+                     should be synced first."
+                ))
+                .add_args::<args::ShieldedHistory<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ShieldedConsolidate(
+        pub args::ShieldedConsolidate<args::CliTypes>,
+    );
+
+    impl SubCmd for ShieldedConsolidate {
+        const CMD: &'static str = "shielded-consolidate";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                ShieldedConsolidate(args::ShieldedConsolidate::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Merge the unspent notes of a spending key into one note \
+                     per token with a chain of shielded self-transfers, each \
+                     within the gas limits. The context should be synced \
+                     first."
+                ))
+                .add_args::<args::ShieldedConsolidate<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Bond(pub args::Bond<args::CliTypes>);
 
@@ -2015,6 +2112,33 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QuerySlashProjection(
+        pub args::QuerySlashProjection<args::CliTypes>,
+    );
+
+    impl SubCmd for QuerySlashProjection {
+        const CMD: &'static str = "slash-projection";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QuerySlashProjection(args::QuerySlashProjection::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Query the projected token losses of a bond owner to the \
+                     PoS slashes that are enqueued to be processed."
+                ))
+                .add_args::<args::QuerySlashProjection<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryRewards(pub args::QueryRewards<args::CliTypes>);
 
@@ -2335,6 +2459,33 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxChangeValidatorKeys(
+        pub args::ValidatorKeysChange<args::CliTypes>,
+    );
+
+    impl SubCmd for TxChangeValidatorKeys {
+        const CMD: &'static str = "change-validator-keys";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxChangeValidatorKeys(args::ValidatorKeysChange::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Change the protocol key and the Ethereum bridge keys of \
+                     a validator."
+                ))
+                .add_args::<args::ValidatorKeysChange<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxVoteProposal(pub args::VoteProposal<args::CliTypes>);
 
@@ -3274,6 +3425,7 @@ pub mod args {
 
     use data_encoding::HEXUPPER;
     use namada_core::masp::{MaspEpoch, PaymentAddress};
+    use namada_sdk::account::{AccountPolicy, VestingSchedule};
     use namada_sdk::address::{Address, EstablishedAddress};
     pub use namada_sdk::args::*;
     use namada_sdk::chain::{ChainId, ChainIdPrefix};
@@ -3285,6 +3437,7 @@ pub mod args {
     use namada_sdk::keccak::KeccakHash;
     use namada_sdk::key::*;
     use namada_sdk::masp::utils::RetryStrategy;
+    use namada_sdk::masp::NoteSelection;
     use namada_sdk::storage::{self, BlockHeight, Epoch};
     use namada_sdk::time::DateTimeUtc;
     use namada_sdk::token::NATIVE_MAX_DECIMAL_PLACES;
@@ -3292,11 +3445,11 @@ pub mod args {
     pub use namada_sdk::tx::{
         TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
         TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+        TX_CHANGE_METADATA_WASM, TX_CHANGE_VALIDATOR_KEYS_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+        TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
+        TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
+        TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
         TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
         VP_USER_WASM,
     };
@@ -3438,6 +3591,9 @@ pub mod args {
         arg("genesis-validator").opt();
     pub const GENESIS_VALIDATOR_ADDRESS: Arg<EstablishedAddress> =
         arg("validator");
+    pub const GEN_ETH_COLD_KEY: ArgFlag = flag("gen-eth-cold-key");
+    pub const GEN_ETH_HOT_KEY: ArgFlag = flag("gen-eth-hot-key");
+    pub const GEN_PROTOCOL_KEY: ArgFlag = flag("gen-protocol-key");
     pub const HALT_ACTION: ArgFlag = flag("halt");
     pub const HASH: Arg<String> = arg("hash");
     pub const HASH_OPT: ArgOpt<String> = arg_opt("hash");
@@ -3476,6 +3632,7 @@ pub mod args {
     pub const MAX_CONCURRENT_FETCHES: ArgDefault<usize> =
         arg_default("max-concurrent-fetches", DefaultFn(|| 100));
     pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
+    pub const MAX_NOTES: ArgOpt<usize> = arg_opt("max-notes");
     pub const MEMO_OPT: ArgOpt<String> = arg_opt("memo");
     pub const MIGRATION_PATH: ArgOpt<PathBuf> = arg_opt("migration-path");
     pub const MODE: ArgOpt<String> = arg_opt("mode");
@@ -3483,6 +3640,8 @@ pub mod args {
     pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
     pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
     pub const NO_EXPIRATION: ArgFlag = flag("no-expiration");
+    pub const NOTE_SELECTION: ArgDefault<NoteSelection> =
+        arg_default("note-selection", DefaultFn(NoteSelection::default));
     pub const NUT: ArgFlag = flag("nut");
     pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
     pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
@@ -3493,6 +3652,7 @@ pub mod args {
     pub const PATH: Arg<PathBuf> = arg("path");
     pub const PATH_OPT: ArgOpt<PathBuf> = arg_opt("path");
     pub const PAYMENT_ADDRESS_TARGET: Arg<WalletPaymentAddr> = arg("target");
+    pub const POLICY_PATH: ArgOpt<PathBuf> = arg_opt("policy-path");
     pub const PORT_ID: ArgDefault<PortId> = arg_default(
         "port-id",
         DefaultFn(|| PortId::from_str("transfer").unwrap()),
@@ -3502,6 +3662,8 @@ pub mod args {
         arg_multi("secret-keys");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_PARAMETER_CHANGE: ArgFlag = flag("parameter-change");
+    pub const PROPOSAL_UPGRADE: ArgFlag = flag("upgrade");
     pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
     pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
     pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
@@ -3584,6 +3746,7 @@ pub mod args {
     pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("eth-hot-key");
     pub const VALUE: Arg<String> = arg("value");
+    pub const VESTING_PATH: ArgOpt<PathBuf> = arg_opt("vesting-path");
     pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VIEWING_KEYS: ArgMulti<WalletViewingKey, GlobStar> =
@@ -3851,6 +4014,25 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerPruneArchive {
+        pub block_height: BlockHeight,
+    }
+
+    impl Args for LedgerPruneArchive {
+        fn parse(matches: &ArgMatches) -> Self {
+            let block_height = BLOCK_HEIGHT.parse(matches);
+            Self { block_height }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BLOCK_HEIGHT.def().help(wrap!(
+                "The lowest block height from which the archive will still be \
+                 queryable."
+            )))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct LedgerQueryDb {
         pub key: storage::Key,
@@ -4666,6 +4848,7 @@ pub mod args {
                 data,
                 gas_spending_keys,
                 disposable_signing_key: self.disposable_signing_key,
+                note_selection: self.note_selection,
                 tx_code_path: self.tx_code_path.to_path_buf(),
             })
         }
@@ -4690,12 +4873,14 @@ pub mod args {
                 gas_spending_keys.push(key);
             }
             let disposable_gas_payer = DISPOSABLE_SIGNING_KEY.parse(matches);
+            let note_selection = NOTE_SELECTION.parse(matches);
 
             Self {
                 tx,
                 data,
                 gas_spending_keys,
                 disposable_signing_key: disposable_gas_payer,
+                note_selection,
                 tx_code_path,
             }
         }
@@ -4731,6 +4916,11 @@ pub mod args {
                         ))
                         .conflicts_with(FEE_PAYER_OPT.name),
                 )
+                .arg(NOTE_SELECTION.def().help(wrap!(
+                    "The strategy to select the shielded notes that fund the \
+                     transfer from \"oldest-first\" (default), \
+                     \"fewest-notes\", \"random\" or \"avoid-exact-match\"."
+                )))
         }
     }
 
@@ -4835,6 +5025,7 @@ pub mod args {
                 data,
                 gas_spending_keys,
                 disposable_signing_key: self.disposable_signing_key,
+                note_selection: self.note_selection,
                 source: chain_ctx.get_cached(&self.source),
                 tx_code_path: self.tx_code_path.to_path_buf(),
             })
@@ -4859,6 +5050,7 @@ pub mod args {
                 gas_spending_keys.push(key);
             }
             let disposable_signing_key = DISPOSABLE_SIGNING_KEY.parse(matches);
+            let note_selection = NOTE_SELECTION.parse(matches);
 
             Self {
                 tx,
@@ -4866,6 +5058,7 @@ pub mod args {
                 data,
                 gas_spending_keys,
                 disposable_signing_key,
+                note_selection,
                 tx_code_path,
             }
         }
@@ -4901,6 +5094,11 @@ pub mod args {
                         ))
                         .conflicts_with(FEE_PAYER_OPT.name),
                 )
+                .arg(NOTE_SELECTION.def().help(wrap!(
+                    "The strategy to select the shielded notes that fund the \
+                     transfer from \"oldest-first\" (default), \
+                     \"fewest-notes\", \"random\" or \"avoid-exact-match\"."
+                )))
         }
     }
 
@@ -5062,6 +5260,8 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                vesting: self.vesting,
+                policy: self.policy,
             })
         }
     }
@@ -5075,11 +5275,18 @@ pub mod args {
             let tx_code_path = PathBuf::from(TX_INIT_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let vesting = VESTING_PATH
+                .parse(matches)
+                .map(parse_vesting_schedules)
+                .unwrap_or_default();
+            let policy = POLICY_PATH.parse(matches).map(parse_account_policy);
             Self {
                 tx,
                 vp_code_path,
                 public_keys,
                 threshold,
+                vesting,
+                policy,
                 tx_code_path,
             }
         }
@@ -5100,7 +5307,53 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided."
                 )))
-        }
+                .arg(VESTING_PATH.def().help(wrap!(
+                    "The path to a JSON file with a list of vesting schedules \
+                     locking a part of the new account's balances. The \
+                     schedules cannot be changed after the account is created."
+                )))
+                .arg(POLICY_PATH.def().help(wrap!(
+                    "The path to a JSON file with the signing policy of the \
+                     new account: weights of its public keys by index, \
+                     thresholds of specific actions and token debit limits."
+                )))
+        }
+    }
+
+    /// Read a list of vesting schedules from a JSON file
+    fn parse_vesting_schedules(path: PathBuf) -> Vec<VestingSchedule> {
+        let file = std::fs::File::open(&path).unwrap_or_else(|err| {
+            eprintln!(
+                "Failed to open the vesting schedules file {}: {err}",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        });
+        serde_json::from_reader(file).unwrap_or_else(|err| {
+            eprintln!(
+                "Failed to decode the vesting schedules file {}: {err}",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        })
+    }
+
+    /// Read an account signing policy from a JSON file
+    fn parse_account_policy(path: PathBuf) -> AccountPolicy {
+        let file = std::fs::File::open(&path).unwrap_or_else(|err| {
+            eprintln!(
+                "Failed to open the account policy file {}: {err}",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        });
+        serde_json::from_reader(file).unwrap_or_else(|err| {
+            eprintln!(
+                "Failed to decode the account policy file {}: {err}",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        })
     }
 
     impl CliToSdk<TxBecomeValidator<SdkTypes>> for TxBecomeValidator<CliTypes> {
@@ -5435,6 +5688,7 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                policy: self.policy,
             })
         }
     }
@@ -5447,6 +5701,7 @@ pub mod args {
             let tx_code_path = PathBuf::from(TX_UPDATE_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let policy = POLICY_PATH.parse(matches).map(parse_account_policy);
             Self {
                 tx,
                 vp_code_path,
@@ -5454,6 +5709,7 @@ pub mod args {
                 tx_code_path,
                 public_keys,
                 threshold,
+                policy,
             }
         }
 
@@ -5475,6 +5731,12 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided."
                 )))
+                .arg(POLICY_PATH.def().help(wrap!(
+                    "The path to a JSON file with the new signing policy of \
+                     the account, replacing the current one. If the public \
+                     keys are updated without a new policy, the weights of \
+                     the previous keys are cleared."
+                )))
         }
     }
 
@@ -5758,6 +6020,8 @@ pub mod args {
                 proposal_data,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_parameter_change: self.is_parameter_change,
+                is_upgrade: self.is_upgrade,
                 tx_code_path: self.tx_code_path,
             })
         }
@@ -5769,6 +6033,8 @@ pub mod args {
             let proposal_data = DATA_PATH.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_parameter_change = PROPOSAL_PARAMETER_CHANGE.parse(matches);
+            let is_upgrade = PROPOSAL_UPGRADE.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -5777,6 +6043,8 @@ pub mod args {
                 tx_code_path,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_parameter_change,
+                is_upgrade,
             }
         }
 
@@ -5792,7 +6060,11 @@ pub mod args {
                             "Flag if the proposal is of type pgf-stewards. \
                              Used to elect/remove stewards."
                         ))
-                        .conflicts_with(PROPOSAL_PGF_FUNDING.name),
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
+                        ]),
                 )
                 .arg(
                     PROPOSAL_PGF_FUNDING
@@ -5801,7 +6073,38 @@ pub mod args {
                             "Flag if the proposal is of type pgf-funding. \
                              Used to control continuous/retro PGF fundings."
                         ))
-                        .conflicts_with(PROPOSAL_PGF_STEWARD.name),
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_PARAMETER_CHANGE
+                        .def()
+                        .help(wrap!(
+                            "Flag if the proposal is of type \
+                             parameter-change. Used to change protocol, PoS, \
+                             governance, PGF and IBC parameters."
+                        ))
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_UPGRADE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_UPGRADE
+                        .def()
+                        .help(wrap!(
+                            "Flag if the proposal is of type upgrade. Used to \
+                             schedule a software upgrade at a block height."
+                        ))
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                        ]),
                 )
         }
     }
@@ -5848,7 +6151,14 @@ pub mod args {
             app.add_args::<Tx<CliTypes>>()
                 .arg(PROPOSAL_ID.def().help(wrap!("The proposal identifier.")))
                 .arg(PROPOSAL_VOTE.def().help(wrap!(
-                    "The vote for the proposal. Either yay, nay, or abstain."
+                    "The vote for the proposal. Either yay, nay, abstain, \
+                     veto (a nay vote that burns the proposal deposit if \
+                     vetoes reach the veto threshold), or a split vote \
+                     `split:yay=<percent>,nay=<percent>,abstain=<percent>` \
+                     adding up to 100. For proposals with a voting mode, \
+                     either `choice:<option>`, \
+                     `weighted:<option>=<weight>,...` or \
+                     `ranked:<option>,...`."
                 )))
                 .arg(ADDRESS.def().help(wrap!("The address of the voter.")))
         }
@@ -6510,6 +6820,99 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<ValidatorKeysChange<SdkTypes>> for ValidatorKeysChange<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<ValidatorKeysChange<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(ValidatorKeysChange::<SdkTypes> {
+                tx,
+                validator: chain_ctx.get(&self.validator),
+                protocol_key: self.protocol_key.map(|x| chain_ctx.get(&x)),
+                eth_cold_key: self.eth_cold_key.map(|x| chain_ctx.get(&x)),
+                eth_hot_key: self.eth_hot_key.map(|x| chain_ctx.get(&x)),
+                gen_protocol_key: self.gen_protocol_key,
+                gen_eth_cold_key: self.gen_eth_cold_key,
+                gen_eth_hot_key: self.gen_eth_hot_key,
+                unsafe_dont_encrypt: self.unsafe_dont_encrypt,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            })
+        }
+    }
+
+    impl Args for ValidatorKeysChange<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            let protocol_key = PROTOCOL_KEY.parse(matches);
+            let eth_cold_key = VALIDATOR_ETH_COLD_KEY.parse(matches);
+            let eth_hot_key = VALIDATOR_ETH_HOT_KEY.parse(matches);
+            let gen_protocol_key = GEN_PROTOCOL_KEY.parse(matches);
+            let gen_eth_cold_key = GEN_ETH_COLD_KEY.parse(matches);
+            let gen_eth_hot_key = GEN_ETH_HOT_KEY.parse(matches);
+            let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_CHANGE_VALIDATOR_KEYS_WASM);
+            Self {
+                tx,
+                validator,
+                protocol_key,
+                eth_cold_key,
+                eth_hot_key,
+                gen_protocol_key,
+                gen_eth_cold_key,
+                gen_eth_hot_key,
+                unsafe_dont_encrypt,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(VALIDATOR.def().help(wrap!(
+                    "The validator's address whose keys to change."
+                )))
+                .arg(PROTOCOL_KEY.def().help(wrap!(
+                    "The desired new public key for signing protocol \
+                     transactions."
+                )))
+                .arg(VALIDATOR_ETH_COLD_KEY.def().help(wrap!(
+                    "The desired new Eth cold key. Note that this must be \
+                     secp256k1."
+                )))
+                .arg(VALIDATOR_ETH_HOT_KEY.def().help(wrap!(
+                    "The desired new Eth hot key. Note that this must be \
+                     secp256k1."
+                )))
+                .arg(
+                    GEN_PROTOCOL_KEY
+                        .def()
+                        .help(wrap!("Generate a new protocol key."))
+                        .conflicts_with(PROTOCOL_KEY.name),
+                )
+                .arg(
+                    GEN_ETH_COLD_KEY
+                        .def()
+                        .help(wrap!("Generate a new Eth cold key."))
+                        .conflicts_with(VALIDATOR_ETH_COLD_KEY.name),
+                )
+                .arg(
+                    GEN_ETH_HOT_KEY
+                        .def()
+                        .help(wrap!("Generate a new Eth hot key."))
+                        .conflicts_with(VALIDATOR_ETH_HOT_KEY.name),
+                )
+                .arg(UNSAFE_DONT_ENCRYPT.def().help(wrap!(
+                    "UNSAFE: Do not encrypt the generated keypairs. Do not \
+                     use this for keys used in a live network."
+                )))
+        }
+    }
+
     impl CliToSdk<MetaDataChange<SdkTypes>> for MetaDataChange<CliTypes> {
         type Error = std::io::Error;
 
@@ -6864,6 +7267,126 @@ pub mod args {
         }
     }
 
+    impl Args for ShieldedHistory<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let viewing_key = VIEWING_KEY.parse(matches);
+            Self { query, viewing_key }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                VIEWING_KEY
+                    .def()
+                    .help(wrap!("The viewing key whose history to show.")),
+            )
+        }
+    }
+
+    impl CliToSdk<ShieldedHistory<SdkTypes>> for ShieldedHistory<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<ShieldedHistory<SdkTypes>, Self::Error> {
+            let query = self.query.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(ShieldedHistory::<SdkTypes> {
+                query,
+                viewing_key: chain_ctx.get_cached(&self.viewing_key),
+            })
+        }
+    }
+
+    impl Args for ShieldedConsolidate<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let source = SPENDING_KEY_SOURCE.parse(matches);
+            let target = PAYMENT_ADDRESS_TARGET.parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            let max_notes = MAX_NOTES.parse(matches);
+            let mut gas_spending_keys = vec![];
+            if let Some(key) = GAS_SPENDING_KEY.parse(matches) {
+                gas_spending_keys.push(key);
+            }
+            let disposable_signing_key = DISPOSABLE_SIGNING_KEY.parse(matches);
+            let tx_code_path = PathBuf::from(TX_TRANSFER_WASM);
+
+            Self {
+                tx,
+                source,
+                target,
+                token,
+                max_notes,
+                gas_spending_keys,
+                disposable_signing_key,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(SPENDING_KEY_SOURCE.def().help(wrap!(
+                    "The shielded spending key whose notes to consolidate."
+                )))
+                .arg(PAYMENT_ADDRESS_TARGET.def().help(wrap!(
+                    "The shielded address of the spending key that receives \
+                     the consolidated notes."
+                )))
+                .arg(TOKEN_OPT.def().help(wrap!(
+                    "Only consolidate the notes of this token. Defaults to \
+                     all the tokens."
+                )))
+                .arg(MAX_NOTES.def().help(wrap!(
+                    "The maximum number of notes to merge in a single \
+                     transaction."
+                )))
+                .arg(GAS_SPENDING_KEY.def().help(wrap!(
+                    "The optional spending key that will be used in addition \
+                     to the source for gas payment."
+                )))
+                .arg(
+                    DISPOSABLE_SIGNING_KEY
+                        .def()
+                        .help(wrap!(
+                            "Generates an ephemeral, disposable keypair to \
+                             sign the wrapper transactions."
+                        ))
+                        .conflicts_with(FEE_PAYER_OPT.name),
+                )
+        }
+    }
+
+    impl CliToSdk<ShieldedConsolidate<SdkTypes>> for ShieldedConsolidate<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<ShieldedConsolidate<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            let gas_spending_keys = self
+                .gas_spending_keys
+                .iter()
+                .map(|key| chain_ctx.get_cached(key))
+                .collect();
+
+            Ok(ShieldedConsolidate::<SdkTypes> {
+                tx,
+                source: chain_ctx.get_cached(&self.source),
+                target: chain_ctx.get(&self.target),
+                token: self.token.map(|token| chain_ctx.get(&token)),
+                max_notes: self.max_notes,
+                gas_spending_keys,
+                disposable_signing_key: self.disposable_signing_key,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            })
+        }
+    }
+
     impl CliToSdk<GenIbcShieldingTransfer<SdkTypes>>
         for GenIbcShieldingTransfer<CliTypes>
     {
@@ -7062,6 +7585,38 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QuerySlashProjection<SdkTypes>>
+        for QuerySlashProjection<CliTypes>
+    {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QuerySlashProjection<SdkTypes>, Self::Error> {
+            Ok(QuerySlashProjection::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            })
+        }
+    }
+
+    impl Args for QuerySlashProjection<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(OWNER.def().help(wrap!(
+                    "The owner account address whose bonds to project the \
+                     slashes for."
+                )))
+        }
+    }
+
     impl CliToSdk<QueryRewards<SdkTypes>> for QueryRewards<CliTypes> {
         type Error = std::convert::Infallible;
 
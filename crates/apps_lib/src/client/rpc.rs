@@ -6,11 +6,13 @@ use std::io;
 use borsh::BorshDeserialize;
 use data_encoding::HEXLOWER;
 use masp_primitives::asset_type::AssetType;
+use masp_primitives::memo::Memo;
 use masp_primitives::merkle_tree::MerklePath;
-use masp_primitives::sapling::Node;
+use masp_primitives::sapling::{Node, ViewingKey};
 use masp_primitives::transaction::components::I128Sum;
+use masp_primitives::transaction::TransparentAddress;
 use masp_primitives::zip32::ExtendedFullViewingKey;
-use namada_core::masp::{BalanceOwner, MaspEpoch};
+use namada_core::masp::{addr_taddr, BalanceOwner, MaspEpoch};
 use namada_sdk::address::{Address, InternalAddress, MASP};
 use namada_sdk::chain::{BlockHeight, Epoch};
 use namada_sdk::collections::{HashMap, HashSet};
@@ -22,15 +24,22 @@ use namada_sdk::governance::pgf::parameters::PgfParameters;
 use namada_sdk::governance::pgf::storage::steward::StewardDetail;
 use namada_sdk::governance::storage::keys as governance_storage;
 use namada_sdk::governance::storage::proposal::{
-    StoragePgfFunding, StorageProposal,
+    ProposalType, StoragePgfFunding, StorageProposal,
 };
 use namada_sdk::governance::utils::{ProposalVotes, VotePower};
 use namada_sdk::governance::ProposalVote;
 use namada_sdk::hash::Hash;
+use namada_sdk::ibc::parameters::IbcParameters;
+use namada_sdk::ibc::storage as ibc_storage;
 use namada_sdk::io::{display, display_line, edisplay_line, Client, Io};
 use namada_sdk::key::*;
+use namada_sdk::masp::shielded_history::ShieldedHistory;
 use namada_sdk::masp::shielded_wallet::ShieldedApi;
-use namada_sdk::masp::MaspTokenRewardData;
+use namada_sdk::masp::{MaspAmount, MaspTokenRewardData};
+use namada_sdk::parameter_change::{
+    GovernanceParameterChange, IbcParameterChange, ParameterChange,
+    PgfParameterChange, PosParameterChange, ProtocolParameterChange,
+};
 use namada_sdk::parameters::{
     storage as param_storage, EpochDuration, ProposalBytes,
 };
@@ -317,6 +326,9 @@ pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
                 "{}",
                 proposal.to_string_with_status(current_epoch)
             );
+            if let ProposalType::ParameterChange(changes) = &proposal.r#type {
+                display_parameter_changes(context, changes).await;
+            }
         } else {
             edisplay_line!(context.io(), "No proposal found with id: {}", id);
         }
@@ -353,6 +365,278 @@ pub async fn query_proposal_by_id<C: Client + Sync>(
     namada_sdk::rpc::query_proposal_by_id(client, proposal_id).await
 }
 
+/// Display the changes of a parameter change proposal next to the current
+/// values of the parameters
+pub async fn display_parameter_changes(
+    context: &impl Namada,
+    changes: &BTreeSet<ParameterChange>,
+) {
+    display_line!(context.io(), "Parameter changes (current -> proposed):");
+    for change in changes {
+        let current = query_current_parameter(context.client(), change).await;
+        display_line!(
+            context.io(),
+            "{:4}{}: {} -> {}",
+            "",
+            change.name(),
+            current.value(),
+            change.value()
+        );
+    }
+}
+
+/// Query the current value of the parameter changed by the given change, in
+/// the form of a change to that value
+async fn query_current_parameter<C: Client + Sync>(
+    client: &C,
+    change: &ParameterChange,
+) -> ParameterChange {
+    match change {
+        ParameterChange::Protocol(change) => ParameterChange::Protocol(
+            query_current_protocol_parameter(client, change).await,
+        ),
+        ParameterChange::Pos(change) => {
+            let params = query_pos_parameters(client).await.owned;
+            ParameterChange::Pos(match change {
+                PosParameterChange::BlockProposerReward(_) => {
+                    PosParameterChange::BlockProposerReward(
+                        params.block_proposer_reward,
+                    )
+                }
+                PosParameterChange::BlockVoteReward(_) => {
+                    PosParameterChange::BlockVoteReward(
+                        params.block_vote_reward,
+                    )
+                }
+                PosParameterChange::MaxInflationRate(_) => {
+                    PosParameterChange::MaxInflationRate(
+                        params.max_inflation_rate,
+                    )
+                }
+                PosParameterChange::TargetStakedRatio(_) => {
+                    PosParameterChange::TargetStakedRatio(
+                        params.target_staked_ratio,
+                    )
+                }
+                PosParameterChange::DuplicateVoteMinSlashRate(_) => {
+                    PosParameterChange::DuplicateVoteMinSlashRate(
+                        params.duplicate_vote_min_slash_rate,
+                    )
+                }
+                PosParameterChange::LightClientAttackMinSlashRate(_) => {
+                    PosParameterChange::LightClientAttackMinSlashRate(
+                        params.light_client_attack_min_slash_rate,
+                    )
+                }
+                PosParameterChange::ValidatorStakeThreshold(_) => {
+                    PosParameterChange::ValidatorStakeThreshold(
+                        params.validator_stake_threshold,
+                    )
+                }
+                PosParameterChange::LivenessThreshold(_) => {
+                    PosParameterChange::LivenessThreshold(
+                        params.liveness_threshold,
+                    )
+                }
+                PosParameterChange::RewardsGainP(_) => {
+                    PosParameterChange::RewardsGainP(params.rewards_gain_p)
+                }
+                PosParameterChange::RewardsGainD(_) => {
+                    PosParameterChange::RewardsGainD(params.rewards_gain_d)
+                }
+                PosParameterChange::MaxValidatorStakeShare(_) => {
+                    PosParameterChange::MaxValidatorStakeShare(
+                        params.max_validator_stake_share,
+                    )
+                }
+                PosParameterChange::InstantUnbondPenalty(_) => {
+                    PosParameterChange::InstantUnbondPenalty(
+                        params.instant_unbond_penalty,
+                    )
+                }
+                PosParameterChange::InstantUnbondPenaltyToPgf(_) => {
+                    PosParameterChange::InstantUnbondPenaltyToPgf(
+                        params.instant_unbond_penalty_to_pgf,
+                    )
+                }
+                PosParameterChange::MinSelfBond(_) => {
+                    PosParameterChange::MinSelfBond(params.min_self_bond)
+                }
+                PosParameterChange::MinSelfBondRatio(_) => {
+                    PosParameterChange::MinSelfBondRatio(
+                        params.min_self_bond_ratio,
+                    )
+                }
+            })
+        }
+        ParameterChange::Governance(change) => {
+            let params = query_governance_parameters(client).await;
+            ParameterChange::Governance(match change {
+                GovernanceParameterChange::MinProposalFund(_) => {
+                    GovernanceParameterChange::MinProposalFund(
+                        params.min_proposal_fund,
+                    )
+                }
+                GovernanceParameterChange::MaxProposalCodeSize(_) => {
+                    GovernanceParameterChange::MaxProposalCodeSize(
+                        params.max_proposal_code_size,
+                    )
+                }
+                GovernanceParameterChange::MinProposalVotingPeriod(_) => {
+                    GovernanceParameterChange::MinProposalVotingPeriod(
+                        params.min_proposal_voting_period,
+                    )
+                }
+                GovernanceParameterChange::MaxProposalPeriod(_) => {
+                    GovernanceParameterChange::MaxProposalPeriod(
+                        params.max_proposal_period,
+                    )
+                }
+                GovernanceParameterChange::MaxProposalContentSize(_) => {
+                    GovernanceParameterChange::MaxProposalContentSize(
+                        params.max_proposal_content_size,
+                    )
+                }
+                GovernanceParameterChange::MinProposalGraceEpochs(_) => {
+                    GovernanceParameterChange::MinProposalGraceEpochs(
+                        params.min_proposal_grace_epochs,
+                    )
+                }
+                GovernanceParameterChange::MaxProposalLatency(_) => {
+                    GovernanceParameterChange::MaxProposalLatency(
+                        params.max_proposal_latency,
+                    )
+                }
+                GovernanceParameterChange::VetoThreshold(_) => {
+                    GovernanceParameterChange::VetoThreshold(
+                        params.veto_threshold,
+                    )
+                }
+            })
+        }
+        ParameterChange::Pgf(change) => {
+            let params = query_pgf_parameters(client).await;
+            ParameterChange::Pgf(match change {
+                PgfParameterChange::PgfInflationRate(_) => {
+                    PgfParameterChange::PgfInflationRate(
+                        params.pgf_inflation_rate,
+                    )
+                }
+                PgfParameterChange::StewardsInflationRate(_) => {
+                    PgfParameterChange::StewardsInflationRate(
+                        params.stewards_inflation_rate,
+                    )
+                }
+                PgfParameterChange::MaximumNumberOfStewards(_) => {
+                    PgfParameterChange::MaximumNumberOfStewards(
+                        params.maximum_number_of_stewards,
+                    )
+                }
+            })
+        }
+        ParameterChange::Ibc(change) => {
+            let params: IbcParameters =
+                query_storage_value(client, &ibc_storage::params_key())
+                    .await
+                    .expect("Parameter should be defined.");
+            ParameterChange::Ibc(match change {
+                IbcParameterChange::DefaultMintLimit(_) => {
+                    IbcParameterChange::DefaultMintLimit(
+                        params.default_mint_limit,
+                    )
+                }
+                IbcParameterChange::DefaultPerEpochThroughputLimit(_) => {
+                    IbcParameterChange::DefaultPerEpochThroughputLimit(
+                        params.default_per_epoch_throughput_limit,
+                    )
+                }
+            })
+        }
+    }
+}
+
+/// Query the current value of the protocol parameter changed by the given
+/// change, in the form of a change to that value
+async fn query_current_protocol_parameter<C: Client + Sync>(
+    client: &C,
+    change: &ProtocolParameterChange,
+) -> ProtocolParameterChange {
+    match change {
+        ProtocolParameterChange::MaxTxBytes(_) => {
+            let key = param_storage::get_max_tx_bytes_key();
+            ProtocolParameterChange::MaxTxBytes(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::MaxProposalBytes(_) => {
+            let key = param_storage::get_max_proposal_bytes_key();
+            let max_proposal_bytes: ProposalBytes =
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined.");
+            ProtocolParameterChange::MaxProposalBytes(max_proposal_bytes.get())
+        }
+        ProtocolParameterChange::MaxBlockGas(_) => {
+            let key = param_storage::get_max_block_gas_key();
+            ProtocolParameterChange::MaxBlockGas(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::VpAllowlist(_) => {
+            let key = param_storage::get_vp_allowlist_storage_key();
+            ProtocolParameterChange::VpAllowlist(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::TxAllowlist(_) => {
+            let key = param_storage::get_tx_allowlist_storage_key();
+            ProtocolParameterChange::TxAllowlist(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::MaspFeePaymentGasLimit(_) => {
+            let key = param_storage::get_masp_fee_payment_gas_limit_key();
+            ProtocolParameterChange::MaspFeePaymentGasLimit(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::GasScale(_) => {
+            let key = param_storage::get_gas_scale_key();
+            ProtocolParameterChange::GasScale(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::MinimumGasPrice(_) => {
+            let key = param_storage::get_gas_cost_key();
+            ProtocolParameterChange::MinimumGasPrice(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+        ProtocolParameterChange::IsNativeTokenTransferable(_) => {
+            let key = param_storage::get_native_token_transferable_key();
+            ProtocolParameterChange::IsNativeTokenTransferable(
+                query_storage_value(client, &key)
+                    .await
+                    .expect("Parameter should be defined."),
+            )
+        }
+    }
+}
+
 /// Query token shielded balance(s)
 async fn query_shielded_balance(
     context: &impl Namada,
@@ -445,6 +729,154 @@ async fn query_shielded_balance(
     }
 }
 
+/// Query the history of the shielded txs of a viewing key
+pub async fn query_shielded_history(
+    context: &impl Namada,
+    args: args::ShieldedHistory,
+) {
+    let viewing_key = ViewingKey::from(args.viewing_key);
+    let history = {
+        let mut shielded = context.shielded_mut().await;
+        let _ = shielded.load().await;
+        let history = shielded
+            .shielded_history(context.client(), &viewing_key)
+            .await;
+        // Save the decoded asset types for future queries
+        let _ = shielded.save().await;
+        history
+    };
+    let history = match history {
+        Ok(history) => history,
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the shielded history: {err}. Make sure that \
+                 the viewing key has been synced with `shielded-sync`."
+            );
+            cli::safe_exit(1)
+        }
+    };
+    if history.is_empty() {
+        display_line!(
+            context.io(),
+            "No shielded txs found for the given viewing key."
+        );
+        return;
+    }
+
+    // Resolve the transparent addresses of the wallet for pretty printing
+    let known_addresses: HashMap<_, _> = context
+        .wallet()
+        .await
+        .get_addresses()
+        .into_iter()
+        .map(|(alias, address)| (addr_taddr(address), alias))
+        .collect();
+    let format_taddrs = |taddrs: &[TransparentAddress]| {
+        taddrs
+            .iter()
+            .map(|taddr| {
+                known_addresses
+                    .get(taddr)
+                    .cloned()
+                    .unwrap_or_else(|| HEXLOWER.encode(&taddr.0))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    for entry in history {
+        let batch_index = entry
+            .indexed_tx
+            .batch_index
+            .map(|index| format!(", inner tx {index}"))
+            .unwrap_or_default();
+        display_line!(
+            context.io(),
+            "{} (height {}, tx {}{batch_index}) MASP tx {}",
+            entry.timestamp,
+            entry.indexed_tx.height,
+            entry.indexed_tx.index.0,
+            entry.txid,
+        );
+        for (kind, notes) in [
+            ("Incoming", &entry.incoming),
+            ("Change", &entry.change),
+            ("Spent", &entry.spends),
+        ] {
+            for note in notes {
+                let memo = match Memo::try_from(&note.memo) {
+                    Ok(Memo::Text(text)) => format!(", memo: {}", &*text),
+                    _ => String::new(),
+                };
+                display_line!(
+                    context.io(),
+                    "  {kind}: {} (note {}{memo})",
+                    format_masp_amount(context, &note.amount).await,
+                    note.note_pos,
+                );
+            }
+        }
+        if let Some(fee) = &entry.fee {
+            display_line!(
+                context.io(),
+                "  Fee: {}",
+                format_masp_amount(context, fee).await
+            );
+        }
+        if let Some(conversion) = &entry.conversion {
+            display_line!(
+                context.io(),
+                "  Conversion: {}",
+                format_masp_amount(context, conversion).await
+            );
+        }
+        display_line!(
+            context.io(),
+            "  Net: {}",
+            format_masp_amount(context, &entry.net).await
+        );
+        if !entry.transparent_sources.is_empty() {
+            display_line!(
+                context.io(),
+                "  Transparent sources: {}",
+                format_taddrs(&entry.transparent_sources)
+            );
+        }
+        if !entry.transparent_targets.is_empty() {
+            display_line!(
+                context.io(),
+                "  Transparent targets: {}",
+                format_taddrs(&entry.transparent_targets)
+            );
+        }
+    }
+}
+
+/// Format a signed MASP amount with the token aliases of the wallet
+async fn format_masp_amount(
+    context: &impl Namada,
+    amount: &MaspAmount,
+) -> String {
+    let mut formatted = vec![];
+    for ((epoch, token), change) in amount.components() {
+        let token_alias = lookup_token_alias(context, token, &MASP).await;
+        let sign = if change.is_negative() { "-" } else { "" };
+        let value = context
+            .format_amount(token, token::Amount::from(change.abs()))
+            .await;
+        let epoch = epoch
+            .map(|epoch| format!(" (epoch {epoch})"))
+            .unwrap_or_default();
+        formatted.push(format!("{token_alias}: {sign}{value}{epoch}"));
+    }
+    if formatted.is_empty() {
+        "0".to_string()
+    } else {
+        formatted.join(", ")
+    }
+}
+
 pub async fn query_proposal_result<N: Namada>(
     context: &N,
     args: args::QueryProposalResult,
@@ -552,8 +984,73 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
         display_line!(context.io(), "Address: {}", account.address);
         display_line!(context.io(), "Threshold: {}", account.threshold);
         display_line!(context.io(), "Public keys:");
-        for (public_key, _) in account.public_keys_map.pk_to_idx {
-            display_line!(context.io(), "- {}", public_key);
+        for (public_key, index) in &account.public_keys_map.pk_to_idx {
+            if account.public_keys_map.idx_to_weight.is_empty() {
+                display_line!(context.io(), "- {}", public_key);
+            } else {
+                display_line!(
+                    context.io(),
+                    "- {} (weight: {})",
+                    public_key,
+                    account.public_keys_map.get_weight_from_index(*index)
+                );
+            }
+        }
+
+        let policy = rpc::get_account_policy(context.client(), &args.owner)
+            .await
+            .unwrap();
+        if !policy.action_thresholds.is_empty() {
+            display_line!(context.io(), "Action thresholds:");
+            for (action, threshold) in policy.action_thresholds {
+                display_line!(context.io(), "- {}: {}", action, threshold);
+            }
+        }
+        if !policy.debit_limits.is_empty() {
+            display_line!(context.io(), "Debit limits:");
+            for (token, limit) in policy.debit_limits {
+                display_line!(
+                    context.io(),
+                    "- {}: threshold {} above {}",
+                    token,
+                    limit.threshold,
+                    context.format_amount(&token, limit.amount).await
+                );
+            }
+        }
+
+        let schedules =
+            rpc::get_account_vesting_schedules(context.client(), &args.owner)
+                .await
+                .unwrap();
+        if !schedules.is_empty() {
+            let epoch = query_epoch(context.client()).await.unwrap();
+            display_line!(context.io(), "Vesting (epoch {}):", epoch);
+            for schedule in schedules {
+                let balance = rpc::get_token_balance(
+                    context.client(),
+                    &schedule.token,
+                    &args.owner,
+                    None,
+                )
+                .await
+                .unwrap();
+                let locked = std::cmp::min(schedule.locked_at(epoch), balance);
+                let spendable = balance.checked_sub(locked).unwrap_or_default();
+                display_line!(context.io(), "- {}", schedule.token);
+                display_line!(
+                    context.io(),
+                    "{:2}Locked: {}",
+                    "",
+                    context.format_amount(&schedule.token, locked).await
+                );
+                display_line!(
+                    context.io(),
+                    "{:2}Spendable: {}",
+                    "",
+                    context.format_amount(&schedule.token, spendable).await
+                );
+            }
         }
     } else {
         display_line!(context.io(), "No account exists for {}", args.owner);
@@ -623,6 +1120,7 @@ pub async fn query_protocol_parameters(
         max_proposal_content_size,
         min_proposal_grace_epochs,
         max_proposal_latency,
+        veto_threshold,
     } = query_governance_parameters(context.client()).await;
 
     display_line!(context.io(), "\nGovernance Parameters");
@@ -668,6 +1166,7 @@ pub async fn query_protocol_parameters(
         "",
         max_proposal_latency
     );
+    display_line!(context.io(), "{:4}Veto threshold: {}", "", veto_threshold);
 
     let PgfParameters {
         stewards: _,
@@ -830,6 +1329,11 @@ pub async fn query_protocol_parameters(
                 liveness_threshold,
                 rewards_gain_p,
                 rewards_gain_d,
+                max_validator_stake_share,
+                instant_unbond_penalty,
+                instant_unbond_penalty_to_pgf,
+                min_self_bond,
+                min_self_bond_ratio,
             },
         max_proposal_period: _,
     } = query_pos_parameters(context.client()).await;
@@ -929,6 +1433,43 @@ pub async fn query_protocol_parameters(
         "",
         tm_votes_per_token
     );
+    if let Some(share) = max_validator_stake_share {
+        display_line!(
+            context.io(),
+            "{:4}Max. validator stake share: {}",
+            "",
+            share
+        );
+    }
+    if let Some(penalty) = instant_unbond_penalty {
+        display_line!(
+            context.io(),
+            "{:4}Instant unbond penalty: {} ({})",
+            "",
+            penalty,
+            if instant_unbond_penalty_to_pgf {
+                "sent to PGF"
+            } else {
+                "burned"
+            }
+        );
+    }
+    if let Some(min_self_bond) = min_self_bond {
+        display_line!(
+            context.io(),
+            "{:4}Min. validator self-bond: {} native tokens",
+            "",
+            min_self_bond.to_string_native()
+        );
+    }
+    if let Some(ratio) = min_self_bond_ratio {
+        display_line!(
+            context.io(),
+            "{:4}Min. validator self-bond ratio: {}",
+            "",
+            ratio
+        );
+    }
 }
 
 pub async fn query_bond<C: Client + Sync>(
@@ -956,9 +1497,7 @@ pub async fn query_unbond_with_slashing<C: Client + Sync>(
 }
 
 pub async fn query_pos_parameters<C: Client + Sync>(client: &C) -> PosParams {
-    unwrap_client_response::<C, PosParams>(
-        RPC.vp().pos().pos_params(client).await,
-    )
+    unwrap_sdk_result(rpc::get_pos_params(client).await)
 }
 
 pub async fn query_consensus_keys<C: Client + Sync>(
@@ -1720,6 +2259,72 @@ pub async fn query_slashes<N: Namada>(context: &N, args: args::QuerySlashes) {
     }
 }
 
+/// Query the projected token losses of a bond owner to the enqueued PoS
+/// slashes
+pub async fn query_slash_projection<N: Namada>(
+    context: &N,
+    args: args::QuerySlashProjection,
+) {
+    let owner = args.owner;
+    let projection = unwrap_sdk_result(
+        rpc::query_slash_projection(context.client(), &owner).await,
+    );
+    if projection.slashes.is_empty() {
+        display_line!(context.io(), "No enqueued slashes found");
+        return;
+    }
+
+    display_line!(context.io(), "Enqueued slashes with their projected rates:");
+    for (validator, slashes) in &projection.slashes {
+        for slash in slashes {
+            display_line!(
+                context.io(),
+                "Infraction epoch {}, block height {}, type {}, rate {}, \
+                 validator {}",
+                slash.epoch,
+                slash.block_height,
+                slash.r#type,
+                slash.rate,
+                validator
+            );
+        }
+    }
+
+    let losses = projection
+        .losses
+        .iter()
+        .filter(|(_, loss)| {
+            !loss.bonded_loss.is_zero() || !loss.unbonded_loss.is_zero()
+        })
+        .collect::<Vec<_>>();
+    if losses.is_empty() {
+        display_line!(
+            context.io(),
+            "\nNo projected losses for the bonds of {owner}"
+        );
+        return;
+    }
+    display_line!(context.io(), "\nProjected losses for the bonds of {owner}:");
+    for (bond_id, loss) in losses {
+        display_line!(
+            context.io(),
+            "Validator {}: bonded {} (loss {}), unbonded {} (loss {})",
+            bond_id.validator,
+            loss.bonded.to_string_native(),
+            loss.bonded_loss.to_string_native(),
+            loss.unbonded.to_string_native(),
+            loss.unbonded_loss.to_string_native(),
+        );
+    }
+    if let Some(total_loss) = projection.total_loss() {
+        display_line!(
+            context.io(),
+            "Total projected loss: {}",
+            total_loss.to_string_native()
+        );
+    }
+}
+
 pub async fn query_and_print_rewards<N: Namada>(
     context: &N,
     args: args::QueryRewards,
@@ -2264,10 +2869,18 @@ pub async fn compute_proposal_votes<C: Client + Sync>(
         }
     }
 
+    let voting_mode =
+        namada_sdk::rpc::query_proposal_by_id(client, proposal_id)
+            .await
+            .unwrap()
+            .map(|proposal| proposal.voting_mode)
+            .unwrap_or_default();
+
     ProposalVotes {
         validators_vote,
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+        voting_mode,
     }
 }
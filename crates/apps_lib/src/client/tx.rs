@@ -1,5 +1,6 @@
 use std::fs::File;
 use std::io::Write;
+use std::path::PathBuf;
 
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
@@ -8,11 +9,14 @@ use namada_sdk::address::{Address, ImplicitAddress};
 use namada_sdk::args::TxBecomeValidator;
 use namada_sdk::collections::HashSet;
 use namada_sdk::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, UpgradeProposal,
 };
+use namada_sdk::governance::storage::proposal::ProposalType;
 use namada_sdk::ibc::convert_masp_tx_to_ibc_memo;
 use namada_sdk::io::{display_line, edisplay_line, Io};
 use namada_sdk::key::*;
+use namada_sdk::proof_of_stake::types::RewardRecipient;
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada_sdk::tx::data::compute_inner_tx_hash;
@@ -31,7 +35,8 @@ use crate::config::TendermintMode;
 use crate::tendermint_node;
 use crate::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use crate::wallet::{
-    gen_validator_keys, read_and_confirm_encryption_password, WalletTransport,
+    gen_validator_keys, read_and_confirm_encryption_password, ValidatorKeys,
+    WalletTransport,
 };
 
 /// Wrapper around `signing::aux_signing_data` that stores the optional
@@ -50,6 +55,7 @@ pub async fn aux_signing_data(
         default_signer,
         vec![],
         disposable_signing_key,
+        &[],
     )
     .await?;
 
@@ -448,6 +454,160 @@ pub async fn submit_change_consensus_key(
     Ok(())
 }
 
+pub async fn submit_change_validator_keys(
+    namada: &impl Namada,
+    args: args::ValidatorKeysChange,
+) -> Result<(), error::Error> {
+    let validator = args.validator.clone();
+
+    let mut wallet = namada.wallet_mut().await;
+    let alias = wallet
+        .find_alias(&validator)
+        .map(|alias| alias.to_string())
+        .unwrap_or_else(|| validator.to_string());
+
+    // Find the secret keys of the given keys that the node has to sign with
+    // or generate new ones
+    let mut find_or_gen_key = |key: Option<&common::PublicKey>,
+                               gen_key: bool,
+                               scheme: SchemeType,
+                               name: &str| {
+        if let Some(key) = key {
+            return wallet.find_key_by_pk(key, None).map(Some).map_err(|err| {
+                error::Error::Other(format!(
+                    "Unable to find the {name} key in the wallet: {err}"
+                ))
+            });
+        }
+        if !gen_key {
+            return Ok(None);
+        }
+        display_line!(namada.io(), "Generating new {name} key...");
+        let key_alias = unused_key_alias(
+            &*wallet,
+            &format!("{alias}-{}-key", name.replace(' ', "-")),
+        );
+        let password =
+            read_and_confirm_encryption_password(args.unsafe_dont_encrypt);
+        let (_alias, sk) = wallet
+            .gen_store_secret_key(
+                scheme,
+                Some(key_alias),
+                args.tx.wallet_alias_force,
+                password,
+                &mut OsRng,
+            )
+            .expect("Key generation should not fail.");
+        Ok(Some(sk))
+    };
+    let protocol_sk = find_or_gen_key(
+        args.protocol_key.as_ref(),
+        args.gen_protocol_key,
+        SchemeType::Ed25519,
+        "protocol",
+    )?;
+    // Note that ETH only allows secp256k1
+    let eth_cold_sk = find_or_gen_key(
+        args.eth_cold_key.as_ref(),
+        args.gen_eth_cold_key,
+        SchemeType::Secp256k1,
+        "eth cold",
+    )?;
+    let eth_hot_sk = find_or_gen_key(
+        args.eth_hot_key.as_ref(),
+        args.gen_eth_hot_key,
+        SchemeType::Secp256k1,
+        "eth hot",
+    )?;
+    // To avoid wallet deadlocks in following operations
+    drop(wallet);
+
+    let args = args::ValidatorKeysChange {
+        protocol_key: protocol_sk.as_ref().map(RefTo::ref_to),
+        eth_cold_key: eth_cold_sk.as_ref().map(RefTo::ref_to),
+        eth_hot_key: eth_hot_sk.as_ref().map(RefTo::ref_to),
+        ..args
+    };
+
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+        return Ok(());
+    }
+
+    sign(namada, &mut tx, &args.tx, signing_data).await?;
+    let cmt = tx.first_commitments().unwrap().to_owned();
+    let wrapper_hash = tx.wrapper_hash();
+    let resp = namada.submit(tx, &args.tx).await?;
+
+    if args.tx.dry_run {
+        display_line!(
+            namada.io(),
+            "Transaction dry run. No new validator keys have been saved."
+        );
+        return Ok(());
+    }
+    if resp
+        .is_applied_and_valid(wrapper_hash.as_ref(), &cmt)
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    // Add the keys that the node has to switch to at the pipeline epoch to
+    // the validator data
+    let mut wallet = namada.wallet_mut().await;
+    match wallet.get_validator_data_mut() {
+        Some(data) if data.address == validator => {
+            let latest_keys =
+                data.pending_keys.last().unwrap_or(&data.keys).clone();
+            data.add_pending_keys(ValidatorKeys {
+                protocol_keypair: protocol_sk
+                    .unwrap_or(latest_keys.protocol_keypair),
+                eth_bridge_keypair: eth_hot_sk
+                    .unwrap_or(latest_keys.eth_bridge_keypair),
+            });
+            display_line!(
+                namada.io(),
+                "The new validator keys were stored in the wallet. The node \
+                 will switch to them at the start of the pipeline epoch \
+                 relative to the current epoch (current epoch + pipeline \
+                 offset).",
+            );
+        }
+        _ if protocol_sk.is_some() || eth_hot_sk.is_some() => {
+            edisplay_line!(
+                namada.io(),
+                "No validator data of {validator} found in the wallet. The \
+                 new protocol and Eth hot keys have to be added to the \
+                 validator data in the wallet of the validator's node before \
+                 the start of the pipeline epoch."
+            );
+        }
+        _ => {}
+    }
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(namada.io(), "{}", err));
+    Ok(())
+}
+
+/// Find an alias for a new key that is not used in the wallet yet
+fn unused_key_alias<U: WalletIo>(
+    wallet: &Wallet<U>,
+    base_alias: &str,
+) -> String {
+    let all_keys = wallet.get_secret_keys();
+    let mut alias = base_alias.to_string();
+    let mut key_counter = 0;
+    while all_keys.contains_key(&alias) {
+        key_counter += 1;
+        alias = format!("{base_alias}-{key_counter}");
+    }
+    alias
+}
+
 pub async fn submit_become_validator(
     namada: &impl Namada,
     config: &mut crate::config::Config,
@@ -739,6 +899,8 @@ pub async fn submit_init_validator(
             tx_code_path: tx_init_account_code_path,
             public_keys: account_keys,
             threshold,
+            vesting: vec![],
+            policy: None,
         },
     )
     .await?;
@@ -831,6 +993,41 @@ pub async fn submit_shielded_transfer(
     Ok(())
 }
 
+pub async fn submit_shielded_consolidation(
+    namada: &impl Namada,
+    args: args::ShieldedConsolidate,
+) -> Result<(), error::Error> {
+    let consolidations = args.plan(namada).await?;
+    if consolidations.is_empty() {
+        display_line!(namada.io(), "No shielded notes to consolidate.");
+        return Ok(());
+    }
+
+    // Each tx is built after the previous one is submitted, so that the
+    // notes spent by the latter are not selected again
+    let num_txs = consolidations.len();
+    let mut remaining = consolidations.as_slice();
+    for idx in 1..=num_txs {
+        let Some((consolidation, later)) = remaining.split_first() else {
+            break;
+        };
+        let (mut tx, signing_data) =
+            args.build(namada, consolidation, later).await?;
+        if args.tx.dump_tx {
+            tx::dump_tx(namada.io(), &args.tx, tx);
+        } else {
+            display_line!(
+                namada.io(),
+                "Submitting consolidation transaction {idx} of {num_txs}..."
+            );
+            sign(namada, &mut tx, &args.tx, signing_data).await?;
+            namada.submit(tx, &args.tx).await?;
+        }
+        remaining = later;
+    }
+    Ok(())
+}
+
 pub async fn submit_shielding_transfer(
     namada: &impl Namada,
     args: args::TxShieldingTransfer,
@@ -989,6 +1186,71 @@ where
             tx::build_pgf_stewards_proposal(namada, &args, proposal).await?,
             proposal_author,
         )
+    } else if args.is_parameter_change {
+        let proposal =
+            ParameterChangeProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = namada_sdk::rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+            None,
+        )
+        .await
+        .unwrap();
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+        rpc::display_parameter_changes(namada, &proposal.data).await;
+        let proposal_author = proposal.proposal.author.clone();
+
+        (
+            tx::build_parameter_change_proposal(namada, &args, proposal)
+                .await?,
+            proposal_author,
+        )
+    } else if args.is_upgrade {
+        let proposal = UpgradeProposal::try_from(args.proposal_data.as_ref())
+            .map_err(|e| {
+            error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                e.to_string(),
+            )
+        })?;
+        let author_balance = namada_sdk::rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+            None,
+        )
+        .await
+        .unwrap();
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+        let proposal_author = proposal.proposal.author.clone();
+
+        (
+            tx::build_upgrade_proposal(namada, &args, proposal).await?,
+            proposal_author,
+        )
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1044,6 +1306,14 @@ pub async fn submit_vote_proposal<N: Namada>(
 where
     <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
 {
+    if let Some(proposal) =
+        rpc::query_proposal_by_id(namada.client(), args.proposal_id).await?
+    {
+        if let ProposalType::ParameterChange(changes) = &proposal.r#type {
+            rpc::display_parameter_changes(namada, changes).await;
+        }
+    }
+
     let (mut tx_builder, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
@@ -1237,10 +1507,63 @@ where
 
     if args.tx.dump_tx {
         tx::dump_tx(namada.io(), &args.tx, tx);
-    } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
+        return Ok(());
+    }
 
-        namada.submit(tx, &args.tx).await?;
+    // The rewards redirected to a shielded recipient are paid out to the
+    // source, so they're shielded to the recipient in the same batch
+    let source = args
+        .source
+        .clone()
+        .unwrap_or_else(|| args.validator.clone());
+    let recipient =
+        namada_sdk::rpc::query_reward_recipient(namada.client(), &source)
+            .await?;
+    let rewards =
+        rpc::query_rewards(namada.client(), &args.source, &args.validator)
+            .await;
+    match recipient {
+        Some(RewardRecipient::Shielded(target)) if !rewards.is_zero() => {
+            display_line!(
+                namada.io(),
+                "Shielding the claimed rewards of {} to {target}.",
+                rewards.to_string_native()
+            );
+            let mut shielding_args = args::TxShieldingTransfer {
+                // The rewards are only credited to the source by the claim
+                // in the same batch, so its balance cannot cover them yet
+                tx: args::Tx {
+                    force: true,
+                    ..args.tx.clone()
+                },
+                target,
+                data: vec![args::TxShieldingTransferData {
+                    source,
+                    token: namada.native_token(),
+                    amount: args::InputAmount::Validated(
+                        rewards.native_denominated(),
+                    ),
+                }],
+                tx_code_path: PathBuf::from(tx::TX_TRANSFER_WASM),
+            };
+            let (shielding_tx, shielding_signing_data, _) =
+                shielding_args.build(namada).await?;
+            let (mut batched_tx, batched_signing_data) =
+                tx::build_batch(vec![
+                    (tx, signing_data),
+                    (shielding_tx, shielding_signing_data),
+                ])?;
+            for sig_data in batched_signing_data {
+                sign(namada, &mut batched_tx, &args.tx, sig_data).await?;
+            }
+
+            namada.submit(batched_tx, &args.tx).await?;
+        }
+        _ => {
+            sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+            namada.submit(tx, &args.tx).await?;
+        }
     }
 
     Ok(())
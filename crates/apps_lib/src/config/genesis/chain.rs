@@ -409,6 +409,11 @@ impl Finalized {
             liveness_threshold,
             rewards_gain_p,
             rewards_gain_d,
+            max_validator_stake_share,
+            instant_unbond_penalty,
+            instant_unbond_penalty_to_pgf,
+            min_self_bond,
+            min_self_bond_ratio,
         } = self.parameters.pos_params.clone();
 
         namada_sdk::proof_of_stake::parameters::PosParams {
@@ -429,6 +434,11 @@ impl Finalized {
                 liveness_threshold,
                 rewards_gain_p,
                 rewards_gain_d,
+                max_validator_stake_share,
+                instant_unbond_penalty,
+                instant_unbond_penalty_to_pgf,
+                min_self_bond,
+                min_self_bond_ratio,
             },
             max_proposal_period: self.parameters.gov_params.max_proposal_period,
         }
@@ -445,6 +455,7 @@ impl Finalized {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             max_proposal_latency,
+            veto_threshold,
         } = self.parameters.gov_params.clone();
         namada_sdk::governance::parameters::GovernanceParameters {
             min_proposal_fund: Amount::native_whole(min_proposal_fund),
@@ -454,6 +465,7 @@ impl Finalized {
             min_proposal_grace_epochs,
             min_proposal_voting_period,
             max_proposal_latency,
+            veto_threshold,
         }
     }
 
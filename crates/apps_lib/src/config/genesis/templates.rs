@@ -13,6 +13,7 @@ use namada_sdk::dec::Dec;
 use namada_sdk::eth_bridge::storage::parameters::{
     Contracts, Erc20WhitelistEntry, MinimumConfirmations,
 };
+use namada_sdk::parameter_change::GovernanceParameterChange;
 use namada_sdk::parameters::ProposalBytes;
 use namada_sdk::token::{
     Amount, DenominatedAmount, Denomination, NATIVE_MAX_DECIMAL_PLACES,
@@ -415,6 +416,26 @@ pub struct PosParams {
     pub rewards_gain_p: Dec,
     /// PoS gain d (read only)
     pub rewards_gain_d: Dec,
+    /// The maximum share of the total consensus stake that a bond or a
+    /// redelegation may bring a validator to, if any
+    #[serde(default)]
+    pub max_validator_stake_share: Option<Dec>,
+    /// The fraction of the tokens charged for an instant unbond, if instant
+    /// unbonds are enabled
+    #[serde(default)]
+    pub instant_unbond_penalty: Option<Dec>,
+    /// Whether the instant unbond penalties are sent to PGF instead of being
+    /// burned
+    #[serde(default)]
+    pub instant_unbond_penalty_to_pgf: bool,
+    /// The minimum amount that a validator in the `consensus` or
+    /// `below_capacity` validator sets has to self-bond, if any
+    #[serde(default)]
+    pub min_self_bond: Option<token::Amount>,
+    /// The minimum ratio of a validator's self-bond to its total stake while
+    /// it's in the `consensus` or `below_capacity` validator sets, if any
+    #[serde(default)]
+    pub min_self_bond_ratio: Option<Dec>,
 }
 
 #[derive(
@@ -444,6 +465,9 @@ pub struct GovernanceParams {
     pub min_proposal_grace_epochs: u64,
     /// Maximum number of epochs between current epoch and start epochs
     pub max_proposal_latency: u64,
+    /// Fraction of the voted power above which the veto votes reject a
+    /// proposal and burn its deposit
+    pub veto_threshold: Dec,
 }
 
 #[derive(
@@ -883,6 +907,14 @@ pub fn validate_parameters(
             );
         }
     }
+    if let Err(err) = GovernanceParameterChange::VetoThreshold(
+        parameters.gov_params.veto_threshold,
+    )
+    .validate()
+    {
+        eprintln!("Invalid governance parameters: {err}");
+        is_valid = false;
+    }
     let Parameters {
         parameters,
         pos_params,
@@ -114,6 +114,13 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When set, the values of all the storage keys are archived at every
+    /// block height, so that they can be queried at any past height.
+    #[serde(default)]
+    pub archival_mode: bool,
+    /// When set in archival mode, indicates for how many blocks in the past
+    /// the archived values must be kept. When not set, they are kept forever.
+    pub archival_retention_blocks: Option<NonZeroU64>,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -151,6 +158,8 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                archival_mode: false,
+                archival_retention_blocks: None,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
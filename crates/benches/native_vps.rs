@@ -20,7 +20,9 @@ use namada_apps_lib::eth_bridge::storage::whitelist;
 use namada_apps_lib::eth_bridge_pool::{GasFee, PendingTransfer};
 use namada_apps_lib::gas::{TxGasMeter, VpGasMeter};
 use namada_apps_lib::governance::pgf::storage::steward::StewardDetail;
-use namada_apps_lib::governance::storage::proposal::ProposalType;
+use namada_apps_lib::governance::storage::proposal::{
+    ProposalType, VotingMode,
+};
 use namada_apps_lib::governance::storage::vote::ProposalVote;
 use namada_apps_lib::governance::{InitProposalData, VoteProposalData};
 use namada_apps_lib::ibc::core::channel::types::channel::Order;
@@ -143,6 +145,7 @@ fn governance(c: &mut Criterion) {
                             .unchecked_add(3_u64),
                         activation_epoch: voting_start_epoch
                             .unchecked_add(9_u64),
+                        voting_mode: VotingMode::default(),
                     },
                     None,
                     Some(vec![content_section]),
@@ -200,6 +203,7 @@ fn governance(c: &mut Criterion) {
                             .unchecked_add(3_u64),
                         activation_epoch: voting_start_epoch
                             .unchecked_add(9_u64),
+                        voting_mode: VotingMode::default(),
                     },
                     None,
                     Some(vec![content_section, wasm_code_section]),
@@ -8,7 +8,7 @@ use std::hash::Hash;
 use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use data_encoding::HEXUPPER;
+use data_encoding::{HEXLOWER, HEXUPPER};
 use ibc::primitives::Signer;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -147,6 +147,11 @@ impl From<raw::Address<'_, raw::Validated>> for Address {
             raw::Discriminant::ReplayProtection => {
                 Address::Internal(InternalAddress::ReplayProtection)
             }
+            raw::Discriminant::LiquidStakingToken => {
+                Address::Internal(InternalAddress::LiquidStakingToken(
+                    LiquidStakingTokenHash(*raw_addr.data()),
+                ))
+            }
         }
     }
 }
@@ -253,6 +258,14 @@ impl<'addr> From<&'addr Address> for raw::Address<'addr, raw::Validated> {
                 .validate()
                 .expect("This raw address is valid")
             }
+            Address::Internal(InternalAddress::LiquidStakingToken(
+                LiquidStakingTokenHash(hash),
+            )) => raw::Address::from_discriminant(
+                raw::Discriminant::LiquidStakingToken,
+            )
+            .with_data_array_ref(hash)
+            .validate()
+            .expect("This raw address is valid"),
         }
     }
 }
@@ -591,6 +604,8 @@ pub enum InternalAddress {
     Masp,
     /// Replay protection
     ReplayProtection,
+    /// Liquid staking token minted by PoS for the delegations to a validator
+    LiquidStakingToken(LiquidStakingTokenHash),
     /// Address with temporary storage is used to pass data from txs to VPs
     /// which is never committed to DB
     TempStorage,
@@ -616,6 +631,9 @@ impl Display for InternalAddress {
                 Self::Pgf => "PGF".to_string(),
                 Self::Masp => "MASP".to_string(),
                 Self::ReplayProtection => "ReplayProtection".to_string(),
+                Self::LiquidStakingToken(hash) => {
+                    format!("LiquidStakingToken: {hash}")
+                }
                 Self::TempStorage => "TempStorage".to_string(),
             }
         )
@@ -639,6 +657,42 @@ impl InternalAddress {
     }
 }
 
+/// Liquid staking token hash derived from the address of a validator.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+#[repr(transparent)]
+pub struct LiquidStakingTokenHash(pub [u8; HASH_LEN]);
+
+impl LiquidStakingTokenHash {
+    /// Derive the liquid staking token hash of the given validator.
+    pub fn from_validator(validator: &Address) -> Self {
+        let hash = Sha256::digest(validator.encode().as_bytes());
+        let mut output = [0; HASH_LEN];
+        output.copy_from_slice(&hash[..HASH_LEN]);
+        Self(output)
+    }
+}
+
+impl Display for LiquidStakingTokenHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.0))
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use borsh_ext::BorshSerializeExt;
@@ -847,6 +901,7 @@ pub mod testing {
             InternalAddress::Masp => {}
             InternalAddress::Multitoken => {}
             InternalAddress::ReplayProtection => {}
+            InternalAddress::LiquidStakingToken(_) => {}
             InternalAddress::TempStorage => {} /* Add new addresses in the
                                                 * `prop_oneof` below. */
         };
@@ -865,6 +920,7 @@ pub mod testing {
             Just(InternalAddress::Pgf),
             Just(InternalAddress::Masp),
             Just(InternalAddress::ReplayProtection),
+            arb_liquid_staking_token(),
             Just(InternalAddress::TempStorage),
         ]
     }
@@ -902,6 +958,16 @@ pub mod testing {
         })
     }
 
+    fn arb_liquid_staking_token() -> impl Strategy<Value = InternalAddress> {
+        arb_established_address().prop_map(|validator| {
+            InternalAddress::LiquidStakingToken(
+                LiquidStakingTokenHash::from_validator(&Address::Established(
+                    validator,
+                )),
+            )
+        })
+    }
+
     /// NAM token address for testing
     pub fn nam() -> Address {
         Address::decode("tnam1q99c37u38grkdcc2qze0hz4zjjd8zr3yucd3mzgz")
@@ -69,6 +69,8 @@ pub enum Discriminant {
     TempStorage = 15,
     /// Replay protection
     ReplayProtection = 16,
+    /// Liquid staking token raw address.
+    LiquidStakingToken = 17,
 }
 
 /// Raw address representation.
@@ -118,7 +120,8 @@ impl<S> Address<'_, S> {
                 | Discriminant::Established
                 | Discriminant::Erc20
                 | Discriminant::Nut
-                | Discriminant::IbcToken,
+                | Discriminant::IbcToken
+                | Discriminant::LiquidStakingToken,
         )
     }
 }
@@ -77,6 +77,7 @@ pub mod internal;
 pub mod keccak;
 pub mod key;
 pub mod masp;
+pub mod parameter_change;
 pub mod parameters;
 pub mod storage;
 pub mod string_encoding;
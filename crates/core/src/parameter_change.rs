@@ -0,0 +1,887 @@
+//! Typed changes of the system parameters that can be proposed by governance
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display};
+
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::address::Address;
+use crate::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use crate::dec::Dec;
+use crate::parameters::{Parameters, ProposalBytes};
+use crate::token;
+
+#[allow(missing_docs)]
+#[derive(Debug, Error)]
+pub enum ParameterChangeError {
+    #[error("The parameter change doesn't change any parameter")]
+    Empty,
+    #[error("The parameter {0} is changed more than once")]
+    Duplicate(&'static str),
+    #[error("Invalid value of the parameter {0}: {1}")]
+    InvalidValue(&'static str, String),
+}
+
+/// A change of a single system parameter
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterChange {
+    /// A change of a protocol parameter
+    Protocol(ProtocolParameterChange),
+    /// A change of a PoS parameter
+    Pos(PosParameterChange),
+    /// A change of a governance parameter
+    Governance(GovernanceParameterChange),
+    /// A change of a PGF parameter
+    Pgf(PgfParameterChange),
+    /// A change of an IBC parameter
+    Ibc(IbcParameterChange),
+}
+
+impl ParameterChange {
+    /// The name of the changed parameter, prefixed by its system
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParameterChange::Protocol(change) => change.name(),
+            ParameterChange::Pos(change) => change.name(),
+            ParameterChange::Governance(change) => change.name(),
+            ParameterChange::Pgf(change) => change.name(),
+            ParameterChange::Ibc(change) => change.name(),
+        }
+    }
+
+    /// The new value of the parameter, formatted for display
+    pub fn value(&self) -> String {
+        match self {
+            ParameterChange::Protocol(change) => change.value(),
+            ParameterChange::Pos(change) => change.value(),
+            ParameterChange::Governance(change) => change.value(),
+            ParameterChange::Pgf(change) => change.value(),
+            ParameterChange::Ibc(change) => change.value(),
+        }
+    }
+
+    /// Validate the new value of the parameter, independently of the current
+    /// value of the other parameters
+    pub fn validate(&self) -> Result<(), ParameterChangeError> {
+        match self {
+            ParameterChange::Protocol(change) => change.validate(),
+            ParameterChange::Pos(change) => change.validate(),
+            ParameterChange::Governance(change) => change.validate(),
+            ParameterChange::Pgf(change) => change.validate(),
+            ParameterChange::Ibc(change) => change.validate(),
+        }
+    }
+}
+
+impl Display for ParameterChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name(), self.value())
+    }
+}
+
+/// Validate a set of parameter changes. The set must not be empty, each
+/// parameter must be changed at most once and every new value must be valid.
+pub fn validate_parameter_changes<'a>(
+    changes: impl IntoIterator<Item = &'a ParameterChange>,
+) -> Result<(), ParameterChangeError> {
+    let mut names = BTreeSet::new();
+    for change in changes {
+        if !names.insert(change.name()) {
+            return Err(ParameterChangeError::Duplicate(change.name()));
+        }
+        change.validate()?;
+    }
+    if names.is_empty() {
+        return Err(ParameterChangeError::Empty);
+    }
+    Ok(())
+}
+
+/// A change of a protocol parameter
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolParameterChange {
+    /// Max payload size, in bytes, for a mempool tx
+    MaxTxBytes(u32),
+    /// Max payload size, in bytes, for a tx batch proposal
+    MaxProposalBytes(u64),
+    /// Max gas for block
+    MaxBlockGas(u64),
+    /// Allowed validity predicate hashes
+    VpAllowlist(Vec<String>),
+    /// Allowed tx hashes
+    TxAllowlist(Vec<String>),
+    /// The gas limit for a masp transaction paying fees
+    MaspFeePaymentGasLimit(u64),
+    /// Gas scale
+    GasScale(u64),
+    /// Map of the cost per gas unit for every token allowed for fee payment
+    MinimumGasPrice(BTreeMap<Address, token::Amount>),
+    /// Enable the native token transfer if it is true
+    IsNativeTokenTransferable(bool),
+}
+
+impl ProtocolParameterChange {
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProtocolParameterChange::MaxTxBytes(_) => "protocol.max_tx_bytes",
+            ProtocolParameterChange::MaxProposalBytes(_) => {
+                "protocol.max_proposal_bytes"
+            }
+            ProtocolParameterChange::MaxBlockGas(_) => "protocol.max_block_gas",
+            ProtocolParameterChange::VpAllowlist(_) => "protocol.vp_allowlist",
+            ProtocolParameterChange::TxAllowlist(_) => "protocol.tx_allowlist",
+            ProtocolParameterChange::MaspFeePaymentGasLimit(_) => {
+                "protocol.masp_fee_payment_gas_limit"
+            }
+            ProtocolParameterChange::GasScale(_) => "protocol.gas_scale",
+            ProtocolParameterChange::MinimumGasPrice(_) => {
+                "protocol.minimum_gas_price"
+            }
+            ProtocolParameterChange::IsNativeTokenTransferable(_) => {
+                "protocol.is_native_token_transferable"
+            }
+        }
+    }
+
+    /// The new value of the parameter, formatted for display
+    pub fn value(&self) -> String {
+        match self {
+            ProtocolParameterChange::MaxTxBytes(value) => value.to_string(),
+            ProtocolParameterChange::MaxProposalBytes(value)
+            | ProtocolParameterChange::MaxBlockGas(value)
+            | ProtocolParameterChange::MaspFeePaymentGasLimit(value)
+            | ProtocolParameterChange::GasScale(value) => value.to_string(),
+            ProtocolParameterChange::VpAllowlist(hashes)
+            | ProtocolParameterChange::TxAllowlist(hashes) => {
+                format!("[{}]", hashes.join(", "))
+            }
+            ProtocolParameterChange::MinimumGasPrice(prices) => format!(
+                "{{{}}}",
+                prices
+                    .iter()
+                    .map(|(token, price)| format!("{token}: {price}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ProtocolParameterChange::IsNativeTokenTransferable(value) => {
+                value.to_string()
+            }
+        }
+    }
+
+    /// Validate the new value of the parameter
+    pub fn validate(&self) -> Result<(), ParameterChangeError> {
+        match self {
+            ProtocolParameterChange::MaxTxBytes(0) => {
+                Err(ParameterChangeError::InvalidValue(self.name(), "0".into()))
+            }
+            ProtocolParameterChange::MaxProposalBytes(value)
+                if ProposalBytes::new(*value).is_none() =>
+            {
+                Err(ParameterChangeError::InvalidValue(
+                    self.name(),
+                    format!(
+                        "{value} is not in the range 1 - {}",
+                        ProposalBytes::MAX.get()
+                    ),
+                ))
+            }
+            ProtocolParameterChange::MaxBlockGas(0)
+            | ProtocolParameterChange::GasScale(0) => {
+                Err(ParameterChangeError::InvalidValue(self.name(), "0".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply the change to the given parameters. Fails without changing them
+    /// if the new value is invalid.
+    pub fn apply(
+        &self,
+        parameters: &mut Parameters,
+    ) -> Result<(), ParameterChangeError> {
+        self.validate()?;
+        match self {
+            ProtocolParameterChange::MaxTxBytes(value) => {
+                parameters.max_tx_bytes = *value;
+            }
+            ProtocolParameterChange::MaxProposalBytes(value) => {
+                parameters.max_proposal_bytes = ProposalBytes::new(*value)
+                    .ok_or_else(|| {
+                        ParameterChangeError::InvalidValue(
+                            self.name(),
+                            value.to_string(),
+                        )
+                    })?;
+            }
+            ProtocolParameterChange::MaxBlockGas(value) => {
+                parameters.max_block_gas = *value;
+            }
+            ProtocolParameterChange::VpAllowlist(hashes) => {
+                parameters.vp_allowlist.clone_from(hashes);
+            }
+            ProtocolParameterChange::TxAllowlist(hashes) => {
+                parameters.tx_allowlist.clone_from(hashes);
+            }
+            ProtocolParameterChange::MaspFeePaymentGasLimit(value) => {
+                parameters.masp_fee_payment_gas_limit = *value;
+            }
+            ProtocolParameterChange::GasScale(value) => {
+                parameters.gas_scale = *value;
+            }
+            ProtocolParameterChange::MinimumGasPrice(prices) => {
+                parameters.minimum_gas_price.clone_from(prices);
+            }
+            ProtocolParameterChange::IsNativeTokenTransferable(value) => {
+                parameters.is_native_token_transferable = *value;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A change of a PoS parameter. The parameters that determine the layout of
+/// the PoS data (e.g. the pipeline and unbonding lengths) cannot be changed.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PosParameterChange {
+    /// Amount of tokens rewarded to a validator for proposing a block
+    BlockProposerReward(Dec),
+    /// Amount of tokens rewarded to each validator that voted on a block
+    /// proposal
+    BlockVoteReward(Dec),
+    /// Maximum staking rewards rate per annum
+    MaxInflationRate(Dec),
+    /// Target ratio of staked NAM tokens to total NAM tokens
+    TargetStakedRatio(Dec),
+    /// Fraction of validator's stake that should be slashed on a duplicate
+    /// vote
+    DuplicateVoteMinSlashRate(Dec),
+    /// Fraction of validator's stake that should be slashed on a light client
+    /// attack
+    LightClientAttackMinSlashRate(Dec),
+    /// The minimum amount of bonded tokens that a validator needs to be in
+    /// either the `consensus` or `below_capacity` validator sets
+    ValidatorStakeThreshold(token::Amount),
+    /// The minimum required activity of consesus validators, in percentage,
+    /// over the liveness window
+    LivenessThreshold(Dec),
+    /// PoS gain p
+    RewardsGainP(Dec),
+    /// PoS gain d
+    RewardsGainD(Dec),
+    /// The maximum share of the total consensus stake that a bond or a
+    /// redelegation may bring a validator to, if any
+    MaxValidatorStakeShare(Option<Dec>),
+    /// The fraction of the tokens charged for an instant unbond, if instant
+    /// unbonds are enabled
+    InstantUnbondPenalty(Option<Dec>),
+    /// Send the instant unbond penalties to PGF if it is true, otherwise burn
+    /// them
+    InstantUnbondPenaltyToPgf(bool),
+    /// The minimum amount that a validator in the `consensus` or
+    /// `below_capacity` validator sets has to self-bond, if any
+    MinSelfBond(Option<token::Amount>),
+    /// The minimum ratio of a validator's self-bond to its total stake while
+    /// it's in the `consensus` or `below_capacity` validator sets, if any
+    MinSelfBondRatio(Option<Dec>),
+}
+
+impl PosParameterChange {
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            PosParameterChange::BlockProposerReward(_) => {
+                "pos.block_proposer_reward"
+            }
+            PosParameterChange::BlockVoteReward(_) => "pos.block_vote_reward",
+            PosParameterChange::MaxInflationRate(_) => "pos.max_inflation_rate",
+            PosParameterChange::TargetStakedRatio(_) => {
+                "pos.target_staked_ratio"
+            }
+            PosParameterChange::DuplicateVoteMinSlashRate(_) => {
+                "pos.duplicate_vote_min_slash_rate"
+            }
+            PosParameterChange::LightClientAttackMinSlashRate(_) => {
+                "pos.light_client_attack_min_slash_rate"
+            }
+            PosParameterChange::ValidatorStakeThreshold(_) => {
+                "pos.validator_stake_threshold"
+            }
+            PosParameterChange::LivenessThreshold(_) => {
+                "pos.liveness_threshold"
+            }
+            PosParameterChange::RewardsGainP(_) => "pos.rewards_gain_p",
+            PosParameterChange::RewardsGainD(_) => "pos.rewards_gain_d",
+            PosParameterChange::MaxValidatorStakeShare(_) => {
+                "pos.max_validator_stake_share"
+            }
+            PosParameterChange::InstantUnbondPenalty(_) => {
+                "pos.instant_unbond_penalty"
+            }
+            PosParameterChange::InstantUnbondPenaltyToPgf(_) => {
+                "pos.instant_unbond_penalty_to_pgf"
+            }
+            PosParameterChange::MinSelfBond(_) => "pos.min_self_bond",
+            PosParameterChange::MinSelfBondRatio(_) => {
+                "pos.min_self_bond_ratio"
+            }
+        }
+    }
+
+    /// The new value of the parameter, formatted for display
+    pub fn value(&self) -> String {
+        match self {
+            PosParameterChange::ValidatorStakeThreshold(amount) => {
+                amount.to_string_native()
+            }
+            PosParameterChange::BlockProposerReward(value)
+            | PosParameterChange::BlockVoteReward(value)
+            | PosParameterChange::MaxInflationRate(value)
+            | PosParameterChange::TargetStakedRatio(value)
+            | PosParameterChange::DuplicateVoteMinSlashRate(value)
+            | PosParameterChange::LightClientAttackMinSlashRate(value)
+            | PosParameterChange::LivenessThreshold(value)
+            | PosParameterChange::RewardsGainP(value)
+            | PosParameterChange::RewardsGainD(value) => value.to_string(),
+            PosParameterChange::MaxValidatorStakeShare(value)
+            | PosParameterChange::InstantUnbondPenalty(value)
+            | PosParameterChange::MinSelfBondRatio(value) => value
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+            PosParameterChange::InstantUnbondPenaltyToPgf(value) => {
+                value.to_string()
+            }
+            PosParameterChange::MinSelfBond(amount) => amount
+                .map(|amount| amount.to_string_native())
+                .unwrap_or_else(|| "none".to_string()),
+        }
+    }
+
+    /// Validate the new value of the parameter
+    pub fn validate(&self) -> Result<(), ParameterChangeError> {
+        match self {
+            PosParameterChange::ValidatorStakeThreshold(_)
+            | PosParameterChange::MaxValidatorStakeShare(None)
+            | PosParameterChange::InstantUnbondPenalty(None)
+            | PosParameterChange::InstantUnbondPenaltyToPgf(_)
+            | PosParameterChange::MinSelfBond(_)
+            | PosParameterChange::MinSelfBondRatio(None) => Ok(()),
+            PosParameterChange::MaxValidatorStakeShare(Some(value)) => {
+                validate_fraction(self.name(), value)?;
+                if value.is_zero() {
+                    return Err(ParameterChangeError::InvalidValue(
+                        self.name(),
+                        "the share cannot be zero".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            PosParameterChange::InstantUnbondPenalty(Some(value)) => {
+                validate_fraction(self.name(), value)?;
+                if value.is_zero() {
+                    return Err(ParameterChangeError::InvalidValue(
+                        self.name(),
+                        "the penalty cannot be zero".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            PosParameterChange::RewardsGainP(value)
+            | PosParameterChange::RewardsGainD(value) => {
+                validate_non_negative(self.name(), value)
+            }
+            PosParameterChange::BlockProposerReward(value)
+            | PosParameterChange::BlockVoteReward(value)
+            | PosParameterChange::MaxInflationRate(value)
+            | PosParameterChange::TargetStakedRatio(value)
+            | PosParameterChange::DuplicateVoteMinSlashRate(value)
+            | PosParameterChange::LightClientAttackMinSlashRate(value)
+            | PosParameterChange::LivenessThreshold(value)
+            | PosParameterChange::MinSelfBondRatio(Some(value)) => {
+                validate_fraction(self.name(), value)
+            }
+        }
+    }
+}
+
+/// A change of a governance parameter
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceParameterChange {
+    /// Minimum amount of locked funds
+    MinProposalFund(token::Amount),
+    /// Maximum length for proposal code in bytes
+    MaxProposalCodeSize(u64),
+    /// Minimum number of epochs between the proposal end epoch and start epoch
+    MinProposalVotingPeriod(u64),
+    /// Maximum number of epochs between the proposal start epoch and
+    /// activation epoch
+    MaxProposalPeriod(u64),
+    /// Maximum number of characters for proposal content
+    MaxProposalContentSize(u64),
+    /// Minimum number of epochs between the end and activation epochs
+    MinProposalGraceEpochs(u64),
+    /// Maximum number of epochs between current epoch and start epoch
+    MaxProposalLatency(u64),
+    /// Fraction of the voted power above which the veto votes reject a
+    /// proposal and burn its deposit
+    VetoThreshold(Dec),
+}
+
+impl GovernanceParameterChange {
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            GovernanceParameterChange::MinProposalFund(_) => {
+                "governance.min_proposal_fund"
+            }
+            GovernanceParameterChange::MaxProposalCodeSize(_) => {
+                "governance.max_proposal_code_size"
+            }
+            GovernanceParameterChange::MinProposalVotingPeriod(_) => {
+                "governance.min_proposal_voting_period"
+            }
+            GovernanceParameterChange::MaxProposalPeriod(_) => {
+                "governance.max_proposal_period"
+            }
+            GovernanceParameterChange::MaxProposalContentSize(_) => {
+                "governance.max_proposal_content_size"
+            }
+            GovernanceParameterChange::MinProposalGraceEpochs(_) => {
+                "governance.min_proposal_grace_epochs"
+            }
+            GovernanceParameterChange::MaxProposalLatency(_) => {
+                "governance.max_proposal_latency"
+            }
+            GovernanceParameterChange::VetoThreshold(_) => {
+                "governance.veto_threshold"
+            }
+        }
+    }
+
+    /// The new value of the parameter, formatted for display
+    pub fn value(&self) -> String {
+        match self {
+            GovernanceParameterChange::MinProposalFund(amount) => {
+                amount.to_string_native()
+            }
+            GovernanceParameterChange::MaxProposalCodeSize(value)
+            | GovernanceParameterChange::MinProposalVotingPeriod(value)
+            | GovernanceParameterChange::MaxProposalPeriod(value)
+            | GovernanceParameterChange::MaxProposalContentSize(value)
+            | GovernanceParameterChange::MinProposalGraceEpochs(value)
+            | GovernanceParameterChange::MaxProposalLatency(value) => {
+                value.to_string()
+            }
+            GovernanceParameterChange::VetoThreshold(value) => {
+                value.to_string()
+            }
+        }
+    }
+
+    /// Validate the new value of the parameter
+    pub fn validate(&self) -> Result<(), ParameterChangeError> {
+        match self {
+            GovernanceParameterChange::MinProposalVotingPeriod(0)
+            | GovernanceParameterChange::MaxProposalPeriod(0) => {
+                Err(ParameterChangeError::InvalidValue(self.name(), "0".into()))
+            }
+            GovernanceParameterChange::VetoThreshold(value) => {
+                validate_fraction(self.name(), value)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A change of a PGF parameter. The stewards are changed with PGF steward
+/// proposals instead.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PgfParameterChange {
+    /// The pgf funding inflation rate
+    PgfInflationRate(Dec),
+    /// The pgf stewards inflation rate
+    StewardsInflationRate(Dec),
+    /// The maximum number of pgf stewards at once
+    MaximumNumberOfStewards(u64),
+}
+
+impl PgfParameterChange {
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            PgfParameterChange::PgfInflationRate(_) => "pgf.pgf_inflation_rate",
+            PgfParameterChange::StewardsInflationRate(_) => {
+                "pgf.stewards_inflation_rate"
+            }
+            PgfParameterChange::MaximumNumberOfStewards(_) => {
+                "pgf.maximum_number_of_stewards"
+            }
+        }
+    }
+
+    /// The new value of the parameter, formatted for display
+    pub fn value(&self) -> String {
+        match self {
+            PgfParameterChange::PgfInflationRate(value)
+            | PgfParameterChange::StewardsInflationRate(value) => {
+                value.to_string()
+            }
+            PgfParameterChange::MaximumNumberOfStewards(value) => {
+                value.to_string()
+            }
+        }
+    }
+
+    /// Validate the new value of the parameter
+    pub fn validate(&self) -> Result<(), ParameterChangeError> {
+        match self {
+            PgfParameterChange::PgfInflationRate(value)
+            | PgfParameterChange::StewardsInflationRate(value) => {
+                validate_fraction(self.name(), value)
+            }
+            PgfParameterChange::MaximumNumberOfStewards(_) => Ok(()),
+        }
+    }
+}
+
+/// A change of an IBC parameter
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IbcParameterChange {
+    /// Default supply limit of each token
+    DefaultMintLimit(token::Amount),
+    /// Default per-epoch throughput limit of each token
+    DefaultPerEpochThroughputLimit(token::Amount),
+}
+
+impl IbcParameterChange {
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            IbcParameterChange::DefaultMintLimit(_) => "ibc.default_mint_limit",
+            IbcParameterChange::DefaultPerEpochThroughputLimit(_) => {
+                "ibc.default_per_epoch_throughput_limit"
+            }
+        }
+    }
+
+    /// The new value of the parameter, formatted for display
+    pub fn value(&self) -> String {
+        match self {
+            IbcParameterChange::DefaultMintLimit(amount)
+            | IbcParameterChange::DefaultPerEpochThroughputLimit(amount) => {
+                amount.to_string()
+            }
+        }
+    }
+
+    /// Validate the new value of the parameter
+    pub fn validate(&self) -> Result<(), ParameterChangeError> {
+        Ok(())
+    }
+}
+
+fn validate_non_negative(
+    name: &'static str,
+    value: &Dec,
+) -> Result<(), ParameterChangeError> {
+    if value.is_negative() {
+        return Err(ParameterChangeError::InvalidValue(
+            name,
+            format!("{value} is negative"),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_fraction(
+    name: &'static str,
+    value: &Dec,
+) -> Result<(), ParameterChangeError> {
+    validate_non_negative(name, value)?;
+    if *value > Dec::one() {
+        return Err(ParameterChangeError::InvalidValue(
+            name,
+            format!("{value} is greater than 1"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(any(test, feature = "testing"))]
+/// Tests and strategies for parameter changes
+pub mod testing {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::dec::testing::arb_non_negative_dec;
+    use crate::token::testing::arb_amount;
+
+    /// Generate an arbitrary valid fraction
+    fn arb_fraction() -> impl Strategy<Value = Dec> {
+        (0_u64..=100).prop_map(|percent| Dec::new(percent.into(), 2).unwrap())
+    }
+
+    /// Generate an arbitrary protocol parameter change
+    pub fn arb_protocol_parameter_change(
+    ) -> impl Strategy<Value = ProtocolParameterChange> {
+        prop_oneof![
+            (1_u32..).prop_map(ProtocolParameterChange::MaxTxBytes),
+            (1..=ProposalBytes::MAX.get())
+                .prop_map(ProtocolParameterChange::MaxProposalBytes),
+            (1_u64..).prop_map(ProtocolParameterChange::MaxBlockGas),
+            any::<bool>()
+                .prop_map(ProtocolParameterChange::IsNativeTokenTransferable),
+        ]
+    }
+
+    /// Generate an arbitrary PoS parameter change
+    pub fn arb_pos_parameter_change(
+    ) -> impl Strategy<Value = PosParameterChange> {
+        prop_oneof![
+            arb_fraction().prop_map(PosParameterChange::BlockProposerReward),
+            arb_fraction().prop_map(PosParameterChange::MaxInflationRate),
+            arb_amount().prop_map(PosParameterChange::ValidatorStakeThreshold),
+            arb_non_negative_dec().prop_map(PosParameterChange::RewardsGainP),
+            proptest::option::of(
+                (1_u64..=100)
+                    .prop_map(|percent| Dec::new(percent.into(), 2).unwrap())
+            )
+            .prop_map(PosParameterChange::MaxValidatorStakeShare),
+            proptest::option::of(
+                (1_u64..=100)
+                    .prop_map(|percent| Dec::new(percent.into(), 2).unwrap())
+            )
+            .prop_map(PosParameterChange::InstantUnbondPenalty),
+            any::<bool>()
+                .prop_map(PosParameterChange::InstantUnbondPenaltyToPgf),
+            proptest::option::of(arb_amount())
+                .prop_map(PosParameterChange::MinSelfBond),
+            proptest::option::of(arb_fraction())
+                .prop_map(PosParameterChange::MinSelfBondRatio),
+        ]
+    }
+
+    /// Generate an arbitrary governance parameter change
+    pub fn arb_governance_parameter_change(
+    ) -> impl Strategy<Value = GovernanceParameterChange> {
+        prop_oneof![
+            arb_amount().prop_map(GovernanceParameterChange::MinProposalFund),
+            (1_u64..)
+                .prop_map(GovernanceParameterChange::MinProposalVotingPeriod),
+            arb_fraction().prop_map(GovernanceParameterChange::VetoThreshold),
+        ]
+    }
+
+    /// Generate an arbitrary PGF parameter change
+    pub fn arb_pgf_parameter_change(
+    ) -> impl Strategy<Value = PgfParameterChange> {
+        prop_oneof![
+            arb_fraction().prop_map(PgfParameterChange::PgfInflationRate),
+            any::<u64>().prop_map(PgfParameterChange::MaximumNumberOfStewards),
+        ]
+    }
+
+    /// Generate an arbitrary IBC parameter change
+    pub fn arb_ibc_parameter_change(
+    ) -> impl Strategy<Value = IbcParameterChange> {
+        prop_oneof![
+            arb_amount().prop_map(IbcParameterChange::DefaultMintLimit),
+            arb_amount()
+                .prop_map(IbcParameterChange::DefaultPerEpochThroughputLimit),
+        ]
+    }
+
+    /// Generate an arbitrary valid parameter change
+    pub fn arb_parameter_change() -> impl Strategy<Value = ParameterChange> {
+        prop_oneof![
+            arb_protocol_parameter_change().prop_map(ParameterChange::Protocol),
+            arb_pos_parameter_change().prop_map(ParameterChange::Pos),
+            arb_governance_parameter_change()
+                .prop_map(ParameterChange::Governance),
+            arb_pgf_parameter_change().prop_map(ParameterChange::Pgf),
+            arb_ibc_parameter_change().prop_map(ParameterChange::Ibc),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::testing::arb_parameter_change;
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_arb_parameter_change_is_valid(
+            change in arb_parameter_change()
+        ) {
+            assert!(change.validate().is_ok());
+            assert!(validate_parameter_changes([&change]).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_parameter_changes() {
+        assert!(matches!(
+            validate_parameter_changes([]),
+            Err(ParameterChangeError::Empty)
+        ));
+
+        let reward = ParameterChange::Pos(
+            PosParameterChange::BlockProposerReward(Dec::new(5, 2).unwrap()),
+        );
+        let other_reward = ParameterChange::Pos(
+            PosParameterChange::BlockProposerReward(Dec::new(6, 2).unwrap()),
+        );
+        assert!(validate_parameter_changes([&reward]).is_ok());
+        assert!(matches!(
+            validate_parameter_changes([&reward, &other_reward]),
+            Err(ParameterChangeError::Duplicate("pos.block_proposer_reward"))
+        ));
+
+        let rate_above_one = ParameterChange::Pgf(
+            PgfParameterChange::PgfInflationRate(Dec::two()),
+        );
+        assert!(matches!(
+            validate_parameter_changes([&reward, &rate_above_one]),
+            Err(ParameterChangeError::InvalidValue(
+                "pgf.pgf_inflation_rate",
+                _
+            ))
+        ));
+        let no_gas =
+            ParameterChange::Protocol(ProtocolParameterChange::MaxBlockGas(0));
+        assert!(no_gas.validate().is_err());
+        let no_penalty = ParameterChange::Pos(
+            PosParameterChange::InstantUnbondPenalty(Some(Dec::zero())),
+        );
+        assert!(no_penalty.validate().is_err());
+    }
+
+    #[test]
+    fn test_apply_protocol_parameter_change() {
+        let mut parameters = Parameters::default();
+        ProtocolParameterChange::MaxBlockGas(42)
+            .apply(&mut parameters)
+            .unwrap();
+        ProtocolParameterChange::IsNativeTokenTransferable(false)
+            .apply(&mut parameters)
+            .unwrap();
+        assert_eq!(parameters.max_block_gas, 42);
+        assert!(!parameters.is_native_token_transferable);
+        assert_eq!(parameters.max_tx_bytes, Parameters::default().max_tx_bytes);
+
+        // Invalid values are rejected
+        let max_proposal_bytes = parameters.max_proposal_bytes;
+        assert!(matches!(
+            ProtocolParameterChange::MaxProposalBytes(0).apply(&mut parameters),
+            Err(ParameterChangeError::InvalidValue(
+                "protocol.max_proposal_bytes",
+                _
+            ))
+        ));
+        assert_eq!(parameters.max_proposal_bytes, max_proposal_bytes);
+    }
+}
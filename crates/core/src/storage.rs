@@ -92,6 +92,10 @@ pub enum DbColFam {
     ROLLBACK,
     /// Replay protection
     REPLAYPROT,
+    /// Archive of the values of all subspace keys at every height
+    ARCHIVE,
+    /// Index of the blocks with MASP txs and their compact notes
+    MASPINDEX,
 }
 
 /// Subspace column family name
@@ -106,6 +110,12 @@ pub const STATE_CF: &str = "state";
 pub const BLOCK_CF: &str = "block";
 /// Replay protection column family name
 pub const REPLAY_PROTECTION_CF: &str = "replay_protection";
+/// Archive of the values of all subspace keys at every height column family
+/// name
+pub const ARCHIVE_CF: &str = "archive";
+/// Index of the blocks with MASP txs and their compact notes column family
+/// name
+pub const MASP_INDEX_CF: &str = "masp_index";
 
 impl DbColFam {
     /// Get the name of the column family
@@ -117,11 +127,13 @@ impl DbColFam {
             DbColFam::DIFFS => DIFFS_CF,
             DbColFam::ROLLBACK => ROLLBACK_CF,
             DbColFam::REPLAYPROT => REPLAY_PROTECTION_CF,
+            DbColFam::ARCHIVE => ARCHIVE_CF,
+            DbColFam::MASPINDEX => MASP_INDEX_CF,
         }
     }
 
     /// Return an array of all column families
-    pub fn all() -> [&'static str; 6] {
+    pub fn all() -> [&'static str; 8] {
         [
             SUBSPACE_CF,
             BLOCK_CF,
@@ -129,6 +141,8 @@ impl DbColFam {
             DIFFS_CF,
             ROLLBACK_CF,
             REPLAY_PROTECTION_CF,
+            ARCHIVE_CF,
+            MASP_INDEX_CF,
         ]
     }
 }
@@ -144,6 +158,8 @@ impl FromStr for DbColFam {
             STATE_CF => Ok(Self::STATE),
             REPLAY_PROTECTION_CF => Ok(Self::REPLAYPROT),
             BLOCK_CF => Ok(Self::BLOCK),
+            ARCHIVE_CF => Ok(Self::ARCHIVE),
+            MASP_INDEX_CF => Ok(Self::MASPINDEX),
             _ => Err(Error::DbColFamily(s.to_string())),
         }
     }
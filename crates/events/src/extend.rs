@@ -490,7 +490,17 @@ impl EventAttributeEntry<'static> for Info {
 
 /// A type representing the possible reference to some MASP data, either a masp
 /// section or ibc tx data
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
 pub enum MaspTxRef {
     /// Reference to a MASP section
     MaspSection(MaspTxId),
@@ -1,9 +1,10 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::address::Address;
 use namada_core::chain::Epoch;
+use namada_core::parameter_change::ParameterChange;
 use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -13,11 +14,12 @@ use serde::{Deserialize, Serialize};
 use super::validation::{
     is_valid_activation_epoch, is_valid_author_balance, is_valid_content,
     is_valid_default_proposal_data, is_valid_end_epoch,
-    is_valid_pgf_funding_data, is_valid_pgf_stewards_data,
-    is_valid_proposal_period, is_valid_start_epoch, ProposalValidation,
+    is_valid_parameter_change_data, is_valid_pgf_funding_data,
+    is_valid_pgf_stewards_data, is_valid_proposal_period, is_valid_start_epoch,
+    is_valid_voting_mode, ProposalValidation,
 };
 use crate::parameters::GovernanceParameters;
-use crate::storage::proposal::PGFTarget;
+use crate::storage::proposal::{PGFTarget, ScheduledUpgrade, VotingMode};
 
 #[derive(
     Debug,
@@ -40,6 +42,9 @@ pub struct OnChainProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch in which any changes are executed and become active
     pub activation_epoch: Epoch,
+    /// How votes are cast and tallied
+    #[serde(default)]
+    pub voting_mode: VotingMode,
 }
 
 /// PGF default proposal
@@ -101,6 +106,9 @@ impl DefaultProposal {
             &self.data,
             governance_parameters.max_proposal_code_size,
         )?;
+        // Proposals with wasm code can't have options to choose from
+        let has_wasm = self.data.as_ref().is_some_and(|data| !data.is_empty());
+        is_valid_voting_mode(&self.proposal.voting_mode, !has_wasm)?;
 
         Ok(self)
     }
@@ -171,6 +179,7 @@ impl PgfStewardProposal {
             governance_parameters.max_proposal_content_size,
         )?;
         is_valid_pgf_stewards_data(&self.data, &self.proposal.author)?;
+        is_valid_voting_mode(&self.proposal.voting_mode, false)?;
 
         Ok(self)
     }
@@ -235,6 +244,7 @@ impl PgfFundingProposal {
             governance_parameters.max_proposal_content_size,
         )?;
         is_valid_pgf_funding_data(&self.data)?;
+        is_valid_voting_mode(&self.proposal.voting_mode, false)?;
 
         Ok(self)
     }
@@ -248,6 +258,130 @@ impl TryFrom<&[u8]> for PgfFundingProposal {
     }
 }
 
+/// Parameter change proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterChangeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The changes of the system parameters
+    pub data: BTreeSet<ParameterChange>,
+}
+
+impl ParameterChangeProposal {
+    /// Validate a parameter change proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_start_epoch(self.proposal.voting_start_epoch, current_epoch)?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_activation_epoch(
+            self.proposal.activation_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.activation_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_parameter_change_data(&self.data)?;
+        is_valid_voting_mode(&self.proposal.voting_mode, false)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for ParameterChangeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Software upgrade proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The scheduled upgrade
+    pub data: ScheduledUpgrade,
+}
+
+impl UpgradeProposal {
+    /// Validate a software upgrade proposal. The upgrade height is validated
+    /// against the block height on-chain.
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_start_epoch(self.proposal.voting_start_epoch, current_epoch)?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_activation_epoch(
+            self.proposal.activation_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.activation_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_voting_mode(&self.proposal.voting_mode, false)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for UpgradeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
 /// PGF stewards
 #[derive(
     Debug,
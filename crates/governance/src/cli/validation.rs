@@ -1,12 +1,16 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::address::Address;
 use namada_core::arith::{self, checked};
 use namada_core::chain::Epoch;
+use namada_core::parameter_change::{
+    validate_parameter_changes, ParameterChange,
+};
 use namada_core::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::storage::proposal::VotingMode;
 
 /// This enum represents proposal data
 #[derive(Debug, Error)]
@@ -63,6 +67,12 @@ pub enum ProposalValidation {
     /// The PGF funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The parameter change data is not valid
+    #[error("Invalid proposal extra data: {0}")]
+    InvalidParameterChangeExtraData(String),
+    /// The voting mode is not valid
+    #[error("Invalid proposal voting mode: {0}")]
+    InvalidVotingMode(String),
     #[error("Arithmetic {0}.")]
     Arith(#[from] arith::Error),
 }
@@ -200,6 +210,21 @@ pub fn is_valid_default_proposal_data(
     }
 }
 
+pub fn is_valid_voting_mode(
+    voting_mode: &VotingMode,
+    allow_choices: bool,
+) -> Result<(), ProposalValidation> {
+    if !allow_choices && !voting_mode.is_yay_nay() {
+        return Err(ProposalValidation::InvalidVotingMode(
+            "only default proposals can have options to choose from"
+                .to_string(),
+        ));
+    }
+    voting_mode
+        .validate()
+        .map_err(|err| ProposalValidation::InvalidVotingMode(err.to_string()))
+}
+
 pub fn is_valid_pgf_stewards_data(
     data: &StewardsUpdate,
     author: &Address,
@@ -229,3 +254,11 @@ pub fn is_valid_pgf_funding_data(
         Err(ProposalValidation::InvalidPgfFundingExtraData)
     }
 }
+
+pub fn is_valid_parameter_change_data(
+    data: &BTreeSet<ParameterChange>,
+) -> Result<(), ProposalValidation> {
+    validate_parameter_changes(data).map_err(|e| {
+        ProposalValidation::InvalidParameterChangeExtraData(e.to_string())
+    })
+}
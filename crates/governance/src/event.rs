@@ -2,10 +2,13 @@
 
 use std::collections::BTreeMap;
 
+use namada_core::token;
 use namada_events::extend::{EventAttributeEntry, ExtendAttributesMap};
 use namada_events::{Event, EventLevel, EventToEmit};
 
-use crate::utils::TallyResult as GovTallyResult;
+use crate::utils::{
+    DepositOutcome as GovDepositOutcome, TallyResult as GovTallyResult,
+};
 use crate::ProposalType as GovProposalType;
 
 pub mod types {
@@ -32,10 +35,31 @@ pub mod types {
         "passed"
     );
 
+    /// Proposal passed, but its execution failed.
+    pub const PROPOSAL_FAILED: EventType = namada_events::event_type!(
+        GovernanceEvent,
+        PROPOSAL_SUBDOMAIN,
+        "failed"
+    );
+
+    /// Proposal vetoed.
+    pub const PROPOSAL_VETOED: EventType = namada_events::event_type!(
+        GovernanceEvent,
+        PROPOSAL_SUBDOMAIN,
+        "vetoed"
+    );
+
     /// New proposal.
     pub const NEW_PROPOSAL: EventType =
         namada_events::event_type!(GovernanceEvent, PROPOSAL_SUBDOMAIN, "new");
 
+    /// Proposal deposit refunded or burned.
+    pub const PROPOSAL_DEPOSIT: EventType = namada_events::event_type!(
+        GovernanceEvent,
+        PROPOSAL_SUBDOMAIN,
+        "deposit"
+    );
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -43,6 +67,8 @@ pub mod types {
         #[test]
         fn test_gov_event_type_as_str() {
             assert_eq!(&*PROPOSAL_PASSED, "governance/proposal/passed");
+            assert_eq!(&*PROPOSAL_DEPOSIT, "governance/proposal/deposit");
+            assert_eq!(&*PROPOSAL_FAILED, "governance/proposal/failed");
         }
     }
 }
@@ -86,6 +112,15 @@ impl GovernanceEvent {
         }
     }
 
+    /// Event for a passed proposal whose execution failed, leaving the state
+    /// untouched
+    pub fn failed_proposal(proposal_id: u64) -> Self {
+        Self::Proposal {
+            id: proposal_id,
+            kind: ProposalEventKind::Failed,
+        }
+    }
+
     /// Event for a reject proposal
     pub fn rejected_proposal(
         proposal_id: u64,
@@ -96,6 +131,26 @@ impl GovernanceEvent {
             kind: ProposalEventKind::Rejected { has_proposal_code },
         }
     }
+
+    /// Event for a vetoed proposal
+    pub fn vetoed_proposal(proposal_id: u64, has_proposal_code: bool) -> Self {
+        Self::Proposal {
+            id: proposal_id,
+            kind: ProposalEventKind::Vetoed { has_proposal_code },
+        }
+    }
+
+    /// Event for the refund or burn of a proposal deposit
+    pub fn proposal_deposit(
+        proposal_id: u64,
+        outcome: GovDepositOutcome,
+        amount: token::Amount,
+    ) -> Self {
+        Self::Proposal {
+            id: proposal_id,
+            kind: ProposalEventKind::Deposit { outcome, amount },
+        }
+    }
 }
 
 /// Proposal event kinds
@@ -113,11 +168,25 @@ pub enum ProposalEventKind {
         /// Did the proposal code run successfully?
         is_proposal_code_successful: bool,
     },
+    /// Passed proposal that failed to be executed
+    Failed,
     /// Rejected proposal
     Rejected {
         /// Does the proposal contain code?
         has_proposal_code: bool,
     },
+    /// Vetoed proposal
+    Vetoed {
+        /// Does the proposal contain code?
+        has_proposal_code: bool,
+    },
+    /// Proposal deposit outcome
+    Deposit {
+        /// Was the deposit refunded or burned?
+        outcome: GovDepositOutcome,
+        /// The amount of the deposit
+        amount: token::Amount,
+    },
 }
 
 impl From<GovernanceEvent> for Event {
@@ -149,6 +218,16 @@ impl From<GovernanceEvent> for Event {
                 );
                 (event_type, attributes)
             }
+            ProposalEventKind::Failed => {
+                let event_type = types::PROPOSAL_FAILED;
+                let attributes = ended_governance_proposal_attributes(
+                    GovTallyResult::Passed,
+                    proposal_id,
+                    false,
+                    false,
+                );
+                (event_type, attributes)
+            }
             ProposalEventKind::Rejected { has_proposal_code } => {
                 let event_type = types::PROPOSAL_REJECTED;
                 let attributes = ended_governance_proposal_attributes(
@@ -159,6 +238,25 @@ impl From<GovernanceEvent> for Event {
                 );
                 (event_type, attributes)
             }
+            ProposalEventKind::Vetoed { has_proposal_code } => {
+                let event_type = types::PROPOSAL_VETOED;
+                let attributes = ended_governance_proposal_attributes(
+                    GovTallyResult::Vetoed,
+                    proposal_id,
+                    has_proposal_code,
+                    false,
+                );
+                (event_type, attributes)
+            }
+            ProposalEventKind::Deposit { outcome, amount } => {
+                let event_type = types::PROPOSAL_DEPOSIT;
+                let attributes = governance_proposal_deposit_attributes(
+                    proposal_id,
+                    outcome,
+                    amount,
+                );
+                (event_type, attributes)
+            }
         };
 
         let mut event = Self::new(event_type, EventLevel::Block);
@@ -201,6 +299,21 @@ fn new_governance_proposal_attributes(
     attrs
 }
 
+/// Return the attributes of a governance proposal deposit outcome.
+#[inline]
+fn governance_proposal_deposit_attributes(
+    id: u64,
+    outcome: GovDepositOutcome,
+    amount: token::Amount,
+) -> BTreeMap<String, String> {
+    let mut attrs = BTreeMap::new();
+    attrs
+        .with_attribute(ProposalId(id))
+        .with_attribute(DepositOutcome(outcome))
+        .with_attribute(DepositAmount(amount));
+    attrs
+}
+
 impl EventToEmit for GovernanceEvent {
     const DOMAIN: &'static str = "governance";
 }
@@ -274,3 +387,31 @@ impl EventAttributeEntry<'static> for ProposalCodeExitStatus {
         self.0
     }
 }
+
+/// Extend an [`Event`] with proposal deposit outcome data.
+pub struct DepositOutcome(pub GovDepositOutcome);
+
+impl EventAttributeEntry<'static> for DepositOutcome {
+    type Value = GovDepositOutcome;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "deposit_outcome";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with proposal deposit amount data.
+pub struct DepositAmount(pub token::Amount);
+
+impl EventAttributeEntry<'static> for DepositAmount {
+    type Value = token::Amount;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "deposit_amount";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
@@ -4,14 +4,19 @@ use std::collections::BTreeSet;
 
 use borsh::BorshDeserialize;
 use namada_core::address::Address;
-use namada_core::chain::Epoch;
+use namada_core::chain::{BlockHeight, Epoch};
 use namada_core::collections::HashMap;
 use namada_core::encode;
 use namada_core::ibc::PGFIbcTarget;
+use namada_core::parameter_change::{
+    validate_parameter_changes, ParameterChange,
+};
 use namada_events::extend::{ComposeEvent, Height};
 use namada_events::{EmitEvents, EventLevel};
-use namada_state::{Key, Result, State, StateRead, StorageRead, StorageWrite};
-use namada_systems::{proof_of_stake, trans_token as token};
+use namada_state::{
+    Error, Key, Result, State, StateRead, StorageRead, StorageWrite,
+};
+use namada_systems::{ibc, parameters, proof_of_stake, trans_token as token};
 use namada_tx::data::TxType;
 use namada_tx::{Code, Data, Tx};
 
@@ -20,18 +25,20 @@ use crate::pgf::storage::keys as pgf_keys;
 use crate::pgf::storage::steward::StewardDetail;
 use crate::pgf::{storage as pgf_storage, ADDRESS as PGF_ADDRESS};
 use crate::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
+    AddRemove, PGFAction, PGFTarget, ProposalType, ScheduledUpgrade,
+    StoragePgfFunding,
 };
 use crate::storage::{keys, load_proposals};
 use crate::utils::{
-    compute_proposal_result, ProposalVotes, TallyResult, TallyType, VotePower,
+    compute_proposal_result, DepositOutcome, ProposalVotes, TallyResult,
+    TallyType, VotePower,
 };
 use crate::{storage, ProposalVote, ADDRESS as GOV_ADDRESS};
 
 /// Apply governance updates for a block. On a new epoch, this will look for
 /// proposals to tally completed proposals and execute accepted proposals.
 #[allow(clippy::too_many_arguments)]
-pub fn finalize_block<S, Token, PoS, FnTx, FnIbcTransfer>(
+pub fn finalize_block<S, Token, PoS, Params, Ibc, FnTx, FnIbcTransfer>(
     state: &mut S,
     events: &mut impl EmitEvents,
     current_epoch: Epoch,
@@ -42,7 +49,9 @@ pub fn finalize_block<S, Token, PoS, FnTx, FnIbcTransfer>(
 where
     S: StateRead + State,
     Token: token::Read<S> + token::Write<S> + token::Events<S>,
-    PoS: proof_of_stake::Read<S>,
+    PoS: proof_of_stake::Write<S>,
+    Params: parameters::Write<S>,
+    Ibc: ibc::Write<S>,
     FnTx: FnMut(&Tx, &mut S) -> Result<bool>,
     FnIbcTransfer: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
 {
@@ -51,6 +60,8 @@ where
             S,
             Token,
             PoS,
+            Params,
+            Ibc,
             FnTx,
             FnIbcTransfer,
         >(state, events, current_epoch, dispatch_tx, transfer_over_ibc)?;
@@ -58,7 +69,15 @@ where
     Ok(())
 }
 
-fn load_and_execute_governance_proposals<S, Token, PoS, FnTx, FnIbcTransfer>(
+fn load_and_execute_governance_proposals<
+    S,
+    Token,
+    PoS,
+    Params,
+    Ibc,
+    FnTx,
+    FnIbcTransfer,
+>(
     state: &mut S,
     events: &mut impl EmitEvents,
     current_epoch: Epoch,
@@ -68,22 +87,34 @@ fn load_and_execute_governance_proposals<S, Token, PoS, FnTx, FnIbcTransfer>(
 where
     S: StateRead + State,
     Token: token::Read<S> + token::Write<S> + token::Events<S>,
-    PoS: proof_of_stake::Read<S>,
+    PoS: proof_of_stake::Write<S>,
+    Params: parameters::Write<S>,
+    Ibc: ibc::Write<S>,
     FnTx: FnMut(&Tx, &mut S) -> Result<bool>,
     FnIbcTransfer: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
 {
     let proposal_ids = load_proposals(state, current_epoch)?;
 
-    execute_governance_proposals::<S, Token, PoS, FnTx, FnIbcTransfer>(
-        state,
-        events,
-        proposal_ids,
-        dispatch_tx,
-        transfer_over_ibc,
-    )
+    execute_governance_proposals::<
+        S,
+        Token,
+        PoS,
+        Params,
+        Ibc,
+        FnTx,
+        FnIbcTransfer,
+    >(state, events, proposal_ids, dispatch_tx, transfer_over_ibc)
 }
 
-fn execute_governance_proposals<S, Token, PoS, FnTx, FnIbcTransfer>(
+fn execute_governance_proposals<
+    S,
+    Token,
+    PoS,
+    Params,
+    Ibc,
+    FnTx,
+    FnIbcTransfer,
+>(
     state: &mut S,
     events: &mut impl EmitEvents,
     proposal_ids: BTreeSet<u64>,
@@ -93,10 +124,14 @@ fn execute_governance_proposals<S, Token, PoS, FnTx, FnIbcTransfer>(
 where
     S: StateRead + State,
     Token: token::Read<S> + token::Write<S> + token::Events<S>,
-    PoS: proof_of_stake::Read<S>,
+    PoS: proof_of_stake::Write<S>,
+    Params: parameters::Write<S>,
+    Ibc: ibc::Write<S>,
     FnTx: FnMut(&Tx, &mut S) -> Result<bool>,
     FnIbcTransfer: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
 {
+    let veto_threshold = storage::get_veto_threshold(state)?;
+
     for id in proposal_ids {
         let proposal_funds_key = keys::get_funds_key(id);
         let proposal_end_epoch_key = keys::get_voting_end_epoch_key(id);
@@ -123,9 +158,10 @@ where
             votes,
             total_active_voting_power,
             tally_type,
+            veto_threshold,
         )
         .expect("Proposal result calculation must not over/underflow");
-        storage::write_proposal_result(state, id, proposal_result)?;
+        storage::write_proposal_result(state, id, proposal_result.clone())?;
 
         let transfer_address = match proposal_result.result {
             TallyResult::Passed => {
@@ -135,6 +171,15 @@ where
                             "Governance proposal #{} (default) has passed.",
                             id,
                         );
+                        if let Some(choice_result) =
+                            &proposal_result.choice_result
+                        {
+                            tracing::info!(
+                                "Governance proposal #{} result: {}.",
+                                id,
+                                choice_result
+                            );
+                        }
 
                         GovernanceEvent::passed_proposal(id, false, false)
                     }
@@ -172,7 +217,11 @@ where
                             }
                         );
 
-                        GovernanceEvent::passed_proposal(id, false, false)
+                        if result {
+                            GovernanceEvent::passed_proposal(id, false, false)
+                        } else {
+                            GovernanceEvent::failed_proposal(id)
+                        }
                     }
                     ProposalType::PGFPayment(payments) => {
                         let native_token = state.get_native_token()?;
@@ -191,6 +240,55 @@ where
 
                         GovernanceEvent::passed_proposal(id, false, false)
                     }
+                    ProposalType::ParameterChange(changes) => {
+                        let result = execute_parameter_change_proposal::<
+                            S,
+                            PoS,
+                            Params,
+                            Ibc,
+                        >(state, changes)?;
+                        tracing::info!(
+                            "Governance proposal #{} for parameter changes \
+                             has been executed. {}.",
+                            id,
+                            if result {
+                                "State changes have been applied successfully"
+                            } else {
+                                "FAILURE trying to apply the state changes - \
+                                 no state change occurred"
+                            }
+                        );
+
+                        GovernanceEvent::passed_proposal(id, false, false)
+                    }
+                    ProposalType::Upgrade(upgrade) => {
+                        let current_height = state
+                            .in_mem()
+                            .get_last_block_height()
+                            .next_height();
+                        let result = execute_upgrade_proposal(
+                            state,
+                            upgrade,
+                            current_height,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal #{} for a software upgrade \
+                             has been executed. {}.",
+                            id,
+                            if result {
+                                "The upgrade has been scheduled successfully"
+                            } else {
+                                "FAILURE trying to schedule the upgrade - no \
+                                 state change occurred"
+                            }
+                        );
+
+                        if result {
+                            GovernanceEvent::passed_proposal(id, false, false)
+                        } else {
+                            GovernanceEvent::failed_proposal(id)
+                        }
+                    }
                 };
                 events.emit(proposal_event);
 
@@ -209,7 +307,7 @@ where
 
                 storage::get_proposal_author(state, id)?
             }
-            TallyResult::Rejected => {
+            TallyResult::Rejected | TallyResult::Vetoed => {
                 if let ProposalType::PGFPayment(_) = proposal_type {
                     if proposal_result.two_thirds_nay_over_two_thirds_total() {
                         pgf_storage::remove_steward(state, &proposal_author)?;
@@ -224,18 +322,43 @@ where
                         );
                     }
                 }
-                let proposal_event = GovernanceEvent::rejected_proposal(
-                    id,
-                    matches!(proposal_type, ProposalType::DefaultWithWasm(_)),
-                );
+                let has_proposal_code =
+                    matches!(proposal_type, ProposalType::DefaultWithWasm(_));
+                // Only the deposit of a vetoed proposal is burned, the
+                // deposit of any other rejected proposal is refunded
+                let (proposal_event, refund_address) =
+                    match proposal_result.result {
+                        TallyResult::Vetoed => {
+                            tracing::info!(
+                                "Governance proposal {} has been vetoed.",
+                                id
+                            );
+                            (
+                                GovernanceEvent::vetoed_proposal(
+                                    id,
+                                    has_proposal_code,
+                                ),
+                                None,
+                            )
+                        }
+                        _ => {
+                            tracing::info!(
+                                "Governance proposal {} has been executed and \
+                                 rejected.",
+                                id
+                            );
+                            (
+                                GovernanceEvent::rejected_proposal(
+                                    id,
+                                    has_proposal_code,
+                                ),
+                                storage::get_proposal_author(state, id)?,
+                            )
+                        }
+                    };
                 events.emit(proposal_event);
 
-                tracing::info!(
-                    "Governance proposal {} has been executed and rejected.",
-                    id
-                );
-
-                None
+                refund_address
             }
         };
 
@@ -260,6 +383,12 @@ where
                 token::UserAccount::Internal(GOV_ADDRESS),
                 token::UserAccount::Internal(address),
             )?;
+
+            events.emit(GovernanceEvent::proposal_deposit(
+                id,
+                DepositOutcome::Refunded,
+                funds,
+            ));
         } else {
             Token::burn_tokens(state, &native_token, &GOV_ADDRESS, funds)?;
 
@@ -272,6 +401,12 @@ where
                 funds,
                 &GOV_ADDRESS,
             )?;
+
+            events.emit(GovernanceEvent::proposal_deposit(
+                id,
+                DepositOutcome::Burned,
+                funds,
+            ));
         }
     }
     Ok(())
@@ -352,11 +487,14 @@ where
         }
     }
 
+    let voting_mode = storage::get_proposal_voting_mode(storage, proposal_id)?;
+
     Ok(ProposalVotes {
         validators_vote,
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+        voting_mode,
     })
 }
 
@@ -433,6 +571,91 @@ where
     Ok(true)
 }
 
+fn execute_parameter_change_proposal<S, PoS, Params, Ibc>(
+    storage: &mut S,
+    changes: BTreeSet<ParameterChange>,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+    PoS: proof_of_stake::Write<S>,
+    Params: parameters::Write<S>,
+    Ibc: ibc::Write<S>,
+{
+    // Leave the storage untouched if any of the changes is invalid
+    if let Err(err) = validate_parameter_changes(&changes) {
+        tracing::warn!("Invalid parameter changes: {err}");
+        return Ok(false);
+    }
+
+    let mut protocol_changes = vec![];
+    let mut pos_changes = vec![];
+    let mut governance_changes = vec![];
+    let mut pgf_changes = vec![];
+    let mut ibc_changes = vec![];
+    for change in changes {
+        match change {
+            ParameterChange::Protocol(change) => protocol_changes.push(change),
+            ParameterChange::Pos(change) => pos_changes.push(change),
+            ParameterChange::Governance(change) => {
+                governance_changes.push(change)
+            }
+            ParameterChange::Pgf(change) => pgf_changes.push(change),
+            ParameterChange::Ibc(change) => ibc_changes.push(change),
+        }
+    }
+
+    // The PoS parameters are validated together with their current values,
+    // so they are changed first to leave the storage untouched on failure
+    if !pos_changes.is_empty() && !PoS::change_params(storage, &pos_changes)? {
+        return Ok(false);
+    }
+
+    if !protocol_changes.is_empty() {
+        let mut parameters = Params::read(storage)?;
+        for change in &protocol_changes {
+            change.apply(&mut parameters).map_err(Error::new)?;
+        }
+        Params::write(storage, &parameters)?;
+    }
+
+    for change in &governance_changes {
+        crate::parameters::write_parameter_change(storage, change)?;
+    }
+
+    for change in &pgf_changes {
+        crate::pgf::parameters::write_parameter_change(storage, change)?;
+    }
+
+    if !ibc_changes.is_empty() {
+        Ibc::change_params(storage, &ibc_changes)?;
+    }
+
+    Ok(true)
+}
+
+fn execute_upgrade_proposal<S>(
+    storage: &mut S,
+    upgrade: ScheduledUpgrade,
+    current_height: BlockHeight,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    // The nodes can only halt for the upgrade at a future height
+    if upgrade.height <= current_height {
+        tracing::warn!(
+            "The upgrade height {} is not after the current block height {}",
+            upgrade.height,
+            current_height
+        );
+        return Ok(false);
+    }
+
+    storage.write(&keys::get_scheduled_upgrade_key(), upgrade)?;
+
+    Ok(true)
+}
+
 fn execute_pgf_funding_proposal<S, Token, FnIbcTransfer>(
     storage: &mut S,
     token: &Address,
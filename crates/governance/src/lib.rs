@@ -39,7 +39,9 @@ pub use finalize_block::finalize_block;
 use namada_state::{StorageRead, StorageWrite};
 pub use namada_systems::governance::*;
 use parameters::GovernanceParameters;
-pub use storage::proposal::{InitProposalData, ProposalType, VoteProposalData};
+pub use storage::proposal::{
+    InitProposalData, ProposalType, VoteProposalData, VotingMode,
+};
 pub use storage::vote::ProposalVote;
 pub use storage::{init_proposal, is_proposal_accepted, vote_proposal};
 
@@ -1,4 +1,6 @@
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::dec::Dec;
+use namada_core::parameter_change::GovernanceParameterChange;
 use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -38,6 +40,9 @@ pub struct GovernanceParameters {
     pub min_proposal_grace_epochs: u64,
     /// Maximum number of epochs between current epoch and start epoch
     pub max_proposal_latency: u64,
+    /// Fraction of the voted power above which the veto votes reject a
+    /// proposal and burn its deposit
+    pub veto_threshold: Dec,
 }
 
 impl Default for GovernanceParameters {
@@ -50,6 +55,7 @@ impl Default for GovernanceParameters {
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
             max_proposal_latency: 30,
+            veto_threshold: Dec::one_third(),
         }
     }
 }
@@ -68,6 +74,7 @@ impl GovernanceParameters {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             max_proposal_latency,
+            veto_threshold,
         } = self;
 
         let min_proposal_fund_key =
@@ -103,7 +110,45 @@ impl GovernanceParameters {
             goverance_storage::get_max_proposal_latency_key();
         storage.write(&max_proposal_latency_key, max_proposal_latency)?;
 
+        let veto_threshold_key = goverance_storage::get_veto_threshold_key();
+        storage.write(&veto_threshold_key, veto_threshold)?;
+
         let counter_key = goverance_storage::get_counter_key();
         storage.write(&counter_key, u64::MIN)
     }
 }
+
+/// Write a change of a governance parameter proposed by governance
+pub fn write_parameter_change<S>(
+    storage: &mut S,
+    change: &GovernanceParameterChange,
+) -> Result<()>
+where
+    S: StorageWrite,
+{
+    match change {
+        GovernanceParameterChange::MinProposalFund(amount) => storage
+            .write(&goverance_storage::get_min_proposal_fund_key(), amount),
+        GovernanceParameterChange::MaxProposalCodeSize(value) => storage
+            .write(&goverance_storage::get_max_proposal_code_size_key(), value),
+        GovernanceParameterChange::MinProposalVotingPeriod(value) => storage
+            .write(
+                &goverance_storage::get_min_proposal_voting_period_key(),
+                value,
+            ),
+        GovernanceParameterChange::MaxProposalPeriod(value) => storage
+            .write(&goverance_storage::get_max_proposal_period_key(), value),
+        GovernanceParameterChange::MaxProposalContentSize(value) => storage
+            .write(&goverance_storage::get_max_proposal_content_key(), value),
+        GovernanceParameterChange::MinProposalGraceEpochs(value) => storage
+            .write(
+                &goverance_storage::get_min_proposal_grace_epochs_key(),
+                value,
+            ),
+        GovernanceParameterChange::MaxProposalLatency(value) => storage
+            .write(&goverance_storage::get_max_proposal_latency_key(), value),
+        GovernanceParameterChange::VetoThreshold(value) => {
+            storage.write(&goverance_storage::get_veto_threshold_key(), value)
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::BTreeSet;
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::dec::Dec;
+use namada_core::parameter_change::PgfParameterChange;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -83,3 +84,25 @@ impl PgfParameters {
             .write(&maximum_number_of_stewards_key, maximum_number_of_stewards)
     }
 }
+
+/// Write a change of a PGF parameter proposed by governance
+pub fn write_parameter_change<S>(
+    storage: &mut S,
+    change: &PgfParameterChange,
+) -> Result<()>
+where
+    S: StorageWrite,
+{
+    match change {
+        PgfParameterChange::PgfInflationRate(value) => {
+            storage.write(&pgf_storage::get_pgf_inflation_rate_key(), value)
+        }
+        PgfParameterChange::StewardsInflationRate(value) => {
+            storage.write(&pgf_storage::get_steward_inflation_rate_key(), value)
+        }
+        PgfParameterChange::MaximumNumberOfStewards(value) => storage.write(
+            &pgf_storage::get_maximum_number_of_pgf_steward_key(),
+            value,
+        ),
+    }
+}
@@ -25,9 +25,14 @@ struct Keys {
     max_content: &'static str,
     max_latency: &'static str,
     min_grace_epochs: &'static str,
+    veto_threshold: &'static str,
     counter: &'static str,
     pending: &'static str,
     result: &'static str,
+    choice_result: &'static str,
+    veto_power: &'static str,
+    voting_mode: &'static str,
+    scheduled_upgrade: &'static str,
 }
 
 /// Check if key is inside governance address space
@@ -199,6 +204,24 @@ pub fn is_proposal_type_key(key: &Key) -> bool {
     }
 }
 
+/// Check if key is voting mode key
+pub fn is_voting_mode_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(voting_mode),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && voting_mode == Keys::VALUES.voting_mode =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
 /// Check if key is counter key
 pub fn is_counter_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(counter)] if addr == &ADDRESS && counter == Keys::VALUES.counter)
@@ -342,6 +365,20 @@ pub fn get_min_proposal_grace_epochs_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get veto threshold key
+pub fn get_veto_threshold_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.veto_threshold.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get key of the software upgrade scheduled by governance
+pub fn get_scheduled_upgrade_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.scheduled_upgrade.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal ids counter
 pub fn get_counter_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -376,6 +413,15 @@ pub fn get_proposal_type_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get key of a proposal voting mode
+pub fn get_voting_mode_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.voting_mode.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal voting start epoch
 pub fn get_voting_start_epoch_key(id: u64) -> Key {
     proposal_prefix()
@@ -477,6 +523,25 @@ pub fn get_proposal_result_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key of the result of the options of a proposal with options to
+/// choose from
+pub fn get_proposal_choice_result_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.choice_result.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the voting power of the veto votes on a proposal
+pub fn get_proposal_veto_power_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.veto_power.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal id from key
 pub fn get_proposal_id(key: &Key) -> Option<u64> {
     match key.get_at(2) {
@@ -13,6 +13,7 @@ use namada_core::address::Address;
 use namada_core::borsh::BorshDeserialize;
 use namada_core::chain::Epoch;
 use namada_core::collections::HashSet;
+use namada_core::dec::Dec;
 use namada_core::hash::Hash;
 use namada_core::token;
 use namada_state::{iter_prefix, Error, Result, StorageRead, StorageWrite};
@@ -21,10 +22,11 @@ use namada_systems::trans_token;
 use crate::parameters::GovernanceParameters;
 use crate::storage::keys as governance_keys;
 use crate::storage::proposal::{
-    InitProposalData, ProposalType, StorageProposal, VoteProposalData,
+    InitProposalData, ProposalType, ScheduledUpgrade, StorageProposal,
+    VoteProposalData, VotingMode,
 };
 use crate::storage::vote::ProposalVote;
-use crate::utils::{ProposalResult, Vote};
+use crate::utils::{ChoiceResult, ProposalResult, Vote, VotePower};
 use crate::ADDRESS as governance_address;
 
 /// A proposal creation transaction.
@@ -65,6 +67,13 @@ where
         _ => storage.write(&proposal_type_key, data.r#type.clone())?,
     }
 
+    // The voting mode is only written for proposals with options to choose
+    // from
+    if !data.voting_mode.is_yay_nay() {
+        let voting_mode_key = governance_keys::get_voting_mode_key(proposal_id);
+        storage.write(&voting_mode_key, data.voting_mode.clone())?;
+    }
+
     let voting_start_epoch_key =
         governance_keys::get_voting_start_epoch_key(proposal_id);
     storage.write(&voting_start_epoch_key, data.voting_start_epoch)?;
@@ -139,6 +148,18 @@ pub fn write_proposal_result<S>(
 where
     S: StorageRead + StorageWrite,
 {
+    // The result of the options and the veto power are kept apart, so that the
+    // encoding of the results stays the same
+    if !proposal_result.total_veto_power.is_zero() {
+        let veto_power_key =
+            governance_keys::get_proposal_veto_power_key(proposal_id);
+        storage.write(&veto_power_key, proposal_result.total_veto_power)?;
+    }
+    if let Some(choice_result) = &proposal_result.choice_result {
+        let choice_result_key =
+            governance_keys::get_proposal_choice_result_key(proposal_id);
+        storage.write(&choice_result_key, choice_result)?;
+    }
     let proposal_result_key =
         governance_keys::get_proposal_result_key(proposal_id);
     storage.write(&proposal_result_key, proposal_result)
@@ -168,6 +189,8 @@ where
     let proposal_type: Option<ProposalType> =
         storage.read(&proposal_type_key)?;
 
+    let voting_mode = get_proposal_voting_mode(storage, id)?;
+
     let proposal_type = if let Some(proposal_type) = proposal_type {
         if let ProposalType::DefaultWithWasm(_) = proposal_type {
             let proposal_code_key = governance_keys::get_proposal_code_key(id);
@@ -190,9 +213,23 @@ where
         voting_start_epoch: voting_start_epoch.unwrap(),
         voting_end_epoch: voting_end_epoch.unwrap(),
         activation_epoch: activation_epoch.unwrap(),
+        voting_mode,
     }))
 }
 
+/// Read the voting mode of a proposal. Proposals without a voting mode in
+/// storage are voted with yay, nay or abstain.
+pub fn get_proposal_voting_mode<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> Result<VotingMode>
+where
+    S: StorageRead,
+{
+    let voting_mode_key = governance_keys::get_voting_mode_key(proposal_id);
+    Ok(storage.read(&voting_mode_key)?.unwrap_or_default())
+}
+
 /// Query all the votes for a proposal_id
 pub fn get_proposal_votes<S>(storage: &S, proposal_id: u64) -> Result<Vec<Vote>>
 where
@@ -298,6 +335,8 @@ where
     let max_proposal_latency: u64 =
         storage.read(&key)?.expect("Parameter should be defined.");
 
+    let veto_threshold = get_veto_threshold(storage)?;
+
     Ok(GovernanceParameters {
         min_proposal_fund,
         max_proposal_code_size,
@@ -306,6 +345,7 @@ where
         max_proposal_content_size,
         min_proposal_grace_epochs,
         max_proposal_latency,
+        veto_threshold,
     })
 }
 
@@ -320,6 +360,19 @@ where
     Ok(max_proposal_period)
 }
 
+/// Get the veto threshold parameter
+pub fn get_veto_threshold<S>(storage: &S) -> Result<Dec>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_veto_threshold_key();
+    // The chains initialized before the veto threshold was introduced use the
+    // default until it's set by a migration or a parameter change proposal
+    let veto_threshold: Option<Dec> = storage.read(&key)?;
+    Ok(veto_threshold
+        .unwrap_or_else(|| GovernanceParameters::default().veto_threshold))
+}
+
 /// Get governance proposal result stored in storage if proposal ended
 pub fn get_proposal_result<S>(
     storage: &S,
@@ -329,10 +382,61 @@ where
     S: StorageRead,
 {
     let key = governance_keys::get_proposal_result_key(proposal_id);
-    let proposal_result: Option<ProposalResult> = storage.read(&key)?;
+    let mut proposal_result: Option<ProposalResult> = storage.read(&key)?;
+    if let Some(proposal_result) = proposal_result.as_mut() {
+        proposal_result.total_veto_power =
+            get_proposal_veto_power(storage, proposal_id)?;
+        proposal_result.choice_result =
+            get_proposal_choice_result(storage, proposal_id)?;
+    }
     Ok(proposal_result)
 }
 
+/// Get the voting power of the veto votes on a proposal stored in storage if
+/// the proposal ended
+pub fn get_proposal_veto_power<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> Result<VotePower>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_proposal_veto_power_key(proposal_id);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Get the result of the options of a proposal with options to choose from
+/// stored in storage if the proposal ended
+pub fn get_proposal_choice_result<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> Result<Option<ChoiceResult>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_proposal_choice_result_key(proposal_id);
+    storage.read(&key)
+}
+
+/// Get the software upgrade scheduled by governance, if any
+pub fn get_scheduled_upgrade<S>(storage: &S) -> Result<Option<ScheduledUpgrade>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_scheduled_upgrade_key();
+    storage.read(&key)
+}
+
+/// Remove the software upgrade scheduled by governance, once the upgraded
+/// binary has processed the block at the upgrade height
+pub fn remove_scheduled_upgrade<S>(storage: &mut S) -> Result<()>
+where
+    S: StorageWrite,
+{
+    let key = governance_keys::get_scheduled_upgrade_key();
+    storage.delete(&key)
+}
+
 /// Load proposals for execution in the current epoch.
 pub fn load_proposals<S>(
     storage: &S,
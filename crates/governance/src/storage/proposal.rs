@@ -4,9 +4,10 @@ use std::fmt::Display;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use itertools::Itertools;
 use namada_core::address::Address;
-use namada_core::chain::Epoch;
+use namada_core::chain::{BlockHeight, Epoch};
 use namada_core::hash::Hash;
 pub use namada_core::ibc::PGFIbcTarget;
+use namada_core::parameter_change::ParameterChange;
 use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -14,10 +15,11 @@ use namada_migrations::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::vote::ProposalVote;
+use super::vote::{ChoiceVote, ProposalVote};
 use crate::cli::onchain::{
-    DefaultProposal, PgfAction, PgfContinuous, PgfFundingProposal, PgfRetro,
-    PgfSteward, PgfStewardProposal, StewardsUpdate,
+    DefaultProposal, ParameterChangeProposal, PgfAction, PgfContinuous,
+    PgfFundingProposal, PgfRetro, PgfSteward, PgfStewardProposal,
+    StewardsUpdate, UpgradeProposal,
 };
 use crate::utils::{ProposalStatus, TallyType};
 
@@ -54,6 +56,9 @@ pub struct InitProposalData {
     pub voting_end_epoch: Epoch,
     /// The epoch in which any changes are executed and become active
     pub activation_epoch: Epoch,
+    /// How votes are cast and tallied
+    #[serde(default)]
+    pub voting_mode: VotingMode,
 }
 
 impl InitProposalData {
@@ -108,6 +113,7 @@ impl TryFrom<DefaultProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             activation_epoch: value.proposal.activation_epoch,
+            voting_mode: value.proposal.voting_mode,
         })
     }
 }
@@ -126,6 +132,7 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             activation_epoch: value.proposal.activation_epoch,
+            voting_mode: value.proposal.voting_mode,
         })
     }
 }
@@ -165,6 +172,39 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             activation_epoch: value.proposal.activation_epoch,
+            voting_mode: value.proposal.voting_mode,
+        })
+    }
+}
+
+impl TryFrom<ParameterChangeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ParameterChangeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ParameterChange(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            activation_epoch: value.proposal.activation_epoch,
+            voting_mode: value.proposal.voting_mode,
+        })
+    }
+}
+
+impl TryFrom<UpgradeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: UpgradeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::Upgrade(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            activation_epoch: value.proposal.activation_epoch,
+            voting_mode: value.proposal.voting_mode,
         })
     }
 }
@@ -222,6 +262,138 @@ pub enum ProposalType {
     PGFSteward(BTreeSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(BTreeSet<PGFAction>),
+    /// Proposal to change system parameters
+    ParameterChange(BTreeSet<ParameterChange>),
+    /// Proposal to upgrade the node software at a given block height
+    Upgrade(ScheduledUpgrade),
+}
+
+/// The maximum number of options of a proposal
+pub const MAX_VOTING_OPTIONS: usize = 32;
+
+/// How the votes on a proposal are cast and tallied
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+    Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum VotingMode {
+    /// Yay, nay or abstain votes, tallied according to the proposal type
+    #[default]
+    YayNay,
+    /// A single option per vote. The option with the most voting power wins.
+    MultipleChoice(Vec<String>),
+    /// The voting power of a vote is split across options by weight. The
+    /// option with the most voting power wins.
+    WeightedChoice(Vec<String>),
+    /// Like [`VotingMode::WeightedChoice`], but the voting power given to an
+    /// option by a vote only counts as its square root.
+    QuadraticChoice(Vec<String>),
+    /// Options are ranked by preference and tallied by instant runoff: the
+    /// least voted options are eliminated until one of them has the majority
+    /// of the votes.
+    RankedChoice(Vec<String>),
+}
+
+impl VotingMode {
+    /// The options to choose from, if any
+    pub fn options(&self) -> Option<&[String]> {
+        match self {
+            VotingMode::YayNay => None,
+            VotingMode::MultipleChoice(options)
+            | VotingMode::WeightedChoice(options)
+            | VotingMode::QuadraticChoice(options)
+            | VotingMode::RankedChoice(options) => Some(options),
+        }
+    }
+
+    /// Check if votes are yay, nay or abstain
+    pub fn is_yay_nay(&self) -> bool {
+        matches!(self, VotingMode::YayNay)
+    }
+
+    /// Validate the options of the voting mode
+    pub fn validate(&self) -> Result<(), ProposalTypeError> {
+        let Some(options) = self.options() else {
+            return Ok(());
+        };
+        if options.len() < 2 || options.len() > MAX_VOTING_OPTIONS {
+            return Err(ProposalTypeError::InvalidVotingOptions(format!(
+                "expected between 2 and {MAX_VOTING_OPTIONS} options, got {}",
+                options.len()
+            )));
+        }
+        if options.iter().any(|option| option.trim().is_empty()) {
+            return Err(ProposalTypeError::InvalidVotingOptions(
+                "options must not be empty".to_string(),
+            ));
+        }
+        if options.iter().collect::<BTreeSet<_>>().len() != options.len() {
+            return Err(ProposalTypeError::InvalidVotingOptions(
+                "options must be unique".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if a vote can be cast on a proposal with this voting mode
+    pub fn is_valid_vote(&self, vote: &ProposalVote) -> bool {
+        match (self, vote) {
+            (
+                VotingMode::YayNay,
+                ProposalVote::Yay
+                | ProposalVote::Nay
+                | ProposalVote::Abstain
+                | ProposalVote::Veto,
+            ) => true,
+            (VotingMode::YayNay, ProposalVote::Split(split)) => {
+                split.is_valid()
+            }
+            (
+                VotingMode::MultipleChoice(options),
+                ProposalVote::Choice(choice @ ChoiceVote::Single(_)),
+            )
+            | (
+                VotingMode::WeightedChoice(options)
+                | VotingMode::QuadraticChoice(options),
+                ProposalVote::Choice(choice @ ChoiceVote::Weighted(_)),
+            )
+            | (
+                VotingMode::RankedChoice(options),
+                ProposalVote::Choice(choice @ ChoiceVote::Ranked(_)),
+            ) => choice.is_valid(options.len()),
+            _ => false,
+        }
+    }
+}
+
+impl Display for VotingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            VotingMode::YayNay => return write!(f, "Yay/Nay"),
+            VotingMode::MultipleChoice(_) => "Multiple choice",
+            VotingMode::WeightedChoice(_) => "Weighted choice",
+            VotingMode::QuadraticChoice(_) => "Quadratic choice",
+            VotingMode::RankedChoice(_) => "Ranked choice",
+        };
+        write!(f, "{name}")?;
+        for (index, option) in
+            self.options().unwrap_or_default().iter().enumerate()
+        {
+            write!(f, "\n  {index}: {option}")?;
+        }
+        Ok(())
+    }
 }
 
 /// An add or remove action for PGF
@@ -362,6 +534,56 @@ pub enum PGFAction {
     Retro(PGFTarget),
 }
 
+/// A software upgrade scheduled by a governance proposal. The nodes halt at
+/// the upgrade height unless they are running the upgraded binary.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+    Eq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+pub struct ScheduledUpgrade {
+    /// The height of the first block that must be processed by the upgraded
+    /// binary
+    pub height: BlockHeight,
+    /// The version of the upgraded node binary, e.g. `v1.1.0`
+    pub version: String,
+    /// The SHA-256 hashes of other builds of the node binary that are also
+    /// accepted as the upgraded binary, e.g. for platforms or patches not
+    /// covered by the version
+    #[serde(default)]
+    pub binary_hashes: BTreeSet<Hash>,
+    /// The SHA-256 hash of the DB migration file to apply at the upgrade
+    /// height, if any
+    pub migration_hash: Option<Hash>,
+}
+
+impl Display for ScheduledUpgrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Height: {}\nVersion: {}\nBinary hashes: [{}]\nMigration hash: {}",
+            self.height,
+            self.version,
+            self.binary_hashes.iter().join(", "),
+            self.migration_hash
+                .as_ref()
+                .map(Hash::to_string)
+                .unwrap_or_else(|| "none".to_string())
+        )
+    }
+}
+
 impl ProposalType {
     /// Check if the proposal type is default
     pub fn is_default(&self) -> bool {
@@ -373,6 +595,16 @@ impl ProposalType {
         matches!(self, ProposalType::DefaultWithWasm(_))
     }
 
+    /// Check if the proposal type is a parameter change
+    pub fn is_parameter_change(&self) -> bool {
+        matches!(self, ProposalType::ParameterChange(_))
+    }
+
+    /// Check if the proposal type is a software upgrade
+    pub fn is_upgrade(&self) -> bool {
+        matches!(self, ProposalType::Upgrade(_))
+    }
+
     fn format_data(&self) -> String {
         match self {
             ProposalType::DefaultWithWasm(hash) => format!("Hash: {}", &hash),
@@ -391,6 +623,14 @@ impl ProposalType {
                     .map(|action| format!("\n  {}", &action))
                     .join("")
             ),
+            ProposalType::ParameterChange(changes) => format!(
+                "Changes:{}",
+                changes
+                    .iter()
+                    .map(|change| format!("\n  {}", &change))
+                    .join("")
+            ),
+            ProposalType::Upgrade(upgrade) => upgrade.to_string(),
         }
     }
 }
@@ -402,6 +642,10 @@ impl Display for ProposalType {
             ProposalType::DefaultWithWasm(_) => write!(f, "Default with Wasm"),
             ProposalType::PGFSteward(_) => write!(f, "PGF steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF funding"),
+            ProposalType::ParameterChange(_) => {
+                write!(f, "Parameter change")
+            }
+            ProposalType::Upgrade(_) => write!(f, "Upgrade"),
         }
     }
 }
@@ -411,6 +655,8 @@ impl Display for ProposalType {
 pub enum ProposalTypeError {
     #[error("Invalid proposal type.")]
     InvalidProposalType,
+    #[error("Invalid voting options: {0}")]
+    InvalidVotingOptions(String),
 }
 
 impl TryFrom<StewardsUpdate> for BTreeSet<AddRemove<Address>> {
@@ -487,6 +733,8 @@ pub struct StorageProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub activation_epoch: Epoch,
+    /// How votes are cast and tallied
+    pub voting_mode: VotingMode,
 }
 
 impl StorageProposal {
@@ -538,6 +786,7 @@ Start Epoch: {}
 End Epoch: {}
 Activation Epoch: {}
 Status: {}
+Voting Mode: {}
 Data: {}",
             self.id,
             self.r#type,
@@ -547,6 +796,7 @@ Data: {}",
             self.voting_end_epoch,
             self.activation_epoch,
             self.get_status(current_epoch),
+            self.voting_mode,
             self.r#type.format_data()
         )
     }
@@ -585,9 +835,10 @@ pub mod testing {
     use namada_core::chain::testing::arb_epoch;
     use namada_core::hash::testing::arb_hash;
     use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
+    use namada_core::parameter_change::testing::arb_parameter_change;
     use namada_core::token::testing::arb_amount;
     use proptest::prelude::*;
-    use proptest::{collection, prop_compose};
+    use proptest::{collection, option, prop_compose};
 
     use super::*;
     use crate::storage::vote::testing::arb_proposal_vote;
@@ -668,6 +919,35 @@ pub mod testing {
         ]
     }
 
+    /// Generate an arbitrary voting mode
+    pub fn arb_voting_mode() -> impl Strategy<Value = VotingMode> {
+        let options = || collection::vec("[a-zA-Z0-9 ]{1,16}", 2..8);
+        prop_oneof![
+            Just(VotingMode::YayNay),
+            options().prop_map(VotingMode::MultipleChoice),
+            options().prop_map(VotingMode::WeightedChoice),
+            options().prop_map(VotingMode::QuadraticChoice),
+            options().prop_map(VotingMode::RankedChoice),
+        ]
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary scheduled upgrade
+        pub fn arb_scheduled_upgrade()(
+            height in any::<u64>().prop_map(BlockHeight),
+            version in "v[0-9]{1,2}\\.[0-9]{1,2}\\.[0-9]{1,2}",
+            binary_hashes in collection::btree_set(arb_hash(), 0..4),
+            migration_hash in option::of(arb_hash()),
+        ) -> ScheduledUpgrade {
+            ScheduledUpgrade {
+                height,
+                version,
+                binary_hashes,
+                migration_hash,
+            }
+        }
+    }
+
     /// Generate an arbitrary proposal type
     pub fn arb_proposal_type() -> impl Strategy<Value = ProposalType> {
         prop_oneof![
@@ -680,6 +960,9 @@ pub mod testing {
             .prop_map(ProposalType::PGFSteward),
             collection::btree_set(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment),
+            collection::btree_set(arb_parameter_change(), 1..5)
+                .prop_map(ProposalType::ParameterChange),
+            arb_scheduled_upgrade().prop_map(ProposalType::Upgrade),
         ]
     }
 
@@ -692,6 +975,7 @@ pub mod testing {
             voting_start_epoch in arb_epoch(),
             voting_end_epoch in arb_epoch(),
             activation_epoch in arb_epoch(),
+            voting_mode in arb_voting_mode(),
         ) -> InitProposalData {
             InitProposalData {
                 content,
@@ -700,6 +984,7 @@ pub mod testing {
                 voting_start_epoch,
                 voting_end_epoch,
                 activation_epoch,
+                voting_mode,
             }
         }
     }
@@ -1,6 +1,10 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::arith::{self, checked};
+use namada_core::token;
+use namada_core::uint::Uint;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -27,6 +31,220 @@ pub enum ProposalVote {
     Nay,
     /// Abstain
     Abstain,
+    /// A vote on a proposal with options to choose from
+    Choice(ChoiceVote),
+    /// A vote with its voting power split between yay, nay and abstain
+    Split(SplitVote),
+    // NB: new variants must be appended to keep the encoding of the stored
+    // votes
+    /// No, with a veto. Counts as a nay vote, and if the vetoes reach the
+    /// veto threshold the proposal is rejected and its deposit is burned.
+    Veto,
+}
+
+/// A vote split between yay, nay and abstain, in percent of the voting power
+/// of the voter. The percentages must add up to 100.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct SplitVote {
+    /// Percentage of the voting power voting yay
+    pub yay: u8,
+    /// Percentage of the voting power voting nay
+    pub nay: u8,
+    /// Percentage of the voting power abstaining
+    pub abstain: u8,
+}
+
+impl SplitVote {
+    /// Check that the percentages add up to 100
+    pub fn is_valid(&self) -> bool {
+        self.yay
+            .checked_add(self.nay)
+            .and_then(|total| total.checked_add(self.abstain))
+            == Some(100)
+    }
+
+    /// Split the given voting power into its yay, nay and abstain shares. The
+    /// shares are rounded down.
+    pub fn split_power(
+        &self,
+        voting_power: token::Amount,
+    ) -> Result<(token::Amount, token::Amount, token::Amount), arith::Error>
+    {
+        let share = |percentage: u8| {
+            checked!(
+                voting_power.raw_amount() * Uint::from(percentage)
+                    / Uint::from(100_u64)
+            )
+            .map(token::Amount::from)
+        };
+        Ok((share(self.yay)?, share(self.nay)?, share(self.abstain)?))
+    }
+}
+
+impl Display for SplitVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "split:yay={},nay={},abstain={}",
+            self.yay, self.nay, self.abstain
+        )
+    }
+}
+
+impl TryFrom<&str> for SplitVote {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let shares = value
+            .strip_prefix("split:")
+            .ok_or_else(|| "invalid vote".to_string())?;
+        let mut split = SplitVote::default();
+        for share in shares.split(',') {
+            let (side, percentage) = share
+                .split_once('=')
+                .ok_or_else(|| format!("invalid share: {share}"))?;
+            let percentage = percentage
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("invalid percentage: {percentage}"))?;
+            match side.trim() {
+                "yay" => split.yay = percentage,
+                "nay" => split.nay = percentage,
+                "abstain" => split.abstain = percentage,
+                side => return Err(format!("invalid side: {side}")),
+            }
+        }
+        if split.is_valid() {
+            Ok(split)
+        } else {
+            Err("the percentages must add up to 100".to_string())
+        }
+    }
+}
+
+/// A vote on a proposal with options to choose from. Options are referred to
+/// by their index in the proposal's voting mode.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub enum ChoiceVote {
+    /// A single option
+    Single(u8),
+    /// Options with the relative weight given to each of them
+    Weighted(BTreeMap<u8, u32>),
+    /// Options ordered by preference
+    Ranked(Vec<u8>),
+}
+
+impl ChoiceVote {
+    /// Check that the vote is well-formed and only refers to options below
+    /// `num_options`
+    pub fn is_valid(&self, num_options: usize) -> bool {
+        let is_option = |option: &u8| usize::from(*option) < num_options;
+        match self {
+            ChoiceVote::Single(option) => is_option(option),
+            ChoiceVote::Weighted(weights) => {
+                !weights.is_empty()
+                    && weights.iter().all(|(option, weight)| {
+                        is_option(option) && *weight > 0
+                    })
+            }
+            ChoiceVote::Ranked(ranking) => {
+                !ranking.is_empty()
+                    && ranking.iter().all(is_option)
+                    && ranking.iter().collect::<BTreeSet<_>>().len()
+                        == ranking.len()
+            }
+        }
+    }
+}
+
+impl Display for ChoiceVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChoiceVote::Single(option) => write!(f, "choice:{option}"),
+            ChoiceVote::Weighted(weights) => write!(
+                f,
+                "weighted:{}",
+                weights
+                    .iter()
+                    .map(|(option, weight)| format!("{option}={weight}"))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            ChoiceVote::Ranked(ranking) => write!(
+                f,
+                "ranked:{}",
+                ranking
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl TryFrom<&str> for ChoiceVote {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let parse_option = |option: &str| {
+            option
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("invalid option index: {option}"))
+        };
+        match value.split_once(':') {
+            Some(("choice", option)) => {
+                parse_option(option).map(ChoiceVote::Single)
+            }
+            Some(("weighted", weights)) => weights
+                .split(',')
+                .map(|weight| {
+                    let (option, weight) = weight
+                        .split_once('=')
+                        .ok_or_else(|| format!("invalid weight: {weight}"))?;
+                    let weight = weight
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid weight: {weight}"))?;
+                    Ok((parse_option(option)?, weight))
+                })
+                .collect::<Result<_, String>>()
+                .map(ChoiceVote::Weighted),
+            Some(("ranked", ranking)) => ranking
+                .split(',')
+                .map(parse_option)
+                .collect::<Result<_, String>>()
+                .map(ChoiceVote::Ranked),
+            _ => Err("invalid vote".to_string()),
+        }
+    }
 }
 
 impl ProposalVote {
@@ -45,6 +263,25 @@ impl ProposalVote {
         matches!(self, ProposalVote::Abstain)
     }
 
+    /// Check if a vote is a veto
+    pub fn is_veto(&self) -> bool {
+        matches!(self, ProposalVote::Veto)
+    }
+
+    /// Check if a vote is split between yay, nay and abstain
+    pub fn is_split(&self) -> bool {
+        matches!(self, ProposalVote::Split(_))
+    }
+
+    /// Check that the vote is well-formed, regardless of the proposal it is
+    /// cast on
+    pub fn is_well_formed(&self) -> bool {
+        match self {
+            ProposalVote::Split(split) => split.is_valid(),
+            _ => true,
+        }
+    }
+
     /// Check if two votes are equal, returns an error if the variants of the
     /// two instances are different
     #[allow(clippy::match_like_matches_macro)]
@@ -53,6 +290,13 @@ impl ProposalVote {
             (ProposalVote::Yay, ProposalVote::Yay) => true,
             (ProposalVote::Nay, ProposalVote::Nay) => true,
             (ProposalVote::Abstain, ProposalVote::Abstain) => true,
+            (ProposalVote::Veto, ProposalVote::Veto) => true,
+            (ProposalVote::Choice(choice), ProposalVote::Choice(other)) => {
+                choice == other
+            }
+            (ProposalVote::Split(split), ProposalVote::Split(other)) => {
+                split == other
+            }
             _ => false,
         }
     }
@@ -64,6 +308,9 @@ impl Display for ProposalVote {
             ProposalVote::Yay => write!(f, "yay"),
             ProposalVote::Nay => write!(f, "nay"),
             ProposalVote::Abstain => write!(f, "abstain"),
+            ProposalVote::Veto => write!(f, "veto"),
+            ProposalVote::Choice(choice) => write!(f, "{choice}"),
+            ProposalVote::Split(split) => write!(f, "{split}"),
         }
     }
 }
@@ -76,7 +323,11 @@ impl TryFrom<String> for ProposalVote {
             "yay" => Ok(ProposalVote::Yay),
             "nay" => Ok(ProposalVote::Nay),
             "abstain" => Ok(ProposalVote::Abstain),
-            _ => Err("invalid vote".to_string()),
+            "veto" => Ok(ProposalVote::Veto),
+            vote if vote.starts_with("split:") => {
+                SplitVote::try_from(vote).map(ProposalVote::Split)
+            }
+            vote => ChoiceVote::try_from(vote).map(ProposalVote::Choice),
         }
     }
 }
@@ -84,16 +335,106 @@ impl TryFrom<String> for ProposalVote {
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
+    use proptest::collection;
     use proptest::prelude::*;
 
     use super::*;
 
+    /// Generate an arbitrary choice vote
+    pub fn arb_choice_vote() -> impl Strategy<Value = ChoiceVote> {
+        prop_oneof![
+            any::<u8>().prop_map(ChoiceVote::Single),
+            collection::btree_map(any::<u8>(), 1..u32::MAX, 1..8)
+                .prop_map(ChoiceVote::Weighted),
+            collection::vec(any::<u8>(), 1..8).prop_map(ChoiceVote::Ranked),
+        ]
+    }
+
+    /// Generate an arbitrary valid split vote
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn arb_split_vote() -> impl Strategy<Value = SplitVote> {
+        (0..=100_u8)
+            .prop_flat_map(|yay| (Just(yay), 0..=(100 - yay)))
+            .prop_map(|(yay, nay)| SplitVote {
+                yay,
+                nay,
+                abstain: 100 - yay - nay,
+            })
+    }
+
     /// Generate an arbitrary proposal vote
     pub fn arb_proposal_vote() -> impl Strategy<Value = ProposalVote> {
         prop_oneof![
             Just(ProposalVote::Yay),
             Just(ProposalVote::Nay),
             Just(ProposalVote::Abstain),
+            Just(ProposalVote::Veto),
+            arb_choice_vote().prop_map(ProposalVote::Choice),
+            arb_split_vote().prop_map(ProposalVote::Split),
         ]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_choice_vote_parse_and_validate() {
+        for vote in ["choice:1", "weighted:0=60,2=40", "ranked:2,0,1"] {
+            let parsed = ProposalVote::try_from(vote.to_string()).unwrap();
+            assert_eq!(parsed.to_string(), vote);
+        }
+
+        let weighted = ChoiceVote::try_from("weighted:0=60,2=40").unwrap();
+        assert!(weighted.is_valid(3));
+        assert!(!weighted.is_valid(2));
+        assert!(!ChoiceVote::Weighted(BTreeMap::from([(0, 0)])).is_valid(3));
+        assert!(!ChoiceVote::Ranked(vec![0, 1, 0]).is_valid(3));
+        assert!(ProposalVote::try_from("ranked:a".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_vote_encoding() {
+        use namada_core::borsh::BorshSerializeExt;
+
+        // The encoding of the votes that may already be in storage must not
+        // change
+        assert_eq!(ProposalVote::Yay.serialize_to_vec(), vec![0]);
+        assert_eq!(ProposalVote::Nay.serialize_to_vec(), vec![1]);
+        assert_eq!(ProposalVote::Abstain.serialize_to_vec(), vec![2]);
+        assert_eq!(ProposalVote::Veto.serialize_to_vec(), vec![5]);
+    }
+
+    #[test]
+    fn test_split_vote_parse_and_split_power() {
+        let vote = ProposalVote::try_from(
+            "split:yay=60,nay=30,abstain=10".to_string(),
+        )
+        .unwrap();
+        assert_eq!(vote.to_string(), "split:yay=60,nay=30,abstain=10");
+        let ProposalVote::Split(split) = vote else {
+            panic!("expected a split vote");
+        };
+        assert_eq!(
+            split.split_power(token::Amount::from_u64(1_000)).unwrap(),
+            (
+                token::Amount::from_u64(600),
+                token::Amount::from_u64(300),
+                token::Amount::from_u64(100)
+            )
+        );
+
+        // Omitted sides get no voting power
+        let split = SplitVote::try_from("split:yay=50,abstain=50").unwrap();
+        assert_eq!(split.nay, 0);
+        // The percentages must add up to 100
+        assert!(SplitVote::try_from("split:yay=50,nay=40").is_err());
+        let split = SplitVote {
+            yay: 200,
+            nay: 0,
+            abstain: 0,
+        };
+        assert!(!ProposalVote::Split(split).is_well_formed());
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -13,8 +14,8 @@ use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
 
-use super::storage::proposal::ProposalType;
-use super::storage::vote::ProposalVote;
+use super::storage::proposal::{ProposalType, VotingMode};
+use super::storage::vote::{ChoiceVote, ProposalVote};
 
 /// Proposal status
 pub enum ProposalStatus {
@@ -87,6 +88,8 @@ impl TallyType {
         match (proposal_type, is_steward) {
             (ProposalType::Default, _) => TallyType::TwoFifths,
             (ProposalType::DefaultWithWasm(_), _) => TallyType::TwoFifths,
+            (ProposalType::ParameterChange(_), _) => TallyType::TwoFifths,
+            (ProposalType::Upgrade(_), _) => TallyType::TwoFifths,
             (ProposalType::PGFSteward(_), _) => TallyType::OneHalfOverOneThird,
             (ProposalType::PGFPayment(_), true) => {
                 TallyType::LessOneHalfOverOneThirdNay
@@ -96,6 +99,22 @@ impl TallyType {
             }
         }
     }
+
+    /// The voting power that must have voted for a proposal to pass
+    pub fn quorum(
+        &self,
+        total_voting_power: VotePower,
+    ) -> Result<VotePower, arith::Error> {
+        match self {
+            TallyType::TwoFifths => {
+                total_voting_power.mul_ceil(Dec::two_fifths())
+            }
+            TallyType::OneHalfOverOneThird => {
+                total_voting_power.mul_ceil(Dec::one_third())
+            }
+            TallyType::LessOneHalfOverOneThirdNay => Ok(VotePower::zero()),
+        }
+    }
 }
 
 /// The result of a proposal
@@ -107,6 +126,8 @@ pub enum TallyResult {
     Passed,
     /// Proposal was rejected
     Rejected,
+    /// Proposal was rejected by veto votes above the veto threshold
+    Vetoed,
 }
 
 impl Display for TallyResult {
@@ -114,6 +135,7 @@ impl Display for TallyResult {
         match self {
             TallyResult::Passed => write!(f, "Passed"),
             TallyResult::Rejected => write!(f, "Rejected"),
+            TallyResult::Vetoed => write!(f, "Vetoed"),
         }
     }
 }
@@ -125,9 +147,43 @@ impl FromStr for TallyResult {
         match s {
             "passed" => Ok(Self::Passed),
             "rejected" => Ok(Self::Rejected),
+            "vetoed" => Ok(Self::Vetoed),
             t => Err(format!(
-                "Tally result value of {t:?} does not match \"passed\" nor \
-                 \"rejected\""
+                "Tally result value of {t:?} does not match \"passed\", \
+                 \"rejected\" nor \"vetoed\""
+            )),
+        }
+    }
+}
+
+/// What happens to the deposit of a proposal once it has been tallied
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DepositOutcome {
+    /// The deposit was refunded to the author of the proposal
+    Refunded,
+    /// The deposit was burned
+    Burned,
+}
+
+impl Display for DepositOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepositOutcome::Refunded => write!(f, "refunded"),
+            DepositOutcome::Burned => write!(f, "burned"),
+        }
+    }
+}
+
+impl FromStr for DepositOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "refunded" => Ok(Self::Refunded),
+            "burned" => Ok(Self::Burned),
+            o => Err(format!(
+                "Deposit outcome value of {o:?} does not match \"refunded\" \
+                 nor \"burned\""
             )),
         }
     }
@@ -198,10 +254,54 @@ impl TallyResult {
     }
 }
 
+/// The result of a proposal with options to choose from
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize, BorshDeserializer)]
+pub struct ChoiceResult {
+    /// The voting mode of the proposal, with its options
+    pub voting_mode: VotingMode,
+    /// The voting power of each option. For ranked choice proposals, this is
+    /// the voting power in the last round of the runoff.
+    pub option_powers: Vec<VotePower>,
+    /// The index of the winning option, if any
+    pub winner: Option<u8>,
+    /// The total voting power that voted on the proposal
+    pub total_voted_power: VotePower,
+}
+
+impl Display for ChoiceResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let options = self.voting_mode.options().unwrap_or_default();
+        let format_power = |power: &VotePower| {
+            // Quadratic voting powers are square roots of the stake, so they
+            // are not denominated in the native token
+            if matches!(self.voting_mode, VotingMode::QuadraticChoice(_)) {
+                power.raw_amount().to_string()
+            } else {
+                power.to_string_native()
+            }
+        };
+        let option_powers = options
+            .iter()
+            .zip(&self.option_powers)
+            .map(|(option, power)| format!("{option}: {}", format_power(power)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let winner = self
+            .winner
+            .and_then(|winner| options.get(usize::from(winner)))
+            .map(String::as_str)
+            .unwrap_or("none");
+        write!(
+            f,
+            "winning option: {winner}, option votes: {option_powers}, total \
+             voted power: {}",
+            self.total_voted_power.to_string_native()
+        )
+    }
+}
+
 /// The result with votes of a proposal
-#[derive(
-    Clone, Debug, Copy, BorshDeserialize, BorshSerialize, BorshDeserializer,
-)]
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize, BorshDeserializer)]
 pub struct ProposalResult {
     /// The result of a proposal
     pub result: TallyResult,
@@ -215,6 +315,14 @@ pub struct ProposalResult {
     pub total_nay_power: VotePower,
     /// The total voting power from abstained votes
     pub total_abstain_power: VotePower,
+    /// The total voting power from veto votes, which is also included in the
+    /// nay votes. It's stored under its own key.
+    #[borsh(skip)]
+    pub total_veto_power: VotePower,
+    /// The result of the options, for proposals with options to choose from.
+    /// It's stored under its own key.
+    #[borsh(skip)]
+    pub choice_result: Option<ChoiceResult>,
 }
 
 impl ProposalResult {
@@ -247,20 +355,36 @@ impl ProposalResult {
 
 impl Display for ProposalResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let threshold = match self.tally_type {
-            TallyType::TwoFifths => {
-                self.total_voting_power.mul_ceil(Dec::two_fifths())
-            }
-            TallyType::LessOneHalfOverOneThirdNay => Ok(token::Amount::zero()),
-            _ => self.total_voting_power.mul_ceil(Dec::one_third()),
-        }
-        .unwrap();
+        let threshold =
+            self.tally_type.quorum(self.total_voting_power).unwrap();
 
         let thresh_frac = Dec::try_from(threshold)
             .unwrap()
             .checked_div(Dec::try_from(self.total_voting_power).unwrap())
             .unwrap();
 
+        if let Some(choice_result) = &self.choice_result {
+            return write!(
+                f,
+                "{} with {choice_result}, total voting power: {}, threshold \
+                 (fraction) of total voting power needed to tally: {} ({})",
+                self.result,
+                self.total_voting_power.to_string_native(),
+                threshold.to_string_native(),
+                thresh_frac
+            );
+        }
+
+        let nay_votes = if self.total_veto_power.is_zero() {
+            self.total_nay_power.to_string_native()
+        } else {
+            format!(
+                "{} (of which {} veto)",
+                self.total_nay_power.to_string_native(),
+                self.total_veto_power.to_string_native()
+            )
+        };
+
         write!(
             f,
             "{} with {} yay votes, {} nay votes and {} abstain votes, total \
@@ -268,7 +392,7 @@ impl Display for ProposalResult {
              needed to tally: {} ({})",
             self.result,
             self.total_yay_power.to_string_native(),
-            self.total_nay_power.to_string_native(),
+            nay_votes,
             self.total_abstain_power.to_string_native(),
             self.total_voting_power.to_string_native(),
             threshold.to_string_native(),
@@ -289,6 +413,8 @@ pub struct ProposalVotes {
     pub delegators_vote: HashMap<Address, ProposalVote>,
     /// Map from delegator address to the corresponding validator voting power
     pub delegator_voting_power: HashMap<Address, HashMap<Address, VotePower>>,
+    /// How the votes are tallied
+    pub voting_mode: VotingMode,
 }
 
 impl ProposalVotes {
@@ -326,92 +452,109 @@ impl ProposalVotes {
             .insert(validator_address.clone(), voting_power);
         self.delegators_vote.insert(address.clone(), vote);
     }
+
+    /// The votes with the voting power that they carry. The voting power of a
+    /// validator's vote excludes the bonds of the delegators that voted
+    /// themselves.
+    pub fn ballots(
+        &self,
+    ) -> Result<Vec<(&ProposalVote, VotePower)>, arith::Error> {
+        let mut validator_voting_power = self.validator_voting_power.clone();
+        let mut ballots = vec![];
+
+        for (delegator, delegations) in &self.delegator_voting_power {
+            let Some(delegator_vote) = self.delegators_vote.get(delegator)
+            else {
+                continue;
+            };
+            for (validator, vote_power) in delegations {
+                if self.validators_vote.contains_key(validator) {
+                    if let Some(validator_power) =
+                        validator_voting_power.get_mut(validator)
+                    {
+                        *validator_power =
+                            checked!(validator_power - vote_power)?;
+                    }
+                }
+                ballots.push((delegator_vote, *vote_power));
+            }
+        }
+
+        for (validator, vote_power) in validator_voting_power {
+            if let Some(validator_vote) = self.validators_vote.get(&validator) {
+                ballots.push((validator_vote, vote_power));
+            }
+        }
+
+        Ok(ballots)
+    }
 }
 
-/// Compute the result of a proposal
+/// Compute the result of a proposal. A yay-nay proposal is vetoed if the
+/// veto votes are more than the `veto_threshold` fraction of the voted power.
 pub fn compute_proposal_result(
     votes: ProposalVotes,
     total_voting_power: VotePower,
     tally_type: TallyType,
+    veto_threshold: Dec,
 ) -> Result<ProposalResult, arith::Error> {
+    if !votes.voting_mode.is_yay_nay() {
+        return compute_choice_proposal_result(
+            votes,
+            total_voting_power,
+            tally_type,
+        );
+    }
+
     let mut yay_voting_power = VotePower::default();
     let mut nay_voting_power = VotePower::default();
     let mut abstain_voting_power = VotePower::default();
-
-    for (address, vote_power) in votes.validator_voting_power {
-        let vote_type = votes.validators_vote.get(&address);
-        if let Some(vote) = vote_type {
-            if vote.is_yay() {
-                checked!(yay_voting_power += vote_power)?;
-            } else if vote.is_nay() {
+    let mut veto_voting_power = VotePower::default();
+
+    // The delegators that voted override the vote of their validator with
+    // the voting power of their bonds
+    for (vote, vote_power) in votes.ballots()? {
+        match vote {
+            ProposalVote::Yay => checked!(yay_voting_power += vote_power)?,
+            ProposalVote::Nay => checked!(nay_voting_power += vote_power)?,
+            ProposalVote::Veto => {
                 checked!(nay_voting_power += vote_power)?;
-            } else if vote.is_abstain() {
-                checked!(abstain_voting_power += vote_power)?;
+                checked!(veto_voting_power += vote_power)?;
             }
-        }
-    }
-
-    for (delegator, delegations) in votes.delegator_voting_power {
-        let delegator_vote = match votes.delegators_vote.get(&delegator) {
-            Some(vote) => vote,
-            None => continue,
-        };
-        for (validator, vote_power) in delegations {
-            let validator_vote = votes.validators_vote.get(&validator);
-            if let Some(validator_vote) = validator_vote {
-                let validator_vote_is_same_side =
-                    validator_vote.is_same_side(delegator_vote);
-
-                if !validator_vote_is_same_side {
-                    if delegator_vote.is_yay() {
-                        yay_voting_power =
-                            checked!(yay_voting_power + vote_power)?;
-                        if validator_vote.is_nay() {
-                            nay_voting_power =
-                                checked!(nay_voting_power - vote_power)?;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power =
-                                checked!(abstain_voting_power - vote_power)?;
-                        }
-                    } else if delegator_vote.is_nay() {
-                        nay_voting_power =
-                            checked!(nay_voting_power + vote_power)?;
-                        if validator_vote.is_yay() {
-                            yay_voting_power =
-                                checked!(yay_voting_power - vote_power)?;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power =
-                                checked!(abstain_voting_power - vote_power)?;
-                        }
-                    } else if delegator_vote.is_abstain() {
-                        abstain_voting_power =
-                            checked!(abstain_voting_power + vote_power)?;
-                        if validator_vote.is_yay() {
-                            yay_voting_power =
-                                checked!(yay_voting_power - vote_power)?;
-                        } else if validator_vote.is_nay() {
-                            nay_voting_power =
-                                checked!(nay_voting_power - vote_power)?;
-                        }
-                    }
-                }
-            } else if delegator_vote.is_yay() {
-                checked!(yay_voting_power += vote_power)?;
-            } else if delegator_vote.is_nay() {
-                checked!(nay_voting_power += vote_power)?;
-            } else if delegator_vote.is_abstain() {
-                checked!(abstain_voting_power += vote_power)?;
+            ProposalVote::Abstain => {
+                checked!(abstain_voting_power += vote_power)?
+            }
+            ProposalVote::Split(split) => {
+                let (yay_power, nay_power, abstain_power) =
+                    split.split_power(vote_power)?;
+                checked!(yay_voting_power += yay_power)?;
+                checked!(nay_voting_power += nay_power)?;
+                checked!(abstain_voting_power += abstain_power)?;
             }
+            // Not counted on yay-nay proposals
+            ProposalVote::Choice(_) => {}
         }
     }
 
-    let tally_result = TallyResult::new(
-        &tally_type,
+    let total_voted_power = TallyResult::get_total_voted_power(
         yay_voting_power,
         nay_voting_power,
         abstain_voting_power,
-        total_voting_power,
     )?;
+    let is_vetoed = !veto_voting_power.is_zero()
+        && veto_voting_power > total_voted_power.mul_floor(veto_threshold)?;
+
+    let tally_result = if is_vetoed {
+        TallyResult::Vetoed
+    } else {
+        TallyResult::new(
+            &tally_type,
+            yay_voting_power,
+            nay_voting_power,
+            abstain_voting_power,
+            total_voting_power,
+        )?
+    };
 
     Ok(ProposalResult {
         result: tally_result,
@@ -420,9 +563,193 @@ pub fn compute_proposal_result(
         total_yay_power: yay_voting_power,
         total_nay_power: nay_voting_power,
         total_abstain_power: abstain_voting_power,
+        total_veto_power: veto_voting_power,
+        choice_result: None,
+    })
+}
+
+/// Compute the result of a proposal with options to choose from. The proposal
+/// passes if the voted power reaches the quorum of the tally type and there
+/// is a winning option.
+fn compute_choice_proposal_result(
+    votes: ProposalVotes,
+    total_voting_power: VotePower,
+    tally_type: TallyType,
+) -> Result<ProposalResult, arith::Error> {
+    let num_options = votes.voting_mode.options().unwrap_or_default().len();
+    // Votes that don't match the voting mode are not counted
+    let ballots = votes
+        .ballots()?
+        .into_iter()
+        .filter(|(vote, _)| votes.voting_mode.is_valid_vote(vote))
+        .filter_map(|(vote, vote_power)| match vote {
+            ProposalVote::Choice(choice) => Some((choice, vote_power)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut total_voted_power = VotePower::zero();
+    for (_, vote_power) in &ballots {
+        checked!(total_voted_power += *vote_power)?;
+    }
+
+    let (option_powers, winner) = match &votes.voting_mode {
+        VotingMode::RankedChoice(_) => instant_runoff(&ballots, num_options)?,
+        voting_mode => {
+            let is_quadratic =
+                matches!(voting_mode, VotingMode::QuadraticChoice(_));
+            let mut option_powers = vec![VotePower::zero(); num_options];
+            for (choice, vote_power) in &ballots {
+                for (option, option_power) in
+                    split_vote_power(choice, *vote_power)?
+                {
+                    let option_power = if is_quadratic {
+                        VotePower::from(
+                            option_power.raw_amount().integer_sqrt(),
+                        )
+                    } else {
+                        option_power
+                    };
+                    checked!(
+                        option_powers[usize::from(option)] += option_power
+                    )?;
+                }
+            }
+            let winner = plurality_winner(&option_powers);
+            (option_powers, winner)
+        }
+    };
+
+    let passed = total_voted_power >= tally_type.quorum(total_voting_power)?
+        && winner.is_some();
+
+    Ok(ProposalResult {
+        result: if passed {
+            TallyResult::Passed
+        } else {
+            TallyResult::Rejected
+        },
+        tally_type,
+        total_voting_power,
+        total_yay_power: VotePower::zero(),
+        total_nay_power: VotePower::zero(),
+        total_abstain_power: VotePower::zero(),
+        total_veto_power: VotePower::zero(),
+        choice_result: Some(ChoiceResult {
+            voting_mode: votes.voting_mode,
+            option_powers,
+            winner,
+            total_voted_power,
+        }),
     })
 }
 
+/// Split the voting power of a vote across the options that it chooses
+fn split_vote_power(
+    choice: &ChoiceVote,
+    vote_power: VotePower,
+) -> Result<Vec<(u8, VotePower)>, arith::Error> {
+    match choice {
+        ChoiceVote::Single(option) => Ok(vec![(*option, vote_power)]),
+        ChoiceVote::Weighted(weights) => {
+            let total_weight: u64 =
+                weights.values().copied().map(u64::from).sum();
+            weights
+                .iter()
+                .map(|(option, weight)| {
+                    let option_power = checked!(
+                        vote_power.raw_amount() * Uint::from(*weight)
+                            / Uint::from(total_weight)
+                    )?;
+                    Ok((*option, VotePower::from(option_power)))
+                })
+                .collect()
+        }
+        ChoiceVote::Ranked(ranking) => Ok(ranking
+            .first()
+            .map(|option| (*option, vote_power))
+            .into_iter()
+            .collect()),
+    }
+}
+
+/// The option with the most voting power, if there's a single one with a
+/// non-zero voting power
+fn plurality_winner(option_powers: &[VotePower]) -> Option<u8> {
+    let max_power = option_powers.iter().max()?;
+    if max_power.is_zero() {
+        return None;
+    }
+    let mut winners = option_powers
+        .iter()
+        .enumerate()
+        .filter(|(_, power)| *power == max_power);
+    match (winners.next(), winners.next()) {
+        (Some((winner, _)), None) => u8::try_from(winner).ok(),
+        _ => None,
+    }
+}
+
+/// Tally ranked votes by instant runoff. Each vote counts for its most
+/// preferred option that has not been eliminated. The least voted options are
+/// eliminated until an option has the majority of the votes or the remaining
+/// options are tied.
+fn instant_runoff(
+    ballots: &[(&ChoiceVote, VotePower)],
+    num_options: usize,
+) -> Result<(Vec<VotePower>, Option<u8>), arith::Error> {
+    let mut eliminated = BTreeSet::new();
+    loop {
+        let mut option_powers = vec![VotePower::zero(); num_options];
+        let mut counted_power = VotePower::zero();
+        for (choice, vote_power) in ballots {
+            let ChoiceVote::Ranked(ranking) = choice else {
+                continue;
+            };
+            if let Some(option) =
+                ranking.iter().find(|option| !eliminated.contains(*option))
+            {
+                checked!(option_powers[usize::from(*option)] += *vote_power)?;
+                checked!(counted_power += *vote_power)?;
+            }
+        }
+
+        let remaining = (0..num_options)
+            .filter_map(|option| u8::try_from(option).ok())
+            .filter(|option| !eliminated.contains(option))
+            .collect::<Vec<u8>>();
+        let remaining_powers = remaining
+            .iter()
+            .map(|option| option_powers[usize::from(*option)])
+            .collect::<BTreeSet<_>>();
+
+        // An option has the majority of the counted votes
+        if let Some(winner) = plurality_winner(&option_powers) {
+            let winner_power = option_powers[usize::from(winner)];
+            if checked!(winner_power + winner_power)? > counted_power {
+                return Ok((option_powers, Some(winner)));
+            }
+        }
+
+        // The remaining options are tied
+        if remaining_powers.len() <= 1 {
+            let winner = match remaining.as_slice() {
+                [winner] if !counted_power.is_zero() => Some(*winner),
+                _ => None,
+            };
+            return Ok((option_powers, winner));
+        }
+
+        // Eliminate the least voted options
+        let min_power = remaining_powers.first().copied().unwrap_or_default();
+        eliminated.extend(
+            remaining.into_iter().filter(|option| {
+                option_powers[usize::from(*option)] == min_power
+            }),
+        );
+    }
+}
+
 /// Calculate the valid voting window for a validator given proposal epoch
 /// details.
 ///
@@ -496,6 +823,44 @@ mod test {
     use namada_core::address;
 
     use super::*;
+    use crate::storage::vote::SplitVote;
+
+    #[test]
+    fn test_proposal_result_encoding() {
+        use namada_core::borsh::BorshSerializeExt;
+
+        let power = |amount| token::Amount::native_whole(amount);
+        let proposal_result = ProposalResult {
+            result: TallyResult::Vetoed,
+            tally_type: TallyType::TwoFifths,
+            total_voting_power: power(100),
+            total_yay_power: power(10),
+            total_nay_power: power(50),
+            total_abstain_power: power(5),
+            total_veto_power: power(40),
+            choice_result: None,
+        };
+
+        // The results are encoded like before the veto votes and the options
+        // were introduced, as these are stored under their own keys
+        let (result, tally_type, total, yay, nay, abstain): (
+            TallyResult,
+            TallyType,
+            VotePower,
+            VotePower,
+            VotePower,
+            VotePower,
+        ) = BorshDeserialize::try_from_slice(
+            &proposal_result.serialize_to_vec(),
+        )
+        .unwrap();
+        assert!(matches!(result, TallyResult::Vetoed));
+        assert!(matches!(tally_type, TallyType::TwoFifths));
+        assert_eq!(
+            (total, yay, nay, abstain),
+            (power(100), power(10), power(50), power(5))
+        );
+    }
 
     #[test]
     fn test_proposal_result_no_votes_should_fail() {
@@ -510,6 +875,7 @@ mod test {
                 proposal_votes.clone(),
                 token::Amount::from_u64(1),
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             let _result = if matches!(
@@ -548,6 +914,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power,
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             assert!(
@@ -597,6 +964,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power,
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             assert!(
@@ -646,6 +1014,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power,
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             assert!(
@@ -707,6 +1076,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power,
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             assert!(
@@ -775,6 +1145,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power,
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             assert!(
@@ -834,6 +1205,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power,
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             assert!(
@@ -891,6 +1263,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power.add(validator_voting_power_two),
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             let _result = if matches!(
@@ -964,6 +1337,7 @@ mod test {
                 proposal_votes.clone(),
                 validator_voting_power.add(validator_voting_power_two),
                 tally_type,
+                Dec::one_third(),
             )
             .unwrap();
             let _result =
@@ -1032,6 +1406,7 @@ mod test {
             proposal_votes.clone(),
             validator_voting_power.add(validator_voting_power_two),
             TallyType::TwoFifths,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1101,6 +1476,7 @@ mod test {
             proposal_votes.clone(),
             validator_voting_power.add(validator_voting_power_two),
             TallyType::TwoFifths,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1157,6 +1533,7 @@ mod test {
             proposal_votes.clone(),
             delegator_voting_power_two.add(delegator_voting_power),
             TallyType::TwoFifths,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1210,6 +1587,7 @@ mod test {
             proposal_votes.clone(),
             token::Amount::from(200),
             TallyType::TwoFifths,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1265,6 +1643,7 @@ mod test {
             proposal_votes.clone(),
             token::Amount::from(403),
             TallyType::OneHalfOverOneThird,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1320,6 +1699,7 @@ mod test {
             proposal_votes.clone(),
             token::Amount::from(402),
             TallyType::OneHalfOverOneThird,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1375,6 +1755,7 @@ mod test {
             proposal_votes.clone(),
             token::Amount::from(100),
             TallyType::LessOneHalfOverOneThirdNay,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1432,6 +1813,7 @@ mod test {
             proposal_votes.clone(),
             token::Amount::from(271),
             TallyType::LessOneHalfOverOneThirdNay,
+            Dec::one_third(),
         )
         .unwrap();
 
@@ -1529,4 +1911,165 @@ mod test {
             ));
         }
     }
+
+    #[test]
+    fn test_proposal_split_votes() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        let validator_voting_power = token::Amount::from_u64(100);
+        proposal_votes.add_validator(
+            &validator_address,
+            validator_voting_power,
+            ProposalVote::Split(SplitVote {
+                yay: 60,
+                nay: 30,
+                abstain: 10,
+            }),
+        );
+
+        // The delegator overrides the split vote of its validator for the
+        // voting power of its bond
+        let delegator_address = address::testing::established_address_2();
+        let delegator_voting_power = token::Amount::from_u64(50);
+        proposal_votes.add_delegator(
+            &delegator_address,
+            &validator_address,
+            delegator_voting_power,
+            ProposalVote::Split(SplitVote {
+                yay: 0,
+                nay: 50,
+                abstain: 50,
+            }),
+        );
+
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            validator_voting_power,
+            TallyType::OneHalfOverOneThird,
+            Dec::one_third(),
+        )
+        .unwrap();
+        assert_eq!(
+            proposal_result.total_yay_power,
+            token::Amount::from_u64(30)
+        );
+        assert_eq!(
+            proposal_result.total_nay_power,
+            token::Amount::from_u64(40)
+        );
+        assert_eq!(
+            proposal_result.total_abstain_power,
+            token::Amount::from_u64(30)
+        );
+        assert!(matches!(proposal_result.result, TallyResult::Rejected));
+    }
+
+    #[test]
+    fn test_proposal_vetoed() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        let validator_voting_power = token::Amount::from_u64(60);
+        proposal_votes.add_validator(
+            &validator_address,
+            validator_voting_power,
+            ProposalVote::Yay,
+        );
+
+        let other_validator_address = address::testing::established_address_2();
+        let other_validator_voting_power = token::Amount::from_u64(40);
+        proposal_votes.add_validator(
+            &other_validator_address,
+            other_validator_voting_power,
+            ProposalVote::Veto,
+        );
+
+        let total_voting_power = token::Amount::from_u64(100);
+
+        // The vetoes are above a third of the voted power
+        let proposal_result = compute_proposal_result(
+            proposal_votes.clone(),
+            total_voting_power,
+            TallyType::OneHalfOverOneThird,
+            Dec::one_third(),
+        )
+        .unwrap();
+        assert!(matches!(proposal_result.result, TallyResult::Vetoed));
+        assert_eq!(proposal_result.total_yay_power, validator_voting_power);
+        assert_eq!(
+            proposal_result.total_nay_power,
+            other_validator_voting_power
+        );
+        assert_eq!(
+            proposal_result.total_veto_power,
+            other_validator_voting_power
+        );
+
+        // The vetoes are below half of the voted power, so they only count
+        // as nay votes
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            total_voting_power,
+            TallyType::OneHalfOverOneThird,
+            Dec::new(5, 1).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(proposal_result.result, TallyResult::Passed));
+    }
+
+    #[test]
+    fn test_proposal_result_ranked_choice() {
+        let voting_mode = VotingMode::RankedChoice(vec![
+            "first".to_string(),
+            "second".to_string(),
+            "third".to_string(),
+        ]);
+        let mut proposal_votes = ProposalVotes {
+            voting_mode: voting_mode.clone(),
+            ..ProposalVotes::default()
+        };
+        for (validator, power, ranking) in [
+            (address::testing::established_address_1(), 40, vec![0, 1, 2]),
+            (address::testing::established_address_2(), 35, vec![1, 2, 0]),
+            (address::testing::established_address_3(), 25, vec![2, 1, 0]),
+        ] {
+            proposal_votes.add_validator(
+                &validator,
+                token::Amount::from_u64(power),
+                ProposalVote::Choice(ChoiceVote::Ranked(ranking)),
+            );
+        }
+
+        // The first option has a plurality, but the second one wins the runoff
+        // once the third option is eliminated
+        let proposal_result = compute_proposal_result(
+            proposal_votes.clone(),
+            token::Amount::from_u64(100),
+            TallyType::TwoFifths,
+            Dec::one_third(),
+        )
+        .unwrap();
+        assert!(matches!(proposal_result.result, TallyResult::Passed));
+        let choice_result = proposal_result.choice_result.unwrap();
+        assert_eq!(choice_result.winner, Some(1));
+        assert_eq!(
+            choice_result.option_powers,
+            vec![
+                token::Amount::from_u64(40),
+                token::Amount::from_u64(60),
+                token::Amount::zero(),
+            ]
+        );
+
+        // Not enough voting power for the quorum
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            token::Amount::from_u64(1000),
+            TallyType::TwoFifths,
+            Dec::one_third(),
+        )
+        .unwrap();
+        assert!(matches!(proposal_result.result, TallyResult::Rejected));
+    }
 }
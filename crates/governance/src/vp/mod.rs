@@ -10,6 +10,7 @@ use borsh::BorshDeserialize;
 use namada_core::arith::checked;
 use namada_core::booleans::{BoolResultUnitExt, ResultBoolExt};
 use namada_core::chain::Epoch;
+use namada_core::parameter_change::validate_parameter_changes;
 use namada_core::storage;
 use namada_systems::{proof_of_stake, trans_token as token};
 use namada_tx::action::{Action, GovAction};
@@ -19,8 +20,12 @@ use thiserror::Error;
 
 use self::utils::ReadType;
 use crate::address::{Address, InternalAddress};
-use crate::storage::proposal::{AddRemove, PGFAction, ProposalType};
-use crate::storage::{is_proposal_accepted, keys as gov_storage};
+use crate::storage::proposal::{
+    AddRemove, PGFAction, ProposalType, VotingMode,
+};
+use crate::storage::{
+    get_proposal_voting_mode, is_proposal_accepted, keys as gov_storage,
+};
 use crate::utils::is_valid_validator_voting_period;
 use crate::ProposalVote;
 
@@ -146,6 +151,9 @@ where
                 (KeyType::TYPE, Some(proposal_id)) => {
                     Self::is_valid_proposal_type(ctx, proposal_id)
                 }
+                (KeyType::VOTING_MODE, Some(proposal_id)) => {
+                    Self::is_valid_voting_mode(ctx, proposal_id)
+                }
                 (KeyType::PROPOSAL_CODE, Some(proposal_id)) => {
                     Self::is_valid_proposal_code(ctx, proposal_id)
                 }
@@ -277,11 +285,17 @@ where
             validator.clone(),
         );
 
-        if Self::force_read::<ProposalVote>(ctx, &vote_key, ReadType::Post)
-            .is_err()
-        {
+        let vote =
+            Self::force_read::<ProposalVote>(ctx, &vote_key, ReadType::Post)
+                .map_err(|_| {
+                    Error::new_alloc(format!("Vote key is not valid: {key}"))
+                })?;
+
+        let voting_mode = get_proposal_voting_mode(&ctx.pre(), proposal_id)?;
+        if !voting_mode.is_valid_vote(&vote) {
             return Err(Error::new_alloc(format!(
-                "Vote key is not valid: {key}"
+                "The vote {vote} is not valid for the voting mode of the \
+                 proposal with id {proposal_id}"
             )));
         }
 
@@ -539,6 +553,21 @@ where
                     )
                 })
             }
+            ProposalType::ParameterChange(changes) => {
+                validate_parameter_changes(&changes).map_err(Error::new)
+            }
+            ProposalType::Upgrade(upgrade) => {
+                let current_height = ctx.get_block_height()?;
+                if upgrade.height > current_height {
+                    Ok(())
+                } else {
+                    Err(Error::new_alloc(format!(
+                        "The upgrade height {} must be after the current \
+                         block height {current_height}",
+                        upgrade.height
+                    )))
+                }
+            }
             // Default proposal condition are checked already for all other
             // proposals.
             // default_with_wasm proposal needs to check only for valid code
@@ -546,6 +575,48 @@ where
         }
     }
 
+    /// Validate the voting mode of a proposal
+    pub fn is_valid_voting_mode(
+        ctx: &'ctx CTX,
+        proposal_id: u64,
+    ) -> Result<()> {
+        // The voting mode can only be set by the tx that creates the proposal
+        let counter_key = gov_storage::get_counter_key();
+        let pre_counter: u64 =
+            Self::force_read(ctx, &counter_key, ReadType::Pre)?;
+        if proposal_id < pre_counter {
+            return Err(Error::new_alloc(format!(
+                "The voting mode of the existing proposal with id \
+                 {proposal_id} cannot be modified"
+            )));
+        }
+        let voting_mode_key = gov_storage::get_voting_mode_key(proposal_id);
+        if ctx.has_key_pre(&voting_mode_key)? {
+            return Err(Error::new_alloc(format!(
+                "Proposal with id {proposal_id} already had a voting mode \
+                 written to storage"
+            )));
+        }
+
+        let voting_mode: VotingMode =
+            Self::force_read(ctx, &voting_mode_key, ReadType::Post)?;
+        voting_mode.validate().map_err(Error::new)?;
+
+        // Only default proposals can have options to choose from, as the
+        // other proposal types need a yay or nay outcome to be executed
+        let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
+        let proposal_type: ProposalType =
+            Self::force_read(ctx, &proposal_type_key, ReadType::Post)?;
+        (voting_mode.is_yay_nay() || proposal_type.is_default()).ok_or_else(
+            || {
+                Error::new_alloc(format!(
+                    "Proposal with id {proposal_id} of type {proposal_type} \
+                     cannot have options to choose from"
+                ))
+            },
+        )
+    }
+
     /// Validate a proposal code
     pub fn is_valid_proposal_code(
         ctx: &'ctx CTX,
@@ -1094,6 +1165,8 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     TYPE,
     #[allow(non_camel_case_types)]
+    VOTING_MODE,
+    #[allow(non_camel_case_types)]
     PROPOSAL_COMMIT,
     #[allow(non_camel_case_types)]
     ACTIVATION_EPOCH,
@@ -1126,6 +1199,8 @@ impl KeyType {
             KeyType::CONTENT
         } else if gov_storage::is_proposal_type_key(key) {
             Self::TYPE
+        } else if gov_storage::is_voting_mode_key(key) {
+            Self::VOTING_MODE
         } else if gov_storage::is_proposal_code_key(key) {
             Self::PROPOSAL_CODE
         } else if gov_storage::is_activation_epoch_key(key) {
@@ -1193,10 +1268,10 @@ mod test {
     use crate::storage::keys::{
         get_activation_epoch_key, get_author_key, get_committing_proposals_key,
         get_content_key, get_counter_key, get_funds_key, get_proposal_type_key,
-        get_vote_proposal_key, get_voting_end_epoch_key,
+        get_vote_proposal_key, get_voting_end_epoch_key, get_voting_mode_key,
         get_voting_start_epoch_key,
     };
-    use crate::{ProposalType, ProposalVote, ADDRESS};
+    use crate::{ProposalType, ProposalVote, VotingMode, ADDRESS};
 
     type CA = WasmCacheRwAccess;
     type Eval<S> = VpEvalWasm<<S as StateRead>::D, <S as StateRead>::H, CA>;
@@ -2219,6 +2294,82 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_governance_voting_mode_of_existing_proposal_failed() {
+        let mut state = init_storage();
+
+        let proposal_id = 0;
+        let activation_epoch = 19;
+
+        let signer = keypair_1();
+        let signer_address = Address::from(&signer.clone().ref_to());
+        let verifiers = BTreeSet::from([signer_address.clone()]);
+
+        initialize_account_balance(
+            &mut state,
+            &signer_address.clone(),
+            token::Amount::native_whole(510),
+        );
+        initialize_account_balance(
+            &mut state,
+            &ADDRESS,
+            token::Amount::native_whole(0),
+        );
+        init_proposal(
+            &mut state,
+            proposal_id,
+            500,
+            3,
+            9,
+            activation_epoch,
+            &signer_address,
+            false,
+        );
+        state.write_log_mut().commit_batch_and_current_tx();
+        state.commit_block().unwrap();
+
+        // Give the existing yay-nay proposal options to choose from
+        let voting_mode_key = get_voting_mode_key(proposal_id);
+        let _ = state
+            .write_log_mut()
+            .write(
+                &voting_mode_key,
+                VotingMode::MultipleChoice(vec!["a".into(), "b".into()])
+                    .serialize_to_vec(),
+            )
+            .unwrap();
+        let keys_changed = BTreeSet::from([voting_mode_key]);
+
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new(u64::MAX),
+        ));
+        let (vp_wasm_cache, _vp_cache_dir) =
+            wasm::compilation_cache::common::testing::vp_cache();
+        let tx_index = TxIndex::default();
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = state.in_mem().chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![]));
+
+        let batched_tx = tx.batch_ref_first_tx().unwrap();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &state,
+            batched_tx.tx,
+            batched_tx.cmt,
+            &tx_index,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        assert_matches!(
+            GovernanceVp::is_valid_voting_mode(&ctx, proposal_id),
+            Err(_)
+        );
+    }
+
     #[test]
     fn test_governance_vote_validator_success() {
         let mut state = init_storage();
@@ -91,6 +91,7 @@ use namada_core::ibc::core::channel::types::commitment::{
 pub use namada_core::ibc::*;
 use namada_core::masp::{addr_taddr, ibc_taddr, TAddrData};
 use namada_core::masp_primitives::transaction::components::ValueSum;
+use namada_core::parameter_change::IbcParameterChange;
 use namada_core::token::Amount;
 use namada_events::EmitEvents;
 use namada_state::{
@@ -108,6 +109,7 @@ use trace::{
     is_sender_chain_source,
 };
 
+use crate::parameters::IbcParameters;
 use crate::storage::{
     channel_counter_key, client_counter_key, connection_counter_key,
     deposit_prefix, withdraw_prefix,
@@ -349,6 +351,26 @@ where
     }
 }
 
+impl<S> namada_systems::ibc::Write<S> for Store<S>
+where
+    S: StorageRead + StorageWrite,
+{
+    fn change_params(
+        storage: &mut S,
+        changes: &[IbcParameterChange],
+    ) -> StorageResult<()> {
+        let key = storage::params_key();
+        let mut params: IbcParameters =
+            storage.read(&key)?.ok_or_else(|| {
+                StorageError::new_const("The IBC parameters are not stored")
+            })?;
+        for change in changes {
+            params.apply_change(change);
+        }
+        storage.write(&key, params)
+    }
+}
+
 fn check_ibc_transfer<S>(
     storage: &S,
     ibc_transfer: &IbcTransferInfo,
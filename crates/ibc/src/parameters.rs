@@ -1,6 +1,7 @@
 //! IBC system parameters
 
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::parameter_change::IbcParameterChange;
 use namada_core::token::Amount;
 use namada_state::{Result, StorageWrite};
 
@@ -31,4 +32,16 @@ impl IbcParameters {
         let key = crate::storage::params_key();
         storage.write(&key, self)
     }
+
+    /// Apply a change of a parameter proposed by governance
+    pub fn apply_change(&mut self, change: &IbcParameterChange) {
+        match change {
+            IbcParameterChange::DefaultMintLimit(amount) => {
+                self.default_mint_limit = *amount;
+            }
+            IbcParameterChange::DefaultPerEpochThroughputLimit(amount) => {
+                self.default_per_epoch_throughput_limit = *amount;
+            }
+        }
+    }
 }
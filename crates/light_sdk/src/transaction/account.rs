@@ -1,3 +1,4 @@
+use namada_sdk::account::{AccountPolicy, VestingSchedule};
 use namada_sdk::address::Address;
 use namada_sdk::hash::Hash;
 use namada_sdk::key::common;
@@ -22,12 +23,16 @@ impl InitAccount {
         public_keys: Vec<common::PublicKey>,
         vp_code_hash: Hash,
         threshold: u8,
+        vesting: Vec<VestingSchedule>,
+        policy: Option<AccountPolicy>,
         args: GlobalArgs,
     ) -> Self {
         let init_account = namada_sdk::account::InitAccount {
             public_keys,
             vp_code_hash,
             threshold,
+            vesting,
+            policy,
         };
 
         Self(transaction::build_tx(
@@ -175,6 +180,7 @@ impl UpdateAccount {
         vp_code_hash: Option<Hash>,
         public_keys: Vec<common::PublicKey>,
         threshold: Option<u8>,
+        policy: Option<AccountPolicy>,
         args: GlobalArgs,
     ) -> Self {
         let update_account = namada_sdk::account::UpdateAccount {
@@ -182,6 +188,7 @@ impl UpdateAccount {
             vp_code_hash,
             public_keys,
             threshold,
+            policy,
         };
 
         Self(transaction::build_tx(
@@ -1,6 +1,6 @@
 use namada_sdk::address::Address;
 use namada_sdk::chain::Epoch;
-use namada_sdk::governance::{ProposalType, ProposalVote};
+use namada_sdk::governance::{ProposalType, ProposalVote, VotingMode};
 use namada_sdk::hash::Hash;
 use namada_sdk::key::common;
 use namada_sdk::token::DenominatedAmount;
@@ -24,6 +24,7 @@ impl InitProposal {
         content: Hash,
         author: Address,
         r#type: ProposalType,
+        voting_mode: VotingMode,
         voting_start_epoch: Epoch,
         voting_end_epoch: Epoch,
         activation_epoch: Epoch,
@@ -36,6 +37,7 @@ impl InitProposal {
             voting_start_epoch,
             voting_end_epoch,
             activation_epoch,
+            voting_mode,
         };
 
         Self(transaction::build_tx(
@@ -30,7 +30,7 @@ use namada_sdk::events::extend::{
 };
 use namada_sdk::events::Event;
 use namada_sdk::gas::TxGasMeter;
-use namada_sdk::governance::storage::proposal::ProposalType;
+use namada_sdk::governance::storage::proposal::{ProposalType, VotingMode};
 use namada_sdk::governance::{self, InitProposalData};
 use namada_sdk::ibc::apps::transfer::types::msgs::transfer::MsgTransfer as IbcMsgTransfer;
 use namada_sdk::ibc::apps::transfer::types::packet::PacketData;
@@ -632,6 +632,7 @@ impl Default for BenchShell {
                 voting_start_epoch,
                 voting_end_epoch: voting_start_epoch.unchecked_add(3_u64),
                 activation_epoch: voting_start_epoch.unchecked_add(9_u64),
+                voting_mode: VotingMode::default(),
             },
             None,
             Some(vec![content_section]),
@@ -1223,6 +1224,7 @@ impl BenchShieldedCtx {
                         None,
                         expiration,
                         true,
+                        &masp::NoteSelection::default(),
                     )
                     .await
             })
@@ -420,6 +420,15 @@ pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
 }
 
+/// Prune the storage archive, only keeping the values needed to read it from
+/// the given height onwards
+pub fn prune_archive(
+    config: config::Ledger,
+    args::LedgerPruneArchive { block_height }: args::LedgerPruneArchive,
+) -> Result<(), shell::Error> {
+    shell::prune_archive(config, block_height)
+}
+
 /// Runs and monitors a few concurrent tasks.
 ///
 /// This includes:
@@ -714,6 +714,9 @@ where
         let first_tx = tx
             .batch_ref_first_tx()
             .ok_or_else(|| Error::MissingInnerTxs)?;
+        // Only the modifications of the fee payment must be discarded if it
+        // fails
+        state.write_log_mut().push_savepoint();
         match apply_wasm_tx(
             &first_tx,
             tx_index,
@@ -736,6 +739,10 @@ where
                 // Ensure that the transaction is actually a masp one, otherwise
                 // reject
                 if is_masp_transfer && result.is_accepted() {
+                    state
+                        .write_log_mut()
+                        .release_savepoint()
+                        .map_err(|err| Error::StateError(err.into()))?;
                     get_optional_masp_ref(
                         *state,
                         first_tx.cmt,
@@ -748,7 +755,10 @@ where
                         }
                     })
                 } else {
-                    state.write_log_mut().drop_tx();
+                    state
+                        .write_log_mut()
+                        .rollback_savepoint()
+                        .map_err(|err| Error::StateError(err.into()))?;
 
                     let mut error_msg = MASP_FEE_PAYMENT_ERROR.to_string();
                     if !is_masp_transfer {
@@ -766,7 +776,10 @@ where
                 }
             }
             Err(e) => {
-                state.write_log_mut().drop_tx();
+                state
+                    .write_log_mut()
+                    .rollback_savepoint()
+                    .map_err(|err| Error::StateError(err.into()))?;
                 tracing::error!(
                     "{MASP_FEE_PAYMENT_ERROR} Wasm run failed: {}",
                     e
@@ -1299,7 +1312,8 @@ where
                                 .map_err(Error::NativeVpError)
                             }
                             internal_addr @ (InternalAddress::IbcToken(_)
-                            | InternalAddress::Erc20(_)) => {
+                            | InternalAddress::Erc20(_)
+                            | InternalAddress::LiquidStakingToken(_)) => {
                                 // The address should be a part of a multitoken
                                 // key
                                 verifiers
@@ -1417,6 +1431,7 @@ mod tests {
     use namada_sdk::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
     use namada_sdk::ethereum_events::{EthereumEvent, TransferToNamada};
     use namada_sdk::keccak::keccak_hash;
+    use namada_sdk::state::StorageWrite;
     use namada_sdk::tx::{SignableEthMessage, Signed};
     use namada_sdk::voting_power::FractionalVotingPower;
     use namada_sdk::{address, key};
@@ -1634,4 +1649,49 @@ mod tests {
         );
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
     }
+
+    /// Test that a failed MASP fee payment only discards its own
+    /// modifications of the write log
+    #[test]
+    fn test_failed_masp_fee_payment_rollback() {
+        let (mut state, _validators) = test_utils::setup_default_storage();
+        parameters::init_test_storage(&mut state).unwrap();
+        state
+            .write(
+                &parameters::storage::get_masp_fee_payment_gas_limit_key(),
+                50_000_u64,
+            )
+            .unwrap();
+        state.commit_tx_batch();
+
+        // A modification of the transaction that precedes the fee payment
+        let key = storage::Key::parse("key").unwrap();
+        state.write_log_mut().write(&key, vec![1]).unwrap();
+
+        // A batch whose first transaction can't be run
+        let mut tx = Tx::from_type(namada_sdk::tx::data::TxType::Raw);
+        tx.set_code(namada_sdk::tx::Code::new(vec![], None));
+        tx.set_data(namada_sdk::tx::Data::new(vec![]));
+
+        let (mut vp_cache, _) =
+            wasm::compilation_cache::common::testing::vp_cache();
+        let (mut tx_cache, _) =
+            wasm::compilation_cache::common::testing::tx_cache();
+        let gas_meter = RefCell::new(TxGasMeter::new(u64::MAX));
+        let result = try_masp_fee_payment(
+            &mut ShellParams::new(
+                &gas_meter,
+                &mut state,
+                &mut vp_cache,
+                &mut tx_cache,
+            ),
+            &tx,
+            &TxIndex::default(),
+        );
+        assert!(matches!(result, Ok(None)));
+
+        // The preceding modification is kept
+        assert_eq!(state.write_log().savepoints_depth(), 0);
+        assert!(state.write_log().read(&key).unwrap().0.is_some());
+    }
 }
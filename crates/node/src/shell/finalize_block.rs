@@ -3,8 +3,10 @@
 use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
+use namada_apps_lib::cli::namada_version;
 use namada_sdk::events::extend::{
-    ComposeEvent, Height, IndexedMaspData, Info, MaspDataRefs, TxHash,
+    ComposeEvent, Height, IndexedMaspData, Info, MaspDataRefs, MaspTxRefs,
+    TxHash,
 };
 use namada_sdk::events::{EmitEvents, Event};
 use namada_sdk::gas::event::GasUsed;
@@ -23,7 +25,7 @@ use namada_sdk::storage::{BlockHeader, BlockResults, Epoch};
 use namada_sdk::tx::data::protocol::ProtocolTxType;
 use namada_sdk::tx::data::VpStatusFlags;
 use namada_sdk::tx::event::{Batch, Code};
-use namada_sdk::tx::new_tx_event;
+use namada_sdk::tx::{new_tx_event, IndexedTx};
 use namada_sdk::{ibc, proof_of_stake};
 use namada_vote_ext::ethereum_events::MultiSignedEthEvent;
 use namada_vote_ext::ethereum_tx_data_variants;
@@ -51,8 +53,20 @@ where
     ) -> ShellResult<shim::response::FinalizeBlock> {
         let mut response = shim::response::FinalizeBlock::default();
 
+        // Halt for a software upgrade scheduled by governance before making
+        // any state changes
+        let is_upgrade_height = self.check_scheduled_upgrade(
+            self.state.in_mem().get_last_block_height().next_height(),
+        )?;
+
         // Begin the new block and check if a new epoch has begun
         let (height, new_epoch) = self.update_state(req.header);
+
+        // The upgraded binary is processing the upgrade height, so the
+        // upgrade is done
+        if is_upgrade_height {
+            governance::storage::remove_scheduled_upgrade(&mut self.state)?;
+        }
         let masp_epoch_multiplier =
             parameters::read_masp_epoch_multiplier_parameter(&self.state)
                 .expect("Must have parameters");
@@ -116,6 +130,8 @@ where
         if new_epoch {
             // Apply PoS and PGF inflation
             self.apply_inflation(current_epoch, emit_events)?;
+            // Pick up any validator keys that were rotated in for this epoch
+            self.update_validator_keys(current_epoch);
         }
 
         let mut stats = InternalStats::default();
@@ -134,6 +150,7 @@ where
         // Tracks the accepted transactions
         self.state.in_mem_mut().block.results = BlockResults::default();
         let mut changed_keys = BTreeSet::new();
+        let mut masp_block_notes = vec![];
 
         // Execute wrapper and protocol transactions
         let successful_wrappers = self.retrieve_and_execute_transactions(
@@ -142,6 +159,7 @@ where
             ExecutionArgs {
                 response: &mut response,
                 changed_keys: &mut changed_keys,
+                masp_block_notes: &mut masp_block_notes,
                 stats: &mut stats,
                 height,
             },
@@ -153,6 +171,7 @@ where
             ExecutionArgs {
                 response: &mut response,
                 changed_keys: &mut changed_keys,
+                masp_block_notes: &mut masp_block_notes,
                 stats: &mut stats,
                 height,
             },
@@ -186,6 +205,9 @@ where
             );
             self.state.write(&anchor_key, ())?;
         }
+        // Keep the compact notes of the MASP txs to index the block once it's
+        // committed
+        self.masp_block_notes = masp_block_notes;
 
         if update_for_tendermint {
             self.update_epoch(&mut response);
@@ -205,6 +227,92 @@ where
         Ok(response)
     }
 
+    /// Check that this node can process the block at the given height. At
+    /// the height of a software upgrade scheduled by governance, only the
+    /// upgraded binary may proceed and, if the upgrade comes with a DB
+    /// migration, the migration must be scheduled at the upgrade height.
+    /// Returns whether the height is the upgrade height.
+    fn check_scheduled_upgrade(
+        &mut self,
+        height: BlockHeight,
+    ) -> ShellResult<bool> {
+        let Some(upgrade) =
+            governance::storage::get_scheduled_upgrade(&self.state)?
+        else {
+            return Ok(false);
+        };
+        if height != upgrade.height {
+            return Ok(false);
+        }
+
+        // The upgraded binary is recognized by its version or, for the builds
+        // that don't report it, by its hash
+        let version = namada_version();
+        if !is_upgrade_version(version, &upgrade.version)
+            && !self.is_allowed_upgrade_binary(&upgrade)?
+        {
+            tracing::error!(
+                "Reached the upgrade height {}. The running binary with \
+                 version {version} must be replaced by the upgraded binary \
+                 with version {}.",
+                upgrade.height,
+                upgrade.version
+            );
+            return Err(Error::ScheduledUpgrade(
+                upgrade.height,
+                format!(
+                    "The version of the running binary {version} doesn't \
+                     match the version of the upgraded binary {}",
+                    upgrade.version
+                ),
+            ));
+        }
+
+        if let Some(migration_hash) = upgrade.migration_hash {
+            let is_migration_scheduled =
+                self.scheduled_migration.as_ref().is_some_and(|migration| {
+                    migration.height == upgrade.height
+                        && migration.hash == migration_hash
+                });
+            if !is_migration_scheduled {
+                return Err(Error::ScheduledUpgrade(
+                    upgrade.height,
+                    format!(
+                        "The migration with hash {migration_hash} is not \
+                         scheduled at the upgrade height"
+                    ),
+                ));
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Check if the hash of the running binary is one of the hashes of the
+    /// upgraded binary accepted by the scheduled upgrade
+    fn is_allowed_upgrade_binary(
+        &mut self,
+        upgrade: &governance::storage::proposal::ScheduledUpgrade,
+    ) -> ShellResult<bool> {
+        if upgrade.binary_hashes.is_empty() {
+            return Ok(false);
+        }
+        let binary_hash = match self.binary_hash {
+            Some(hash) => hash,
+            None => {
+                let hash = running_binary_hash().map_err(|err| {
+                    Error::ScheduledUpgrade(
+                        upgrade.height,
+                        format!("Couldn't read the running binary: {err}"),
+                    )
+                })?;
+                self.binary_hash = Some(hash);
+                hash
+            }
+        };
+        Ok(upgrade.binary_hashes.contains(&binary_hash))
+    }
+
     /// Sets the metadata necessary for a new block, including the height,
     /// validator changes, and evidence of byzantine behavior. Applies slashes
     /// if necessary. Returns a boolean indicating if a new epoch and the height
@@ -474,6 +582,11 @@ where
                 .results
                 .accept(tx_data.tx_index);
             temp_log.commit(tx_logs, response);
+            record_masp_block_notes(
+                &extended_tx_result.masp_tx_refs,
+                &tx_data,
+                tx_logs.masp_block_notes,
+            );
 
             // Atomic successful batches or non-atomic batches (even if the
             // inner txs failed) are marked as Ok
@@ -547,6 +660,11 @@ where
             // from the function that runs the actual batch)
             self.state.write_log_mut().drop_tx();
             self.state.write_log_mut().commit_batch_only();
+            record_masp_block_notes(
+                &extended_tx_result.masp_tx_refs,
+                &tx_data,
+                tx_logs.masp_block_notes,
+            );
         }
 
         if commit_batch_hash {
@@ -598,6 +716,7 @@ where
         ExecutionArgs {
             response,
             changed_keys,
+            masp_block_notes,
             stats,
             height,
         }: ExecutionArgs<'_>,
@@ -804,6 +923,7 @@ where
                     tx_event,
                     stats,
                     changed_keys,
+                    masp_block_notes,
                 },
             ) {
                 successful_wrappers.push(wrapper_cache);
@@ -820,6 +940,7 @@ where
         ExecutionArgs {
             response,
             changed_keys,
+            masp_block_notes,
             stats,
             height,
         }: ExecutionArgs<'_>,
@@ -877,6 +998,7 @@ where
                     tx_event,
                     stats,
                     changed_keys,
+                    masp_block_notes,
                 },
             );
         }
@@ -886,6 +1008,7 @@ where
 struct ExecutionArgs<'finalize> {
     response: &'finalize mut shim::response::FinalizeBlock,
     changed_keys: &'finalize mut BTreeSet<Key>,
+    masp_block_notes: &'finalize mut Vec<token::block_index::CompactMaspTx>,
     stats: &'finalize mut InternalStats,
     height: BlockHeight,
 }
@@ -914,6 +1037,48 @@ struct TxLogs<'finalize> {
     tx_event: Event,
     stats: &'finalize mut InternalStats,
     changed_keys: &'finalize mut BTreeSet<Key>,
+    masp_block_notes: &'finalize mut Vec<token::block_index::CompactMaspTx>,
+}
+
+// Record the compact note data of the MASP txs of a committed batch, to serve
+// it to the shielded sync clients
+fn record_masp_block_notes(
+    masp_tx_refs: &MaspTxRefs,
+    tx_data: &TxData<'_>,
+    masp_block_notes: &mut Vec<token::block_index::CompactMaspTx>,
+) {
+    if masp_tx_refs.0.is_empty() {
+        return;
+    }
+    let masp_txs =
+        match namada_sdk::masp::extract_masp_tx(tx_data.tx, masp_tx_refs) {
+            Ok(masp_txs) => masp_txs,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to extract the MASP txs of tx {} at height {}: {}",
+                    tx_data.tx_index,
+                    tx_data.height,
+                    err
+                );
+                return;
+            }
+        };
+    // The MASP txs are indexed sequentially within the batch, as done by the
+    // shielded sync clients
+    let compact_txs = (0_u32..).zip(masp_tx_refs.0.iter().zip(masp_txs)).map(
+        |(batch_index, (masp_ref, masp_tx))| {
+            token::block_index::CompactMaspTx::new(
+                IndexedTx {
+                    height: tx_data.height,
+                    index: TxIndex::must_from_usize(tx_data.tx_index),
+                    batch_index: Some(batch_index),
+                },
+                masp_ref.clone(),
+                &masp_tx,
+            )
+        },
+    );
+    masp_block_notes.extend(compact_txs);
 }
 
 #[derive(Default)]
@@ -1057,6 +1222,31 @@ struct ReplayProtectionHashes {
     header_hash: Hash,
 }
 
+/// Check if the version of the running binary is the version of a scheduled
+/// upgrade. The versions are compared without their leading `v` and without
+/// the suffix that `git describe` adds to the builds past a release tag.
+fn is_upgrade_version(version: &str, upgrade_version: &str) -> bool {
+    fn release(version: &str) -> &str {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version.strip_suffix("-dirty").unwrap_or(version);
+        // Strip the number of commits since the tag and the commit hash
+        match version.rsplitn(3, '-').collect::<Vec<_>>()[..] {
+            [hash, commits, tag]
+                if hash.strip_prefix('g').is_some_and(|hash| {
+                    !hash.is_empty()
+                        && hash.chars().all(|c| c.is_ascii_hexdigit())
+                }) && !commits.is_empty()
+                    && commits.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                tag
+            }
+            _ => version,
+        }
+    }
+    release(version) == release(upgrade_version)
+}
+
 /// Convert ABCI vote info to PoS vote info. Any info which fails the conversion
 /// will be skipped and errors logged.
 ///
@@ -1135,6 +1325,8 @@ where
         _,
         token::Store<_>,
         proof_of_stake::Store<_>,
+        parameters::Store<_>,
+        ibc::Store<_>,
         _,
         _,
     >(
@@ -1234,7 +1426,7 @@ fn pos_finalize_block<S>(
 where
     S: StorageWrite + StorageRead,
 {
-    proof_of_stake::finalize_block::<_, governance::Store<_>>(
+    proof_of_stake::finalize_block::<_, governance::Store<_>, token::Store<_>>(
         storage,
         events,
         is_new_epoch,
@@ -1267,7 +1459,7 @@ where
 #[allow(clippy::arithmetic_side_effects, clippy::cast_possible_truncation)]
 #[cfg(test)]
 mod test_finalize_block {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::num::NonZeroU64;
     use std::str::FromStr;
 
@@ -1287,8 +1479,12 @@ mod test_finalize_block {
     use namada_sdk::ethereum_events::{EthAddress, Uint as ethUint};
     use namada_sdk::events::Event;
     use namada_sdk::gas::VpGasMeter;
-    use namada_sdk::governance::storage::keys::get_proposal_execution_key;
-    use namada_sdk::governance::storage::proposal::ProposalType;
+    use namada_sdk::governance::storage::keys::{
+        get_proposal_execution_key, get_scheduled_upgrade_key,
+    };
+    use namada_sdk::governance::storage::proposal::{
+        ProposalType, ScheduledUpgrade, VotingMode,
+    };
     use namada_sdk::governance::{
         InitProposalData, ProposalVote, VoteProposalData,
     };
@@ -1942,6 +2138,7 @@ mod test_finalize_block {
                 voting_end_epoch: Epoch::default().next(),
                 activation_epoch: Epoch::default().next(),
                 r#type: ProposalType::Default,
+                voting_mode: VotingMode::default(),
             };
 
             namada_sdk::governance::init_proposal::<_, token::Store<_>>(
@@ -2490,16 +2687,19 @@ mod test_finalize_block {
 
         // Unbond some tokens
         let unbond_amount = token::Amount::native_whole(50_000);
-        let unbond_res =
-            proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
-                &mut shell.state,
-                None,
-                &validator.address,
-                unbond_amount,
-                current_epoch,
-                false,
-            )
-            .unwrap();
+        let unbond_res = proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
+            &mut shell.state,
+            None,
+            &validator.address,
+            unbond_amount,
+            current_epoch,
+            false,
+        )
+        .unwrap();
         assert_eq!(unbond_res.sum, unbond_amount);
 
         // Query the available rewards
@@ -2973,7 +3173,11 @@ mod test_finalize_block {
         .unwrap();
 
         // Val 2 is fully unbonded
-        proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
+        proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
             &mut shell.state,
             None,
             &validator2.address,
@@ -2984,7 +3188,11 @@ mod test_finalize_block {
         .unwrap();
 
         // Val 3 is fully unbonded and changes consensus key
-        proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
+        proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
             &mut shell.state,
             None,
             &validator3.address,
@@ -4495,7 +4703,11 @@ mod test_finalize_block {
 
         // Self-unbond
         let self_unbond_1_amount = token::Amount::native_whole(84_654);
-        proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
+        proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
             &mut shell.state,
             None,
             &val1.address,
@@ -4538,7 +4750,11 @@ mod test_finalize_block {
         let (current_epoch, _) = advance_epoch(&mut shell, &pkh1, &votes, None);
         tracing::debug!("\nUnbonding in epoch 2");
         let del_unbond_1_amount = token::Amount::native_whole(18_000);
-        proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
+        proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
             &mut shell.state,
             Some(&delegator),
             &val1.address,
@@ -4604,7 +4820,11 @@ mod test_finalize_block {
         assert_eq!(current_epoch.0, 4_u64);
 
         let self_unbond_2_amount = token::Amount::native_whole(15_000);
-        proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
+        proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
             &mut shell.state,
             None,
             &val1.address,
@@ -5313,7 +5533,11 @@ mod test_finalize_block {
         // Completely unbond one of the validator to test the pruning at the
         // pipeline epoch
         let mut current_epoch = shell.state.in_mem().block.epoch;
-        proof_of_stake::unbond_tokens::<_, governance::Store<_>>(
+        proof_of_stake::unbond_tokens::<
+            _,
+            governance::Store<_>,
+            token::Store<_>,
+        >(
             &mut shell.state,
             None,
             &val5,
@@ -6160,6 +6384,102 @@ mod test_finalize_block {
         assert!(tx_results.are_any_err());
     }
 
+    /// Test that the node halts at the height of a software upgrade scheduled
+    /// by governance, unless it's running the upgraded binary
+    #[test]
+    fn test_scheduled_upgrade_halt() {
+        let (mut shell, _recv, _, _) = setup();
+        let upgrade_height = shell
+            .state
+            .in_mem()
+            .get_last_block_height()
+            .next_height()
+            .next_height();
+        let upgrade = ScheduledUpgrade {
+            height: upgrade_height,
+            version: "v999.0.0".to_string(),
+            binary_hashes: BTreeSet::from([Hash::sha256(b"upgraded binary")]),
+            migration_hash: None,
+        };
+        shell
+            .state
+            .write(&get_scheduled_upgrade_key(), &upgrade)
+            .expect("Test failed");
+
+        // The blocks before the upgrade height are processed
+        shell.finalize_and_commit(None);
+
+        // The old binary halts at the upgrade height
+        let result = shell.finalize_block(FinalizeBlock::default());
+        assert!(matches!(
+            result,
+            Err(Error::ScheduledUpgrade(height, _)) if height == upgrade_height
+        ));
+        assert_eq!(
+            shell.state.in_mem().get_last_block_height().next_height(),
+            upgrade_height
+        );
+
+        // A binary with one of the accepted hashes proceeds, but only with the
+        // scheduled migration
+        let upgrade = ScheduledUpgrade {
+            binary_hashes: BTreeSet::from([
+                Hash::sha256(b"upgraded binary"),
+                running_binary_hash().expect("Test failed"),
+            ]),
+            migration_hash: Some(Hash::sha256(b"migration")),
+            ..upgrade
+        };
+        shell
+            .state
+            .write(&get_scheduled_upgrade_key(), &upgrade)
+            .expect("Test failed");
+        let result = shell.finalize_block(FinalizeBlock::default());
+        assert!(matches!(
+            result,
+            Err(Error::ScheduledUpgrade(_, msg)) if msg.contains("migration")
+        ));
+
+        // A binary with the upgraded version proceeds
+        let upgrade = ScheduledUpgrade {
+            version: namada_version().to_string(),
+            binary_hashes: BTreeSet::new(),
+            migration_hash: None,
+            ..upgrade
+        };
+        shell
+            .state
+            .write(&get_scheduled_upgrade_key(), &upgrade)
+            .expect("Test failed");
+        shell.finalize_and_commit(None);
+        assert_eq!(
+            shell.state.in_mem().get_last_block_height(),
+            upgrade_height
+        );
+
+        // The upgrade is done, so it doesn't halt the later binaries
+        assert!(governance::storage::get_scheduled_upgrade(&shell.state)
+            .expect("Test failed")
+            .is_none());
+        shell.finalize_and_commit(None);
+        assert_eq!(
+            shell.state.in_mem().get_last_block_height(),
+            upgrade_height.next_height()
+        );
+    }
+
+    /// Test that the version of the running binary matches the version of an
+    /// upgrade regardless of the leading `v` and of the `git describe` suffix
+    #[test]
+    fn test_is_upgrade_version() {
+        assert!(is_upgrade_version("v1.1.0", "v1.1.0"));
+        assert!(is_upgrade_version("1.1.0", "v1.1.0"));
+        assert!(is_upgrade_version("v1.1.0-3-gabc123-dirty", "v1.1.0"));
+        assert!(is_upgrade_version("v1.1.0-rc1-2-g0f1e", "1.1.0-rc1"));
+        assert!(!is_upgrade_version("v1.1.0-rc1", "v1.1.0"));
+        assert!(!is_upgrade_version("v1.0.9-3-gabc123", "v1.1.0"));
+    }
+
     /// DI indirection
     pub fn read_pos_params<S>(
         storage: &S,
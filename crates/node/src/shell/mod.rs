@@ -35,7 +35,7 @@ use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use namada_apps_lib::wallet::{self, ValidatorData, ValidatorKeys};
 use namada_sdk::address::Address;
-use namada_sdk::chain::{BlockHeight, ChainId};
+use namada_sdk::chain::{BlockHeight, ChainId, Epoch};
 use namada_sdk::eth_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
 use namada_sdk::eth_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
 use namada_sdk::eth_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
@@ -47,6 +47,9 @@ use namada_sdk::hash::Hash;
 use namada_sdk::key::*;
 use namada_sdk::migrations::ScheduledMigration;
 use namada_sdk::parameters::{get_gas_scale, validate_tx_bytes};
+use namada_sdk::proof_of_stake::queries::{
+    get_validator_eth_hot_key, get_validator_protocol_key,
+};
 use namada_sdk::proof_of_stake::storage::read_pos_params;
 use namada_sdk::proof_of_stake::types::{
     ConsensusValidator, ValidatorSetUpdate,
@@ -96,6 +99,10 @@ fn key_to_tendermint(
     }
 }
 
+/// The maximum number of archived storage values visited by each step of the
+/// incremental pruning of the archive
+const ARCHIVE_PRUNING_ENTRIES_PER_STEP: usize = 10_000;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Error removing the DB data: {0}")]
@@ -133,6 +140,8 @@ pub enum Error {
     RejectedBlockProposal,
     #[error("Received an invalid block proposal")]
     InvalidBlockProposal,
+    #[error("Halting for the upgrade scheduled at block height {0}: {1}")]
+    ScheduledUpgrade(BlockHeight, String),
 }
 
 impl From<Error> for TxResult {
@@ -158,6 +167,55 @@ pub fn reset(config: config::Ledger) -> ShellResult<()> {
     Ok(())
 }
 
+/// Look up the DB migration of a software upgrade scheduled by governance,
+/// if the upgrade height hasn't been reached yet. The migration file is
+/// expected at `<chain-dir>/migrations/<migration-hash>.json`.
+fn scheduled_upgrade_migration<D, H>(
+    state: &FullAccessState<D, H>,
+    chain_dir: &Path,
+) -> Option<ScheduledMigration<D::Migrator>>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let upgrade = governance::storage::get_scheduled_upgrade(state)
+        .expect("Must be able to read the scheduled upgrade")?;
+    let migration_hash = upgrade.migration_hash?;
+    #[allow(clippy::disallowed_methods)]
+    let current = state.get_block_height().unwrap_or_default();
+    if upgrade.height <= current {
+        return None;
+    }
+    let path = chain_dir
+        .join("migrations")
+        .join(format!("{migration_hash}.json"));
+    match ScheduledMigration::from_path(&path, migration_hash, upgrade.height) {
+        Ok(migration) => {
+            tracing::info!(
+                "Scheduled the migration {} of the upgrade at block height {}",
+                path.to_string_lossy(),
+                upgrade.height
+            );
+            Some(migration)
+        }
+        Err(err) => {
+            tracing::warn!(
+                "Couldn't load the migration {} of the upgrade at block \
+                 height {}: {err}",
+                path.to_string_lossy(),
+                upgrade.height
+            );
+            None
+        }
+    }
+}
+
+/// The SHA-256 hash of the running node binary
+fn running_binary_hash() -> std::io::Result<Hash> {
+    let path = std::env::current_exe()?;
+    Ok(Hash::sha256(std::fs::read(path)?))
+}
+
 pub fn rollback(config: config::Ledger) -> ShellResult<()> {
     // Rollback Tendermint state
     tracing::info!("Rollback Tendermint state");
@@ -174,6 +232,23 @@ pub fn rollback(config: config::Ledger) -> ShellResult<()> {
         .map_err(|e| Error::Storage(namada_sdk::state::Error::new(e)))
 }
 
+/// Prune the archive of the DB, only keeping the values needed to read it from
+/// the given height onwards
+pub fn prune_archive(
+    config: config::Ledger,
+    height: BlockHeight,
+) -> ShellResult<()> {
+    let db_path = config.shell.db_dir(&config.chain_id);
+    let mut db = storage::PersistentDB::open(db_path, None);
+    tracing::info!("Pruning the storage archive");
+
+    while !db
+        .prune_archive(height, ARCHIVE_PRUNING_ENTRIES_PER_STEP)
+        .map_err(|e| Error::Storage(namada_sdk::state::Error::new(e)))?
+    {}
+    Ok(())
+}
+
 #[derive(Debug)]
 #[allow(dead_code, clippy::large_enum_variant)]
 pub(super) enum ShellMode {
@@ -382,9 +457,20 @@ where
     /// When set, indicates after how many blocks a new snapshot
     /// will be taken (counting from the first block)
     pub blocks_between_snapshots: Option<NonZeroU64>,
+    /// When set, indicates for how many blocks in the past the archived
+    /// storage values must be kept
+    pub archival_retention_blocks: Option<NonZeroU64>,
+    /// The height of the ongoing incremental pruning of the archive, if any
+    archive_pruning_height: Option<BlockHeight>,
+    /// The compact data of the MASP txs of the last finalized block, to be
+    /// added to the node-local MASP block index once it's committed
+    masp_block_notes: Vec<token::block_index::CompactMaspTx>,
     /// Data for a node downloading and apply snapshots as part of
     /// the fast sync protocol.
     pub syncing: Option<SnapshotSync>,
+    /// The SHA-256 hash of the running node binary, computed when first
+    /// needed to check a software upgrade scheduled by governance
+    binary_hash: Option<Hash>,
 }
 
 /// Storage key filter to store the diffs into the storage. Return `false` for
@@ -516,7 +602,7 @@ where
         };
 
         // load last state from storage
-        let state = FullAccessState::open(
+        let mut state = FullAccessState::open(
             db_path,
             db_cache,
             chain_id.clone(),
@@ -524,6 +610,12 @@ where
             config.shell.storage_read_past_height_limit,
             is_key_diff_storable,
         );
+        let archive_setup = if config.shell.archival_mode {
+            state.db_mut().enable_archive()
+        } else {
+            state.db_mut().disable_archive()
+        };
+        archive_setup.expect("Failed to set up the storage archive");
         let vp_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
         let tx_wasm_cache_dir =
@@ -595,6 +687,7 @@ where
                                 protocol_keypair,
                                 eth_bridge_keypair,
                             },
+                            pending_keys: Vec::new(),
                         },
                         broadcast_sender,
                         eth_oracle,
@@ -632,6 +725,14 @@ where
             TendermintMode::Seed => ShellMode::Seed,
         };
 
+        // A migration of a software upgrade scheduled by governance is applied
+        // automatically, unless another migration was provided
+        let scheduled_migration = scheduled_migration.or_else(|| {
+            scheduled_upgrade_migration(
+                &state,
+                &base_dir.join(chain_id.as_str()),
+            )
+        });
         if let Some(schedule_migration) = scheduled_migration.as_ref() {
             #[allow(clippy::disallowed_methods)]
             let current = state.get_block_height().unwrap_or_default();
@@ -667,9 +768,18 @@ where
             event_log: EventLog::default(),
             scheduled_migration,
             blocks_between_snapshots: config.shell.blocks_between_snapshots,
+            archival_retention_blocks: config
+                .shell
+                .archival_retention_blocks
+                .filter(|_| config.shell.archival_mode),
+            archive_pruning_height: None,
+            masp_block_notes: vec![],
             syncing: None,
+            binary_hash: None,
         };
         shell.update_eth_oracle(&Default::default());
+        let last_epoch = shell.state.in_mem().last_epoch;
+        shell.update_validator_keys(last_epoch);
         shell
     }
 
@@ -791,6 +901,8 @@ where
             committed_height,
             &mut self.scheduled_migration,
         );
+        self.prune_archive();
+        self.index_masp_block(committed_height);
         let merkle_root = self.state.in_mem().merkle_root();
 
         tracing::info!(
@@ -812,6 +924,64 @@ where
         )
     }
 
+    /// Prune the archived storage values older than the configured retention.
+    /// A new pruning starts every time that many blocks have been committed
+    /// and it's spread across the following blocks, with a bounded number of
+    /// archived values visited at each commit.
+    fn prune_archive(&mut self) {
+        let Some(retention) = self.archival_retention_blocks else {
+            return;
+        };
+        if self.archive_pruning_height.is_none() {
+            let committed_height = self.state.in_mem().get_last_block_height();
+            if committed_height.0 % retention != 0 {
+                return;
+            }
+            self.archive_pruning_height = committed_height
+                .0
+                .checked_sub(retention.get())
+                .map(BlockHeight);
+        }
+        let Some(height) = self.archive_pruning_height else {
+            return;
+        };
+        match self
+            .state
+            .db_mut()
+            .prune_archive(height, ARCHIVE_PRUNING_ENTRIES_PER_STEP)
+        {
+            Ok(false) => {}
+            Ok(true) => {
+                tracing::info!(
+                    "Pruned the storage archive below height {height}"
+                );
+                self.archive_pruning_height = None;
+            }
+            Err(err) => {
+                // The pruning is attempted again at the next retention period
+                tracing::error!(
+                    "Failed to prune the storage archive below height \
+                     {height}: {err}"
+                );
+                self.archive_pruning_height = None;
+            }
+        }
+    }
+
+    /// Add the committed block to the node-local MASP block index, which
+    /// starts over from the next block if this fails
+    fn index_masp_block(&mut self, height: BlockHeight) {
+        let notes = std::mem::take(&mut self.masp_block_notes);
+        if let Err(err) =
+            token::block_index::index_block(self.state.db_mut(), height, notes)
+        {
+            tracing::error!(
+                "Failed to index the MASP txs of the block at height \
+                 {height}: {err}"
+            );
+        }
+    }
+
     /// Check if we have reached a block height at which we should take a
     /// snapshot
     fn check_snapshot_required(&self) -> TakeSnapshot {
@@ -924,6 +1094,77 @@ where
         }
     }
 
+    /// Switch this validator to the protocol and Ethereum hot keys that it
+    /// rotated in, once they are active on chain at the given epoch. The
+    /// client stores the rotated keys in the wallet after the keys change tx
+    /// has been applied, so they are read back from it first.
+    fn update_validator_keys(&mut self, epoch: Epoch) {
+        let ShellMode::Validator { data, .. } = &mut self.mode else {
+            return;
+        };
+        if self.state.in_mem().last_block.is_none() {
+            return;
+        }
+        let read_active_keys = || -> namada_sdk::storage::Result<_> {
+            type Gov<S> = governance::Store<S>;
+            let address = &data.address;
+            let protocol_pk = get_validator_protocol_key::<_, Gov<_>>(
+                &self.state,
+                address,
+                epoch,
+            )?;
+            let eth_hot_pk = get_validator_eth_hot_key::<_, Gov<_>>(
+                &self.state,
+                address,
+                epoch,
+            )?;
+            Ok(protocol_pk.zip(eth_hot_pk))
+        };
+        let (protocol_pk, eth_hot_pk) = match read_active_keys() {
+            Ok(Some(keys)) => keys,
+            Ok(None) => return,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to read the active keys of the validator: {err}"
+                );
+                return;
+            }
+        };
+        if data.keys.protocol_keypair.ref_to() == protocol_pk
+            && data.keys.eth_bridge_keypair.ref_to() == eth_hot_pk
+        {
+            return;
+        }
+
+        #[cfg(not(any(test, fuzzing)))]
+        {
+            let wallet_path = self.base_dir.join(self.chain_id.as_str());
+            match wallet::load(&wallet_path)
+                .ok()
+                .and_then(|wallet| wallet.into_validator_data())
+            {
+                Some(wallet_data) if wallet_data.address == data.address => {
+                    data.pending_keys = wallet_data.pending_keys;
+                }
+                _ => tracing::warn!(
+                    "Failed to read the validator data from the wallet in {}",
+                    wallet_path.to_string_lossy()
+                ),
+            }
+        }
+        if data.activate_pending_keys(&protocol_pk, &eth_hot_pk) {
+            tracing::info!(
+                "Switched to the rotated validator keys active at epoch \
+                 {epoch}"
+            );
+        } else {
+            tracing::error!(
+                "The protocol and Ethereum hot keys of the validator that are \
+                 active at epoch {epoch} were not found in the wallet"
+            );
+        }
+    }
+
     /// If a handle to an Ethereum oracle was provided to the [`Shell`], attempt
     /// to send it an updated configuration, using a configuration
     /// based on Ethereum bridge parameters in blockchain storage.
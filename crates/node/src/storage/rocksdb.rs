@@ -19,6 +19,8 @@
 //!     - `commit_only_data_commitment`
 //!     - `update_epoch_blocks_delay`
 //!   - `conversion_state`: MASP conversion state
+//!   - `archive_start_height`: the lowest height that can be read from the
+//!     archive, only present when the archive is enabled
 //! - `subspace`: accounts sub-spaces
 //!   - `{address}/{dyn}`: any byte data associated with accounts
 //! - `diffs`: diffs in account subspaces' key-vals modified with `persist_diff
@@ -43,7 +45,18 @@
 //! - `replay_protection`: hashes of processed tx for replay protection purposes
 //!     - `current/{hash}`: a hash included in the current block
 //!     - `{hash}`: a hash included in previous blocks
-
+//! - `archive`: values of all the account subspace keys at every height, only
+//!   written when the archive is enabled
+//!   - `{dyn}\0{height}`: value set (or `None` if deleted) in block height `h`
+//! - `masp_index`: node-local index of the blocks with MASP txs, written after
+//!   each block is committed
+//!   - `start_height`: the height of the first indexed block
+//!   - `height`: the height of the last indexed block
+//!   - `chunks/{chunk}`: the bitmap of the blocks with MASP txs of a chunk of
+//!     block heights
+//!   - `notes/{height}`: the compact notes of the MASP txs of a recent block
+
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufWriter, Read, Seek, Write};
 use std::mem::ManuallyDrop;
@@ -72,8 +85,9 @@ use namada_sdk::state::{
     StoreType, DB,
 };
 use namada_sdk::storage::{
-    BlockHeader, BlockHeight, DbColFam, Epoch, Key, KeySeg, BLOCK_CF, DIFFS_CF,
-    REPLAY_PROTECTION_CF, ROLLBACK_CF, STATE_CF, SUBSPACE_CF,
+    BlockHeader, BlockHeight, DbColFam, Epoch, Key, KeySeg, ARCHIVE_CF,
+    BLOCK_CF, DIFFS_CF, MASP_INDEX_CF, REPLAY_PROTECTION_CF, ROLLBACK_CF,
+    STATE_CF, SUBSPACE_CF,
 };
 use namada_sdk::{decode, encode, ethereum_events};
 use rayon::prelude::*;
@@ -101,6 +115,8 @@ const COMMIT_ONLY_DATA_KEY: &str = "commit_only_data_commitment";
 const CONVERSION_STATE_KEY: &str = "conversion_state";
 const ETHEREUM_HEIGHT_KEY: &str = "ethereum_height";
 const ETH_EVENTS_QUEUE_KEY: &str = "eth_events_queue";
+const ARCHIVE_START_HEIGHT_KEY: &str = "archive_start_height";
+const ARCHIVE_PRUNING_KEY: &str = "archive_pruning";
 const RESULTS_KEY_PREFIX: &str = "results";
 const PRED_KEY_PREFIX: &str = "pred";
 
@@ -115,6 +131,23 @@ const ADDRESS_GEN_KEY_SEGMENT: &str = "address_gen";
 const OLD_DIFF_PREFIX: &str = "old";
 const NEW_DIFF_PREFIX: &str = "new";
 
+/// Separates a storage key from the height of its value in the archive. It
+/// sorts before any character of a storage key, so that all the heights of a
+/// key are adjacent.
+const ARCHIVE_HEIGHT_SEPARATOR: char = '\0';
+
+/// The progress of an incremental pruning of the archive
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+struct ArchivePruning {
+    /// The height below which the archive is being pruned
+    height: BlockHeight,
+    /// The last visited archive key, after which the pruning resumes
+    cursor: String,
+    /// The last visited archived value that's not above the pruning height,
+    /// with its storage key, its archive key and whether it's a deletion
+    last: Option<(String, String, bool)>,
+}
+
 // 10 MB
 const MAX_STATE_SYNC_CHUNK_SIZE: usize = 10_000_000;
 
@@ -127,6 +160,8 @@ pub struct RocksDB {
     read_only: bool,
     /// Whether the handle is invalid
     invalid_handle: bool,
+    /// Whether the values of all the subspace keys are archived
+    archived: bool,
 }
 
 /// DB Handle for batch writes.
@@ -232,7 +267,27 @@ pub fn open(
         REPLAY_PROTECTION_CF,
         replay_protection_cf_opts,
     ));
-    Ok(if read_only {
+
+    // for the archive (insert-intensive)
+    let mut archive_cf_opts = Options::default();
+    archive_cf_opts.set_compression_type(DBCompressionType::Zstd);
+    archive_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    archive_cf_opts.set_compaction_style(DBCompactionStyle::Universal);
+    archive_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(ARCHIVE_CF, archive_cf_opts));
+
+    // for the MASP block index (insert-intensive)
+    let mut masp_index_cf_opts = Options::default();
+    masp_index_cf_opts.set_compression_type(DBCompressionType::Zstd);
+    masp_index_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    masp_index_cf_opts.set_compaction_style(DBCompactionStyle::Universal);
+    masp_index_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(
+        MASP_INDEX_CF,
+        masp_index_cf_opts,
+    ));
+
+    let mut db = if read_only {
         RocksDB {
             inner: ManuallyDrop::new(
                 rocksdb::DB::open_cf_descriptors_read_only(
@@ -242,6 +297,7 @@ pub fn open(
             ),
             invalid_handle: false,
             read_only: true,
+            archived: false,
         }
     } else {
         RocksDB {
@@ -251,8 +307,11 @@ pub fn open(
             ),
             invalid_handle: false,
             read_only: false,
+            archived: false,
         }
-    })
+    };
+    db.archived = db.read_archive_start_height()?.is_some();
+    Ok(db)
 }
 
 impl Drop for RocksDB {
@@ -369,6 +428,31 @@ impl RocksDB {
         if let Some(new_value) = new_value {
             batch.0.put_cf(cf, new_val_key, new_value);
         }
+
+        if self.archived {
+            let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+            batch.0.put_cf(
+                archive_cf,
+                archive_key(key, height),
+                encode(&new_value),
+            );
+        }
+        Ok(())
+    }
+
+    /// Delete all the archived values and the archive start height in a batch
+    /// write.
+    fn batch_delete_archive(
+        &self,
+        batch: &mut RocksDBWriteBatch,
+    ) -> Result<()> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        batch.0.delete_cf(state_cf, ARCHIVE_START_HEIGHT_KEY);
+        batch.0.delete_cf(state_cf, ARCHIVE_PRUNING_KEY);
+        let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+        for (key, _val, _) in iter_prefix(self, archive_cf, None, None) {
+            batch.0.delete_cf(archive_cf, key);
+        }
         Ok(())
     }
 
@@ -706,6 +790,36 @@ impl RocksDB {
             }
         }
 
+        if self.archived {
+            if self.read_archive_start_height()? >= Some(last_block.height) {
+                // The archive doesn't have the values from before the last
+                // block, so it cannot be rolled back
+                tracing::info!("Deleting the archive");
+                self.batch_delete_archive(&mut batch)?;
+            } else {
+                tracing::info!(
+                    "Removing the archived values of the last block"
+                );
+                let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+                for cf in [diffs_cf, rollback_cf] {
+                    for is_old in [true, false] {
+                        for (key_str, _val, _) in iter_diffs_prefix(
+                            self,
+                            cf,
+                            last_block.height,
+                            None,
+                            is_old,
+                        ) {
+                            batch.0.delete_cf(
+                                archive_cf,
+                                archive_key(&key_str, last_block.height),
+                            )
+                        }
+                    }
+                }
+            }
+        }
+
         tracing::info!("Deleting keys prepended with the last height");
         let prefix = last_block.height.to_string();
         let mut delete_keys = |cf: &ColumnFamily| {
@@ -734,7 +848,7 @@ impl RocksDB {
     }
 
     #[inline]
-    pub fn column_families(&self) -> [(&'static str, &ColumnFamily); 6] {
+    pub fn column_families(&self) -> [(&'static str, &ColumnFamily); 8] {
         DbColFam::all()
             .iter()
             .map(|cf| {
@@ -746,7 +860,7 @@ impl RocksDB {
             })
             .collect::<Vec<_>>()
             .try_into()
-            .map_err(|_| "There should be exactly six column families")
+            .map_err(|_| "There should be exactly eight column families")
             .unwrap()
     }
 
@@ -1773,6 +1887,259 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn enable_archive(&mut self) -> Result<()> {
+        if self.archived {
+            return Ok(());
+        }
+        let state_cf = self.get_column_family(STATE_CF)?;
+        let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+        let start_height: BlockHeight = self
+            .read_value(state_cf, BLOCK_HEIGHT_KEY)?
+            .unwrap_or_default();
+        tracing::info!("Archiving the state at height {start_height}");
+
+        // Archive the current values of all the keys
+        let mut batch = RocksDB::batch();
+        for (key, val, _) in iter_subspace_prefix(self, None) {
+            batch.0.put_cf(
+                archive_cf,
+                archive_key(&key, start_height),
+                encode(&Some(val)),
+            );
+        }
+        self.add_value_to_batch(
+            state_cf,
+            ARCHIVE_START_HEIGHT_KEY,
+            &start_height,
+            &mut batch,
+        );
+        self.exec_batch(batch)?;
+        self.archived = true;
+        Ok(())
+    }
+
+    fn disable_archive(&mut self) -> Result<()> {
+        if !self.archived {
+            return Ok(());
+        }
+        tracing::info!("Deleting the archive");
+        let mut batch = RocksDB::batch();
+        self.batch_delete_archive(&mut batch)?;
+        self.exec_batch(batch)?;
+        self.archived = false;
+        Ok(())
+    }
+
+    fn read_archive_start_height(&self) -> Result<Option<BlockHeight>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        self.read_value(state_cf, ARCHIVE_START_HEIGHT_KEY)
+    }
+
+    fn read_archived_subspace_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+    ) -> Result<Option<Option<Vec<u8>>>> {
+        match self.read_archive_start_height()? {
+            Some(start_height) if height >= start_height => {}
+            _ => return Ok(None),
+        }
+        let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+
+        // Find the value of the key at the greatest height that's not above
+        // the given one
+        let mut read_opts = make_iter_read_opts(None);
+        read_opts.set_iterate_lower_bound(
+            format!("{key}{ARCHIVE_HEIGHT_SEPARATOR}").into_bytes(),
+        );
+        let seek_key = archive_key(key, height);
+        let mut iter = self.inner.iterator_cf_opt(
+            archive_cf,
+            read_opts,
+            IteratorMode::From(seek_key.as_bytes(), Direction::Reverse),
+        );
+        match iter.next() {
+            Some(result) => {
+                let (_key, val) =
+                    result.map_err(|e| Error::DBError(e.into_string()))?;
+                decode(val).map(Some).map_err(Error::CodingError)
+            }
+            // The key didn't exist at the given height
+            None => Ok(Some(None)),
+        }
+    }
+
+    fn read_archived_subspace_prefix(
+        &self,
+        prefix: Option<&Key>,
+        height: BlockHeight,
+    ) -> Result<Option<Vec<(String, Vec<u8>)>>> {
+        match self.read_archive_start_height()? {
+            Some(start_height) if height >= start_height => {}
+            _ => return Ok(None),
+        }
+        let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+        let height = height.raw();
+
+        // The values of each key are ordered by height, so the last one that's
+        // not above the given height is the one we're looking for
+        let mut key_vals = BTreeMap::<String, Option<Vec<u8>>>::new();
+        for (archive_key, val, _) in iter_prefix(self, archive_cf, None, prefix)
+        {
+            let Some((key, key_height)) =
+                archive_key.rsplit_once(ARCHIVE_HEIGHT_SEPARATOR)
+            else {
+                continue;
+            };
+            if key_height <= height.as_str() {
+                let val = decode(val).map_err(Error::CodingError)?;
+                key_vals.insert(key.to_owned(), val);
+            }
+        }
+        Ok(Some(
+            key_vals
+                .into_iter()
+                .filter_map(|(key, val)| Some((key, val?)))
+                .collect(),
+        ))
+    }
+
+    fn prune_archive(
+        &mut self,
+        height: BlockHeight,
+        max_entries: usize,
+    ) -> Result<bool> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+        let mut batch = RocksDB::batch();
+
+        // Resume the pruning at the same height, if any. Otherwise, start a
+        // new one from the beginning of the archive, which is also fine if a
+        // pruning at a lower height was interrupted.
+        let pruning = self
+            .read_value::<ArchivePruning>(state_cf, ARCHIVE_PRUNING_KEY)?
+            .filter(|pruning| pruning.height == height);
+        let (cursor, mut last) = match pruning {
+            Some(ArchivePruning { cursor, last, .. }) => (Some(cursor), last),
+            None => {
+                match self.read_archive_start_height()? {
+                    Some(start_height) if height > start_height => {}
+                    _ => return Ok(true),
+                }
+                tracing::info!("Pruning the archive below height {height}");
+                // The heights below the pruning height can't be read anymore
+                // as soon as the pruning starts
+                self.add_value_to_batch(
+                    state_cf,
+                    ARCHIVE_START_HEIGHT_KEY,
+                    &height,
+                    &mut batch,
+                );
+                (None, None)
+            }
+        };
+
+        // Only the last value of each key that's not above the pruning height
+        // is needed to read the archive from that height, unless the key was
+        // deleted
+        let raw_height = height.raw();
+        let start = cursor.clone().unwrap_or_default();
+        let iter = self.inner.iterator_cf_opt(
+            archive_cf,
+            make_iter_read_opts(None),
+            IteratorMode::From(start.as_bytes(), Direction::Forward),
+        );
+        let entries =
+            PersistentPrefixIterator(PrefixIterator::new(iter, String::new()))
+                .filter(|(archive_key, _, _)| {
+                    Some(archive_key) != cursor.as_ref()
+                })
+                .take(max_entries);
+        let mut num_visited = 0_usize;
+        let mut new_cursor = cursor;
+        for (archive_key, val, _) in entries {
+            num_visited = checked!(num_visited + 1)?;
+            new_cursor = Some(archive_key.clone());
+            let Some((key, key_height)) =
+                archive_key.rsplit_once(ARCHIVE_HEIGHT_SEPARATOR)
+            else {
+                continue;
+            };
+            if key_height > raw_height.as_str() {
+                continue;
+            }
+            if let Some((last_key, last_archive_key, is_deleted)) = last.take()
+            {
+                if is_deleted || last_key == key {
+                    batch.0.delete_cf(archive_cf, last_archive_key);
+                }
+            }
+            let is_deleted = decode::<Option<Vec<u8>>>(val)
+                .map_err(Error::CodingError)?
+                .is_none();
+            last = Some((key.to_owned(), archive_key.clone(), is_deleted));
+        }
+
+        // The pruning is done once it runs out of entries to visit
+        let is_done = num_visited < max_entries;
+        if is_done {
+            if let Some((_, last_archive_key, true)) = last {
+                batch.0.delete_cf(archive_cf, last_archive_key);
+            }
+            batch.0.delete_cf(state_cf, ARCHIVE_PRUNING_KEY);
+        } else {
+            self.add_value_to_batch(
+                state_cf,
+                ARCHIVE_PRUNING_KEY,
+                &ArchivePruning {
+                    height,
+                    cursor: new_cursor.unwrap_or_default(),
+                    last,
+                },
+                &mut batch,
+            );
+        }
+        self.exec_batch(batch)?;
+        Ok(is_done)
+    }
+
+    fn read_masp_index_val(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let masp_index_cf = self.get_column_family(MASP_INDEX_CF)?;
+        self.read_value_bytes(masp_index_cf, key)
+    }
+
+    fn read_masp_index_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let masp_index_cf = self.get_column_family(MASP_INDEX_CF)?;
+        let iter = self.inner.iterator_cf_opt(
+            masp_index_cf,
+            make_iter_read_opts(Some(prefix.to_owned())),
+            IteratorMode::From(prefix.as_bytes(), Direction::Forward),
+        );
+        Ok(
+            PersistentPrefixIterator(PrefixIterator::new(iter, String::new()))
+                .map(|(key, val, _)| (key, val))
+                .collect(),
+        )
+    }
+
+    fn write_masp_index(
+        &mut self,
+        changes: Vec<(String, Option<Vec<u8>>)>,
+    ) -> Result<()> {
+        let masp_index_cf = self.get_column_family(MASP_INDEX_CF)?;
+        let mut batch = RocksDB::batch();
+        for (key, val) in changes {
+            match val {
+                Some(val) => batch.0.put_cf(masp_index_cf, key, val),
+                None => batch.0.delete_cf(masp_index_cf, key),
+            }
+        }
+        self.exec_batch(batch)
+    }
+
     #[inline]
     fn overwrite_entry(
         &self,
@@ -1803,6 +2170,16 @@ impl DB for RocksDB {
                 new_value.as_ref().to_vec(),
                 batch,
             );
+
+            if self.archived {
+                let archive_cf = self.get_column_family(ARCHIVE_CF)?;
+                self.add_value_to_batch(
+                    archive_cf,
+                    archive_key(key, last_height),
+                    &Some(new_value.as_ref()),
+                    batch,
+                );
+            }
         }
 
         Ok(())
@@ -2166,6 +2543,11 @@ fn old_and_new_diff_key(
     Ok((old.to_string(), new.to_string()))
 }
 
+/// The key of the archived value of the given storage key at the given height
+fn archive_key(key: &impl std::fmt::Display, height: BlockHeight) -> String {
+    format!("{key}{ARCHIVE_HEIGHT_SEPARATOR}{}", height.raw())
+}
+
 /// Try to increase NOFILE limit and set the `max_open_files` limit to it in
 /// RocksDB options.
 fn set_max_open_files(cf_opts: &mut rocksdb::Options) {
@@ -2588,6 +2970,146 @@ mod test {
         }
     }
 
+    /// Test that the values of all the keys can be read from the archive at
+    /// any height since it was enabled, until pruned.
+    #[test]
+    fn test_archive() {
+        let dir = tempdir().unwrap();
+        let mut db = RocksDB::open(dir.path(), None);
+
+        let key_a = Key::parse("a").unwrap();
+        let key_b = Key::parse("b").unwrap();
+        let val_1 = vec![1_u8];
+        let val_2 = vec![2_u8];
+
+        let write_block =
+            |db: &RocksDB,
+             height: BlockHeight,
+             writes: &[(&Key, Option<&Vec<u8>>)]| {
+                let mut batch = RocksDB::batch();
+                for (key, val) in writes {
+                    // Keys without persisted diffs are archived too
+                    match val {
+                        Some(val) => db
+                            .batch_write_subspace_val(
+                                &mut batch, height, key, val, false,
+                            )
+                            .unwrap(),
+                        None => db
+                            .batch_delete_subspace_val(
+                                &mut batch, height, key, false,
+                            )
+                            .unwrap(),
+                    };
+                }
+                add_block_to_batch(
+                    db,
+                    &mut batch,
+                    height,
+                    Epoch::default(),
+                    Epochs::default(),
+                    &ConversionState::default(),
+                )
+                .unwrap();
+                db.exec_batch(batch).unwrap();
+            };
+
+        write_block(&db, BlockHeight(1), &[(&key_a, Some(&val_1))]);
+        db.enable_archive().unwrap();
+        assert_eq!(
+            db.read_archive_start_height().unwrap(),
+            Some(BlockHeight(1))
+        );
+        write_block(
+            &db,
+            BlockHeight(2),
+            &[(&key_a, Some(&val_2)), (&key_b, Some(&val_1))],
+        );
+        write_block(&db, BlockHeight(3), &[(&key_a, None)]);
+
+        // Heights before the archive was enabled are not covered
+        assert_eq!(
+            db.read_archived_subspace_val(&key_a, BlockHeight(0))
+                .unwrap(),
+            None
+        );
+        for (key, height, expected) in [
+            (&key_a, 1, Some(&val_1)),
+            (&key_a, 2, Some(&val_2)),
+            (&key_a, 3, None),
+            (&key_b, 1, None),
+            (&key_b, 2, Some(&val_1)),
+            (&key_b, 3, Some(&val_1)),
+        ] {
+            assert_eq!(
+                db.read_archived_subspace_val(key, BlockHeight(height))
+                    .unwrap(),
+                Some(expected.cloned()),
+                "Unexpected value of {key} at height {height}"
+            );
+        }
+        assert_eq!(
+            db.read_archived_subspace_prefix(None, BlockHeight(2))
+                .unwrap(),
+            Some(vec![
+                (key_a.to_string(), val_2.clone()),
+                (key_b.to_string(), val_1.clone())
+            ])
+        );
+        assert_eq!(
+            db.read_archived_subspace_prefix(None, BlockHeight(3))
+                .unwrap(),
+            Some(vec![(key_b.to_string(), val_1.clone())])
+        );
+
+        // Prune the archive below the last height, one value at a time. The
+        // heights below it can't be read as soon as the pruning starts.
+        assert!(!db.prune_archive(BlockHeight(3), 1).unwrap());
+        assert_eq!(
+            db.read_archive_start_height().unwrap(),
+            Some(BlockHeight(3))
+        );
+        assert_eq!(
+            db.read_archived_subspace_val(&key_b, BlockHeight(3))
+                .unwrap(),
+            Some(Some(val_1.clone()))
+        );
+        let mut num_steps = 1;
+        loop {
+            num_steps += 1;
+            if db.prune_archive(BlockHeight(3), 1).unwrap() {
+                break;
+            }
+        }
+        // There's a step for each of the 4 archived values and a last one to
+        // find out that there are no more
+        assert_eq!(num_steps, 5);
+        assert_eq!(
+            db.read_archived_subspace_val(&key_a, BlockHeight(2))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            db.read_archived_subspace_val(&key_a, BlockHeight(3))
+                .unwrap(),
+            Some(None)
+        );
+        assert_eq!(
+            db.read_archived_subspace_val(&key_b, BlockHeight(3))
+                .unwrap(),
+            Some(Some(val_1))
+        );
+        // Only the last value of `key_b` is left
+        let archive_cf = db.get_column_family(ARCHIVE_CF).unwrap();
+        assert_eq!(iter_prefix(&db, archive_cf, None, None).count(), 1);
+
+        // Disabling the archive deletes it
+        db.disable_archive().unwrap();
+        assert_eq!(db.read_archive_start_height().unwrap(), None);
+        let archive_cf = db.get_column_family(ARCHIVE_CF).unwrap();
+        assert_eq!(iter_prefix(&db, archive_cf, None, None).count(), 0);
+    }
+
     #[test]
     fn test_diffs() {
         let dir = tempdir().unwrap();
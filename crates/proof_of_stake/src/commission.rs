@@ -0,0 +1,254 @@
+//! PoS validator commission schedules.
+//!
+//! Besides changing their commission rate from the pipeline epoch, validators
+//! can publish a commission schedule with future-dated rate changes, lower
+//! commission tiers for large delegations and a time-locked commitment not to
+//! raise their commission rate. The tiers are applied in rewards distribution.
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::chain::Epoch;
+use namada_core::dec::Dec;
+use namada_systems::governance;
+
+use crate::storage::{
+    commission_schedules_handle, read_pos_params,
+    read_validator_max_commission_rate_change,
+    validator_commission_rate_handle,
+};
+use crate::types::CommissionSchedule;
+use crate::{
+    CommissionRateChangeError, OptionExt, PosParams, Result, StorageRead,
+    StorageWrite,
+};
+
+/// The maximum number of future-dated changes or of tiers in a commission
+/// schedule
+pub const MAX_COMMISSION_SCHEDULE_LEN: usize = 32;
+
+/// Publish a validator's commission schedule, replacing its current one.
+pub fn change_commission_schedule<S, Gov>(
+    storage: &mut S,
+    validator: &Address,
+    schedule: CommissionSchedule,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    if read_validator_max_commission_rate_change(storage, validator)?.is_none()
+    {
+        return Err(CommissionRateChangeError::NoMaxSetInStorage(
+            validator.clone(),
+        )
+        .into());
+    }
+
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let handle = commission_schedules_handle();
+    let prior = handle.get(storage, validator)?;
+    validate_commission_schedule(
+        validator,
+        prior.as_ref(),
+        &schedule,
+        current_epoch,
+        params.pipeline_len,
+    )?;
+
+    if schedule.is_expired(current_epoch) {
+        handle.remove(storage, validator)?;
+    } else {
+        handle.insert(storage, validator.clone(), schedule)?;
+    }
+    Ok(())
+}
+
+/// Validate the replacement of a validator's `prior` commission schedule, if
+/// any, with a new `schedule` in the current epoch.
+pub fn validate_commission_schedule(
+    validator: &Address,
+    prior: Option<&CommissionSchedule>,
+    schedule: &CommissionSchedule,
+    current_epoch: Epoch,
+    pipeline_len: u64,
+) -> Result<()> {
+    if schedule.changes.len() > MAX_COMMISSION_SCHEDULE_LEN
+        || schedule.tiers.len() > MAX_COMMISSION_SCHEDULE_LEN
+    {
+        return Err(CommissionRateChangeError::ScheduleTooLong(
+            validator.clone(),
+            MAX_COMMISSION_SCHEDULE_LEN,
+        )
+        .into());
+    }
+
+    for rate in schedule.changes.values().chain(schedule.tiers.values()) {
+        if rate.is_negative() {
+            return Err(CommissionRateChangeError::NegativeRate(
+                *rate,
+                validator.clone(),
+            )
+            .into());
+        }
+        if *rate > Dec::one() {
+            return Err(CommissionRateChangeError::LargerThanOne(
+                *rate,
+                validator.clone(),
+            )
+            .into());
+        }
+    }
+
+    // New changes have to be dated after the pipeline epoch
+    let pipeline_epoch = checked!(current_epoch + pipeline_len)?;
+    for (epoch, rate) in &schedule.changes {
+        let is_new =
+            prior.and_then(|prior| prior.changes.get(epoch)) != Some(rate);
+        if is_new && *epoch <= pipeline_epoch {
+            return Err(CommissionRateChangeError::ScheduledTooEarly(
+                validator.clone(),
+                *epoch,
+                pipeline_epoch,
+            )
+            .into());
+        }
+    }
+
+    // Hold the validator to its commitment not to raise its commission
+    let Some(prior) = prior else {
+        return Ok(());
+    };
+    let Some(until) = prior.raise_locked_until(current_epoch) else {
+        return Ok(());
+    };
+    if schedule
+        .no_raise_until
+        .map_or(true, |new_until| new_until < until)
+    {
+        return Err(CommissionRateChangeError::CommitmentShortened(
+            validator.clone(),
+            until,
+        )
+        .into());
+    }
+    // The tier rates only change at the tiers' thresholds
+    let raises_tier_rate =
+        prior
+            .tiers
+            .keys()
+            .chain(schedule.tiers.keys())
+            .any(|amount| {
+                schedule.tier_rate(Dec::one(), *amount)
+                    > prior.tier_rate(Dec::one(), *amount)
+            });
+    if raises_tier_rate {
+        return Err(CommissionRateChangeError::RaiseLocked(
+            validator.clone(),
+            until,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Apply the scheduled commission rate changes that are due by the pipeline
+/// epoch of a new epoch. Only the latest due change of a validator is applied,
+/// as it supersedes the earlier ones. A change may take multiple epochs to
+/// apply in steps of the validator's maximum commission rate change and a
+/// raise is held back while the validator is committed not to raise its
+/// commission rate.
+pub fn apply_commission_schedules<S, Gov>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+
+    let handle = commission_schedules_handle();
+    let schedules = handle.iter(storage)?.collect::<Result<Vec<_>>>()?;
+    for (validator, mut schedule) in schedules {
+        let due = schedule
+            .changes
+            .range(..=pipeline_epoch)
+            .next_back()
+            .map(|(epoch, rate)| (*epoch, *rate));
+        if let Some((due_epoch, rate)) = due {
+            schedule.changes = schedule.changes.split_off(&due_epoch);
+            let is_applied = apply_scheduled_rate::<S, Gov>(
+                storage,
+                &params,
+                &validator,
+                &schedule,
+                rate,
+                current_epoch,
+            )?;
+            if is_applied {
+                schedule.changes.remove(&due_epoch);
+            }
+        } else if !schedule.is_expired(current_epoch) {
+            continue;
+        }
+
+        if schedule.is_expired(current_epoch) {
+            handle.remove(storage, &validator)?;
+        } else {
+            handle.insert(storage, validator, schedule)?;
+        }
+    }
+    Ok(())
+}
+
+/// Move a validator's commission rate at the pipeline epoch towards the
+/// scheduled `rate`. Returns whether the scheduled rate has been reached.
+fn apply_scheduled_rate<S, Gov>(
+    storage: &mut S,
+    params: &PosParams,
+    validator: &Address,
+    schedule: &CommissionSchedule,
+    rate: Dec,
+    current_epoch: Epoch,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let max_change =
+        read_validator_max_commission_rate_change(storage, validator)?
+            .ok_or_err_msg(
+                "Validator's max commission rate change must be set",
+            )?;
+    let commission_handle = validator_commission_rate_handle(validator);
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let rate_before_pipeline = commission_handle
+        .get(
+            storage,
+            pipeline_epoch.prev().expect("Pipeline epoch cannot be 0"),
+            params,
+        )?
+        .ok_or_err_msg("Validator's commission rate must be set")?;
+
+    let new_rate = if rate > rate_before_pipeline {
+        if schedule.raise_locked_until(pipeline_epoch).is_some() {
+            return Ok(false);
+        }
+        rate.min(checked!(rate_before_pipeline + max_change)?)
+    } else {
+        rate.max(checked!(rate_before_pipeline - max_change)?)
+    };
+    commission_handle.set::<S, Gov>(
+        storage,
+        new_rate,
+        current_epoch,
+        params.pipeline_len,
+    )?;
+    tracing::debug!(
+        "Applied scheduled commission rate {new_rate} of validator \
+         {validator} from epoch {pipeline_epoch}"
+    );
+    Ok(new_rate == rate)
+}
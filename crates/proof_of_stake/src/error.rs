@@ -2,6 +2,7 @@
 use std::num::TryFromIntError;
 
 use namada_core::address::Address;
+use namada_core::arith;
 use namada_core::chain::Epoch;
 use namada_core::dec::Dec;
 use thiserror::Error;
@@ -82,6 +83,44 @@ pub enum SlashError {
     NegativeStake(i128, Address),
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum LiquidStakingError {
+    #[error("The given address {0} is not a validator address")]
+    NotAValidator(Address),
+    #[error(
+        "Trying to unbond more liquid staking shares ({0}) than the amount \
+         held ({1})"
+    )]
+    UnbondAmountGreaterThanShares(String, String),
+    #[error("The liquid staking pool of the validator {0} is fully slashed")]
+    PoolFullySlashed(Address),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum StakeLimitError {
+    #[error("The given address {0} is not a validator address")]
+    NotAValidator(Address),
+    #[error(
+        "The stake of the validator {0} would reach {1} out of a total \
+         consensus stake of {2}, above the maximum share of {3}"
+    )]
+    MaxStakeShareExceeded(Address, String, String, Dec),
+    #[error(
+        "The stake delegated to the validator {0} would reach {1}, above its \
+         maximum delegation of {2}"
+    )]
+    MaxDelegationExceeded(Address, String, String),
+    #[error(
+        "The self-bond of the validator {0} would be {1}, below the minimum \
+         self-bond of {2} required for its stake"
+    )]
+    MinSelfBondNotMet(Address, String, String),
+    #[error("Stake limits arithmetic error: {0}")]
+    Arith(#[from] arith::Error),
+}
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum CommissionRateChangeError {
@@ -99,6 +138,26 @@ pub enum CommissionRateChangeError {
     CannotWrite(Address),
     #[error("Cannot read storage for validator {0}")]
     CannotRead(Address),
+    #[error(
+        "Validator {0} has committed not to raise its commission rate until \
+         epoch {1}"
+    )]
+    RaiseLocked(Address, Epoch),
+    #[error(
+        "Commission rate change of validator {0} scheduled for epoch {1} must \
+         be after the pipeline epoch {2}"
+    )]
+    ScheduledTooEarly(Address, Epoch, Epoch),
+    #[error(
+        "Validator {0} cannot shorten its commitment not to raise its \
+         commission rate until epoch {1}"
+    )]
+    CommitmentShortened(Address, Epoch),
+    #[error(
+        "Commission schedule of validator {0} has more than {1} changes or \
+         tiers"
+    )]
+    ScheduleTooLong(Address, usize),
 }
 
 #[allow(missing_docs)]
@@ -165,6 +224,31 @@ pub enum ConsensusKeyChangeError {
     MustBeEd25519,
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum ValidatorKeysChangeError {
+    #[error("The given address {0} is not a validator address")]
+    NotAValidator(Address),
+    #[error("At least one of the validator keys must be changed")]
+    NoKeysChanged,
+    #[error("The Ethereum bridge keys are required to be Secp256k1 keys")]
+    EthKeyMustBeSecp256k1,
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum InstantUnbondError {
+    #[error("Instant unbonds are not enabled")]
+    Disabled,
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum RewardRecipientError {
+    #[error("The internal address {0} cannot receive redirected rewards")]
+    InternalAddress(Address),
+}
+
 impl From<BecomeValidatorError> for Error {
     fn from(err: BecomeValidatorError) -> Self {
         Self::new(err)
@@ -183,6 +267,18 @@ impl From<UnbondError> for Error {
     }
 }
 
+impl From<LiquidStakingError> for Error {
+    fn from(err: LiquidStakingError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<StakeLimitError> for Error {
+    fn from(err: StakeLimitError) -> Self {
+        Self::new(err)
+    }
+}
+
 impl From<CommissionRateChangeError> for Error {
     fn from(err: CommissionRateChangeError) -> Self {
         Self::new(err)
@@ -230,3 +326,21 @@ impl From<ConsensusKeyChangeError> for Error {
         Self::new(err)
     }
 }
+
+impl From<ValidatorKeysChangeError> for Error {
+    fn from(err: ValidatorKeysChangeError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<InstantUnbondError> for Error {
+    fn from(err: InstantUnbondError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<RewardRecipientError> for Error {
+    fn from(err: RewardRecipientError) -> Self {
+        Self::new(err)
+    }
+}
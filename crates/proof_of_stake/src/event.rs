@@ -15,6 +15,12 @@ pub mod types {
 
     /// Slash event.
     pub const SLASH: EventType = event_type!(PosEvent, "slash");
+
+    /// Rewards auto-compounding event.
+    pub const AUTO_COMPOUND: EventType = event_type!(PosEvent, "auto-compound");
+
+    /// Redirected rewards sweep event.
+    pub const REWARDS_SWEEP: EventType = event_type!(PosEvent, "rewards-sweep");
 }
 
 /// Proof of Stake event.
@@ -27,6 +33,26 @@ pub enum PosEvent {
         /// Amount of tokens that have been slashed.
         amount: token::Amount,
     },
+    /// Rewards auto-compounding event.
+    AutoCompound {
+        /// The source of the bond.
+        source: Address,
+        /// The validator of the bond.
+        validator: Address,
+        /// Amount of reward tokens that have been bonded.
+        amount: token::Amount,
+    },
+    /// Redirected rewards sweep event.
+    RewardsSweep {
+        /// The source of the bond.
+        source: Address,
+        /// The validator of the bond.
+        validator: Address,
+        /// The recipient of the source's rewards.
+        recipient: Address,
+        /// Amount of reward tokens that have been transferred.
+        amount: token::Amount,
+    },
 }
 
 impl EventToEmit for PosEvent {
@@ -42,6 +68,26 @@ impl From<PosEvent> for Event {
                     .with(SlashedAmount(&amount.into()))
                     .into()
             }
+            PosEvent::AutoCompound {
+                source,
+                validator,
+                amount,
+            } => Event::new(types::AUTO_COMPOUND, EventLevel::Block)
+                .with(BondSource(source))
+                .with(BondValidator(validator))
+                .with(CompoundedAmount(&amount.into()))
+                .into(),
+            PosEvent::RewardsSweep {
+                source,
+                validator,
+                recipient,
+                amount,
+            } => Event::new(types::REWARDS_SWEEP, EventLevel::Block)
+                .with(BondSource(source))
+                .with(BondValidator(validator))
+                .with(RewardRecipient(recipient))
+                .with(SweptAmount(&amount.into()))
+                .into(),
         }
     }
 }
@@ -73,3 +119,73 @@ impl<'amt> EventAttributeEntry<'amt> for SlashedAmount<'amt> {
         self.0
     }
 }
+
+/// Extend an [`Event`] with the source of a bond.
+pub struct BondSource(pub Address);
+
+impl EventAttributeEntry<'static> for BondSource {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "bond-source";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the validator of a bond.
+pub struct BondValidator(pub Address);
+
+impl EventAttributeEntry<'static> for BondValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "bond-validator";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with compounded rewards amount data.
+pub struct CompoundedAmount<'amt>(pub &'amt Uint);
+
+impl<'amt> EventAttributeEntry<'amt> for CompoundedAmount<'amt> {
+    type Value = &'amt Uint;
+    type ValueOwned = Uint;
+
+    const KEY: &'static str = "compounded-amount";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the recipient of a bond source's rewards.
+pub struct RewardRecipient(pub Address);
+
+impl EventAttributeEntry<'static> for RewardRecipient {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "reward-recipient";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with swept rewards amount data.
+pub struct SweptAmount<'amt>(pub &'amt Uint);
+
+impl<'amt> EventAttributeEntry<'amt> for SweptAmount<'amt> {
+    type Value = &'amt Uint;
+    type ValueOwned = Uint;
+
+    const KEY: &'static str = "swept-amount";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
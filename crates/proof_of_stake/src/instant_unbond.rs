@@ -0,0 +1,250 @@
+//! PoS instant unbonds.
+//!
+//! When enabled by governance with the `instant_unbond_penalty` parameter, a
+//! bond may be unbonded instantly, releasing the unbonded tokens right away
+//! instead of after the unbonding period. The penalty charged for it is held
+//! by PoS until the unbond would have become withdrawable, so that it covers
+//! the slashes for infractions committed before the unbond that are only
+//! processed afterwards. When such a slash could exceed the penalty, the part
+//! of the unbond that it could take is held back too, as a reserve. Once the
+//! unbond matures, the rest of the penalty is burned or sent to PGF and the
+//! rest of the reserve is returned to the source.
+
+use namada_core::address::{Address, PGF};
+use namada_core::arith::checked;
+use namada_core::chain::Epoch;
+use namada_core::token;
+use namada_systems::{governance, trans_token};
+
+use crate::lazy_map::{self, NestedSubKey};
+use crate::slashing::{
+    compute_amount_after_slashing_withdraw, estimate_slash_rate,
+    find_validator_slashes,
+};
+use crate::storage::{
+    delegator_redelegated_instant_unbonds_handle, instant_unbond_handle,
+    instant_unbond_queue_handle, read_pos_params,
+};
+use crate::types::{InstantUnbondPenalty, InstantUnbondQuote};
+use crate::{
+    collect_unbonds_and_redelegated_unbonds, staking_token_address,
+    unbond_tokens_aux, InstantUnbondError, Result, StorageRead, StorageWrite,
+    UnbondKind, ADDRESS,
+};
+
+/// Unbond tokens from a bond and release them to the source right away, for
+/// the penalty set by governance.
+pub fn instant_unbond_tokens<S, Gov, Token>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> Result<InstantUnbondQuote>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let quote = quote_instant_unbond::<S, Gov>(
+        storage,
+        source,
+        validator,
+        amount,
+        current_epoch,
+    )?;
+
+    let source = source.unwrap_or(validator);
+    let staking_token = staking_token_address(storage);
+    Token::transfer(storage, &staking_token, &ADDRESS, source, quote.released)?;
+
+    Ok(quote)
+}
+
+/// Quote an instant unbond by applying it to the PoS state, without releasing
+/// the unbonded tokens.
+///
+/// The instant unbond is written to the given storage, so when it's only
+/// being quoted the storage must be discarded afterwards (e.g. a temporary
+/// write log).
+pub fn quote_instant_unbond<S, Gov>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> Result<InstantUnbondQuote>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let penalty_rate = params
+        .instant_unbond_penalty
+        .ok_or(InstantUnbondError::Disabled)?;
+    let maturity_epoch =
+        checked!(current_epoch + params.withdrawable_epoch_offset())?;
+
+    let result_slashing = unbond_tokens_aux::<S, Gov>(
+        storage,
+        source,
+        validator,
+        amount,
+        current_epoch,
+        UnbondKind::Instant,
+    )?;
+    let amount = result_slashing.sum;
+    let penalty = amount.mul_ceil(penalty_rate)?;
+    // Hold back the part of the unbond that a slash for an infraction found
+    // later could take in excess of the penalty
+    let slash_rate =
+        estimate_slash_rate(storage, &params, validator, current_epoch)?;
+    let reserve = amount
+        .mul_ceil(slash_rate)?
+        .checked_sub(penalty)
+        .unwrap_or_default();
+    let released = checked!(amount - penalty - reserve)?;
+
+    let source = source.unwrap_or(validator);
+    instant_unbond_queue_handle()
+        .at(&maturity_epoch)
+        .at(source)
+        .try_update(storage, validator.clone(), |current| {
+            let current = current.unwrap_or_default();
+            Ok(InstantUnbondPenalty {
+                amount: checked!(current.amount + amount)?,
+                penalty: checked!(current.penalty + penalty)?,
+                reserve: checked!(current.reserve + reserve)?,
+            })
+        })?;
+
+    Ok(InstantUnbondQuote {
+        amount,
+        penalty,
+        reserve,
+        released,
+        maturity_epoch,
+    })
+}
+
+/// Settle the penalties of the instant unbonds that mature in the current
+/// epoch. The slashes processed since an unbond are taken out of its penalty
+/// and then out of its reserve. The rest of the penalty is burned or sent to
+/// PGF and the rest of the reserve is returned to the source. Returns the
+/// settled amount of the penalties.
+///
+/// Invariant: This has to be applied after the slashes for the current epoch
+/// are processed.
+pub fn settle_instant_unbonds<S, Gov, Token>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let queue = instant_unbond_queue_handle();
+    let matured = queue
+        .at(&current_epoch)
+        .iter(storage)?
+        .map(|res| {
+            let (
+                NestedSubKey::Data {
+                    key: source,
+                    nested_sub_key: lazy_map::SubKey::Data(validator),
+                },
+                penalty,
+            ) = res?;
+            Ok((source, validator, penalty))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let staking_token = staking_token_address(storage);
+    let mut settled = token::Amount::zero();
+    for (
+        source,
+        validator,
+        InstantUnbondPenalty {
+            amount,
+            penalty,
+            reserve,
+        },
+    ) in matured
+    {
+        let unbonds = instant_unbond_handle(&source, &validator);
+        let redelegated_unbonds =
+            delegator_redelegated_instant_unbonds_handle(&source)
+                .at(&validator);
+        let unbonds_and_redelegated_unbonds =
+            collect_unbonds_and_redelegated_unbonds(
+                storage,
+                &unbonds,
+                &redelegated_unbonds,
+                Some(current_epoch),
+            )?;
+
+        let slashes = find_validator_slashes(storage, &validator)?;
+        let amount_after_slashing = compute_amount_after_slashing_withdraw(
+            storage,
+            &params,
+            &unbonds_and_redelegated_unbonds,
+            slashes,
+        )?
+        .sum;
+        let slashed = amount
+            .checked_sub(amount_after_slashing)
+            .unwrap_or_default();
+        let remaining = penalty.checked_sub(slashed).unwrap_or_default();
+        checked!(settled += remaining)?;
+        let slashed_in_excess =
+            slashed.checked_sub(penalty).unwrap_or_default();
+        if slashed_in_excess > reserve {
+            tracing::error!(
+                "The slashed amount {} of the instant unbonds from {source} \
+                 to {validator} exceeds their penalty of {} and reserve of {}",
+                slashed.to_string_native(),
+                penalty.to_string_native(),
+                reserve.to_string_native(),
+            );
+        }
+        let returned =
+            reserve.checked_sub(slashed_in_excess).unwrap_or_default();
+        if !returned.is_zero() {
+            Token::transfer(
+                storage,
+                &staking_token,
+                &ADDRESS,
+                &source,
+                returned,
+            )?;
+        }
+
+        for (start_epoch, withdraw_epoch) in
+            unbonds_and_redelegated_unbonds.into_keys()
+        {
+            unbonds.at(&start_epoch).remove(storage, &withdraw_epoch)?;
+            redelegated_unbonds
+                .at(&start_epoch)
+                .remove_all(storage, &withdraw_epoch)?;
+
+            if unbonds.at(&start_epoch).is_empty(storage)? {
+                unbonds.remove_all(storage, &start_epoch)?;
+            }
+            if redelegated_unbonds.at(&start_epoch).is_empty(storage)? {
+                redelegated_unbonds.remove_all(storage, &start_epoch)?;
+            }
+        }
+    }
+    queue.remove_all(storage, &current_epoch)?;
+
+    if !settled.is_zero() {
+        if params.instant_unbond_penalty_to_pgf {
+            Token::transfer(storage, &staking_token, &ADDRESS, &PGF, settled)?;
+        } else {
+            Token::burn_tokens(storage, &staking_token, &ADDRESS, settled)?;
+        }
+    }
+    Ok(settled)
+}
@@ -17,12 +17,17 @@
     clippy::print_stderr
 )]
 
+pub mod commission;
 pub mod epoched;
 pub mod event;
+pub mod instant_unbond;
+pub mod liquid_staking;
 pub mod parameters;
+pub mod performance;
 pub mod queries;
 pub mod rewards;
 pub mod slashing;
+pub mod stake_limits;
 pub mod storage;
 pub mod storage_key;
 pub mod types;
@@ -47,6 +52,7 @@ pub use namada_core::chain::Epoch;
 use namada_core::collections::HashSet;
 pub use namada_core::dec::Dec;
 use namada_core::key::common;
+use namada_core::parameter_change::PosParameterChange;
 use namada_core::tendermint::abci::types::Misbehavior;
 use namada_core::token;
 use namada_events::EmitEvents;
@@ -77,19 +83,24 @@ use crate::slashing::{
     compute_amount_after_slashing_withdraw, find_validator_slashes,
 };
 use crate::storage::{
-    below_capacity_validator_set_handle, bond_handle,
-    consensus_validator_set_handle, delegation_targets_handle,
-    delegator_redelegated_bonds_handle, delegator_redelegated_unbonds_handle,
-    get_last_reward_claim_epoch, liveness_missed_votes_handle,
+    auto_compound_handle, below_capacity_validator_set_handle, bond_handle,
+    commission_schedules_handle, consensus_validator_set_handle,
+    delegation_targets_handle, delegator_redelegated_bonds_handle,
+    delegator_redelegated_instant_unbonds_handle,
+    delegator_redelegated_unbonds_handle, get_last_reward_claim_epoch,
+    instant_unbond_handle, liveness_missed_votes_handle,
     liveness_sum_missed_votes_handle, read_consensus_validator_set_addresses,
     read_non_pos_owned_params, read_pos_params,
-    read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
-    read_validator_stake, total_bonded_handle, total_consensus_stake_handle,
-    total_unbonded_handle, try_insert_consensus_key, unbond_handle,
-    update_total_deltas, update_validator_deltas, validator_addresses_handle,
+    read_validator_last_slash_epoch, read_validator_liquid_staking,
+    read_validator_max_commission_rate_change, read_validator_stake,
+    reward_recipients_handle, total_bonded_handle,
+    total_consensus_stake_handle, total_unbonded_handle,
+    try_insert_consensus_key, unbond_handle, update_total_deltas,
+    update_validator_deltas, validator_addresses_handle,
     validator_commission_rate_handle, validator_consensus_key_handle,
-    validator_deltas_handle, validator_eth_cold_key_handle,
-    validator_eth_hot_key_handle, validator_incoming_redelegations_handle,
+    validator_delegators_handle, validator_deltas_handle,
+    validator_eth_cold_key_handle, validator_eth_hot_key_handle,
+    validator_incoming_redelegations_handle,
     validator_outgoing_redelegations_handle, validator_protocol_key_handle,
     validator_rewards_products_handle, validator_set_positions_handle,
     validator_slashes_handle, validator_state_handle,
@@ -105,8 +116,9 @@ use crate::storage::{
 use crate::storage_key::{bonds_for_source_prefix, is_bond_key};
 use crate::types::{
     BondId, ConsensusValidator, EagerRedelegatedBondsMap,
-    RedelegatedBondsOrUnbonds, RedelegatedTokens, ResultSlashing, Slash,
-    Unbonds, ValidatorMetaData, ValidatorSetUpdate, ValidatorState, VoteInfo,
+    RedelegatedBondsOrUnbonds, RedelegatedTokens, ResultSlashing,
+    RewardRecipient, Slash, Unbonds, ValidatorMetaData, ValidatorSetUpdate,
+    ValidatorState, VoteInfo,
 };
 use crate::validator_set_update::{
     copy_validator_sets_and_positions, insert_validator_into_validator_set,
@@ -198,6 +210,28 @@ where
     }
 }
 
+impl<S> Write<S> for Store<S>
+where
+    S: StorageRead + StorageWrite,
+{
+    fn change_params(
+        storage: &mut S,
+        changes: &[PosParameterChange],
+    ) -> Result<bool> {
+        let mut params = storage::read_owned_pos_params(storage)?;
+        for change in changes {
+            params.apply_change(change);
+        }
+        let errors = params.validate();
+        if !errors.is_empty() {
+            tracing::warn!("Invalid change of PoS parameters: {errors:?}");
+            return Ok(false);
+        }
+        write_pos_params(storage, &params)?;
+        Ok(true)
+    }
+}
+
 /// Address of the PoS account implemented as a native VP
 pub const ADDRESS: Address = Address::Internal(InternalAddress::PoS);
 
@@ -354,6 +388,65 @@ where
     let staking_token = staking_token_address(storage);
     Token::transfer(storage, &staking_token, source, &ADDRESS, amount)?;
 
+    // Delegations to a validator with liquid staking enabled are pooled and
+    // the source receives shares of the pool in exchange
+    let is_delegation = source != validator;
+    if is_delegation && read_validator_liquid_staking(storage, validator)? {
+        liquid_staking::bond_shares::<S, Gov, Token>(
+            storage,
+            source,
+            validator,
+            amount,
+            current_epoch,
+            offset_opt,
+        )?;
+    } else {
+        bond_tokens_aux::<S, Gov>(
+            storage,
+            source,
+            validator,
+            amount,
+            current_epoch,
+            offset_opt,
+        )?;
+    }
+
+    // The bonds applied at genesis are not limited, as the validator sets are
+    // still being formed
+    if offset_opt.is_none() {
+        stake_limits::enforce_stake_limits::<S, Gov>(
+            storage,
+            validator,
+            is_delegation,
+            current_epoch,
+        )?;
+        // A delegation cannot take the self-bond of the validator below the
+        // minimum ratio of its stake
+        if is_delegation {
+            stake_limits::enforce_min_self_bond::<S, Gov>(
+                storage,
+                validator,
+                current_epoch,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Bond tokens that are already held by the PoS account from the `source` to
+/// the `validator`.
+pub(crate) fn bond_tokens_aux<S, Gov>(
+    storage: &mut S,
+    source: &Address,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+    offset_opt: Option<u64>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
     let params = read_pos_params::<S, Gov>(storage)?;
     let offset = offset_opt.unwrap_or(params.pipeline_len);
     let offset_epoch = checked!(current_epoch + offset)?;
@@ -481,9 +574,14 @@ where
 /// Unbond tokens that are bonded between a validator and a source (self or
 /// delegator).
 ///
+/// When the source holds shares of the validator's liquid staking pool and has
+/// no bond of its own left with the validator, the `amount` of shares is
+/// burned instead and the tokens they represent are unbonded from the pool to
+/// the source.
+///
 /// This fn is also called during redelegation for a source validator, in
 /// which case the `is_redelegation` param must be true.
-pub fn unbond_tokens<S, Gov>(
+pub fn unbond_tokens<S, Gov, Token>(
     storage: &mut S,
     source: Option<&Address>,
     validator: &Address,
@@ -491,6 +589,69 @@ pub fn unbond_tokens<S, Gov>(
     current_epoch: Epoch,
     is_redelegation: bool,
 ) -> Result<ResultSlashing>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    if amount.is_zero() {
+        return Ok(ResultSlashing::default());
+    }
+
+    if let Some(source) = source {
+        if !is_redelegation
+            && liquid_staking::is_shares_unbond::<S, Gov, Token>(
+                storage,
+                source,
+                validator,
+                current_epoch,
+            )?
+        {
+            return liquid_staking::unbond_shares::<S, Gov, Token>(
+                storage,
+                source,
+                validator,
+                amount,
+                current_epoch,
+            );
+        }
+    }
+
+    let kind = if is_redelegation {
+        UnbondKind::Redelegation
+    } else {
+        UnbondKind::Regular
+    };
+    unbond_tokens_aux::<S, Gov>(
+        storage,
+        source,
+        validator,
+        amount,
+        current_epoch,
+        kind,
+    )
+}
+
+/// How the tokens unbonded from a bond are recorded for its source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnbondKind {
+    /// A regular unbond that can be withdrawn once it becomes withdrawable
+    Regular,
+    /// The unbonded tokens are redelegated, so no unbond is recorded
+    Redelegation,
+    /// An instant unbond, whose tokens are released right away
+    Instant,
+}
+
+/// Unbond tokens that are bonded between a validator and a source.
+pub(crate) fn unbond_tokens_aux<S, Gov>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+    kind: UnbondKind,
+) -> Result<ResultSlashing>
 where
     S: StorageRead + StorageWrite,
     Gov: governance::Read<S>,
@@ -547,7 +708,12 @@ where
         tracing::debug!("\nBonds before decrementing: {bonds:#?}");
     }
 
-    let unbonds = unbond_handle(source, validator);
+    let unbonds = match kind {
+        UnbondKind::Instant => instant_unbond_handle(source, validator),
+        UnbondKind::Regular | UnbondKind::Redelegation => {
+            unbond_handle(source, validator)
+        }
+    };
 
     let redelegated_bonds =
         delegator_redelegated_bonds_handle(source).at(validator);
@@ -651,7 +817,7 @@ where
 
     // `updatedUnbonded`
     // Update the unbonds in storage using the eager map computed above
-    if !is_redelegation {
+    if kind != UnbondKind::Redelegation {
         for (start_epoch, &unbond_amount) in new_unbonds_map.iter() {
             unbonds.at(start_epoch).try_update(
                 storage,
@@ -693,11 +859,18 @@ where
         }
     }
 
-    if !is_redelegation {
+    if kind != UnbondKind::Redelegation {
         // `val updatedRedelegatedUnbonded` with updates applied below
         // Delegator's redelegated unbonds to this validator.
-        let delegator_redelegated_unbonded =
-            delegator_redelegated_unbonds_handle(source).at(validator);
+        let delegator_redelegated_unbonded = match kind {
+            UnbondKind::Instant => {
+                delegator_redelegated_instant_unbonds_handle(source)
+            }
+            UnbondKind::Regular | UnbondKind::Redelegation => {
+                delegator_redelegated_unbonds_handle(source)
+            }
+        }
+        .at(validator);
 
         // Quint `def updateRedelegatedUnbonded` with `val
         // updatedRedelegatedUnbonded` together with last statement
@@ -839,6 +1012,15 @@ where
         !is_jailed_or_inactive_at_pipeline,
     )?;
 
+    // A validator cannot unbond its own stake below the minimum self-bond
+    if source == validator {
+        stake_limits::enforce_min_self_bond::<S, Gov>(
+            storage,
+            validator,
+            current_epoch,
+        )?;
+    }
+
     if tracing::level_enabled!(tracing::Level::DEBUG) {
         let bonds = find_bonds(storage, source, validator)?;
         tracing::debug!("\nBonds after decrementing: {bonds:#?}");
@@ -1519,6 +1701,77 @@ where
     Ok(())
 }
 
+/// Protocol and Ethereum bridge keys change for a validator. Like the
+/// consensus key, the keys that are given are set at the pipeline epoch, from
+/// which on the validator's votes and the Ethereum bridge validator set use
+/// them.
+pub fn change_validator_keys<S, Gov>(
+    storage: &mut S,
+    validator: &Address,
+    protocol_key: Option<&common::PublicKey>,
+    eth_cold_key: Option<&common::PublicKey>,
+    eth_hot_key: Option<&common::PublicKey>,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    tracing::debug!(
+        "Changing protocol and Ethereum keys for validator {}",
+        validator
+    );
+
+    if !is_validator(storage, validator)? {
+        return Err(
+            ValidatorKeysChangeError::NotAValidator(validator.clone()).into()
+        );
+    }
+    if protocol_key.is_none() && eth_cold_key.is_none() && eth_hot_key.is_none()
+    {
+        return Err(ValidatorKeysChangeError::NoKeysChanged.into());
+    }
+
+    // The Ethereum addresses of the bridge validator set are derived from the
+    // Ethereum keys, which therefore have to be Secp256k1 keys
+    if eth_cold_key
+        .into_iter()
+        .chain(eth_hot_key)
+        .any(|key| !matches!(key, common::PublicKey::Secp256k1(_)))
+    {
+        return Err(ValidatorKeysChangeError::EthKeyMustBeSecp256k1.into());
+    }
+
+    // Set the new keys at the pipeline epoch
+    let params = read_pos_params::<S, Gov>(storage)?;
+    if let Some(protocol_key) = protocol_key {
+        validator_protocol_key_handle(validator).set::<S, Gov>(
+            storage,
+            protocol_key.clone(),
+            current_epoch,
+            params.pipeline_len,
+        )?;
+    }
+    if let Some(eth_cold_key) = eth_cold_key {
+        validator_eth_cold_key_handle(validator).set::<S, Gov>(
+            storage,
+            eth_cold_key.clone(),
+            current_epoch,
+            params.pipeline_len,
+        )?;
+    }
+    if let Some(eth_hot_key) = eth_hot_key {
+        validator_eth_hot_key_handle(validator).set::<S, Gov>(
+            storage,
+            eth_hot_key.clone(),
+            current_epoch,
+            params.pipeline_len,
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Withdraw tokens from those that have been unbonded from proof-of-stake
 pub fn withdraw_tokens<S, Gov, Token>(
     storage: &mut S,
@@ -1546,56 +1799,12 @@ where
         return Ok(token::Amount::zero());
     }
 
-    let mut unbonds_and_redelegated_unbonds: BTreeMap<
-        (Epoch, Epoch),
-        (token::Amount, EagerRedelegatedBondsMap),
-    > = BTreeMap::new();
-
-    for unbond in unbond_handle.iter(storage)? {
-        let (
-            lazy_map::NestedSubKey::Data {
-                key: start_epoch,
-                nested_sub_key: lazy_map::SubKey::Data(withdraw_epoch),
-            },
-            amount,
-        ) = unbond?;
-
-        // Logging
-        tracing::debug!(
-            "Unbond delta ({start_epoch}..{withdraw_epoch}), amount {}",
-            amount.to_string_native()
-        );
-        // Consider only unbonds that are eligible to be withdrawn
-        if withdraw_epoch > current_epoch {
-            tracing::debug!(
-                "Not yet withdrawable until epoch {withdraw_epoch}"
-            );
-            continue;
-        }
-
-        let mut eager_redelegated_unbonds = EagerRedelegatedBondsMap::default();
-        let matching_redelegated_unbonds =
-            redelegated_unbonds.at(&start_epoch).at(&withdraw_epoch);
-        for ub in matching_redelegated_unbonds.iter(storage)? {
-            let (
-                lazy_map::NestedSubKey::Data {
-                    key: address,
-                    nested_sub_key: lazy_map::SubKey::Data(epoch),
-                },
-                amount,
-            ) = ub?;
-            eager_redelegated_unbonds
-                .entry(address)
-                .or_default()
-                .entry(epoch)
-                .or_insert(amount);
-        }
-
-        unbonds_and_redelegated_unbonds.insert(
-            (start_epoch, withdraw_epoch),
-            (amount, eager_redelegated_unbonds),
-        );
-    }
+    let unbonds_and_redelegated_unbonds = find_unbonds_and_redelegated_unbonds(
+        storage,
+        source,
+        validator,
+        Some(current_epoch),
+    )?;
 
     let slashes = find_validator_slashes(storage, validator)?;
 
@@ -1656,6 +1865,94 @@ where
     Ok(withdrawable_amount)
 }
 
+/// Find the unbonds of a bond, together with their redelegated unbonds, keyed
+/// by their start and withdrawable epochs. When an epoch is given, only the
+/// unbonds that are withdrawable by it are included.
+pub(crate) fn find_unbonds_and_redelegated_unbonds<S>(
+    storage: &S,
+    source: &Address,
+    validator: &Address,
+    withdrawable_by: Option<Epoch>,
+) -> Result<BTreeMap<(Epoch, Epoch), (token::Amount, EagerRedelegatedBondsMap)>>
+where
+    S: StorageRead,
+{
+    let unbond_handle: Unbonds = unbond_handle(source, validator);
+    let redelegated_unbonds =
+        delegator_redelegated_unbonds_handle(source).at(validator);
+    collect_unbonds_and_redelegated_unbonds(
+        storage,
+        &unbond_handle,
+        &redelegated_unbonds,
+        withdrawable_by,
+    )
+}
+
+/// Collect the unbonds from the given handles, together with their
+/// redelegated unbonds, keyed by their start and withdrawable epochs. When an
+/// epoch is given, only the unbonds that are withdrawable by it are included.
+pub(crate) fn collect_unbonds_and_redelegated_unbonds<S>(
+    storage: &S,
+    unbond_handle: &Unbonds,
+    redelegated_unbonds: &NestedMap<Epoch, RedelegatedBondsOrUnbonds>,
+    withdrawable_by: Option<Epoch>,
+) -> Result<BTreeMap<(Epoch, Epoch), (token::Amount, EagerRedelegatedBondsMap)>>
+where
+    S: StorageRead,
+{
+    let mut unbonds_and_redelegated_unbonds: BTreeMap<
+        (Epoch, Epoch),
+        (token::Amount, EagerRedelegatedBondsMap),
+    > = BTreeMap::new();
+
+    for unbond in unbond_handle.iter(storage)? {
+        let (
+            lazy_map::NestedSubKey::Data {
+                key: start_epoch,
+                nested_sub_key: lazy_map::SubKey::Data(withdraw_epoch),
+            },
+            amount,
+        ) = unbond?;
+
+        // Logging
+        tracing::debug!(
+            "Unbond delta ({start_epoch}..{withdraw_epoch}), amount {}",
+            amount.to_string_native()
+        );
+        // Consider only unbonds that are eligible to be withdrawn
+        if withdrawable_by.is_some_and(|epoch| withdraw_epoch > epoch) {
+            tracing::debug!(
+                "Not yet withdrawable until epoch {withdraw_epoch}"
+            );
+            continue;
+        }
+
+        let mut eager_redelegated_unbonds = EagerRedelegatedBondsMap::default();
+        let matching_redelegated_unbonds =
+            redelegated_unbonds.at(&start_epoch).at(&withdraw_epoch);
+        for ub in matching_redelegated_unbonds.iter(storage)? {
+            let (
+                lazy_map::NestedSubKey::Data {
+                    key: address,
+                    nested_sub_key: lazy_map::SubKey::Data(epoch),
+                },
+                amount,
+            ) = ub?;
+            eager_redelegated_unbonds
+                .entry(address)
+                .or_default()
+                .entry(epoch)
+                .or_insert(amount);
+        }
+
+        unbonds_and_redelegated_unbonds.insert(
+            (start_epoch, withdraw_epoch),
+            (amount, eager_redelegated_unbonds),
+        );
+    }
+    Ok(unbonds_and_redelegated_unbonds)
+}
+
 /// Change the commission rate of a validator
 pub fn change_validator_commission_rate<S, Gov>(
     storage: &mut S,
@@ -1716,6 +2013,19 @@ where
         .into());
     }
 
+    if new_rate > rate_before_pipeline {
+        let schedule = commission_schedules_handle().get(storage, validator)?;
+        if let Some(until) = schedule
+            .and_then(|schedule| schedule.raise_locked_until(pipeline_epoch))
+        {
+            return Err(CommissionRateChangeError::RaiseLocked(
+                validator.clone(),
+                until,
+            )
+            .into());
+        }
+    }
+
     commission_handle.set::<S, Gov>(
         storage,
         new_rate,
@@ -1866,68 +2176,82 @@ pub fn bond_amount<S, Gov>(
     bond_id: &BondId,
     epoch: Epoch,
 ) -> Result<token::Amount>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    let amounts = slashed_bond_amounts::<S, Gov>(storage, bond_id, epoch)?;
+    token::Amount::sum(amounts.values().map(|(_raw, slashed)| *slashed))
+        .ok_or_err_msg("token amount overflow")
+}
+
+/// Get the bond amounts for a given bond ID and epoch by their start epoch,
+/// both before and after slashing. For future epochs, the values are subject
+/// to change.
+pub(crate) fn slashed_bond_amounts<S, Gov>(
+    storage: &S,
+    bond_id: &BondId,
+    epoch: Epoch,
+) -> Result<BTreeMap<Epoch, (token::Amount, token::Amount)>>
 where
     S: StorageRead,
     Gov: governance::Read<S>,
 {
     let params = read_pos_params::<S, Gov>(storage)?;
-    let mut amounts = bond_amounts_for_query(storage, &params, bond_id, epoch)?;
+    let amounts = bond_amounts_for_query(storage, &params, bond_id, epoch)?;
+    if amounts.is_empty() {
+        return Ok(BTreeMap::new());
+    }
 
-    if !amounts.is_empty() {
-        let slashes = find_validator_slashes(storage, &bond_id.validator)?;
-        let redelegated_bonded =
-            delegator_redelegated_bonds_handle(&bond_id.source)
-                .at(&bond_id.validator);
+    let slashes = find_validator_slashes(storage, &bond_id.validator)?;
+    let redelegated_bonded =
+        delegator_redelegated_bonds_handle(&bond_id.source)
+            .at(&bond_id.validator);
 
-        // Apply slashes
-        for (&start, amount) in amounts.iter_mut() {
-            let list_slashes = slashes
-                .iter()
-                .filter(|slash| {
-                    let processing_epoch = slash
-                        .epoch
-                        .unchecked_add(params.slash_processing_epoch_offset());
-                    // Only use slashes that were processed before or at the
-                    // epoch associated with the bond amount. This assumes
-                    // that slashes are applied before inflation.
-                    processing_epoch <= epoch && start <= slash.epoch
-                })
-                .cloned()
-                .collect::<Vec<_>>();
+    // Apply slashes
+    let mut slashed_amounts = BTreeMap::new();
+    for (start, amount) in amounts {
+        let list_slashes = slashes
+            .iter()
+            .filter(|slash| {
+                let processing_epoch = slash
+                    .epoch
+                    .unchecked_add(params.slash_processing_epoch_offset());
+                // Only use slashes that were processed before or at the
+                // epoch associated with the bond amount. This assumes
+                // that slashes are applied before inflation.
+                processing_epoch <= epoch && start <= slash.epoch
+            })
+            .cloned()
+            .collect::<Vec<_>>();
 
-            let slash_epoch_filter = |e: Epoch| {
-                e.unchecked_add(params.slash_processing_epoch_offset()) <= epoch
-            };
+        let slash_epoch_filter = |e: Epoch| {
+            e.unchecked_add(params.slash_processing_epoch_offset()) <= epoch
+        };
 
-            let redelegated_bonds =
-                redelegated_bonded.at(&start).collect_map(storage)?;
+        let redelegated_bonds =
+            redelegated_bonded.at(&start).collect_map(storage)?;
 
-            let result_fold = fold_and_slash_redelegated_bonds(
-                storage,
-                &params,
-                &redelegated_bonds,
-                start,
-                &list_slashes,
-                slash_epoch_filter,
-            )?;
+        let result_fold = fold_and_slash_redelegated_bonds(
+            storage,
+            &params,
+            &redelegated_bonds,
+            start,
+            &list_slashes,
+            slash_epoch_filter,
+        )?;
 
-            let total_not_redelegated =
-                checked!(amount - result_fold.total_redelegated)?;
+        let total_not_redelegated =
+            checked!(amount - result_fold.total_redelegated)?;
 
-            let after_not_redelegated = apply_list_slashes(
-                &params,
-                &list_slashes,
-                total_not_redelegated,
-            )?;
+        let after_not_redelegated =
+            apply_list_slashes(&params, &list_slashes, total_not_redelegated)?;
 
-            *amount = checked!(
-                after_not_redelegated + result_fold.total_after_slashing
-            )?;
-        }
+        let slashed_amount =
+            checked!(after_not_redelegated + result_fold.total_after_slashing)?;
+        slashed_amounts.insert(start, (amount, slashed_amount));
     }
-
-    token::Amount::sum(amounts.values().copied())
-        .ok_or_err_msg("token amount overflow")
+    Ok(slashed_amounts)
 }
 
 /// Get bond amounts within the `claim_start..=claim_end` epoch range for
@@ -2134,8 +2458,20 @@ where
 
     // Re-insert the validator into the validator set and update its state
     let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
-    let stake =
-        read_validator_stake(storage, &params, validator, pipeline_epoch)?;
+    let (self_bond, stake) = stake_limits::read_self_bond_and_stake::<S, Gov>(
+        storage,
+        &params,
+        validator,
+        pipeline_epoch,
+    )?;
+
+    // Make sure the validator won't be jailed again for its self-bond once
+    // it's back in the validator sets
+    if stake >= params.validator_stake_threshold {
+        stake_limits::check_min_self_bond(
+            &params, validator, self_bond, stake,
+        )?;
+    }
 
     insert_validator_into_validator_set::<S, Gov>(
         storage,
@@ -2264,13 +2600,13 @@ where
 
     // Unbond the redelegated tokens from the src validator.
     // `resultUnbond` in quint
-    let result_unbond = unbond_tokens::<S, Gov>(
+    let result_unbond = unbond_tokens_aux::<S, Gov>(
         storage,
         Some(delegator),
         src_validator,
         amount,
         current_epoch,
-        true,
+        UnbondKind::Redelegation,
     )?;
 
     // The unbonded amount after slashing is what is going to be redelegated.
@@ -2414,7 +2750,19 @@ where
         !is_jailed_or_inactive_at_pipeline,
     )?;
 
-    Ok(())
+    stake_limits::enforce_stake_limits::<S, Gov>(
+        storage,
+        dest_validator,
+        true,
+        current_epoch,
+    )?;
+    // A redelegation cannot take the self-bond of the destination validator
+    // below the minimum ratio of its stake
+    stake_limits::enforce_min_self_bond::<S, Gov>(
+        storage,
+        dest_validator,
+        current_epoch,
+    )
 }
 
 /// Deactivate a validator by removing it from any validator sets. A validator
@@ -2824,9 +3172,22 @@ where
     // Update the last claim epoch in storage
     write_last_reward_claim_epoch(storage, &source, validator, current_epoch)?;
 
-    // Transfer the bonded tokens from PoS to the source
+    // Transfer the reward tokens from PoS to the source, or to the transparent
+    // recipient that the source redirected its rewards to. The rewards
+    // redirected to a shielded recipient are paid out to the source, which
+    // shields them in the same tx batch.
+    let recipient = match reward_recipients_handle().get(storage, &source)? {
+        Some(RewardRecipient::Transparent(recipient)) => recipient,
+        Some(RewardRecipient::Shielded(_)) | None => source,
+    };
     let staking_token = staking_token_address(storage);
-    Token::transfer(storage, &staking_token, &ADDRESS, &source, reward_tokens)?;
+    Token::transfer(
+        storage,
+        &staking_token,
+        &ADDRESS,
+        &recipient,
+        reward_tokens,
+    )?;
 
     Ok(reward_tokens)
 }
@@ -2857,6 +3218,74 @@ where
     Ok(res)
 }
 
+/// Opt in or out of the automatic compounding of the rewards of a bond, which
+/// bonds them to the same validator at every epoch boundary. The bond is a
+/// self-bond when `source` is `None` or equal to the `validator` address.
+pub fn change_auto_compound<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    enabled: bool,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !is_validator(storage, validator)? {
+        return Err(BondError::NotAValidator(validator.clone()).into());
+    }
+    let source = source.unwrap_or(validator);
+    let auto_compounds = auto_compound_handle().at(source);
+    if enabled {
+        auto_compounds.insert(storage, validator.clone())?;
+    } else {
+        auto_compounds.remove(storage, validator)?;
+    }
+    Ok(())
+}
+
+/// Redirect the rewards of all the bonds of a source (a delegator or a
+/// self-bonded validator) to the given recipient, or back to the source when
+/// `None`.
+///
+/// The rewards redirected to a transparent recipient are paid out to it when
+/// they're claimed and they're also swept to it at every epoch boundary. The
+/// protocol cannot shield tokens, so the rewards redirected to a shielded
+/// payment address are not swept. They accumulate until they're claimed,
+/// when the client batches the claim with a shielding transfer of the claimed
+/// rewards to the payment address.
+pub fn change_reward_recipient<S>(
+    storage: &mut S,
+    source: &Address,
+    recipient: Option<&RewardRecipient>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let recipients = reward_recipients_handle();
+    match recipient {
+        Some(RewardRecipient::Transparent(recipient))
+            if recipient == source =>
+        {
+            recipients.remove(storage, source)?;
+        }
+        Some(RewardRecipient::Transparent(
+            recipient @ Address::Internal(_),
+        )) => {
+            return Err(RewardRecipientError::InternalAddress(
+                recipient.clone(),
+            )
+            .into());
+        }
+        Some(recipient) => {
+            recipients.insert(storage, source.clone(), recipient.clone())?;
+        }
+        None => {
+            recipients.remove(storage, source)?;
+        }
+    }
+    Ok(())
+}
+
 /// Jail a validator by removing it from and updating the validator sets and
 /// changing a its state to `Jailed`. Validators are jailed for liveness and for
 /// misbehaving.
@@ -2956,7 +3385,7 @@ where
 }
 
 /// Apply PoS updates for a block
-pub fn finalize_block<S, Gov>(
+pub fn finalize_block<S, Gov, Token>(
     storage: &mut S,
     events: &mut impl EmitEvents,
     is_new_epoch: bool,
@@ -2967,6 +3396,7 @@ pub fn finalize_block<S, Gov>(
 where
     S: StorageWrite + StorageRead,
     Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
 {
     let height = storage.get_block_height()?;
     let current_epoch = storage.get_block_epoch()?;
@@ -2988,6 +3418,13 @@ where
             storage,
             current_epoch,
         )?;
+
+        // Apply the scheduled commission rate changes that are due by the
+        // new pipeline epoch
+        commission::apply_commission_schedules::<S, Gov>(
+            storage,
+            current_epoch,
+        )?;
     }
 
     // Invariant: Has to be applied before `record_slashes_from_evidence`
@@ -3031,6 +3468,37 @@ where
             );
             panic!("Error while processing slashes");
         }
+
+        // Compound the rewards of the bonds that opted in to it. Invariant:
+        // Has to be applied after the slashes are processed as they affect
+        // the rewards.
+        rewards::compound_rewards::<S, Gov>(storage, events, current_epoch)?;
+
+        // Sweep the rewards of the bond sources that redirected them to their
+        // recipients. Invariant: Has to be applied after the slashes are
+        // processed as they affect the rewards.
+        rewards::sweep_redirected_rewards::<S, Gov, Token>(
+            storage,
+            events,
+            current_epoch,
+        )?;
+
+        // Settle the penalties of the instant unbonds that mature in this
+        // epoch. Invariant: Has to be applied after the slashes are processed
+        // as the penalties cover them.
+        instant_unbond::settle_instant_unbonds::<S, Gov, Token>(
+            storage,
+            current_epoch,
+        )?;
+
+        // Jail the validators whose self-bond no longer meets the minimum.
+        // Invariant: Has to be applied after the slashes are processed as
+        // they reduce the self-bonds.
+        stake_limits::jail_for_min_self_bond::<S, Gov>(
+            storage,
+            &pos_params,
+            current_epoch,
+        )?;
     }
 
     // Consensus set liveness check
@@ -3113,6 +3581,11 @@ where
     // After all, `prev_ranges` only grows when `remove_delegation_target` is
     // called.
 
+    if delegator != validator {
+        validator_delegators_handle(validator)
+            .insert(storage, delegator.clone())?;
+    }
+
     Ok(())
 }
 
@@ -3141,6 +3614,7 @@ where
     } else {
         panic!("Delegation should exist since we are removing it right now!!!");
     }
+    validator_delegators_handle(validator).remove(storage, delegator)?;
 
     Ok(())
 }
@@ -0,0 +1,454 @@
+//! PoS liquid staking.
+//!
+//! When liquid staking is enabled for a validator, delegations to it are pooled
+//! into a single bond owned by the validator's liquid staking token address and
+//! the delegators are minted transferable shares of the pool in exchange. The
+//! rewards of the pooled bond are compounded into it and its slashes are
+//! shared by all the holders, which drives the price of a share.
+
+use std::collections::BTreeMap;
+
+use namada_core::address::{Address, InternalAddress, LiquidStakingTokenHash};
+use namada_core::arith::checked;
+use namada_core::chain::Epoch;
+use namada_core::dec::Dec;
+use namada_core::token;
+use namada_systems::{governance, trans_token};
+
+use crate::rewards::{
+    compute_current_rewards_from_bonds, take_rewards_from_counter,
+};
+use crate::storage::{
+    bond_handle, read_pos_params, unbond_handle, write_last_reward_claim_epoch,
+    write_validator_liquid_staking,
+};
+use crate::types::{BondId, ResultSlashing};
+use crate::{
+    bond_amount, bond_tokens_aux, is_validator, query_reward_tokens,
+    slashed_bond_amounts, unbond_tokens_aux, Collectable, LiquidStakingError,
+    OptionExt, Result, ResultExt, StorageRead, StorageWrite, UnbondKind,
+    ADDRESS,
+};
+
+/// Get the address of the liquid staking token of a validator. This address
+/// is also the owner of the validator's pooled bond.
+pub fn liquid_staking_token(validator: &Address) -> Address {
+    Address::Internal(InternalAddress::LiquidStakingToken(
+        LiquidStakingTokenHash::from_validator(validator),
+    ))
+}
+
+/// Enable or disable liquid staking of the new delegations to a validator.
+/// Shares that were minted while it was enabled can still be unbonded after it
+/// gets disabled.
+pub fn change_liquid_staking<S, Token>(
+    storage: &mut S,
+    validator: &Address,
+    enabled: bool,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Token: trans_token::Keys,
+{
+    if !is_validator(storage, validator)? {
+        return Err(LiquidStakingError::NotAValidator(validator.clone()).into());
+    }
+    if enabled {
+        // Only the PoS account may mint the liquid staking token
+        let token = liquid_staking_token(validator);
+        storage.write(&Token::minter_key(&token), ADDRESS)?;
+    }
+    write_validator_liquid_staking(storage, validator, enabled)
+}
+
+/// Query the price of a share of a validator's liquid staking pool in the
+/// staking token, including the rewards that are yet to be compounded into the
+/// pool. Returns `None` when there are no shares.
+pub fn query_share_price<S, Gov, Token>(
+    storage: &S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> Result<Option<Dec>>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+    Token: trans_token::Read<S>,
+{
+    let token = liquid_staking_token(validator);
+    let shares = Token::read_total_supply(storage, &token)?;
+    if shares.is_zero() {
+        return Ok(None);
+    }
+    let pooled = pooled_amount::<S, Gov>(storage, validator, current_epoch)?;
+    let rewards = query_reward_tokens::<S, Gov>(
+        storage,
+        Some(&token),
+        validator,
+        current_epoch,
+    )?;
+    let value =
+        Dec::try_from(checked!(pooled + rewards)?).into_storage_result()?;
+    let shares = Dec::try_from(shares).into_storage_result()?;
+    value
+        .checked_div(shares)
+        .ok_or_err_msg("share price overflow")
+        .map(Some)
+}
+
+/// Bond tokens that are already held by the PoS account into the liquid
+/// staking pool of the `validator` and credit the `source` with the shares
+/// they are worth.
+pub(crate) fn bond_shares<S, Gov, Token>(
+    storage: &mut S,
+    source: &Address,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+    offset_opt: Option<u64>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let token = liquid_staking_token(validator);
+    compound_rewards::<S, Gov>(storage, validator, current_epoch)?;
+
+    // The pending rewards were just compounded, so the shares are worth as
+    // much as at the price before the bond
+    let total_shares = Token::read_total_supply(storage, &token)?;
+    let shares = if total_shares.is_zero() {
+        amount
+    } else {
+        let pooled =
+            pooled_amount::<S, Gov>(storage, validator, current_epoch)?;
+        if pooled.is_zero() {
+            return Err(LiquidStakingError::PoolFullySlashed(
+                validator.clone(),
+            )
+            .into());
+        }
+        mul_div(amount, total_shares, pooled)?
+    };
+    tracing::debug!(
+        "Bonding {} to the liquid staking pool of {validator} for {} shares",
+        amount.to_string_native(),
+        shares.to_string_native(),
+    );
+
+    bond_tokens_aux::<S, Gov>(
+        storage,
+        &token,
+        validator,
+        amount,
+        current_epoch,
+        offset_opt,
+    )?;
+    Token::credit_tokens(storage, &token, source, shares)
+}
+
+/// Check if unbonding from the `validator` by the `source` should burn shares
+/// of the validator's liquid staking pool. This is the case when the source
+/// holds some shares and has no bond of its own left with the validator.
+pub(crate) fn is_shares_unbond<S, Gov, Token>(
+    storage: &S,
+    source: &Address,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> Result<bool>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+    Token: trans_token::Read<S>,
+{
+    let token = liquid_staking_token(validator);
+    let shares = Token::read_balance(storage, &token, source)?;
+    holds_only_shares::<S, Gov>(
+        storage,
+        source,
+        validator,
+        shares,
+        current_epoch,
+    )
+}
+
+/// Check if the `source` holding the given `shares` of the `validator`'s
+/// liquid staking pool has no bond of its own left with the validator, so
+/// that its unbonds burn shares.
+pub(crate) fn holds_only_shares<S, Gov>(
+    storage: &S,
+    source: &Address,
+    validator: &Address,
+    shares: token::Amount,
+    current_epoch: Epoch,
+) -> Result<bool>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    if source == validator || shares.is_zero() {
+        return Ok(false);
+    }
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let bonded = bond_handle(source, validator)
+        .get_sum(storage, pipeline_epoch, &params)?
+        .unwrap_or_default();
+    Ok(bonded.is_zero())
+}
+
+/// Burn the `shares` of the `validator`'s liquid staking pool held by the
+/// `source` and unbond the tokens they represent from the pool to the source.
+pub(crate) fn unbond_shares<S, Gov, Token>(
+    storage: &mut S,
+    source: &Address,
+    validator: &Address,
+    shares: token::Amount,
+    current_epoch: Epoch,
+) -> Result<ResultSlashing>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let token = liquid_staking_token(validator);
+    let balance = Token::read_balance(storage, &token, source)?;
+    if shares > balance {
+        return Err(LiquidStakingError::UnbondAmountGreaterThanShares(
+            shares.to_string_native(),
+            balance.to_string_native(),
+        )
+        .into());
+    }
+    compound_rewards::<S, Gov>(storage, validator, current_epoch)?;
+
+    // The shares are worth their part of the pooled bond after slashing,
+    // like when they're minted. The pooled bond is unbonded from its latest
+    // bonds, so the amount to unbond before slashing is the one that's worth
+    // as much in these bonds.
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let bond_id = BondId {
+        source: token.clone(),
+        validator: validator.clone(),
+    };
+    let bond_amounts =
+        slashed_bond_amounts::<S, Gov>(storage, &bond_id, pipeline_epoch)?;
+    let pooled = token::Amount::sum(
+        bond_amounts.values().map(|(_raw, slashed)| *slashed),
+    )
+    .ok_or_err_msg("token amount overflow")?;
+    let total_shares = Token::read_total_supply(storage, &token)?;
+    let value = mul_div(shares, pooled, total_shares)?;
+    let amount = raw_amount_worth(&bond_amounts, value)?;
+    tracing::debug!(
+        "Unbonding {} from the liquid staking pool of {validator} for {} \
+         shares",
+        amount.to_string_native(),
+        shares.to_string_native(),
+    );
+
+    let result_slashing = unbond_tokens_aux::<S, Gov>(
+        storage,
+        Some(&token),
+        validator,
+        amount,
+        current_epoch,
+        UnbondKind::Regular,
+    )?;
+    move_pool_unbonds(storage, &token, source, validator)?;
+    Token::burn_tokens(storage, &token, source, shares)?;
+
+    Ok(result_slashing)
+}
+
+/// Get the shares of a validator's liquid staking pool that bonding the given
+/// `amount` is worth, at the price of the `total_shares` before the bond. The
+/// pending rewards of the pool count towards its value, like when they get
+/// compounded before minting the shares.
+pub(crate) fn bond_shares_worth<S, Gov>(
+    storage: &S,
+    validator: &Address,
+    amount: token::Amount,
+    total_shares: token::Amount,
+    current_epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    if total_shares.is_zero() {
+        return Ok(amount);
+    }
+    let token = liquid_staking_token(validator);
+    let pooled = pooled_amount::<S, Gov>(storage, validator, current_epoch)?;
+    let rewards = query_reward_tokens::<S, Gov>(
+        storage,
+        Some(&token),
+        validator,
+        current_epoch,
+    )?;
+    let value = checked!(pooled + rewards)?;
+    if value.is_zero() {
+        return Err(
+            LiquidStakingError::PoolFullySlashed(validator.clone()).into()
+        );
+    }
+    mul_div(amount, total_shares, value)
+}
+
+/// Bond the rewards earned by a validator's liquid staking pool into the pool.
+fn compound_rewards<S, Gov>(
+    storage: &mut S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let token = liquid_staking_token(validator);
+    let rewards = compute_current_rewards_from_bonds::<S, Gov>(
+        storage,
+        &token,
+        validator,
+        current_epoch,
+    )?;
+    let counter_rewards =
+        take_rewards_from_counter(storage, &token, validator)?;
+    let rewards = checked!(rewards + counter_rewards)?;
+    write_last_reward_claim_epoch(storage, &token, validator, current_epoch)?;
+    if rewards.is_zero() {
+        return Ok(());
+    }
+
+    // The rewards are already held by the PoS account
+    bond_tokens_aux::<S, Gov>(
+        storage,
+        &token,
+        validator,
+        rewards,
+        current_epoch,
+        None,
+    )
+}
+
+/// Get the amount bonded by a validator's liquid staking pool at the pipeline
+/// epoch, after slashing.
+fn pooled_amount<S, Gov>(
+    storage: &S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let bond_id = BondId {
+        source: liquid_staking_token(validator),
+        validator: validator.clone(),
+    };
+    bond_amount::<S, Gov>(storage, &bond_id, pipeline_epoch)
+}
+
+/// Hand the unbonds of a validator's liquid staking pool over to the `owner`
+/// of the burned shares. The pool itself never holds any unbonds.
+fn move_pool_unbonds<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    validator: &Address,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let pool_unbonds = unbond_handle(token, validator);
+    let owner_unbonds = unbond_handle(owner, validator);
+    for (start_epoch, unbonds) in pool_unbonds.collect_map(storage)? {
+        for (withdrawable_epoch, amount) in unbonds {
+            owner_unbonds.at(&start_epoch).try_update(
+                storage,
+                withdrawable_epoch,
+                |current| {
+                    let current = current.unwrap_or_default();
+                    Ok(checked!(current + amount)?)
+                },
+            )?;
+        }
+        pool_unbonds.remove_all(storage, &start_epoch)?;
+    }
+    Ok(())
+}
+
+/// Get the amount to unbond before slashing from the bonds with the given
+/// amounts before and after slashing by their start epoch, so that it's worth
+/// the given `value` after slashing. Like when unbonding, the latest bonds are
+/// taken first.
+fn raw_amount_worth(
+    bond_amounts: &BTreeMap<Epoch, (token::Amount, token::Amount)>,
+    value: token::Amount,
+) -> Result<token::Amount> {
+    let mut remaining = value;
+    let mut amount = token::Amount::zero();
+    for &(raw, slashed) in bond_amounts.values().rev() {
+        if remaining.is_zero() {
+            break;
+        }
+        if slashed >= remaining {
+            let raw_part = mul_div(remaining, raw, slashed)?;
+            amount = checked!(amount + raw_part)?;
+            remaining = token::Amount::zero();
+        } else {
+            amount = checked!(amount + raw)?;
+            remaining = checked!(remaining - slashed)?;
+        }
+    }
+    Ok(amount)
+}
+
+/// Compute `amount * numerator / denominator`, rounded down.
+fn mul_div(
+    amount: token::Amount,
+    numerator: token::Amount,
+    denominator: token::Amount,
+) -> Result<token::Amount> {
+    amount
+        .checked_mul(numerator)
+        .and_then(|product| product.checked_div(denominator))
+        .ok_or_err_msg("token amount overflow")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_amount_worth() {
+        let amount = token::Amount::native_whole;
+        // The bond from epoch 1 lost half of its value to a slash
+        let bond_amounts = BTreeMap::from([
+            (Epoch(1), (amount(10), amount(5))),
+            (Epoch(2), (amount(10), amount(10))),
+        ]);
+
+        // The latest bond is unbonded first
+        assert_eq!(
+            raw_amount_worth(&bond_amounts, amount(4)).unwrap(),
+            amount(4)
+        );
+        // The slashed part is worth half of its amount
+        assert_eq!(
+            raw_amount_worth(&bond_amounts, amount(12)).unwrap(),
+            amount(14)
+        );
+        assert_eq!(
+            raw_amount_worth(&bond_amounts, amount(15)).unwrap(),
+            amount(20)
+        );
+        assert!(raw_amount_worth(&bond_amounts, token::Amount::zero())
+            .unwrap()
+            .is_zero());
+    }
+}
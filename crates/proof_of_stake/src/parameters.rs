@@ -6,6 +6,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::arith::checked;
 use namada_core::chain::Epoch;
 use namada_core::dec::Dec;
+use namada_core::parameter_change::PosParameterChange;
 use namada_core::token;
 use namada_core::uint::Uint;
 #[cfg(test)]
@@ -80,6 +81,57 @@ pub struct OwnedPosParams {
     pub rewards_gain_p: Dec,
     /// PoS gain d (read only)
     pub rewards_gain_d: Dec,
+    /// The maximum share of the total consensus stake that a bond or a
+    /// redelegation may bring a validator to. No cap is applied when `None`.
+    /// Stored separately with the other [`ExtraPosParams`].
+    #[borsh(skip)]
+    pub max_validator_stake_share: Option<Dec>,
+    /// The fraction of the tokens charged for an instant unbond, which
+    /// releases the unbonded tokens without waiting for the unbonding period.
+    /// Instant unbonds are disabled when `None`. Stored separately with the
+    /// other [`ExtraPosParams`].
+    #[borsh(skip)]
+    pub instant_unbond_penalty: Option<Dec>,
+    /// Whether the instant unbond penalties are sent to PGF instead of being
+    /// burned. Stored separately with the other [`ExtraPosParams`].
+    #[borsh(skip)]
+    pub instant_unbond_penalty_to_pgf: bool,
+    /// The minimum amount that a validator in the `consensus` or
+    /// `below_capacity` validator sets has to self-bond, if any. Stored
+    /// separately with the other [`ExtraPosParams`].
+    #[borsh(skip)]
+    pub min_self_bond: Option<token::Amount>,
+    /// The minimum ratio of a validator's self-bond to its total stake while
+    /// it's in the `consensus` or `below_capacity` validator sets, if any.
+    /// Stored separately with the other [`ExtraPosParams`].
+    #[borsh(skip)]
+    pub min_self_bond_ratio: Option<Dec>,
+}
+
+/// The PoS parameters added after [`OwnedPosParams`] got stored by existing
+/// chains. They're left out of its encoding and stored under their own key,
+/// so that the stored parameters of these chains still decode. The features
+/// that they control are disabled until they're set.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    Serialize,
+)]
+pub struct ExtraPosParams {
+    /// See [`OwnedPosParams::max_validator_stake_share`]
+    pub max_validator_stake_share: Option<Dec>,
+    /// See [`OwnedPosParams::instant_unbond_penalty`]
+    pub instant_unbond_penalty: Option<Dec>,
+    /// See [`OwnedPosParams::instant_unbond_penalty_to_pgf`]
+    pub instant_unbond_penalty_to_pgf: bool,
+    /// See [`OwnedPosParams::min_self_bond`]
+    pub min_self_bond: Option<token::Amount>,
+    /// See [`OwnedPosParams::min_self_bond_ratio`]
+    pub min_self_bond_ratio: Option<Dec>,
 }
 
 impl Default for OwnedPosParams {
@@ -108,6 +160,11 @@ impl Default for OwnedPosParams {
             liveness_threshold: Dec::new(9, 1).expect("Test failed"),
             rewards_gain_p: Dec::from_str("0.25").expect("Test failed"),
             rewards_gain_d: Dec::from_str("0.25").expect("Test failed"),
+            max_validator_stake_share: None,
+            instant_unbond_penalty: None,
+            instant_unbond_penalty_to_pgf: false,
+            min_self_bond: None,
+            min_self_bond_ratio: None,
         }
     }
 }
@@ -143,6 +200,17 @@ pub enum ValidationError {
          pipeline: {1}"
     )]
     UnbondingLenTooShort(u64, u64),
+    #[error("Max validator stake share must be in (0, 1], got {0}")]
+    MaxValidatorStakeShareOutOfRange(Dec),
+    #[error("Instant unbond penalty must be in (0, 1], got {0}")]
+    InstantUnbondPenaltyOutOfRange(Dec),
+    #[error(
+        "Instant unbond penalty must not be lower than the minimum slash \
+         rates, got {0}"
+    )]
+    InstantUnbondPenaltyBelowMinSlashRate(Dec),
+    #[error("Min self-bond ratio must be in [0, 1], got {0}")]
+    MinSelfBondRatioOutOfRange(Dec),
 }
 
 /// The maximum string length of any validator metadata
@@ -212,9 +280,121 @@ impl OwnedPosParams {
             ))
         }
 
+        if let Some(share) = self.max_validator_stake_share {
+            if share <= Dec::zero() || share > Dec::one() {
+                errors.push(ValidationError::MaxValidatorStakeShareOutOfRange(
+                    share,
+                ))
+            }
+        }
+
+        if let Some(penalty) = self.instant_unbond_penalty {
+            if penalty <= Dec::zero() || penalty > Dec::one() {
+                errors.push(ValidationError::InstantUnbondPenaltyOutOfRange(
+                    penalty,
+                ))
+            }
+            // The penalty has to cover the slash of any prior infraction at
+            // its minimum rate
+            if penalty < self.duplicate_vote_min_slash_rate
+                || penalty < self.light_client_attack_min_slash_rate
+            {
+                errors.push(
+                    ValidationError::InstantUnbondPenaltyBelowMinSlashRate(
+                        penalty,
+                    ),
+                )
+            }
+        }
+
+        if let Some(ratio) = self.min_self_bond_ratio {
+            if ratio.is_negative() || ratio > Dec::one() {
+                errors.push(ValidationError::MinSelfBondRatioOutOfRange(ratio))
+            }
+        }
+
         errors
     }
 
+    /// Get the parameters that are stored separately
+    pub fn extra(&self) -> ExtraPosParams {
+        ExtraPosParams {
+            max_validator_stake_share: self.max_validator_stake_share,
+            instant_unbond_penalty: self.instant_unbond_penalty,
+            instant_unbond_penalty_to_pgf: self.instant_unbond_penalty_to_pgf,
+            min_self_bond: self.min_self_bond,
+            min_self_bond_ratio: self.min_self_bond_ratio,
+        }
+    }
+
+    /// Set the parameters that are stored separately
+    pub fn set_extra(&mut self, extra: ExtraPosParams) {
+        let ExtraPosParams {
+            max_validator_stake_share,
+            instant_unbond_penalty,
+            instant_unbond_penalty_to_pgf,
+            min_self_bond,
+            min_self_bond_ratio,
+        } = extra;
+        self.max_validator_stake_share = max_validator_stake_share;
+        self.instant_unbond_penalty = instant_unbond_penalty;
+        self.instant_unbond_penalty_to_pgf = instant_unbond_penalty_to_pgf;
+        self.min_self_bond = min_self_bond;
+        self.min_self_bond_ratio = min_self_bond_ratio;
+    }
+
+    /// Apply a change of a parameter proposed by governance. The changed
+    /// parameters should be validated afterwards.
+    pub fn apply_change(&mut self, change: &PosParameterChange) {
+        match change {
+            PosParameterChange::BlockProposerReward(value) => {
+                self.block_proposer_reward = *value;
+            }
+            PosParameterChange::BlockVoteReward(value) => {
+                self.block_vote_reward = *value;
+            }
+            PosParameterChange::MaxInflationRate(value) => {
+                self.max_inflation_rate = *value;
+            }
+            PosParameterChange::TargetStakedRatio(value) => {
+                self.target_staked_ratio = *value;
+            }
+            PosParameterChange::DuplicateVoteMinSlashRate(value) => {
+                self.duplicate_vote_min_slash_rate = *value;
+            }
+            PosParameterChange::LightClientAttackMinSlashRate(value) => {
+                self.light_client_attack_min_slash_rate = *value;
+            }
+            PosParameterChange::ValidatorStakeThreshold(amount) => {
+                self.validator_stake_threshold = *amount;
+            }
+            PosParameterChange::LivenessThreshold(value) => {
+                self.liveness_threshold = *value;
+            }
+            PosParameterChange::RewardsGainP(value) => {
+                self.rewards_gain_p = *value;
+            }
+            PosParameterChange::RewardsGainD(value) => {
+                self.rewards_gain_d = *value;
+            }
+            PosParameterChange::MaxValidatorStakeShare(value) => {
+                self.max_validator_stake_share = *value;
+            }
+            PosParameterChange::InstantUnbondPenalty(value) => {
+                self.instant_unbond_penalty = *value;
+            }
+            PosParameterChange::InstantUnbondPenaltyToPgf(value) => {
+                self.instant_unbond_penalty_to_pgf = *value;
+            }
+            PosParameterChange::MinSelfBond(value) => {
+                self.min_self_bond = *value;
+            }
+            PosParameterChange::MinSelfBondRatio(value) => {
+                self.min_self_bond_ratio = *value;
+            }
+        }
+    }
+
     /// Get the epoch offset from which an unbonded bond can withdrawn
     pub fn withdrawable_epoch_offset(&self) -> u64 {
         checked!(
@@ -305,6 +485,8 @@ impl std::ops::Deref for PosParams {
 
 #[cfg(test)]
 mod tests {
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_core::parameter_change::testing::arb_pos_parameter_change;
     use proptest::prelude::*;
     use testing::arb_pos_params;
 
@@ -322,6 +504,40 @@ mod tests {
                 errors
             );
         }
+
+        #[test]
+        fn test_apply_valid_pos_parameter_change(
+            mut pos_params in arb_pos_params(None),
+            change in arb_pos_parameter_change(),
+        ) {
+            pos_params.apply_change(&change);
+            assert!(pos_params.validate().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_extra_params_encoding() {
+        let params = OwnedPosParams::default();
+        let mut with_extra = params.clone();
+        with_extra.set_extra(ExtraPosParams {
+            instant_unbond_penalty: Some(Dec::new(1, 2).unwrap()),
+            min_self_bond_ratio: Some(Dec::new(1, 1).unwrap()),
+            ..Default::default()
+        });
+
+        // The extra parameters are left out of the encoding, so that the
+        // parameters stored before they were added still decode
+        let bytes = with_extra.serialize_to_vec();
+        assert_eq!(bytes, params.serialize_to_vec());
+        let mut decoded = OwnedPosParams::try_from_slice(&bytes).unwrap();
+        assert_eq!(decoded.instant_unbond_penalty, None);
+
+        decoded.set_extra(with_extra.extra());
+        assert_eq!(
+            decoded.instant_unbond_penalty,
+            with_extra.instant_unbond_penalty
+        );
+        assert_eq!(decoded.min_self_bond_ratio, with_extra.min_self_bond_ratio);
     }
 }
 
@@ -0,0 +1,124 @@
+//! PoS validator performance history.
+//!
+//! Unlike the liveness data, which only covers the `liveness_window_check`
+//! blocks needed for jailing, the performance of the consensus validators is
+//! tallied per epoch and kept for the last [`PERFORMANCE_HISTORY_LEN`] epochs,
+//! so that delegators can compare validators without running an indexer.
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::chain::Epoch;
+use namada_core::token;
+
+use crate::storage::validator_performance_handle;
+use crate::types::ValidatorEpochPerformance;
+use crate::{Result, StorageRead, StorageWrite};
+
+/// The number of the most recent epochs whose performance is kept for each
+/// validator
+pub const PERFORMANCE_HISTORY_LEN: u64 = 100;
+
+/// Record a validator's performance in a block of the given epoch in which it
+/// was in the consensus set.
+pub(crate) fn record_block_performance<S>(
+    storage: &mut S,
+    validator: &Address,
+    epoch: Epoch,
+    is_proposer: bool,
+    has_voted: bool,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    update_performance(storage, validator, epoch, |performance| {
+        checked!(performance.expected_votes += 1)?;
+        if !has_voted {
+            checked!(performance.missed_votes += 1)?;
+        }
+        if is_proposer {
+            checked!(performance.proposed_blocks += 1)?;
+        }
+        Ok(())
+    })
+}
+
+/// Record the rewards earned by a validator's stake in the given epoch and
+/// the commissions it took out of them.
+pub(crate) fn record_epoch_rewards<S>(
+    storage: &mut S,
+    validator: &Address,
+    epoch: Epoch,
+    rewards: token::Amount,
+    commissions: token::Amount,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    update_performance(storage, validator, epoch, |performance| {
+        performance.rewards = rewards;
+        performance.commissions = commissions;
+        Ok(())
+    })
+}
+
+/// Update a validator's performance in the given epoch. The epochs that fall
+/// out of the history are pruned when a new epoch is recorded.
+fn update_performance<S>(
+    storage: &mut S,
+    validator: &Address,
+    epoch: Epoch,
+    update: impl FnOnce(&mut ValidatorEpochPerformance) -> Result<()>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let handle = validator_performance_handle(validator);
+    let mut performance = match handle.get(storage, &epoch)? {
+        Some(performance) => performance,
+        None => {
+            if let Some(last_pruned) =
+                epoch.checked_sub(PERFORMANCE_HISTORY_LEN)
+            {
+                let pruned_epochs = handle
+                    .iter(storage)?
+                    .map(|entry| entry.map(|(epoch, _)| epoch))
+                    .filter(|epoch| {
+                        epoch
+                            .as_ref()
+                            .map_or(true, |epoch| *epoch <= last_pruned)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                for epoch in pruned_epochs {
+                    handle.remove(storage, &epoch)?;
+                }
+            }
+            ValidatorEpochPerformance::default()
+        }
+    };
+    update(&mut performance)?;
+    handle.insert(storage, epoch, performance)?;
+    Ok(())
+}
+
+/// Read a validator's performance history, starting from the given epoch, if
+/// any.
+pub fn read_validator_performance_history<S>(
+    storage: &S,
+    validator: &Address,
+    from_epoch: Option<Epoch>,
+) -> Result<BTreeMap<Epoch, ValidatorEpochPerformance>>
+where
+    S: StorageRead,
+{
+    let from_epoch = from_epoch.unwrap_or_default();
+    validator_performance_handle(validator)
+        .iter(storage)?
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .map_or(true, |(epoch, _)| *epoch >= from_epoch)
+        })
+        .collect()
+}
@@ -1,36 +1,65 @@
 //! PoS rewards distribution.
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use namada_controller::PDController;
 use namada_core::address::{self, Address};
 use namada_core::arith::{self, checked};
 use namada_core::chain::{BlockHeight, Epoch};
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::dec::Dec;
+use namada_core::storage::Key;
 use namada_core::token;
 use namada_core::uint::{Uint, I256};
+use namada_events::EmitEvents;
 use namada_systems::{governance, parameters, trans_token};
 use thiserror::Error;
 
+use crate::event::PosEvent;
 use crate::lazy_map::NestedSubKey;
+use crate::performance::{record_block_performance, record_epoch_rewards};
+use crate::queries::find_delegation_validators;
 use crate::storage::{
+    auto_compound_handle, bond_handle, commission_schedules_handle,
     consensus_validator_set_handle, get_last_reward_claim_epoch,
     read_last_pos_inflation_amount, read_last_staked_ratio, read_pos_params,
-    read_total_stake, read_validator_stake, rewards_accumulator_handle,
-    validator_commission_rate_handle, validator_rewards_products_handle,
+    read_total_stake, read_validator_stake, reward_recipients_handle,
+    rewards_accumulator_handle, validator_commission_rate_handle,
+    validator_delegators_handle, validator_rewards_products_handle,
     validator_state_handle, write_last_pos_inflation_amount,
-    write_last_staked_ratio,
+    write_last_reward_claim_epoch, write_last_staked_ratio,
+};
+use crate::types::{
+    into_tm_voting_power, BondId, RewardRecipient, ValidatorState, VoteInfo,
 };
-use crate::types::{into_tm_voting_power, BondId, ValidatorState, VoteInfo};
 use crate::{
-    bond_amounts_for_rewards, get_total_consensus_stake, staking_token_address,
-    storage, storage_key, InflationError, PosParams, Result, ResultExt,
-    StorageRead, StorageWrite,
+    bond_amounts_for_rewards, bond_tokens_aux, get_total_consensus_stake,
+    iter_prefix_bytes, lazy_set, staking_token_address, storage, storage_key,
+    InflationError, PosParams, Result, ResultExt, StorageRead, StorageWrite,
+    ADDRESS,
 };
 
 /// This is equal to 0.01.
 const MIN_PROPOSER_REWARD: Dec =
     Dec(I256(Uint([10000000000u64, 0u64, 0u64, 0u64])));
 
+/// Gas charged to the protocol for compounding the rewards of a single bond.
+pub const AUTO_COMPOUND_GAS: u64 = 50_000;
+
+/// Gas budget of the protocol for compounding rewards at an epoch boundary.
+/// The bonds that don't fit in the budget are compounded first at the next
+/// epoch boundary.
+pub const AUTO_COMPOUND_GAS_BUDGET: u64 = 500_000_000;
+
+/// Gas charged to the protocol for sweeping the rewards of a single bond to
+/// the recipient of its source.
+pub const REWARDS_SWEEP_GAS: u64 = 50_000;
+
+/// Gas budget of the protocol for sweeping redirected rewards at an epoch
+/// boundary. The bonds that don't fit in the budget are swept first at the
+/// next epoch boundary.
+pub const REWARDS_SWEEP_GAS_BUDGET: u64 = 500_000_000;
+
 /// Errors during rewards calculation
 #[derive(Debug, Error)]
 #[allow(missing_docs)]
@@ -346,6 +375,14 @@ where
         values.insert(address, rewards_frac);
     }
     for (address, value) in values.into_iter() {
+        // Record the validator's performance in the block
+        record_block_performance(
+            storage,
+            &address,
+            epoch,
+            address == *proposer_address,
+            signer_set.contains(&address),
+        )?;
         // Update the rewards accumulator
         rewards_accumulator_handle().try_update(storage, address, |prev| {
             let prev = prev.unwrap_or_default();
@@ -428,7 +465,10 @@ where
 #[derive(Clone, Debug)]
 struct Rewards {
     product: Dec,
+    tokens: token::Amount,
     commissions: token::Amount,
+    commission_rate: Dec,
+    tokens_per_stake: Dec,
 }
 
 /// Update validator and delegators rewards products and mint the inflation
@@ -479,6 +519,7 @@ where
         // validator so they get it back.
         let reward_tokens_dec =
             Dec::try_from(reward_tokens).into_storage_result()?;
+        let tokens_per_stake = checked!(reward_tokens_dec / stake)?;
         let product = checked!(
             (Dec::one() - commission_rate) * reward_tokens_dec / stake
         )?;
@@ -492,23 +533,49 @@ where
             validator,
             Rewards {
                 product,
+                tokens: reward_tokens,
                 commissions,
+                commission_rate,
+                tokens_per_stake,
             },
         );
 
         reward_tokens_remaining =
             checked!(reward_tokens_remaining - reward_tokens)?;
     }
+
+    // The commissions charged in excess of the validators' commission tiers
+    // are given back to the delegations
+    index_validator_delegators(storage)?;
+    let rebates = compute_tier_rebates(
+        storage,
+        params,
+        last_epoch,
+        &mut new_rewards_products,
+    )?;
+    for (BondId { source, validator }, rebate) in rebates {
+        add_rewards_to_counter(storage, &source, &validator, rebate)?;
+    }
+
     for (
         validator,
         Rewards {
             product,
+            tokens,
             commissions,
+            ..
         },
     ) in new_rewards_products
     {
         validator_rewards_products_handle(&validator)
             .insert(storage, last_epoch, product)?;
+        record_epoch_rewards(
+            storage,
+            &validator,
+            last_epoch,
+            tokens,
+            commissions,
+        )?;
         // The commissions belong to the validator
         add_rewards_to_counter(storage, &validator, &validator, commissions)?;
     }
@@ -554,6 +621,99 @@ where
     Ok(())
 }
 
+/// Index the delegators of the validators from their bonds, unless already
+/// done. The index is then kept up to date with the delegation targets, but
+/// it has to be built once for the bonds made before it was introduced.
+fn index_validator_delegators<S>(storage: &mut S) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let indexed_key = storage_key::validator_delegators_indexed_key();
+    if storage.has_key(&indexed_key)? {
+        return Ok(());
+    }
+
+    let mut bond_ids = BTreeSet::new();
+    for next in iter_prefix_bytes(storage, &storage_key::bonds_prefix())? {
+        let (key, _) = next?;
+        if let Some((bond_id, _)) = storage_key::is_bond_key(&key) {
+            if bond_id.source != bond_id.validator {
+                bond_ids.insert(bond_id);
+            }
+        }
+    }
+    for BondId { source, validator } in bond_ids {
+        validator_delegators_handle(&validator).insert(storage, source)?;
+    }
+    storage.write(&indexed_key, ())
+}
+
+/// Compute the rebates of the commissions charged in the given epoch in excess
+/// of the validators' commission tiers to their delegations and deduct them
+/// from the validators' commissions. Returns the rebates by bond.
+///
+/// The delegations are found in the index of the delegators of the validators
+/// with commission tiers that earned rewards in the epoch.
+fn compute_tier_rebates<S>(
+    storage: &S,
+    params: &PosParams,
+    epoch: Epoch,
+    rewards: &mut HashMap<Address, Rewards>,
+) -> Result<Vec<(BondId, token::Amount)>>
+where
+    S: StorageRead,
+{
+    let mut schedules = HashMap::new();
+    for schedule in commission_schedules_handle().iter(storage)? {
+        let (validator, schedule) = schedule?;
+        if !schedule.tiers.is_empty() && rewards.contains_key(&validator) {
+            schedules.insert(validator, schedule);
+        }
+    }
+    if schedules.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Find the delegations to the validators with commission tiers
+    let mut bond_ids = BTreeSet::new();
+    for validator in schedules.keys() {
+        for delegator in validator_delegators_handle(validator).iter(storage)? {
+            bond_ids.insert(BondId {
+                source: delegator?,
+                validator: validator.clone(),
+            });
+        }
+    }
+
+    let mut rebates = vec![];
+    for bond_id in bond_ids {
+        let (Some(schedule), Some(validator_rewards)) = (
+            schedules.get(&bond_id.validator),
+            rewards.get_mut(&bond_id.validator),
+        ) else {
+            continue;
+        };
+        let amount = bond_handle(&bond_id.source, &bond_id.validator)
+            .get_sum(storage, epoch, params)?
+            .unwrap_or_default();
+        let commission_rate = validator_rewards.commission_rate;
+        let tier_rate = schedule.tier_rate(commission_rate, amount);
+        let rebate_per_stake = checked!(
+            (commission_rate - tier_rate) * validator_rewards.tokens_per_stake
+        )?;
+        // The rebates cannot exceed the validator's commissions
+        let rebate = amount
+            .mul_floor(rebate_per_stake)?
+            .min(validator_rewards.commissions);
+        if !rebate.is_zero() {
+            validator_rewards.commissions =
+                checked!(validator_rewards.commissions - rebate)?;
+            rebates.push((bond_id, rebate));
+        }
+    }
+    Ok(rebates)
+}
+
 /// Compute the current available rewards amount due only to existing bonds.
 /// This does not include pending rewards held in the rewards counter due to
 /// unbonds and redelegations.
@@ -693,6 +853,291 @@ where
     Ok(est_staking_reward_rate)
 }
 
+/// Compound the rewards of the bonds that opted in to auto-compounding by
+/// bonding them to the same validator, within the protocol's gas budget.
+///
+/// This is executed in the first block of a new epoch, before the inflation
+/// for the last epoch is applied, so the rewards are claimed up to the epoch
+/// before the last one.
+pub fn compound_rewards<S, Gov>(
+    storage: &mut S,
+    events: &mut impl EmitEvents,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let Some(claim_epoch) = current_epoch.prev() else {
+        return Ok(());
+    };
+    let params = read_pos_params::<S, Gov>(storage)?;
+
+    let bonds = auto_compound_handle()
+        .iter(storage)?
+        .map(|res| {
+            let (
+                NestedSubKey::Data {
+                    key: source,
+                    nested_sub_key: lazy_set::SubKey::Data(validator),
+                },
+                (),
+            ) = res?;
+            Ok(BondId { source, validator })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let num_bonds = bonds.len();
+    let (gas_used, num_compounded) = for_each_bond_within_budget(
+        storage,
+        bonds,
+        &storage_key::auto_compound_cursor_key(),
+        AUTO_COMPOUND_GAS,
+        AUTO_COMPOUND_GAS_BUDGET,
+        |storage, bond_id| {
+            let amount = compound_bond_rewards::<S, Gov>(
+                storage,
+                &params,
+                bond_id,
+                claim_epoch,
+                current_epoch,
+            )?;
+            if !amount.is_zero() {
+                events.emit(PosEvent::AutoCompound {
+                    source: bond_id.source.clone(),
+                    validator: bond_id.validator.clone(),
+                    amount,
+                });
+            }
+            Ok(())
+        },
+    )?;
+    tracing::info!(
+        "Compounded the rewards of {num_compounded} out of {num_bonds} bonds \
+         in epoch {current_epoch} using {gas_used} gas",
+    );
+
+    Ok(())
+}
+
+/// Claim the rewards of a bond up to the `claim_epoch` and bond them to the
+/// same validator. Returns the bonded amount.
+fn compound_bond_rewards<S, Gov>(
+    storage: &mut S,
+    params: &PosParams,
+    bond_id: &BondId,
+    claim_epoch: Epoch,
+    current_epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let BondId { source, validator } = bond_id;
+
+    // Don't bond the rewards of a bond that is being fully unbonded
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let bonded = bond_handle(source, validator)
+        .get_sum(storage, pipeline_epoch, params)?
+        .unwrap_or_default();
+    if bonded.is_zero() {
+        return Ok(token::Amount::zero());
+    }
+
+    let mut reward_tokens = compute_current_rewards_from_bonds::<S, Gov>(
+        storage,
+        source,
+        validator,
+        claim_epoch,
+    )?;
+    let counter_rewards =
+        take_rewards_from_counter(storage, source, validator)?;
+    checked!(reward_tokens += counter_rewards)?;
+    write_last_reward_claim_epoch(storage, source, validator, claim_epoch)?;
+
+    if !reward_tokens.is_zero() {
+        // The reward tokens are already held by the PoS account
+        bond_tokens_aux::<S, Gov>(
+            storage,
+            source,
+            validator,
+            reward_tokens,
+            current_epoch,
+            None,
+        )?;
+    }
+    Ok(reward_tokens)
+}
+
+/// Sweep the rewards of the bonds of the sources that redirected their rewards
+/// to transparent recipients, within the protocol's gas budget. The bonds
+/// whose rewards are compounded automatically are left out, and so are the
+/// rewards redirected to shielded recipients, which cannot be shielded by the
+/// protocol and accumulate until they're claimed.
+///
+/// Like [`compound_rewards`], this is executed in the first block of a new
+/// epoch, so the rewards are claimed up to the epoch before the last one.
+pub fn sweep_redirected_rewards<S, Gov, Token>(
+    storage: &mut S,
+    events: &mut impl EmitEvents,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let Some(claim_epoch) = current_epoch.prev() else {
+        return Ok(());
+    };
+
+    let mut recipients = BTreeMap::new();
+    for entry in reward_recipients_handle().iter(storage)? {
+        let (source, recipient) = entry?;
+        if let RewardRecipient::Transparent(recipient) = recipient {
+            recipients.insert(source, recipient);
+        }
+    }
+    let mut bonds = vec![];
+    for source in recipients.keys() {
+        let validators: BTreeSet<Address> =
+            find_delegation_validators(storage, source, &claim_epoch)?
+                .into_iter()
+                .collect();
+        let auto_compounds = auto_compound_handle().at(source);
+        for validator in validators {
+            if !auto_compounds.contains(storage, &validator)? {
+                bonds.push(BondId {
+                    source: source.clone(),
+                    validator,
+                });
+            }
+        }
+    }
+
+    let num_bonds = bonds.len();
+    let (gas_used, num_swept) = for_each_bond_within_budget(
+        storage,
+        bonds,
+        &storage_key::rewards_sweep_cursor_key(),
+        REWARDS_SWEEP_GAS,
+        REWARDS_SWEEP_GAS_BUDGET,
+        |storage, bond_id| {
+            let recipient = recipients
+                .get(&bond_id.source)
+                .expect("The source of a swept bond must have a recipient");
+            let amount = sweep_bond_rewards::<S, Gov, Token>(
+                storage,
+                bond_id,
+                recipient,
+                claim_epoch,
+            )?;
+            if !amount.is_zero() {
+                events.emit(PosEvent::RewardsSweep {
+                    source: bond_id.source.clone(),
+                    validator: bond_id.validator.clone(),
+                    recipient: recipient.clone(),
+                    amount,
+                });
+            }
+            Ok(())
+        },
+    )?;
+    tracing::info!(
+        "Swept the rewards of {num_swept} out of {num_bonds} bonds in epoch \
+         {current_epoch} using {gas_used} gas",
+    );
+
+    Ok(())
+}
+
+/// Claim the rewards of a bond up to the `claim_epoch` and transfer them to
+/// the given recipient. Returns the transferred amount.
+fn sweep_bond_rewards<S, Gov, Token>(
+    storage: &mut S,
+    bond_id: &BondId,
+    recipient: &Address,
+    claim_epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let BondId { source, validator } = bond_id;
+
+    let mut reward_tokens = compute_current_rewards_from_bonds::<S, Gov>(
+        storage,
+        source,
+        validator,
+        claim_epoch,
+    )?;
+    let counter_rewards =
+        take_rewards_from_counter(storage, source, validator)?;
+    checked!(reward_tokens += counter_rewards)?;
+    write_last_reward_claim_epoch(storage, source, validator, claim_epoch)?;
+
+    if !reward_tokens.is_zero() {
+        let staking_token = staking_token_address(storage);
+        Token::transfer(
+            storage,
+            &staking_token,
+            &ADDRESS,
+            recipient,
+            reward_tokens,
+        )?;
+    }
+    Ok(reward_tokens)
+}
+
+/// Apply `f` to the given bonds, starting after the bond stored at the
+/// `cursor_key` if any, for as long as the gas of each bond fits in the
+/// protocol's gas budget. When the budget runs out, the last processed bond is
+/// stored at the `cursor_key` so that the next epoch boundary starts after it.
+/// Returns the gas used and the number of processed bonds.
+fn for_each_bond_within_budget<S>(
+    storage: &mut S,
+    mut bonds: Vec<BondId>,
+    cursor_key: &Key,
+    gas_per_bond: u64,
+    gas_budget: u64,
+    mut f: impl FnMut(&mut S, &BondId) -> Result<()>,
+) -> Result<(u64, usize)>
+where
+    S: StorageRead + StorageWrite,
+{
+    // Start after the last bond processed before running out of gas, if any
+    if let Some(cursor) = storage.read::<BondId>(cursor_key)? {
+        if let Some(ix) = bonds.iter().position(|bond_id| bond_id == &cursor) {
+            bonds.rotate_left(checked!(ix + 1)?);
+        }
+    }
+
+    let mut gas_used = 0_u64;
+    let mut num_processed = 0_usize;
+    for bond_id in &bonds {
+        let gas = checked!(gas_used + gas_per_bond)?;
+        if gas > gas_budget {
+            break;
+        }
+        gas_used = gas;
+        checked!(num_processed += 1)?;
+
+        f(storage, bond_id)?;
+    }
+
+    if num_processed < bonds.len() {
+        let last_processed =
+            num_processed.checked_sub(1).and_then(|ix| bonds.get(ix));
+        if let Some(bond_id) = last_processed {
+            storage.write(cursor_key, bond_id)?;
+        }
+    } else {
+        storage.delete(cursor_key)?;
+    }
+    Ok((gas_used, num_processed))
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
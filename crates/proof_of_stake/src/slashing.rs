@@ -17,6 +17,7 @@ use namada_systems::governance;
 
 use crate::event::PosEvent;
 use crate::lazy_map::{Collectable, NestedMap, NestedSubKey, SubKey};
+use crate::queries::bonds_and_unbonds;
 use crate::storage::{
     enqueued_slashes_handle, read_pos_params, read_validator_last_slash_epoch,
     read_validator_stake, total_bonded_handle, total_unbonded_handle,
@@ -27,11 +28,13 @@ use crate::storage::{
     write_validator_last_slash_epoch,
 };
 use crate::types::{
-    EagerRedelegatedBondsMap, ResultSlashing, Slash, SlashType, SlashedAmount,
-    Slashes, TotalRedelegatedUnbonded, ValidatorState,
+    BondId, EagerRedelegatedBondsMap, ProjectedSlashLoss, ResultSlashing,
+    Slash, SlashProjection, SlashType, SlashedAmount, Slashes,
+    TotalRedelegatedUnbonded, ValidatorState,
 };
 use crate::validator_set_update::update_validator_set;
 use crate::{
+    bond_amount, find_unbonds_and_redelegated_unbonds,
     fold_and_slash_redelegated_bonds, get_total_consensus_stake,
     iter_prefix_bytes, jail_validator, storage, storage_key, types,
     EagerRedelegatedUnbonds, Error, FoldRedelegatedBondsResult, LazyMap,
@@ -951,6 +954,141 @@ where
     Ok(enqueued)
 }
 
+/// Project the slashes enqueued to be processed after the current epoch with
+/// the rates that `process_slashes` would compute for them from the current
+/// state. The projected cubic slash rates don't account for the misbehaviors
+/// in their windows that are yet to be discovered.
+pub fn project_enqueued_slashes<S>(
+    storage: &S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> Result<BTreeMap<Address, Vec<Slash>>>
+where
+    S: StorageRead,
+{
+    let mut enqueued = BTreeMap::<Epoch, Vec<(Address, Slash)>>::new();
+    for (validator, slashes) in
+        find_all_enqueued_slashes(storage, current_epoch)?
+    {
+        for (processing_epoch, slashes) in slashes {
+            enqueued.entry(processing_epoch).or_default().extend(
+                slashes.into_iter().map(|slash| (validator.clone(), slash)),
+            );
+        }
+    }
+
+    let mut projected = BTreeMap::<Address, Vec<Slash>>::new();
+    for (processing_epoch, slashes) in enqueued {
+        let infraction_epoch = checked!(
+            processing_epoch - params.slash_processing_epoch_offset()
+        )?;
+        let cubic_slash_rate =
+            compute_cubic_slash_rate(storage, params, infraction_epoch)?;
+        for (validator, slash) in slashes {
+            let rate = cmp::min(
+                Dec::one(),
+                cmp::max(slash.r#type.get_slash_rate(params), cubic_slash_rate),
+            );
+            projected
+                .entry(validator)
+                .or_default()
+                .push(Slash { rate, ..slash });
+        }
+    }
+    Ok(projected)
+}
+
+/// Simulate the processing of the enqueued slashes to project the token losses
+/// of a bond owner's bonds and unbonds, including the redelegated ones.
+///
+/// The projected slashes are written to the given storage, so it must be
+/// discarded afterwards (e.g. a temporary write log).
+pub fn simulate_enqueued_slashes<S, Gov>(
+    storage: &mut S,
+    owner: &Address,
+    current_epoch: Epoch,
+) -> Result<SlashProjection>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let slashes = project_enqueued_slashes(storage, &params, current_epoch)?;
+
+    // The bonds are compared at the epoch by which all of the slashes are
+    // processed
+    let last_processing_epoch = slashes
+        .values()
+        .flatten()
+        .map(|slash| {
+            slash
+                .epoch
+                .unchecked_add(params.slash_processing_epoch_offset())
+        })
+        .max()
+        .unwrap_or(current_epoch);
+    let epoch = cmp::max(
+        last_processing_epoch,
+        checked!(current_epoch + params.pipeline_len)?,
+    );
+
+    let mut losses = BTreeMap::new();
+    for bond_id in
+        bonds_and_unbonds::<S, Gov>(storage, Some(owner.clone()), None)?
+            .into_keys()
+    {
+        let loss = ProjectedSlashLoss {
+            bonded: bond_amount::<S, Gov>(storage, &bond_id, epoch)?,
+            unbonded: unbonded_amount_after_slashing(
+                storage, &params, &bond_id,
+            )?,
+            ..Default::default()
+        };
+        losses.insert(bond_id, loss);
+    }
+
+    for (validator, slashes) in &slashes {
+        let validator_slashes = validator_slashes_handle(validator);
+        for slash in slashes {
+            validator_slashes.push(storage, slash.clone())?;
+        }
+    }
+
+    for (bond_id, loss) in losses.iter_mut() {
+        let bonded = bond_amount::<S, Gov>(storage, bond_id, epoch)?;
+        loss.bonded_loss = loss.bonded.checked_sub(bonded).unwrap_or_default();
+        let unbonded =
+            unbonded_amount_after_slashing(storage, &params, bond_id)?;
+        loss.unbonded_loss =
+            loss.unbonded.checked_sub(unbonded).unwrap_or_default();
+    }
+
+    Ok(SlashProjection { slashes, losses })
+}
+
+/// Compute the amount of a bond's unbonds that are not withdrawn yet after
+/// applying the validator's slashes.
+fn unbonded_amount_after_slashing<S>(
+    storage: &S,
+    params: &PosParams,
+    bond_id: &BondId,
+) -> Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let unbonds = find_unbonds_and_redelegated_unbonds(
+        storage,
+        &bond_id.source,
+        &bond_id.validator,
+        None,
+    )?;
+    let slashes = find_validator_slashes(storage, &bond_id.validator)?;
+    let result_slashing = compute_amount_after_slashing_withdraw(
+        storage, params, &unbonds, slashes,
+    )?;
+    Ok(result_slashing.sum)
+}
+
 /// Find PoS slashes applied to a validator, if any
 pub fn find_validator_slashes<S>(
     storage: &S,
@@ -1241,6 +1379,24 @@ where
         "Computing the cubic slash rate for infraction epoch \
          {infraction_epoch}."
     );
+    let sum_vp_fraction =
+        compute_infracting_stake_fraction(storage, params, infraction_epoch)?;
+    let nine = Dec::from(9_u64);
+    let cubic_rate = checked!(nine * sum_vp_fraction * sum_vp_fraction)?;
+    tracing::debug!("Cubic slash rate: {}", cubic_rate);
+    Ok(cubic_rate)
+}
+
+/// Sum the fractions of the consensus stake of the validators with slashes
+/// enqueued within a window around the given infraction epoch.
+fn compute_infracting_stake_fraction<S>(
+    storage: &S,
+    params: &PosParams,
+    infraction_epoch: Epoch,
+) -> Result<Dec>
+where
+    S: StorageRead,
+{
     let mut sum_vp_fraction = Dec::zero();
     let (start_epoch, end_epoch) =
         params.cubic_slash_epoch_window(infraction_epoch);
@@ -1279,8 +1435,43 @@ where
         sum_vp_fraction =
             checked!(sum_vp_fraction + (infracting_stake / consensus_stake))?;
     }
+    Ok(sum_vp_fraction)
+}
+
+/// Estimate the rate at which a validator would be slashed for an infraction
+/// committed in the given epoch that is only found later, on top of the
+/// slashes already enqueued around it. The rate is capped at 1.
+pub(crate) fn estimate_slash_rate<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    infraction_epoch: Epoch,
+) -> Result<Dec>
+where
+    S: StorageRead,
+{
+    let consensus_stake = Dec::try_from(get_total_consensus_stake(
+        storage,
+        infraction_epoch,
+        params,
+    )?)
+    .into_storage_result()?;
+    let validator_stake = Dec::try_from(read_validator_stake(
+        storage,
+        params,
+        validator,
+        infraction_epoch,
+    )?)
+    .into_storage_result()?;
+    let infracting_fraction =
+        compute_infracting_stake_fraction(storage, params, infraction_epoch)?;
+    let sum_vp_fraction =
+        checked!(infracting_fraction + (validator_stake / consensus_stake))?;
     let nine = Dec::from(9_u64);
     let cubic_rate = checked!(nine * sum_vp_fraction * sum_vp_fraction)?;
-    tracing::debug!("Cubic slash rate: {}", cubic_rate);
-    Ok(cubic_rate)
+    let min_rate = cmp::max(
+        params.duplicate_vote_min_slash_rate,
+        params.light_client_attack_min_slash_rate,
+    );
+    Ok(cmp::min(Dec::one(), cmp::max(min_rate, cubic_rate)))
 }
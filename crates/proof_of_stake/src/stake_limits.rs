@@ -0,0 +1,346 @@
+//! PoS validator stake limits.
+//!
+//! To keep stake from concentrating on a few validators, governance may cap a
+//! validator's share of the total consensus stake with the
+//! `max_validator_stake_share` parameter and each validator may cap the stake
+//! delegated to it. The limits are checked at the pipeline epoch of a bond or
+//! a redelegation to a validator, so they only prevent new stake from being
+//! added and never force out the stake that a validator already holds.
+//!
+//! Governance may also require the validators in the `consensus` and
+//! `below_capacity` validator sets to self-bond a minimum amount and a minimum
+//! ratio of their total stake with the `min_self_bond` and
+//! `min_self_bond_ratio` parameters. A validator cannot unbond its own stake
+//! below the minimum and the delegations and redelegations that would take
+//! its self-bond below the minimum ratio of its stake are rejected, so that
+//! nobody else can get a validator jailed by delegating to it. A validator
+//! that no longer meets the minimum at the start of an epoch (e.g. because of
+//! slashes or because governance raised it) is jailed from the pipeline
+//! epoch, until it bonds enough to be unjailed.
+
+use std::cmp;
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::chain::Epoch;
+use namada_core::token;
+use namada_systems::governance;
+
+use crate::storage::{
+    read_below_capacity_validator_set_addresses,
+    read_consensus_validator_set_addresses, read_pos_params,
+    read_validator_max_delegation, read_validator_stake,
+    validator_state_handle, write_validator_max_delegation,
+};
+use crate::types::{BondId, PipelineStake, ValidatorState};
+use crate::{
+    bond_amount, compute_total_consensus_stake, is_validator, jail_validator,
+    OwnedPosParams, PosParams, Result, StakeLimitError, StorageRead,
+    StorageWrite,
+};
+
+impl PipelineStake {
+    /// Estimate the stake after bonding the `amount` to the validator,
+    /// assuming that it ends up in the consensus set without displacing any
+    /// other validator from it.
+    pub fn with_bond(
+        self,
+        amount: token::Amount,
+        is_delegation: bool,
+    ) -> std::result::Result<Self, StakeLimitError> {
+        let stake = checked!(self.stake + amount)?;
+        let delegated = if is_delegation {
+            checked!(self.delegated + amount)?
+        } else {
+            self.delegated
+        };
+        let total_consensus_stake = if self.is_consensus {
+            checked!(self.total_consensus_stake + amount)?
+        } else {
+            checked!(self.total_consensus_stake + stake)?
+        };
+        Ok(Self {
+            stake,
+            delegated,
+            total_consensus_stake,
+            is_consensus: true,
+        })
+    }
+}
+
+/// Set or remove the maximum amount that may be delegated to a validator.
+/// Lowering it below the stake that is already delegated only prevents new
+/// delegations.
+pub fn change_max_delegation<S>(
+    storage: &mut S,
+    validator: &Address,
+    max_delegation: Option<token::Amount>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !is_validator(storage, validator)? {
+        return Err(StakeLimitError::NotAValidator(validator.clone()).into());
+    }
+    write_validator_max_delegation(storage, validator, max_delegation)
+}
+
+/// Read the stake of a validator at the pipeline epoch that is subject to the
+/// stake limits.
+pub fn read_pipeline_stake<S, Gov>(
+    storage: &S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> Result<PipelineStake>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let stake =
+        read_validator_stake(storage, &params, validator, pipeline_epoch)?;
+    let self_bond_id = BondId {
+        source: validator.clone(),
+        validator: validator.clone(),
+    };
+    let self_bond =
+        bond_amount::<S, Gov>(storage, &self_bond_id, pipeline_epoch)?;
+    let is_consensus = matches!(
+        validator_state_handle(validator).get(
+            storage,
+            pipeline_epoch,
+            &params
+        )?,
+        Some(ValidatorState::Consensus)
+    );
+    Ok(PipelineStake {
+        stake,
+        delegated: stake.checked_sub(self_bond).unwrap_or_default(),
+        total_consensus_stake: compute_total_consensus_stake(
+            storage,
+            pipeline_epoch,
+        )?,
+        is_consensus,
+    })
+}
+
+/// Check that the stake of a validator is within its limits. The limit on the
+/// delegated stake only applies to delegations and redelegations, while the
+/// stake share cap also applies to self-bonds, but only once the validator is
+/// in the consensus set.
+pub fn check_stake_limits(
+    params: &OwnedPosParams,
+    validator: &Address,
+    max_delegation: Option<token::Amount>,
+    is_delegation: bool,
+    stake: &PipelineStake,
+) -> std::result::Result<(), StakeLimitError> {
+    if let Some(max_delegation) = max_delegation {
+        if is_delegation && stake.delegated > max_delegation {
+            return Err(StakeLimitError::MaxDelegationExceeded(
+                validator.clone(),
+                stake.delegated.to_string_native(),
+                max_delegation.to_string_native(),
+            ));
+        }
+    }
+    if let Some(max_share) = params.max_validator_stake_share {
+        let max_stake = stake.total_consensus_stake.mul_floor(max_share)?;
+        if stake.is_consensus && stake.stake > max_stake {
+            return Err(StakeLimitError::MaxStakeShareExceeded(
+                validator.clone(),
+                stake.stake.to_string_native(),
+                stake.total_consensus_stake.to_string_native(),
+                max_share,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Enforce the stake limits of a validator after a bond or a redelegation to
+/// it has been applied.
+pub(crate) fn enforce_stake_limits<S, Gov>(
+    storage: &S,
+    validator: &Address,
+    is_delegation: bool,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let max_delegation = if is_delegation {
+        read_validator_max_delegation(storage, validator)?
+    } else {
+        None
+    };
+    if max_delegation.is_none() && params.max_validator_stake_share.is_none() {
+        return Ok(());
+    }
+    let stake =
+        read_pipeline_stake::<S, Gov>(storage, validator, current_epoch)?;
+    check_stake_limits(
+        &params,
+        validator,
+        max_delegation,
+        is_delegation,
+        &stake,
+    )?;
+    Ok(())
+}
+
+/// The minimum self-bond required from a validator with the given stake, if
+/// any.
+pub fn min_self_bond(
+    params: &OwnedPosParams,
+    stake: token::Amount,
+) -> std::result::Result<Option<token::Amount>, StakeLimitError> {
+    let min_from_ratio = params
+        .min_self_bond_ratio
+        .map(|ratio| stake.mul_ceil(ratio))
+        .transpose()?;
+    Ok(match (params.min_self_bond, min_from_ratio) {
+        (Some(min_amount), Some(min_from_ratio)) => {
+            Some(cmp::max(min_amount, min_from_ratio))
+        }
+        (min_amount, min_from_ratio) => min_amount.or(min_from_ratio),
+    })
+}
+
+/// Check that the self-bond of a validator meets the minimum required for its
+/// stake.
+pub fn check_min_self_bond(
+    params: &OwnedPosParams,
+    validator: &Address,
+    self_bond: token::Amount,
+    stake: token::Amount,
+) -> std::result::Result<(), StakeLimitError> {
+    if let Some(min_self_bond) = min_self_bond(params, stake)? {
+        if self_bond < min_self_bond {
+            return Err(StakeLimitError::MinSelfBondNotMet(
+                validator.clone(),
+                self_bond.to_string_native(),
+                min_self_bond.to_string_native(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check the minimum self-bond of a validator at the pipeline epoch, if it's
+/// in the `consensus` or `below_capacity` validator sets by then.
+pub(crate) fn enforce_min_self_bond<S, Gov>(
+    storage: &S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    let params = read_pos_params::<S, Gov>(storage)?;
+    if params.min_self_bond.is_none() && params.min_self_bond_ratio.is_none() {
+        return Ok(());
+    }
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let is_in_validator_sets = matches!(
+        validator_state_handle(validator).get(
+            storage,
+            pipeline_epoch,
+            &params
+        )?,
+        Some(ValidatorState::Consensus) | Some(ValidatorState::BelowCapacity)
+    );
+    if !is_in_validator_sets {
+        return Ok(());
+    }
+    let (self_bond, stake) = read_self_bond_and_stake::<S, Gov>(
+        storage,
+        &params,
+        validator,
+        pipeline_epoch,
+    )?;
+    check_min_self_bond(&params, validator, self_bond, stake)?;
+    Ok(())
+}
+
+/// Jail the validators in the `consensus` and `below_capacity` validator sets
+/// at the pipeline epoch whose self-bond no longer meets the minimum, starting
+/// from the pipeline epoch. Returns the jailed validators.
+///
+/// Invariant: This has to be applied at the start of a new epoch, after
+/// the validator sets are copied to the new pipeline epoch and the slashes
+/// for the current epoch are processed.
+pub fn jail_for_min_self_bond<S, Gov>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> Result<Vec<Address>>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+{
+    if params.min_self_bond.is_none() && params.min_self_bond_ratio.is_none() {
+        return Ok(vec![]);
+    }
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let mut validators =
+        read_consensus_validator_set_addresses(storage, pipeline_epoch)?
+            .into_iter()
+            .chain(read_below_capacity_validator_set_addresses(
+                storage,
+                pipeline_epoch,
+            )?)
+            .collect::<Vec<_>>();
+    validators.sort();
+
+    let mut jailed = vec![];
+    for validator in validators {
+        let (self_bond, stake) = read_self_bond_and_stake::<S, Gov>(
+            storage,
+            params,
+            &validator,
+            pipeline_epoch,
+        )?;
+        if let Err(err) =
+            check_min_self_bond(params, &validator, self_bond, stake)
+        {
+            tracing::info!(
+                "Jailing validator {validator} starting in epoch \
+                 {pipeline_epoch}: {err}"
+            );
+            jail_validator::<S, Gov>(
+                storage,
+                params,
+                &validator,
+                current_epoch,
+                pipeline_epoch,
+            )?;
+            jailed.push(validator);
+        }
+    }
+    Ok(jailed)
+}
+
+/// Read the self-bond and the stake of a validator in the given epoch.
+pub(crate) fn read_self_bond_and_stake<S, Gov>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> Result<(token::Amount, token::Amount)>
+where
+    S: StorageRead,
+    Gov: governance::Read<S>,
+{
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    let self_bond_id = BondId {
+        source: validator.clone(),
+        validator: validator.clone(),
+    };
+    let self_bond = bond_amount::<S, Gov>(storage, &self_bond_id, epoch)?;
+    Ok((self_bond, stake))
+}
@@ -16,17 +16,18 @@ use namada_systems::governance;
 use crate::lazy_map::NestedSubKey;
 use crate::storage_key::consensus_keys_key;
 use crate::types::{
-    BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
-    ConsensusValidatorSets, DelegationTargets, DelegatorRedelegatedBonded,
-    DelegatorRedelegatedUnbonded, EpochedSlashes, IncomingRedelegations,
-    LivenessMissedVotes, LivenessSumMissedVotes, OutgoingRedelegations,
-    ReverseOrdTokenAmount, RewardsAccumulator, RewardsProducts, Slashes,
+    AutoCompounds, BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
+    CommissionSchedules, ConsensusValidatorSets, DelegationTargets,
+    DelegatorRedelegatedBonded, DelegatorRedelegatedUnbonded, EpochedSlashes,
+    IncomingRedelegations, InstantUnbondQueue, LivenessMissedVotes,
+    LivenessSumMissedVotes, OutgoingRedelegations, ReverseOrdTokenAmount,
+    RewardRecipients, RewardsAccumulator, RewardsProducts, Slashes,
     TotalConsensusStakes, TotalDeltas, TotalRedelegatedBonded,
     TotalRedelegatedUnbonded, Unbonds, ValidatorAddresses,
-    ValidatorConsensusKeys, ValidatorDeltas, ValidatorEthColdKeys,
-    ValidatorEthHotKeys, ValidatorMetaData, ValidatorProtocolKeys,
-    ValidatorSetPositions, ValidatorState, ValidatorStates,
-    ValidatorTotalUnbonded, WeightedValidator,
+    ValidatorConsensusKeys, ValidatorDelegators, ValidatorDeltas,
+    ValidatorEthColdKeys, ValidatorEthHotKeys, ValidatorMetaData,
+    ValidatorPerformanceHistory, ValidatorProtocolKeys, ValidatorSetPositions,
+    ValidatorState, ValidatorStates, ValidatorTotalUnbonded, WeightedValidator,
 };
 use crate::{
     storage_key, LazyCollection, LazySet, MetadataError, OwnedPosParams,
@@ -146,6 +147,17 @@ pub fn unbond_handle(source: &Address, validator: &Address) -> Unbonds {
     Unbonds::open(key)
 }
 
+/// Get the storage handle to the instant unbonds of a bond that are yet to
+/// mature
+pub fn instant_unbond_handle(source: &Address, validator: &Address) -> Unbonds {
+    let bond_id = BondId {
+        source: source.clone(),
+        validator: validator.clone(),
+    };
+    let key = storage_key::instant_unbond_key(&bond_id);
+    Unbonds::open(key)
+}
+
 /// Get the storage handle to a validator's total-unbonded map
 pub fn total_unbonded_handle(validator: &Address) -> ValidatorTotalUnbonded {
     let key = storage_key::validator_total_unbonded_key(validator);
@@ -186,6 +198,14 @@ pub fn validator_rewards_products_handle(
     RewardsProducts::open(key)
 }
 
+/// Get the storage handle to a validator's performance history
+pub fn validator_performance_handle(
+    validator: &Address,
+) -> ValidatorPerformanceHistory {
+    let key = storage_key::validator_performance_key(validator);
+    ValidatorPerformanceHistory::open(key)
+}
+
 /// Get the storage handle to a validator's incoming redelegations
 pub fn validator_incoming_redelegations_handle(
     validator: &Address,
@@ -234,6 +254,15 @@ pub fn delegator_redelegated_unbonds_handle(
     DelegatorRedelegatedUnbonded::open(key)
 }
 
+/// Get the storage handle to a delegator's redelegated instant unbonds that
+/// are yet to mature
+pub fn delegator_redelegated_instant_unbonds_handle(
+    delegator: &Address,
+) -> DelegatorRedelegatedUnbonded {
+    let key = storage_key::delegator_redelegated_instant_unbonds_key(delegator);
+    DelegatorRedelegatedUnbonded::open(key)
+}
+
 /// Get the storage handle to the missed votes for liveness tracking
 pub fn liveness_missed_votes_handle() -> LivenessMissedVotes {
     let key = storage_key::liveness_missed_votes_key();
@@ -258,6 +287,37 @@ pub fn delegation_targets_handle(delegator: &Address) -> DelegationTargets {
     DelegationTargets::open(key)
 }
 
+/// Get the storage handle to the delegators of a validator
+pub fn validator_delegators_handle(validator: &Address) -> ValidatorDelegators {
+    let key = storage_key::validator_delegators_key(validator);
+    ValidatorDelegators::open(key)
+}
+
+/// Get the storage handle to the bonds whose rewards are automatically
+/// compounded
+pub fn auto_compound_handle() -> AutoCompounds {
+    let key = storage_key::auto_compound_prefix();
+    AutoCompounds::open(key)
+}
+
+/// Get the storage handle to the validators' commission schedules
+pub fn commission_schedules_handle() -> CommissionSchedules {
+    let key = storage_key::commission_schedules_prefix();
+    CommissionSchedules::open(key)
+}
+
+/// Get the storage handle to the queue of instant unbond penalties
+pub fn instant_unbond_queue_handle() -> InstantUnbondQueue {
+    let key = storage_key::instant_unbond_queue_key();
+    InstantUnbondQueue::open(key)
+}
+
+/// Get the storage handle to the recipients of redirected rewards
+pub fn reward_recipients_handle() -> RewardRecipients {
+    let key = storage_key::reward_recipients_prefix();
+    RewardRecipients::open(key)
+}
+
 // ---- Storage read + write ----
 
 /// Read owned PoS parameters
@@ -265,9 +325,14 @@ pub fn read_owned_pos_params<S>(storage: &S) -> Result<OwnedPosParams>
 where
     S: StorageRead,
 {
-    Ok(storage
+    let mut params: OwnedPosParams = storage
         .read(&storage_key::params_key())?
-        .expect("PosParams should always exist in storage after genesis"))
+        .expect("PosParams should always exist in storage after genesis");
+    // The extra parameters are missing on the chains that predate them
+    if let Some(extra) = storage.read(&storage_key::extra_params_key())? {
+        params.set_extra(extra);
+    }
+    Ok(params)
 }
 
 /// Read PoS parameters
@@ -306,7 +371,9 @@ where
     S: StorageRead + StorageWrite,
 {
     let key = storage_key::params_key();
-    storage.write(&key, params)
+    storage.write(&key, params)?;
+    let key = storage_key::extra_params_key();
+    storage.write(&key, params.extra())
 }
 
 /// Get the validator address given the raw hash of the Tendermint consensus key
@@ -387,6 +454,60 @@ where
     storage.write(&key, epoch)
 }
 
+/// Read whether liquid staking of the delegations to a validator is enabled.
+pub fn read_validator_liquid_staking<S>(
+    storage: &S,
+    validator: &Address,
+) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let key = storage_key::validator_liquid_staking_key(validator);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Write whether liquid staking of the delegations to a validator is enabled.
+pub fn write_validator_liquid_staking<S>(
+    storage: &mut S,
+    validator: &Address,
+    enabled: bool,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::validator_liquid_staking_key(validator);
+    storage.write(&key, enabled)
+}
+
+/// Read the maximum amount that may be delegated to a validator, if any.
+pub fn read_validator_max_delegation<S>(
+    storage: &S,
+    validator: &Address,
+) -> Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    let key = storage_key::validator_max_delegation_key(validator);
+    storage.read(&key)
+}
+
+/// Write the maximum amount that may be delegated to a validator. The limit is
+/// removed when `None`.
+pub fn write_validator_max_delegation<S>(
+    storage: &mut S,
+    validator: &Address,
+    max_delegation: Option<token::Amount>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::validator_max_delegation_key(validator);
+    match max_delegation {
+        Some(amount) => storage.write(&key, amount),
+        None => storage.delete(&key),
+    }
+}
+
 /// Read last block proposer address.
 pub fn read_last_block_proposer_address<S>(
     storage: &S,
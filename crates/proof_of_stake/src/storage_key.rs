@@ -8,6 +8,7 @@ use crate::types::BondId;
 use crate::{epoched, lazy_map, lazy_vec, Epoch, Key, KeySeg};
 
 const PARAMS_STORAGE_KEY: &str = "params";
+const EXTRA_PARAMS_STORAGE_KEY: &str = "extra_params";
 const VALIDATOR_ADDRESSES_KEY: &str = "validator_addresses";
 #[allow(missing_docs)]
 pub const VALIDATOR_STORAGE_PREFIX: &str = "validator";
@@ -21,11 +22,15 @@ const VALIDATOR_COMMISSION_RATE_STORAGE_KEY: &str = "commission_rate";
 const VALIDATOR_MAX_COMMISSION_CHANGE_STORAGE_KEY: &str =
     "max_commission_rate_change";
 const VALIDATOR_REWARDS_PRODUCT_KEY: &str = "validator_rewards_product";
+const VALIDATOR_PERFORMANCE_KEY: &str = "performance";
 const VALIDATOR_LAST_KNOWN_PRODUCT_EPOCH_KEY: &str =
     "last_known_rewards_product_epoch";
 const SLASHES_PREFIX: &str = "slash";
 const ENQUEUED_SLASHES_KEY: &str = "enqueued_slashes";
 const VALIDATOR_LAST_SLASH_EPOCH: &str = "last_slash_epoch";
+const VALIDATOR_LIQUID_STAKING_KEY: &str = "liquid_staking";
+const VALIDATOR_MAX_DELEGATION_KEY: &str = "max_delegation";
+const VALIDATOR_DELEGATORS_KEY: &str = "delegators";
 const BOND_STORAGE_KEY: &str = "bond";
 const UNBOND_STORAGE_KEY: &str = "unbond";
 const VALIDATOR_TOTAL_BONDED_STORAGE_KEY: &str = "total_bonded";
@@ -62,6 +67,16 @@ const LAST_STAKED_RATIO_KEY: &str = "last_staked_ratio";
 const LAST_POS_INFLATION_AMOUNT_KEY: &str = "last_inflation_amount";
 const TOTAL_ACTIVE_DELTAS_KEY: &str = "total_active_deltas";
 const DELEGATION_TARGETS_PREFIX: &str = "delegation_targets";
+const VALIDATOR_DELEGATORS_INDEXED_KEY: &str = "validator_delegators_indexed";
+const AUTO_COMPOUND_PREFIX: &str = "auto_compound";
+const AUTO_COMPOUND_CURSOR_KEY: &str = "auto_compound_cursor";
+const COMMISSION_SCHEDULES_PREFIX: &str = "commission_schedules";
+const INSTANT_UNBOND_STORAGE_KEY: &str = "instant_unbond";
+const DELEGATOR_REDELEGATED_INSTANT_UNBONDS_KEY: &str =
+    "delegator_redelegated_instant_unbonds";
+const INSTANT_UNBOND_QUEUE_KEY: &str = "instant_unbond_queue";
+const REWARD_RECIPIENTS_PREFIX: &str = "reward_recipients";
+const REWARDS_SWEEP_CURSOR_KEY: &str = "rewards_sweep_cursor";
 
 /// Is the given key a PoS storage key?
 pub fn is_pos_key(key: &Key) -> bool {
@@ -78,9 +93,16 @@ pub fn params_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the PoS parameters stored apart from the others.
+pub fn extra_params_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&EXTRA_PARAMS_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Is storage key for PoS parameters?
 pub fn is_params_key(key: &Key) -> bool {
-    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == PARAMS_STORAGE_KEY)
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && (key == PARAMS_STORAGE_KEY || key == EXTRA_PARAMS_STORAGE_KEY))
 }
 
 /// Storage key prefix for validator data.
@@ -311,6 +333,13 @@ pub fn is_validator_rewards_product_key(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Storage key for validator's performance history.
+pub fn validator_performance_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_PERFORMANCE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage prefix for rewards counter.
 pub fn rewards_counter_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -639,6 +668,62 @@ pub fn validator_last_slash_key(validator: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the flag enabling liquid staking of the delegations to a
+/// validator.
+pub fn validator_liquid_staking_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_LIQUID_STAKING_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the delegators of a validator.
+pub fn validator_delegators_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_DELEGATORS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the maximum amount that may be delegated to a validator.
+pub fn validator_max_delegation_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_MAX_DELEGATION_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the maximum amount that may be delegated to a
+/// validator?
+pub fn is_validator_max_delegation_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::AddressSeg(validator), DbKeySeg::StringSeg(key)]
+            if addr == &ADDRESS
+                && prefix == VALIDATOR_STORAGE_PREFIX
+                && key == VALIDATOR_MAX_DELEGATION_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
+/// Is storage key for the flag enabling liquid staking of the delegations to a
+/// validator?
+pub fn is_validator_liquid_staking_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::StringSeg(key),
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_STORAGE_PREFIX
+            && key == VALIDATOR_LIQUID_STAKING_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Storage key prefix for all bonds.
 pub fn bonds_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -1122,3 +1207,137 @@ pub fn is_delegation_targets_key(key: &Key) -> bool {
         false
     }
 }
+
+/// Storage prefix for the bonds whose rewards are automatically compounded.
+pub fn auto_compound_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_COMPOUND_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a bond whose rewards are automatically compounded?
+/// Returns the bond ID if so.
+pub fn is_auto_compound_key(key: &Key) -> Option<BondId> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::AddressSeg(source),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS
+            && prefix == AUTO_COMPOUND_PREFIX
+            && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(BondId {
+                source: source.clone(),
+                validator: validator.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the last bond whose rewards got compounded before the
+/// protocol ran out of its auto-compounding gas budget.
+pub fn auto_compound_cursor_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_COMPOUND_CURSOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the flag set once the delegators of the validators are
+/// indexed.
+pub fn validator_delegators_indexed_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&VALIDATOR_DELEGATORS_INDEXED_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage prefix for the validators' commission schedules.
+pub fn commission_schedules_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&COMMISSION_SCHEDULES_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a validator's commission schedule? Returns the validator
+/// if so.
+pub fn is_commission_schedule_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::AddressSeg(validator)]
+            if addr == &ADDRESS
+                && prefix == COMMISSION_SCHEDULES_PREFIX
+                && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key prefix for all instant unbonds.
+pub fn instant_unbonds_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&INSTANT_UNBOND_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the instant unbonds with the given ID (source and
+/// validator) that are yet to mature.
+pub fn instant_unbond_key(bond_id: &BondId) -> Key {
+    instant_unbonds_prefix()
+        .push(&bond_id.source.to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&bond_id.validator.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for a particular delegator's redelegated instant unbonds that
+/// are yet to mature.
+pub fn delegator_redelegated_instant_unbonds_key(delegator: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATOR_REDELEGATED_INSTANT_UNBONDS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&delegator.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the queue of the instant unbond penalties held until the
+/// unbonds mature.
+pub fn instant_unbond_queue_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&INSTANT_UNBOND_QUEUE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage prefix for the recipients of the rewards of bond sources that
+/// redirected them.
+pub fn reward_recipients_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&REWARD_RECIPIENTS_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the recipient of a bond source's rewards? Returns the
+/// source if so.
+pub fn is_reward_recipient_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::AddressSeg(source)]
+            if addr == &ADDRESS
+                && prefix == REWARD_RECIPIENTS_PREFIX
+                && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(source)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the last bond whose rewards got swept to its source's
+/// reward recipient before the protocol ran out of its sweeping gas budget.
+pub fn rewards_sweep_cursor_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&REWARDS_SWEEP_CURSOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
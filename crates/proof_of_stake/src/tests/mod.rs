@@ -6,7 +6,10 @@ use namada_state::storage::Result;
 use namada_state::{Epoch, StorageRead, StorageWrite};
 use namada_trans_token as token;
 
-use crate::types::{BondId, BondsAndUnbondsDetails, ResultSlashing, SlashType};
+use crate::types::{
+    BondId, BondsAndUnbondsDetails, InstantUnbondQuote, ResultSlashing,
+    SlashType,
+};
 use crate::{BecomeValidator, GenesisValidator, OwnedPosParams, PosParams};
 
 mod helpers;
@@ -99,7 +102,7 @@ pub fn unbond_tokens<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    crate::unbond_tokens::<S, GovStore<S>>(
+    crate::unbond_tokens::<S, GovStore<S>, token::Store<_>>(
         storage,
         source,
         validator,
@@ -141,6 +144,28 @@ where
     )
 }
 
+/// DI indirection
+pub fn change_validator_keys<S>(
+    storage: &mut S,
+    validator: &Address,
+    protocol_key: Option<&common::PublicKey>,
+    eth_cold_key: Option<&common::PublicKey>,
+    eth_hot_key: Option<&common::PublicKey>,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    crate::change_validator_keys::<S, GovStore<S>>(
+        storage,
+        validator,
+        protocol_key,
+        eth_cold_key,
+        eth_hot_key,
+        current_epoch,
+    )
+}
+
 /// DI indirection
 pub fn process_slashes<S>(
     storage: &mut S,
@@ -198,6 +223,55 @@ where
     )
 }
 
+/// DI indirection
+pub fn instant_unbond_tokens<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> Result<InstantUnbondQuote>
+where
+    S: StorageRead + StorageWrite,
+{
+    crate::instant_unbond::instant_unbond_tokens::<
+        S,
+        GovStore<S>,
+        token::Store<_>,
+    >(storage, source, validator, amount, current_epoch)
+}
+
+/// DI indirection
+pub fn settle_instant_unbonds<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> Result<token::Amount>
+where
+    S: StorageRead + StorageWrite,
+{
+    crate::instant_unbond::settle_instant_unbonds::<
+        S,
+        GovStore<S>,
+        token::Store<_>,
+    >(storage, current_epoch)
+}
+
+/// DI indirection
+pub fn jail_for_min_self_bond<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> Result<Vec<Address>>
+where
+    S: StorageRead + StorageWrite,
+{
+    crate::stake_limits::jail_for_min_self_bond::<S, GovStore<S>>(
+        storage,
+        params,
+        current_epoch,
+    )
+}
+
 /// DI indirection
 pub fn redelegate_tokens<S>(
     storage: &mut S,
@@ -11,11 +11,13 @@ use namada_core::collections::HashSet;
 use namada_core::dec::Dec;
 use namada_core::key::testing::{common_sk_from_simple_seed, gen_keypair};
 use namada_core::key::RefTo;
+use namada_core::masp::PaymentAddress;
 use namada_core::{address, key};
+use namada_events::Event;
 use namada_state::testing::TestState;
 use namada_trans_token::{
     self as token, credit_tokens, get_effective_total_native_supply,
-    read_balance,
+    read_balance, read_total_supply,
 };
 use proptest::prelude::*;
 use proptest::test_runner::Config;
@@ -23,21 +25,40 @@ use proptest::test_runner::Config;
 // `tracing` logs from tests
 use test_log::test;
 
+use crate::commission::{
+    apply_commission_schedules, change_commission_schedule,
+};
 use crate::epoched::EpochOffset;
 use crate::lazy_map::Collectable;
+use crate::liquid_staking::{
+    bond_shares_worth, change_liquid_staking, holds_only_shares,
+    liquid_staking_token, query_share_price,
+};
 use crate::parameters::testing::arb_pos_params;
 use crate::parameters::OwnedPosParams;
+use crate::performance::{
+    read_validator_performance_history, record_block_performance,
+    PERFORMANCE_HISTORY_LEN,
+};
 use crate::queries::find_delegation_validators;
 use crate::rewards::{
-    log_block_rewards_aux, update_rewards_products_and_mint_inflation,
+    compound_rewards, log_block_rewards_aux, read_rewards_counter,
+    sweep_redirected_rewards, update_rewards_products_and_mint_inflation,
     PosRewardsCalculator,
 };
+use crate::stake_limits::{
+    change_max_delegation, check_stake_limits, read_pipeline_stake,
+};
 use crate::storage::{
-    delegation_targets_handle, get_consensus_key_set,
-    liveness_sum_missed_votes_handle,
-    read_consensus_validator_set_addresses_with_stake, read_total_stake,
-    read_validator_deltas_value, rewards_accumulator_handle,
-    total_deltas_handle,
+    commission_schedules_handle, delegation_targets_handle,
+    get_consensus_key_set, instant_unbond_handle, instant_unbond_queue_handle,
+    liveness_sum_missed_votes_handle, read_consensus_validator_set_addresses,
+    read_consensus_validator_set_addresses_with_stake, read_pos_params,
+    read_total_stake, read_validator_deltas_value,
+    read_validator_max_delegation, reward_recipients_handle,
+    rewards_accumulator_handle, total_deltas_handle,
+    validator_commission_rate_handle, validator_delegators_handle,
+    write_pos_params,
 };
 use crate::tests::helpers::{
     advance_epoch, arb_genesis_validators, arb_params_and_genesis_validators,
@@ -45,22 +66,28 @@ use crate::tests::helpers::{
 };
 use crate::tests::{
     bond_amount, bond_tokens, bonds_and_unbonds, change_consensus_key,
-    find_delegations, process_slashes,
-    read_below_threshold_validator_set_addresses, redelegate_tokens, slash,
-    test_init_genesis, unbond_tokens, unjail_validator, withdraw_tokens,
-    GovStore,
+    change_validator_keys, find_delegations, instant_unbond_tokens,
+    jail_for_min_self_bond, process_slashes,
+    read_below_threshold_validator_set_addresses, redelegate_tokens,
+    settle_instant_unbonds, slash, test_init_genesis, unbond_tokens,
+    unjail_validator, withdraw_tokens, GovStore,
 };
 use crate::types::{
     into_tm_voting_power, BondDetails, BondId, BondsAndUnbondsDetails,
-    GenesisValidator, SlashType, UnbondDetails, ValidatorState, VoteInfo,
-    WeightedValidator,
+    CommissionSchedule, GenesisValidator, InstantUnbondPenalty,
+    InstantUnbondQuote, PipelineStake, RewardRecipient, SlashType,
+    UnbondDetails, ValidatorState, VoteInfo, WeightedValidator,
 };
 use crate::{
-    below_capacity_validator_set_handle, bond_handle,
-    consensus_validator_set_handle, is_delegator, is_validator,
-    jail_for_liveness, read_validator_stake, staking_token_address,
-    unbond_handle, validator_consensus_key_handle,
-    validator_set_positions_handle, validator_state_handle, StorageRead,
+    below_capacity_validator_set_handle, bond_handle, change_auto_compound,
+    change_reward_recipient, change_validator_commission_rate,
+    claim_reward_tokens, consensus_validator_set_handle, event, is_delegator,
+    is_validator, jail_for_liveness, query_reward_tokens, read_validator_stake,
+    staking_token_address, unbond_handle, validator_consensus_key_handle,
+    validator_eth_cold_key_handle, validator_eth_hot_key_handle,
+    validator_protocol_key_handle, validator_set_positions_handle,
+    validator_state_handle, InstantUnbondError, RewardRecipientError,
+    StakeLimitError, StorageRead, ValidatorKeysChangeError,
 };
 
 proptest! {
@@ -2210,3 +2237,1498 @@ fn test_delegation_targets() {
     assert!(de_2.prev_ranges.is_empty());
     assert_eq!(de_2.last_range.1, None);
 }
+
+#[test]
+fn test_liquid_staking_shares() {
+    let stakes = vec![token::Amount::native_whole(100)];
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators = get_genesis_validators(1, stakes);
+    let validator = genesis_validators[0].address.clone();
+
+    let delegator1 = address::testing::gen_implicit_address();
+    let delegator2 = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    for delegator in [&delegator1, &delegator2] {
+        credit_tokens(
+            &mut storage,
+            &staking_token,
+            delegator,
+            token::Amount::native_whole(20),
+        )
+        .unwrap();
+    }
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // Only a validator can enable liquid staking
+    let res = change_liquid_staking::<_, token::Store<_>>(
+        &mut storage,
+        &delegator1,
+        true,
+    );
+    assert!(res.is_err());
+    change_liquid_staking::<_, token::Store<_>>(&mut storage, &validator, true)
+        .unwrap();
+
+    let lst = liquid_staking_token(&validator);
+    let pool_bond_id = BondId {
+        source: lst.clone(),
+        validator: validator.clone(),
+    };
+
+    // Delegations are pooled and the delegators receive shares
+    bond_tokens(
+        &mut storage,
+        Some(&delegator1),
+        &validator,
+        token::Amount::native_whole(10),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    // A bond is worth as many shares as the bonded tokens at the share price
+    let worth = bond_shares_worth::<_, GovStore<_>>(
+        &storage,
+        &validator,
+        token::Amount::native_whole(5),
+        token::Amount::native_whole(10),
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(worth, token::Amount::native_whole(5));
+    bond_tokens(
+        &mut storage,
+        Some(&delegator2),
+        &validator,
+        token::Amount::native_whole(5),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(
+        bond_amount(&storage, &pool_bond_id, pipeline_epoch).unwrap(),
+        token::Amount::native_whole(15)
+    );
+    let delegator1_bond_id = BondId {
+        source: delegator1.clone(),
+        validator: validator.clone(),
+    };
+    assert!(bond_amount(&storage, &delegator1_bond_id, pipeline_epoch)
+        .unwrap()
+        .is_zero());
+    assert_eq!(
+        read_balance(&storage, &lst, &delegator1).unwrap(),
+        token::Amount::native_whole(10)
+    );
+    assert_eq!(
+        read_balance(&storage, &lst, &delegator2).unwrap(),
+        token::Amount::native_whole(5)
+    );
+    // The unbonds of the delegators burn their shares
+    assert!(holds_only_shares::<_, GovStore<_>>(
+        &storage,
+        &delegator1,
+        &validator,
+        token::Amount::native_whole(10),
+        current_epoch,
+    )
+    .unwrap());
+    let price = query_share_price::<_, GovStore<_>, token::Store<_>>(
+        &storage,
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(price, Some(Dec::one()));
+
+    // Burning more shares than held must fail
+    let res = unbond_tokens(
+        &mut storage,
+        Some(&delegator2),
+        &validator,
+        token::Amount::native_whole(6),
+        current_epoch,
+        false,
+    );
+    assert!(res.is_err());
+
+    // Burn some shares and check that the unbond is owned by the delegator
+    current_epoch = advance_epoch(&mut storage, &params);
+    unbond_tokens(
+        &mut storage,
+        Some(&delegator1),
+        &validator,
+        token::Amount::native_whole(4),
+        current_epoch,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        read_balance(&storage, &lst, &delegator1).unwrap(),
+        token::Amount::native_whole(6)
+    );
+    assert!(unbond_handle(&lst, &validator).is_empty(&storage).unwrap());
+    let unbonds = unbond_handle(&delegator1, &validator)
+        .collect_map(&storage)
+        .unwrap();
+    let unbonded: token::Amount = unbonds
+        .values()
+        .flat_map(|unbonds| unbonds.values())
+        .copied()
+        .sum();
+    assert_eq!(unbonded, token::Amount::native_whole(4));
+
+    // The unbonded tokens can be withdrawn as usual
+    let withdrawable_epoch = current_epoch + params.withdrawable_epoch_offset();
+    while current_epoch < withdrawable_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    let withdrawn = withdraw_tokens(
+        &mut storage,
+        Some(&delegator1),
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(withdrawn, token::Amount::native_whole(4));
+    assert_eq!(
+        read_balance(&storage, &staking_token, &delegator1).unwrap(),
+        token::Amount::native_whole(14)
+    );
+}
+
+#[test]
+fn test_auto_compound_rewards() {
+    let stakes = vec![token::Amount::native_whole(100)];
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators = get_genesis_validators(1, stakes);
+    let validator = genesis_validators[0].address.clone();
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(20),
+    )
+    .unwrap();
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // Only a bond with a validator can be opted in
+    let res =
+        change_auto_compound(&mut storage, Some(&delegator), &delegator, true);
+    assert!(res.is_err());
+    change_auto_compound(&mut storage, Some(&delegator), &validator, true)
+        .unwrap();
+
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        token::Amount::native_whole(10),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    let bond_id = BondId {
+        source: delegator.clone(),
+        validator: validator.clone(),
+    };
+
+    // Distribute inflation for an epoch in which the delegation is active
+    let rewards_epoch = current_epoch + params.pipeline_len;
+    while current_epoch <= rewards_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    rewards_accumulator_handle()
+        .insert(&mut storage, validator.clone(), Dec::one())
+        .unwrap();
+    let total_native_tokens =
+        get_effective_total_native_supply(&storage).unwrap();
+    update_rewards_products_and_mint_inflation::<_, token::Store<_>>(
+        &mut storage,
+        &params,
+        rewards_epoch,
+        1,
+        token::Amount::native_whole(1_000),
+        &staking_token,
+        total_native_tokens,
+    )
+    .unwrap();
+
+    // The rewards are compounded at the start of the next epoch
+    current_epoch = advance_epoch(&mut storage, &params);
+    let claim_epoch = current_epoch.prev().unwrap();
+    let rewards = query_reward_tokens::<_, GovStore<_>>(
+        &storage,
+        Some(&delegator),
+        &validator,
+        claim_epoch,
+    )
+    .unwrap();
+    assert!(!rewards.is_zero());
+
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let bond_pre = bond_amount(&storage, &bond_id, pipeline_epoch).unwrap();
+    let mut events: Vec<Event> = vec![];
+    compound_rewards::<_, GovStore<_>>(
+        &mut storage,
+        &mut events,
+        current_epoch,
+    )
+    .unwrap();
+    let bond_post = bond_amount(&storage, &bond_id, pipeline_epoch).unwrap();
+    assert_eq!(bond_post, bond_pre + rewards);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind(), &event::types::AUTO_COMPOUND);
+
+    // The compounded rewards cannot be claimed again
+    let rewards = query_reward_tokens::<_, GovStore<_>>(
+        &storage,
+        Some(&delegator),
+        &validator,
+        claim_epoch,
+    )
+    .unwrap();
+    assert!(rewards.is_zero());
+
+    // Nothing is compounded once opted out
+    change_auto_compound(&mut storage, Some(&delegator), &validator, false)
+        .unwrap();
+    current_epoch = advance_epoch(&mut storage, &params);
+    let mut events: Vec<Event> = vec![];
+    compound_rewards::<_, GovStore<_>>(
+        &mut storage,
+        &mut events,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn test_commission_schedule() {
+    let stakes = vec![token::Amount::native_whole(100)];
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators = get_genesis_validators(1, stakes);
+    let validator = genesis_validators[0].address.clone();
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(20),
+    )
+    .unwrap();
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        token::Amount::native_whole(10),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+
+    // Only the delegations are indexed by validator
+    let delegators = validator_delegators_handle(&validator);
+    assert!(delegators.contains(&storage, &delegator).unwrap());
+    assert!(!delegators.contains(&storage, &validator).unwrap());
+
+    let initial_rate = Dec::new(5, 2).unwrap();
+    let tier_rate = Dec::new(1, 2).unwrap();
+    let scheduled_rate = Dec::new(8, 2).unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let tiers = BTreeMap::from([(token::Amount::native_whole(10), tier_rate)]);
+
+    // Changes must be scheduled after the pipeline epoch
+    let schedule = CommissionSchedule {
+        changes: BTreeMap::from([(pipeline_epoch, scheduled_rate)]),
+        ..Default::default()
+    };
+    let res = change_commission_schedule::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        schedule,
+        current_epoch,
+    );
+    assert!(res.is_err());
+
+    // Rates must be valid
+    let schedule = CommissionSchedule {
+        tiers: BTreeMap::from([(token::Amount::zero(), Dec::two())]),
+        ..Default::default()
+    };
+    let res = change_commission_schedule::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        schedule,
+        current_epoch,
+    );
+    assert!(res.is_err());
+
+    let schedule = CommissionSchedule {
+        changes: BTreeMap::from([(pipeline_epoch.next(), scheduled_rate)]),
+        tiers: tiers.clone(),
+        no_raise_until: None,
+    };
+    change_commission_schedule::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        schedule,
+        current_epoch,
+    )
+    .unwrap();
+
+    // The scheduled change is applied in steps of the max change per epoch
+    let commission_handle = validator_commission_rate_handle(&validator);
+    for expected_rate in [6, 7, 8] {
+        current_epoch = advance_epoch(&mut storage, &params);
+        apply_commission_schedules::<_, GovStore<_>>(
+            &mut storage,
+            current_epoch,
+        )
+        .unwrap();
+        let rate = commission_handle
+            .get(&storage, current_epoch + params.pipeline_len, &params)
+            .unwrap();
+        assert_eq!(rate, Some(Dec::new(expected_rate, 2).unwrap()));
+    }
+    let schedule = commission_schedules_handle()
+        .get(&storage, &validator)
+        .unwrap()
+        .unwrap();
+    assert!(schedule.changes.is_empty());
+    assert_eq!(schedule.tiers, tiers);
+
+    // Delegations in a tier get a rebate of the commission in excess of the
+    // tier's rate
+    let last_epoch = current_epoch.prev().unwrap();
+    let inflation = token::Amount::native_whole(1_000);
+    rewards_accumulator_handle()
+        .insert(&mut storage, validator.clone(), Dec::one())
+        .unwrap();
+    let total_native_tokens =
+        get_effective_total_native_supply(&storage).unwrap();
+    update_rewards_products_and_mint_inflation::<_, token::Store<_>>(
+        &mut storage,
+        &params,
+        last_epoch,
+        1,
+        inflation,
+        &staking_token,
+        total_native_tokens,
+    )
+    .unwrap();
+
+    let stake = read_validator_stake(&storage, &params, &validator, last_epoch)
+        .unwrap();
+    let tokens_per_stake =
+        Dec::try_from(inflation).unwrap() / Dec::try_from(stake).unwrap();
+    let expected_rebate = token::Amount::native_whole(10)
+        .mul_floor((initial_rate - tier_rate) * tokens_per_stake)
+        .unwrap();
+    let rebate =
+        read_rewards_counter(&storage, &delegator, &validator).unwrap();
+    assert!(!rebate.is_zero());
+    assert_eq!(rebate, expected_rebate);
+    let commissions =
+        read_rewards_counter(&storage, &validator, &validator).unwrap();
+    assert_eq!(
+        commissions + rebate,
+        inflation.mul_floor(initial_rate).unwrap()
+    );
+
+    // Commit not to raise the commission rate
+    let until = current_epoch + 10;
+    let schedule = CommissionSchedule {
+        tiers: tiers.clone(),
+        no_raise_until: Some(until),
+        ..Default::default()
+    };
+    change_commission_schedule::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        schedule,
+        current_epoch,
+    )
+    .unwrap();
+
+    // The commission rate can be lowered, but not raised
+    change_validator_commission_rate::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        Dec::new(7, 2).unwrap(),
+        current_epoch,
+    )
+    .unwrap();
+    let res = change_validator_commission_rate::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        scheduled_rate,
+        current_epoch,
+    );
+    assert!(res.is_err());
+
+    // The commitment cannot be shortened
+    let schedule = CommissionSchedule {
+        tiers: tiers.clone(),
+        no_raise_until: Some(until.prev().unwrap()),
+        ..Default::default()
+    };
+    let res = change_commission_schedule::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        schedule,
+        current_epoch,
+    );
+    assert!(res.is_err());
+
+    // Nor can the tier rates be raised
+    let schedule = CommissionSchedule {
+        tiers: BTreeMap::from([(
+            token::Amount::native_whole(10),
+            Dec::new(2, 2).unwrap(),
+        )]),
+        no_raise_until: Some(until),
+        ..Default::default()
+    };
+    let res = change_commission_schedule::<_, GovStore<_>>(
+        &mut storage,
+        &validator,
+        schedule,
+        current_epoch,
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_validator_performance_history() {
+    let stakes = vec![
+        token::Amount::native_whole(300),
+        token::Amount::native_whole(100),
+    ];
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators = get_genesis_validators(2, stakes.clone());
+    let voter = genesis_validators[0].address.clone();
+    let non_voter = genesis_validators[1].address.clone();
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // Only one of the validators proposes and votes for the blocks
+    let votes = vec![VoteInfo {
+        validator_address: voter.clone(),
+        validator_vp: into_tm_voting_power(params.tm_votes_per_token, stakes[0])
+            as u64,
+    }];
+    let num_blocks = 2;
+    for _ in 0..num_blocks {
+        log_block_rewards_aux::<_, GovStore<_>>(
+            &mut storage,
+            current_epoch,
+            &voter,
+            votes.clone(),
+        )
+        .unwrap();
+    }
+    let staking_token = staking_token_address(&storage);
+    let total_native_tokens =
+        get_effective_total_native_supply(&storage).unwrap();
+    update_rewards_products_and_mint_inflation::<_, token::Store<_>>(
+        &mut storage,
+        &params,
+        current_epoch,
+        num_blocks,
+        token::Amount::native_whole(1_000),
+        &staking_token,
+        total_native_tokens,
+    )
+    .unwrap();
+
+    let history =
+        read_validator_performance_history(&storage, &voter, None).unwrap();
+    let performance = history[&current_epoch];
+    assert_eq!(performance.expected_votes, num_blocks);
+    assert_eq!(performance.missed_votes, 0);
+    assert_eq!(performance.proposed_blocks, num_blocks);
+    assert_eq!(performance.uptime(), Some(Dec::one()));
+    assert!(!performance.rewards.is_zero());
+    let commission_rate = validator_commission_rate_handle(&voter)
+        .get(&storage, current_epoch, &params)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        performance.commissions,
+        performance.rewards.mul_floor(commission_rate).unwrap()
+    );
+
+    let history =
+        read_validator_performance_history(&storage, &non_voter, None).unwrap();
+    let performance = history[&current_epoch];
+    assert_eq!(performance.expected_votes, num_blocks);
+    assert_eq!(performance.missed_votes, num_blocks);
+    assert_eq!(performance.proposed_blocks, 0);
+    assert_eq!(performance.uptime(), Some(Dec::zero()));
+
+    // Only the most recent epochs are kept in the history
+    let later_epoch = current_epoch + PERFORMANCE_HISTORY_LEN;
+    record_block_performance(&mut storage, &voter, later_epoch, false, true)
+        .unwrap();
+    let history =
+        read_validator_performance_history(&storage, &voter, None).unwrap();
+    assert_eq!(
+        history.keys().copied().collect::<Vec<_>>(),
+        vec![later_epoch]
+    );
+}
+
+#[test]
+fn test_stake_limits() {
+    let stakes = vec![token::Amount::native_whole(100); 3];
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams {
+        max_validator_stake_share: Some(Dec::new(4, 1).unwrap()),
+        ..Default::default()
+    };
+
+    let genesis_validators = get_genesis_validators(3, stakes);
+    let validator1 = genesis_validators[0].address.clone();
+    let validator2 = genesis_validators[1].address.clone();
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    for owner in [&delegator, &validator1, &validator2] {
+        credit_tokens(
+            &mut storage,
+            &staking_token,
+            owner,
+            token::Amount::native_whole(100),
+        )
+        .unwrap();
+    }
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // A delegation within the stake share cap
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        token::Amount::native_whole(20),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    let stake = read_pipeline_stake::<_, GovStore<_>>(
+        &storage,
+        &validator1,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(
+        stake,
+        PipelineStake {
+            stake: token::Amount::native_whole(120),
+            delegated: token::Amount::native_whole(20),
+            total_consensus_stake: token::Amount::native_whole(320),
+            is_consensus: true,
+        }
+    );
+    storage.commit_block().unwrap();
+
+    // Bonds that would bring the validator above the cap are rejected, be
+    // they delegations or self-bonds, and the estimate used by clients agrees
+    let expected = stake
+        .with_bond(token::Amount::native_whole(30), true)
+        .unwrap();
+    assert_matches!(
+        check_stake_limits(&params, &validator1, None, true, &expected),
+        Err(StakeLimitError::MaxStakeShareExceeded(..))
+    );
+    for source in [Some(&delegator), None] {
+        let mut temp_state = storage.with_temp_write_log();
+        let err = bond_tokens(
+            &mut temp_state,
+            source,
+            &validator1,
+            token::Amount::native_whole(30),
+            current_epoch,
+            None,
+        )
+        .unwrap_err();
+        assert_matches!(
+            *err.downcast::<StakeLimitError>().unwrap(),
+            StakeLimitError::MaxStakeShareExceeded(..)
+        );
+    }
+
+    // Only a validator can set its max delegation
+    assert!(change_max_delegation(
+        &mut storage,
+        &delegator,
+        Some(token::Amount::native_whole(10))
+    )
+    .is_err());
+    change_max_delegation(
+        &mut storage,
+        &validator2,
+        Some(token::Amount::native_whole(10)),
+    )
+    .unwrap();
+    assert_eq!(
+        read_validator_max_delegation(&storage, &validator2).unwrap(),
+        Some(token::Amount::native_whole(10))
+    );
+
+    // Delegations are limited by the max delegation, but self-bonds are not
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator2,
+        token::Amount::native_whole(10),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    bond_tokens(
+        &mut storage,
+        None,
+        &validator2,
+        token::Amount::native_whole(5),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+    {
+        let mut temp_state = storage.with_temp_write_log();
+        let err = bond_tokens(
+            &mut temp_state,
+            Some(&delegator),
+            &validator2,
+            token::Amount::native_whole(1),
+            current_epoch,
+            None,
+        )
+        .unwrap_err();
+        assert_matches!(
+            *err.downcast::<StakeLimitError>().unwrap(),
+            StakeLimitError::MaxDelegationExceeded(..)
+        );
+    }
+
+    // Redelegations are limited too
+    {
+        let mut temp_state = storage.with_temp_write_log();
+        let err = redelegate_tokens(
+            &mut temp_state,
+            &delegator,
+            &validator1,
+            &validator2,
+            current_epoch,
+            token::Amount::native_whole(5),
+        )
+        .unwrap_err();
+        assert_matches!(
+            *err.downcast::<StakeLimitError>().unwrap(),
+            StakeLimitError::MaxDelegationExceeded(..)
+        );
+    }
+
+    // Once the max delegation is removed, the redelegation goes through
+    change_max_delegation(&mut storage, &validator2, None).unwrap();
+    assert!(read_validator_max_delegation(&storage, &validator2)
+        .unwrap()
+        .is_none());
+    redelegate_tokens(
+        &mut storage,
+        &delegator,
+        &validator1,
+        &validator2,
+        current_epoch,
+        token::Amount::native_whole(5),
+    )
+    .unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(
+        read_validator_stake(&storage, &params, &validator2, pipeline_epoch)
+            .unwrap(),
+        token::Amount::native_whole(120)
+    );
+}
+
+#[test]
+fn test_validator_keys_change() {
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(1, vec![token::Amount::native_whole(100)]);
+    let validator = genesis_validators[0].address.clone();
+    let og_protocol_key = genesis_validators[0].protocol_key.clone();
+    let og_eth_cold_key = genesis_validators[0].eth_cold_key.clone();
+    let og_eth_hot_key = genesis_validators[0].eth_hot_key.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let protocol_key = common_sk_from_simple_seed(1).ref_to();
+    let eth_hot_key = gen_keypair::<key::secp256k1::SigScheme>();
+    let eth_hot_key = key::common::SecretKey::Secp256k1(eth_hot_key).ref_to();
+
+    // At least one key has to be changed
+    let err = change_validator_keys(
+        &mut storage,
+        &validator,
+        None,
+        None,
+        None,
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        *err.downcast::<ValidatorKeysChangeError>().unwrap(),
+        ValidatorKeysChangeError::NoKeysChanged
+    );
+
+    // Ethereum keys must be secp256k1 keys
+    let err = change_validator_keys(
+        &mut storage,
+        &validator,
+        None,
+        None,
+        Some(&protocol_key),
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        *err.downcast::<ValidatorKeysChangeError>().unwrap(),
+        ValidatorKeysChangeError::EthKeyMustBeSecp256k1
+    );
+
+    // Only validators may change their keys
+    let err = change_validator_keys(
+        &mut storage,
+        &address::testing::gen_established_address(),
+        Some(&protocol_key),
+        None,
+        None,
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        *err.downcast::<ValidatorKeysChangeError>().unwrap(),
+        ValidatorKeysChangeError::NotAValidator(_)
+    );
+
+    // Rotate the protocol and Ethereum hot keys, keeping the cold key
+    change_validator_keys(
+        &mut storage,
+        &validator,
+        Some(&protocol_key),
+        None,
+        Some(&eth_hot_key),
+        current_epoch,
+    )
+    .unwrap();
+
+    let read_keys = |storage: &TestState, epoch: Epoch| {
+        (
+            validator_protocol_key_handle(&validator)
+                .get(storage, epoch, &params)
+                .unwrap()
+                .unwrap(),
+            validator_eth_cold_key_handle(&validator)
+                .get(storage, epoch, &params)
+                .unwrap()
+                .unwrap(),
+            validator_eth_hot_key_handle(&validator)
+                .get(storage, epoch, &params)
+                .unwrap()
+                .unwrap(),
+        )
+    };
+    let og_keys = (og_protocol_key, og_eth_cold_key.clone(), og_eth_hot_key);
+    let new_keys = (protocol_key, og_eth_cold_key, eth_hot_key);
+
+    // The new keys only take effect at the pipeline epoch
+    for epoch in current_epoch.iter_range(params.pipeline_len) {
+        assert_eq!(read_keys(&storage, epoch), og_keys);
+    }
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(read_keys(&storage, pipeline_epoch), new_keys);
+
+    // The keys stay rotated after advancing to the pipeline epoch
+    while current_epoch < pipeline_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    for epoch in current_epoch.iter_range(params.pipeline_len + 1) {
+        assert_eq!(read_keys(&storage, epoch), new_keys);
+    }
+}
+
+#[test]
+fn test_instant_unbond() {
+    let stakes = vec![token::Amount::native_whole(100); 10];
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams {
+        unbonding_len: 4,
+        instant_unbond_penalty: Some(Dec::new(5, 1).unwrap()),
+        ..Default::default()
+    };
+
+    let genesis_validators = get_genesis_validators(10, stakes);
+    let validator1 = genesis_validators[0].address.clone();
+    let validator2 = genesis_validators[1].address.clone();
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(200),
+    )
+    .unwrap();
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    for validator in [&validator1, &validator2] {
+        bond_tokens(
+            &mut storage,
+            Some(&delegator),
+            validator,
+            token::Amount::native_whole(100),
+            current_epoch,
+            None,
+        )
+        .unwrap();
+    }
+    storage.commit_block().unwrap();
+
+    let advance = |storage: &mut TestState| {
+        let current_epoch = advance_epoch(storage, &params);
+        process_slashes(
+            storage,
+            &mut namada_events::testing::VoidEventSink,
+            current_epoch,
+        )
+        .unwrap();
+        let settled = settle_instant_unbonds(storage, current_epoch).unwrap();
+        (current_epoch, settled)
+    };
+    for _ in 0..params.pipeline_len {
+        (current_epoch, _) = advance(&mut storage);
+    }
+
+    // Instant unbonds are only possible once enabled by governance
+    {
+        let mut temp_state = storage.with_temp_write_log();
+        write_pos_params(
+            &mut temp_state,
+            &OwnedPosParams {
+                instant_unbond_penalty: None,
+                ..params.owned.clone()
+            },
+        )
+        .unwrap();
+        let err = instant_unbond_tokens(
+            &mut temp_state,
+            Some(&delegator),
+            &validator1,
+            token::Amount::native_whole(50),
+            current_epoch,
+        )
+        .unwrap_err();
+        assert_matches!(
+            *err.downcast::<InstantUnbondError>().unwrap(),
+            InstantUnbondError::Disabled
+        );
+    }
+
+    // The unbonded tokens are released right away less the penalty, which is
+    // held until the unbond would have become withdrawable
+    let quote1 = instant_unbond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        token::Amount::native_whole(50),
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(
+        quote1,
+        InstantUnbondQuote {
+            amount: token::Amount::native_whole(50),
+            penalty: token::Amount::native_whole(25),
+            reserve: token::Amount::zero(),
+            released: token::Amount::native_whole(25),
+            maturity_epoch: current_epoch + params.withdrawable_epoch_offset(),
+        }
+    );
+    assert_eq!(
+        read_balance(&storage, &staking_token, &delegator).unwrap(),
+        token::Amount::native_whole(25)
+    );
+    assert_eq!(
+        instant_unbond_queue_handle()
+            .at(&quote1.maturity_epoch)
+            .at(&delegator)
+            .get(&storage, &validator1)
+            .unwrap(),
+        Some(InstantUnbondPenalty {
+            amount: quote1.amount,
+            penalty: quote1.penalty,
+            reserve: quote1.reserve,
+        })
+    );
+    assert!(unbond_handle(&delegator, &validator1)
+        .is_empty(&storage)
+        .unwrap());
+    assert!(!instant_unbond_handle(&delegator, &validator1)
+        .is_empty(&storage)
+        .unwrap());
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(
+        read_validator_stake(&storage, &params, &validator1, pipeline_epoch)
+            .unwrap(),
+        token::Amount::native_whole(150)
+    );
+
+    // An infraction committed before the instant unbond is found after it
+    slash(
+        &mut storage,
+        &params,
+        current_epoch,
+        current_epoch,
+        0u64,
+        SlashType::DuplicateVote,
+        &validator1,
+        current_epoch.next(),
+    )
+    .unwrap();
+
+    (current_epoch, _) = advance(&mut storage);
+    let quote2 = instant_unbond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator2,
+        token::Amount::native_whole(50),
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(quote2.penalty, token::Amount::native_whole(25));
+    // With the slash enqueued for the first validator, a slash of the second
+    // one could exceed the penalty, so more of the unbond is held back
+    assert!(!quote2.reserve.is_zero());
+    assert_eq!(
+        quote2.released + quote2.penalty + quote2.reserve,
+        quote2.amount
+    );
+
+    // Nothing is settled before the first unbond matures
+    while current_epoch.next() < quote1.maturity_epoch {
+        let settled;
+        (current_epoch, settled) = advance(&mut storage);
+        assert!(settled.is_zero());
+    }
+
+    // The slash of the first unbond is taken out of its penalty and the rest
+    // of it is burned
+    let total_supply = read_total_supply(&storage, &staking_token).unwrap();
+    let pos_balance =
+        read_balance(&storage, &staking_token, &crate::ADDRESS).unwrap();
+    let settled;
+    (current_epoch, settled) = advance(&mut storage);
+    assert_eq!(current_epoch, quote1.maturity_epoch);
+    assert!(!settled.is_zero());
+    assert!(settled < quote1.penalty);
+    assert_eq!(
+        read_total_supply(&storage, &staking_token).unwrap(),
+        total_supply - settled
+    );
+    assert_eq!(
+        read_balance(&storage, &staking_token, &crate::ADDRESS).unwrap(),
+        pos_balance - settled
+    );
+    assert!(instant_unbond_handle(&delegator, &validator1)
+        .is_empty(&storage)
+        .unwrap());
+    assert!(instant_unbond_queue_handle()
+        .at(&quote1.maturity_epoch)
+        .is_empty(&storage)
+        .unwrap());
+
+    // Without a slash, the whole penalty of the second unbond is settled and
+    // it's sent to PGF, while its reserve is returned to the source
+    write_pos_params(
+        &mut storage,
+        &OwnedPosParams {
+            instant_unbond_penalty_to_pgf: true,
+            ..params.owned.clone()
+        },
+    )
+    .unwrap();
+    let pgf_balance =
+        read_balance(&storage, &staking_token, &address::PGF).unwrap();
+    let delegator_balance =
+        read_balance(&storage, &staking_token, &delegator).unwrap();
+    let settled;
+    (current_epoch, settled) = advance(&mut storage);
+    assert_eq!(current_epoch, quote2.maturity_epoch);
+    assert_eq!(settled, quote2.penalty);
+    assert_eq!(
+        read_balance(&storage, &staking_token, &address::PGF).unwrap(),
+        pgf_balance + quote2.penalty
+    );
+    assert_eq!(
+        read_balance(&storage, &staking_token, &delegator).unwrap(),
+        delegator_balance + quote2.reserve
+    );
+    assert!(instant_unbond_handle(&delegator, &validator2)
+        .is_empty(&storage)
+        .unwrap());
+}
+
+#[test]
+fn test_reward_recipient() {
+    let stakes = vec![token::Amount::native_whole(100)];
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators = get_genesis_validators(1, stakes);
+    let validator = genesis_validators[0].address.clone();
+
+    let delegator = address::testing::gen_implicit_address();
+    let treasury = address::testing::gen_established_address();
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(20),
+    )
+    .unwrap();
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // Rewards cannot be redirected to an internal address
+    let err = change_reward_recipient(
+        &mut storage,
+        &delegator,
+        Some(&RewardRecipient::Transparent(address::PGF)),
+    )
+    .unwrap_err();
+    assert_matches!(
+        *err.downcast::<RewardRecipientError>().unwrap(),
+        RewardRecipientError::InternalAddress(_)
+    );
+    let recipient = RewardRecipient::Transparent(treasury.clone());
+    change_reward_recipient(&mut storage, &delegator, Some(&recipient))
+        .unwrap();
+    assert_eq!(
+        reward_recipients_handle()
+            .get(&storage, &delegator)
+            .unwrap(),
+        Some(recipient)
+    );
+
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        token::Amount::native_whole(10),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+
+    let distribute_rewards = |storage: &mut TestState, rewards_epoch| {
+        rewards_accumulator_handle()
+            .insert(storage, validator.clone(), Dec::one())
+            .unwrap();
+        let total_native_tokens =
+            get_effective_total_native_supply(storage).unwrap();
+        update_rewards_products_and_mint_inflation::<_, token::Store<_>>(
+            storage,
+            &params,
+            rewards_epoch,
+            1,
+            token::Amount::native_whole(1_000),
+            &staking_token,
+            total_native_tokens,
+        )
+        .unwrap();
+    };
+
+    // Distribute inflation for an epoch in which the delegation is active
+    let rewards_epoch = current_epoch + params.pipeline_len;
+    while current_epoch <= rewards_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    distribute_rewards(&mut storage, rewards_epoch);
+
+    // The rewards are swept to the recipient at the start of the next epoch
+    current_epoch = advance_epoch(&mut storage, &params);
+    let claim_epoch = current_epoch.prev().unwrap();
+    let rewards = query_reward_tokens::<_, GovStore<_>>(
+        &storage,
+        Some(&delegator),
+        &validator,
+        claim_epoch,
+    )
+    .unwrap();
+    assert!(!rewards.is_zero());
+
+    let mut events: Vec<Event> = vec![];
+    sweep_redirected_rewards::<_, GovStore<_>, token::Store<_>>(
+        &mut storage,
+        &mut events,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(
+        read_balance(&storage, &staking_token, &treasury).unwrap(),
+        rewards
+    );
+    assert_eq!(
+        read_balance(&storage, &staking_token, &delegator).unwrap(),
+        token::Amount::native_whole(10)
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind(), &event::types::REWARDS_SWEEP);
+
+    // The bonds whose rewards are compounded are not swept
+    change_auto_compound(&mut storage, Some(&delegator), &validator, true)
+        .unwrap();
+    current_epoch = advance_epoch(&mut storage, &params);
+    let mut events: Vec<Event> = vec![];
+    sweep_redirected_rewards::<_, GovStore<_>, token::Store<_>>(
+        &mut storage,
+        &mut events,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(events.is_empty());
+    change_auto_compound(&mut storage, Some(&delegator), &validator, false)
+        .unwrap();
+
+    // Claimed rewards are also paid out to the recipient
+    distribute_rewards(&mut storage, current_epoch);
+    current_epoch = advance_epoch(&mut storage, &params);
+    let rewards = query_reward_tokens::<_, GovStore<_>>(
+        &storage,
+        Some(&delegator),
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(!rewards.is_zero());
+    let treasury_pre =
+        read_balance(&storage, &staking_token, &treasury).unwrap();
+    let claimed = claim_reward_tokens::<_, GovStore<_>, token::Store<_>>(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(claimed, rewards);
+    assert_eq!(
+        read_balance(&storage, &staking_token, &treasury).unwrap(),
+        treasury_pre + claimed
+    );
+    assert_eq!(
+        read_balance(&storage, &staking_token, &delegator).unwrap(),
+        token::Amount::native_whole(10)
+    );
+
+    // The rewards redirected to a shielded recipient are not swept
+    let payment_address: PaymentAddress =
+        "znam1ky620tz7z658cralqt693qpvk42wvth468zp38nqvq2apmex5rfut3dfqm2asrsqv0tc7saqje7"
+            .parse()
+            .unwrap();
+    let recipient = RewardRecipient::Shielded(payment_address);
+    change_reward_recipient(&mut storage, &delegator, Some(&recipient))
+        .unwrap();
+    assert_eq!(
+        reward_recipients_handle()
+            .get(&storage, &delegator)
+            .unwrap(),
+        Some(recipient)
+    );
+    distribute_rewards(&mut storage, current_epoch);
+    current_epoch = advance_epoch(&mut storage, &params);
+    let mut events: Vec<Event> = vec![];
+    sweep_redirected_rewards::<_, GovStore<_>, token::Store<_>>(
+        &mut storage,
+        &mut events,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(events.is_empty());
+
+    // They're paid out to the source when claimed, for the client to shield
+    // them
+    let rewards = query_reward_tokens::<_, GovStore<_>>(
+        &storage,
+        Some(&delegator),
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(!rewards.is_zero());
+    let treasury_pre =
+        read_balance(&storage, &staking_token, &treasury).unwrap();
+    let claimed = claim_reward_tokens::<_, GovStore<_>, token::Store<_>>(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(claimed, rewards);
+    assert_eq!(
+        read_balance(&storage, &staking_token, &treasury).unwrap(),
+        treasury_pre
+    );
+    assert_eq!(
+        read_balance(&storage, &staking_token, &delegator).unwrap(),
+        token::Amount::native_whole(10) + claimed
+    );
+
+    // Redirecting the rewards back to the source removes the recipient
+    change_reward_recipient(
+        &mut storage,
+        &delegator,
+        Some(&RewardRecipient::Transparent(delegator.clone())),
+    )
+    .unwrap();
+    assert!(reward_recipients_handle()
+        .get(&storage, &delegator)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_min_self_bond() {
+    let stakes = vec![
+        token::Amount::native_whole(100),
+        token::Amount::native_whole(100),
+    ];
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams {
+        min_self_bond: Some(token::Amount::native_whole(50)),
+        min_self_bond_ratio: Some(Dec::new(5, 1).unwrap()),
+        ..Default::default()
+    };
+
+    let genesis_validators = get_genesis_validators(2, stakes);
+    let validator1 = genesis_validators[0].address.clone();
+    let validator2 = genesis_validators[1].address.clone();
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(200),
+    )
+    .unwrap();
+
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    current_epoch = advance_epoch(&mut storage, &params);
+    storage.commit_block().unwrap();
+
+    // A validator cannot unbond its self-bond below the minimum amount
+    let mut temp_state = storage.with_temp_write_log();
+    let err = unbond_tokens(
+        &mut temp_state,
+        None,
+        &validator1,
+        token::Amount::native_whole(60),
+        current_epoch,
+        false,
+    )
+    .unwrap_err();
+    assert_matches!(
+        *err.downcast::<StakeLimitError>().unwrap(),
+        StakeLimitError::MinSelfBondNotMet(..)
+    );
+    unbond_tokens(
+        &mut storage,
+        None,
+        &validator1,
+        token::Amount::native_whole(50),
+        current_epoch,
+        false,
+    )
+    .unwrap();
+
+    // Delegations cannot take the self-bond of a validator below the minimum
+    // ratio of its stake
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator2,
+        token::Amount::native_whole(100),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    let mut temp_state = storage.with_temp_write_log();
+    let err = bond_tokens(
+        &mut temp_state,
+        Some(&delegator),
+        &validator2,
+        token::Amount::native_whole(10),
+        current_epoch,
+        None,
+    )
+    .unwrap_err();
+    assert_matches!(
+        *err.downcast::<StakeLimitError>().unwrap(),
+        StakeLimitError::MinSelfBondNotMet(..)
+    );
+    current_epoch = advance_epoch(&mut storage, &params);
+    let jailed =
+        jail_for_min_self_bond(&mut storage, &params, current_epoch).unwrap();
+    assert!(jailed.is_empty());
+
+    // A validator that no longer meets the minimum, e.g. because governance
+    // raised it, is jailed from the pipeline epoch
+    write_pos_params(
+        &mut storage,
+        &OwnedPosParams {
+            min_self_bond_ratio: Some(Dec::new(6, 1).unwrap()),
+            ..params.owned.clone()
+        },
+    )
+    .unwrap();
+    let params = read_pos_params::<_, GovStore<_>>(&storage).unwrap();
+    current_epoch = advance_epoch(&mut storage, &params);
+    let jailed =
+        jail_for_min_self_bond(&mut storage, &params, current_epoch).unwrap();
+    assert_eq!(jailed, vec![validator2.clone()]);
+
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(
+        validator_state_handle(&validator2)
+            .get(&storage, current_epoch, &params)
+            .unwrap(),
+        Some(ValidatorState::Consensus)
+    );
+    assert_eq!(
+        validator_state_handle(&validator2)
+            .get(&storage, pipeline_epoch, &params)
+            .unwrap(),
+        Some(ValidatorState::Jailed)
+    );
+    assert!(
+        !read_consensus_validator_set_addresses(&storage, pipeline_epoch)
+            .unwrap()
+            .contains(&validator2)
+    );
+
+    // The validator can only be unjailed once it meets the minimum again
+    while current_epoch < pipeline_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    let err =
+        unjail_validator(&mut storage, &validator2, current_epoch).unwrap_err();
+    assert_matches!(
+        *err.downcast::<StakeLimitError>().unwrap(),
+        StakeLimitError::MinSelfBondNotMet(..)
+    );
+    bond_tokens(
+        &mut storage,
+        None,
+        &validator2,
+        token::Amount::native_whole(50),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    unjail_validator(&mut storage, &validator2, current_epoch).unwrap();
+    assert_eq!(
+        validator_state_handle(&validator2)
+            .get(&storage, current_epoch + params.pipeline_len, &params)
+            .unwrap(),
+        Some(ValidatorState::Consensus)
+    );
+}
@@ -23,6 +23,7 @@ use proptest::test_runner::Config;
 use test_log::test;
 
 use crate::lazy_map::Collectable;
+use crate::slashing::simulate_enqueued_slashes;
 use crate::storage::{
     bond_handle, delegator_redelegated_bonds_handle,
     delegator_redelegated_unbonds_handle, enqueued_slashes_handle,
@@ -35,12 +36,12 @@ use crate::storage::{
 };
 use crate::tests::helpers::{
     advance_epoch, arb_genesis_validators, arb_redelegation_amounts,
-    test_slashes_with_unbonding_params,
+    get_genesis_validators, test_slashes_with_unbonding_params,
 };
 use crate::tests::{
     bond_amount, bond_tokens, bonds_and_unbonds, process_slashes,
     redelegate_tokens, slash, test_init_genesis, unbond_tokens,
-    withdraw_tokens,
+    withdraw_tokens, GovStore,
 };
 use crate::types::{BondId, GenesisValidator, Slash, SlashType};
 use crate::{
@@ -1808,3 +1809,166 @@ fn test_one_slash_per_block_height() {
         .unwrap();
     assert_eq!(res, exp);
 }
+
+#[test]
+fn test_slash_projection() {
+    let mut storage = TestState::default();
+    let params = OwnedPosParams {
+        unbonding_len: 4,
+        validator_stake_threshold: token::Amount::zero(),
+        ..Default::default()
+    };
+    let validators = get_genesis_validators(
+        4,
+        vec![
+            token::Amount::native_whole(1_000),
+            token::Amount::native_whole(100_000),
+            token::Amount::native_whole(100_000),
+            token::Amount::native_whole(100_000),
+        ],
+    );
+    let validator1 = validators[0].address.clone();
+    let validator2 = validators[1].address.clone();
+
+    // Genesis
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+
+    // Get a delegator with some tokens
+    let staking_token = staking_token_address(&storage);
+    let delegator = address::testing::gen_implicit_address();
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(1_000),
+    )
+    .unwrap();
+
+    // Bond to validator 1 and once the bond is active, unbond and redelegate
+    // some of it
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        token::Amount::native_whole(500),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    for _ in 0..params.pipeline_len {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    unbond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        token::Amount::native_whole(100),
+        current_epoch,
+        false,
+    )
+    .unwrap();
+    redelegate_tokens(
+        &mut storage,
+        &delegator,
+        &validator1,
+        &validator2,
+        current_epoch,
+        token::Amount::native_whole(100),
+    )
+    .unwrap();
+
+    // Validator 1 misbehaves while the tokens still contribute to its stake
+    current_epoch = advance_epoch(&mut storage, &params);
+    let infraction_epoch = current_epoch;
+    slash(
+        &mut storage,
+        &params,
+        current_epoch,
+        infraction_epoch,
+        1_u64,
+        SlashType::DuplicateVote,
+        &validator1,
+        current_epoch,
+    )
+    .unwrap();
+
+    // Project the slash once its cubic slashing window is over
+    let (_, window_end) = params.cubic_slash_epoch_window(infraction_epoch);
+    while current_epoch <= window_end {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    storage.commit_block().unwrap();
+    let projection = {
+        let mut temp_state = storage.with_temp_write_log();
+        simulate_enqueued_slashes::<_, GovStore<_>>(
+            &mut temp_state,
+            &delegator,
+            current_epoch,
+        )
+        .unwrap()
+    };
+    assert_eq!(projection.slashes.len(), 1);
+    let projected_slashes = &projection.slashes[&validator1];
+    assert_eq!(projected_slashes.len(), 1);
+    assert_eq!(projection.losses.len(), 2);
+    let bond1 = BondId {
+        source: delegator.clone(),
+        validator: validator1.clone(),
+    };
+    let bond2 = BondId {
+        source: delegator.clone(),
+        validator: validator2.clone(),
+    };
+    let loss1 = &projection.losses[&bond1];
+    let loss2 = &projection.losses[&bond2];
+    assert!(!loss1.bonded_loss.is_zero());
+    assert!(!loss1.unbonded_loss.is_zero());
+    assert!(!loss2.bonded_loss.is_zero());
+    assert!(loss2.unbonded.is_zero());
+
+    // The projection matches the processed slash
+    let processing_epoch =
+        infraction_epoch + params.slash_processing_epoch_offset();
+    while current_epoch < processing_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+        process_slashes(
+            &mut storage,
+            &mut namada_events::testing::VoidEventSink,
+            current_epoch,
+        )
+        .unwrap();
+    }
+    let slashes = validator_slashes_handle(&validator1)
+        .iter(&storage)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(&slashes, projected_slashes);
+    for (bond_id, loss) in [(&bond1, loss1), (&bond2, loss2)] {
+        assert_eq!(
+            bond_amount(&storage, bond_id, processing_epoch).unwrap(),
+            loss.bonded - loss.bonded_loss
+        );
+    }
+
+    // And so do the withdrawn tokens
+    while current_epoch < processing_epoch + params.pipeline_len {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    let withdrawn = withdraw_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(withdrawn, loss1.unbonded - loss1.unbonded_loss);
+}
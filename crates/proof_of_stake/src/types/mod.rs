@@ -17,6 +17,7 @@ use namada_core::token::Amount;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
+pub use namada_tx::data::pos::RewardRecipient;
 pub use rev_order::ReverseOrdTokenAmount;
 use serde::{Deserialize, Serialize};
 
@@ -158,6 +159,35 @@ pub type Unbonds = NestedMap<Epoch, LazyMap<Epoch, token::Amount>>;
 /// Consensus keys set, used to ensure uniqueness
 pub type ConsensusKeys = LazySet<common::PublicKey>;
 
+/// Bonds whose rewards are automatically compounded at every epoch boundary.
+///
+/// The map keys from outside in are:
+/// - source of the bond (delegator or self-bonded validator)
+/// - validator of the bond
+pub type AutoCompounds = NestedMap<Address, LazySet<Address>>;
+
+/// The delegators of a validator, i.e. the sources of its bonds other than the
+/// validator itself
+pub type ValidatorDelegators = LazySet<Address>;
+
+/// Commission schedules published by validators
+pub type CommissionSchedules = LazyMap<Address, CommissionSchedule>;
+
+/// Recipients of the rewards of the bond sources (delegators or self-bonded
+/// validators) that redirected them, keyed by the source
+pub type RewardRecipients = LazyMap<Address, RewardRecipient>;
+
+/// Penalties of the instant unbonds held by PoS until the unbonds mature.
+///
+/// The map keys from outside in are:
+/// - epoch at which the instant unbonds mature
+/// - source of the bond
+/// - validator of the bond
+pub type InstantUnbondQueue = NestedMap<
+    Epoch,
+    NestedMap<Address, LazyMap<Address, InstantUnbondPenalty>>,
+>;
+
 /// Total unbonded for validators needed for slashing computations.
 ///
 /// The outer `Epoch` corresponds to the epoch at which the unbond is active
@@ -315,6 +345,10 @@ pub type RewardsProducts = LazyMap<Epoch, Dec>;
 /// rewards owed over the course of an epoch)
 pub type RewardsAccumulator = LazyMap<Address, Dec>;
 
+/// A validator's performance history, keyed by epoch
+pub type ValidatorPerformanceHistory =
+    LazyMap<Epoch, ValidatorEpochPerformance>;
+
 /// Eager data for a generic redelegation
 #[derive(Debug)]
 pub struct Redelegation {
@@ -397,6 +431,101 @@ pub struct ValidatorMetaData {
     pub name: Option<String>,
 }
 
+/// A validator's commission schedule
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    BorshSerialize,
+    BorshSchema,
+    BorshDeserialize,
+    BorshDeserializer,
+    Deserialize,
+    Serialize,
+    Eq,
+    PartialEq,
+)]
+pub struct CommissionSchedule {
+    /// Future-dated commission rate changes, keyed by the epoch from which
+    /// they take effect
+    pub changes: BTreeMap<Epoch, Dec>,
+    /// Commission tiers by delegation size, keyed by the minimum delegated
+    /// amount. A tier's rate only applies when it's lower than the
+    /// validator's commission rate.
+    pub tiers: BTreeMap<token::Amount, Dec>,
+    /// The epoch until which the validator has committed not to raise its
+    /// commission rate
+    pub no_raise_until: Option<Epoch>,
+}
+
+impl CommissionSchedule {
+    /// Get the commission rate of a delegation of the given size, which is the
+    /// lower of the validator's commission rate and the rate of the highest
+    /// tier that the delegation reaches.
+    pub fn tier_rate(
+        &self,
+        commission_rate: Dec,
+        amount: token::Amount,
+    ) -> Dec {
+        self.tiers
+            .range(..=amount)
+            .next_back()
+            .map(|(_, rate)| *rate)
+            .map_or(commission_rate, |rate| rate.min(commission_rate))
+    }
+
+    /// Get the epoch until which the validator is committed not to raise its
+    /// commission rate, if the commitment is in effect in the given epoch.
+    pub fn raise_locked_until(&self, epoch: Epoch) -> Option<Epoch> {
+        self.no_raise_until.filter(|until| epoch < *until)
+    }
+
+    /// Is there nothing left to apply or enforce from the schedule from the
+    /// given epoch on?
+    pub fn is_expired(&self, epoch: Epoch) -> bool {
+        self.changes.is_empty()
+            && self.tiers.is_empty()
+            && self.raise_locked_until(epoch).is_none()
+    }
+}
+
+/// A validator's performance in an epoch in which it was in the consensus set
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    BorshSerialize,
+    BorshSchema,
+    BorshDeserialize,
+    BorshDeserializer,
+    Deserialize,
+    Serialize,
+    Eq,
+    PartialEq,
+)]
+pub struct ValidatorEpochPerformance {
+    /// The number of blocks whose votes were expected from the validator
+    pub expected_votes: u64,
+    /// The number of blocks that the validator didn't vote for
+    pub missed_votes: u64,
+    /// The number of blocks proposed by the validator
+    pub proposed_blocks: u64,
+    /// The rewards earned by the validator's stake, including the commissions
+    pub rewards: token::Amount,
+    /// The commissions taken by the validator out of the rewards
+    pub commissions: token::Amount,
+}
+
+impl ValidatorEpochPerformance {
+    /// Get the fraction of the expected votes that the validator cast, if any
+    /// were expected.
+    pub fn uptime(&self) -> Option<Dec> {
+        let votes = self.expected_votes.checked_sub(self.missed_votes)?;
+        Dec::from(votes).checked_div(self.expected_votes)
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 impl Default for ValidatorMetaData {
     fn default() -> Self {
@@ -646,6 +775,142 @@ pub struct ResultSlashing {
     pub epoch_map: BTreeMap<Epoch, token::Amount>,
 }
 
+/// The stake of a validator at the pipeline epoch that is subject to the stake
+/// limits
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct PipelineStake {
+    /// The validator's stake
+    pub stake: token::Amount,
+    /// The part of the validator's stake that is delegated to it
+    pub delegated: token::Amount,
+    /// The total stake of the consensus validators
+    pub total_consensus_stake: token::Amount,
+    /// Whether the validator is in the consensus set
+    pub is_consensus: bool,
+}
+
+/// The penalty of the instant unbonds from a bond in a single epoch, held by
+/// PoS until the unbonds mature to cover the slashes that are yet to be
+/// processed
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct InstantUnbondPenalty {
+    /// The unbonded amount after the slashes that were processed before the
+    /// unbond, including the penalty
+    pub amount: token::Amount,
+    /// The penalty held by PoS
+    pub penalty: token::Amount,
+    /// The part of the unbonded amount that may still be slashed in excess of
+    /// the penalty, held by PoS and returned to the source less these slashes
+    pub reserve: token::Amount,
+}
+
+/// The outcome of an instant unbond
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct InstantUnbondQuote {
+    /// The unbonded amount after the slashes that have been processed
+    pub amount: token::Amount,
+    /// The penalty charged for the instant unbond
+    pub penalty: token::Amount,
+    /// The part of the unbonded amount that may still be slashed for prior
+    /// infractions in excess of the penalty, held until the unbond matures
+    pub reserve: token::Amount,
+    /// The amount released to the source right away
+    pub released: token::Amount,
+    /// The epoch at which the unbond matures. The slashes for prior
+    /// infractions processed by then are taken out of the penalty first and
+    /// then out of the reserve. The rest of the penalty is burned or sent to
+    /// PGF and the rest of the reserve is returned to the source.
+    pub maturity_epoch: Epoch,
+}
+
+/// The projected token losses of a bond to the enqueued slashes
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct ProjectedSlashLoss {
+    /// The bonded tokens after the slashes that have been processed
+    pub bonded: token::Amount,
+    /// The bonded tokens that the enqueued slashes are projected to slash
+    pub bonded_loss: token::Amount,
+    /// The unbonded tokens that are not withdrawn yet after the slashes that
+    /// have been processed
+    pub unbonded: token::Amount,
+    /// The unbonded tokens that the enqueued slashes are projected to slash
+    pub unbonded_loss: token::Amount,
+}
+
+/// The projected outcome of processing the enqueued slashes for a bond owner
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct SlashProjection {
+    /// The enqueued slashes with their projected rates by validator
+    pub slashes: BTreeMap<Address, Vec<Slash>>,
+    /// The projected token losses of the owner's bonds
+    pub losses: BTreeMap<BondId, ProjectedSlashLoss>,
+}
+
+impl SlashProjection {
+    /// Get the total projected token loss of the owner's bonds and unbonds
+    pub fn total_loss(&self) -> Option<token::Amount> {
+        self.losses
+            .values()
+            .try_fold(token::Amount::zero(), |acc, loss| {
+                acc.checked_add(loss.bonded_loss)?
+                    .checked_add(loss.unbonded_loss)
+            })
+    }
+}
+
 /// Bonds and unbonds with all details (slashes and rewards, if any)
 /// grouped by their bond IDs.
 pub type BondsAndUnbondsDetails = HashMap<BondId, BondsAndUnbondsDetail>;
@@ -3,19 +3,32 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::marker::PhantomData;
 
-use namada_core::address::Address;
+use namada_core::address::{Address, InternalAddress};
+use namada_core::arith::checked;
 use namada_core::booleans::BoolResultUnitExt;
 use namada_core::storage::Key;
-use namada_systems::governance;
+use namada_systems::{governance, trans_token};
 use namada_tx::action::{
-    Action, Bond, ClaimRewards, PosAction, Redelegation, Unbond, Withdraw,
+    Action, AutoCompoundChange, Bond, ClaimRewards, PosAction, Redelegation,
+    Unbond, Withdraw,
 };
 use namada_tx::BatchedTxRef;
 use namada_vp_env::{Error, Result, VpEnv};
 use thiserror::Error;
 
-use crate::storage::read_owned_pos_params;
-use crate::storage_key::is_params_key;
+use crate::commission::validate_commission_schedule;
+use crate::liquid_staking::{
+    bond_shares_worth, holds_only_shares, liquid_staking_token,
+};
+use crate::storage::{
+    commission_schedules_handle, read_owned_pos_params, read_pos_params,
+};
+use crate::storage_key::{
+    is_auto_compound_key, is_commission_schedule_key, is_params_key,
+    is_reward_recipient_key, is_validator_eth_cold_key_key,
+    is_validator_eth_hot_key_key, is_validator_liquid_staking_key,
+    is_validator_max_delegation_key,
+};
 use crate::types::BondId;
 use crate::{storage_key, token};
 
@@ -35,15 +48,16 @@ impl From<VpError> for Error {
 }
 
 /// Proof-of-Stake validity predicate
-pub struct PosVp<'ctx, CTX, Gov> {
+pub struct PosVp<'ctx, CTX, Gov, TokenKeys> {
     /// Generic types for DI
-    pub _marker: PhantomData<(&'ctx CTX, Gov)>,
+    pub _marker: PhantomData<(&'ctx CTX, Gov, TokenKeys)>,
 }
 
-impl<'ctx, CTX, Gov> PosVp<'ctx, CTX, Gov>
+impl<'ctx, CTX, Gov, TokenKeys> PosVp<'ctx, CTX, Gov, TokenKeys>
 where
     CTX: VpEnv<'ctx> + namada_tx::action::Read<Err = Error>,
     Gov: governance::Read<<CTX as VpEnv<'ctx>>::Pre>,
+    TokenKeys: trans_token::Keys,
 {
     /// Run the validity predicate
     pub fn validate_tx(
@@ -100,9 +114,17 @@ where
         let mut redelegations: BTreeMap<BondId, (Address, token::Amount)> =
             Default::default();
         let mut claimed_rewards: BTreeSet<BondId> = Default::default();
+        let mut changed_auto_compound: BTreeSet<BondId> = Default::default();
         let mut changed_commission: BTreeSet<Address> = Default::default();
+        let mut changed_commission_schedule: BTreeSet<Address> =
+            Default::default();
         let mut changed_metadata: BTreeSet<Address> = Default::default();
         let mut changed_consensus_key: BTreeSet<Address> = Default::default();
+        let mut changed_liquid_staking: BTreeSet<Address> = Default::default();
+        let mut changed_max_delegation: BTreeSet<Address> = Default::default();
+        let mut changed_validator_keys: BTreeSet<Address> = Default::default();
+        let mut changed_reward_recipient: BTreeSet<Address> =
+            Default::default();
 
         // Accumulate changes from the actions
         for action in actions {
@@ -174,7 +196,8 @@ where
                             )
                             .into());
                         }
-                        bonds.insert(bond_id, amount);
+                        let bonded = bonds.entry(bond_id).or_default();
+                        *bonded = checked!(bonded + amount)?;
                     }
                     PosAction::Unbond(Unbond {
                         validator,
@@ -193,7 +216,30 @@ where
                             )
                             .into());
                         }
-                        unbonds.insert(bond_id, amount);
+                        let unbonded = unbonds.entry(bond_id).or_default();
+                        *unbonded = checked!(unbonded + amount)?;
+                    }
+                    PosAction::InstantUnbond(Unbond {
+                        validator,
+                        amount,
+                        source,
+                    }) => {
+                        let bond_id = BondId {
+                            source: source.unwrap_or_else(|| validator.clone()),
+                            validator,
+                        };
+                        if !verifiers.contains(&bond_id.source) {
+                            tracing::info!(
+                                "Unauthorized PosAction::InstantUnbond"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "InstantUnbond",
+                                bond_id.source,
+                            )
+                            .into());
+                        }
+                        let unbonded = unbonds.entry(bond_id).or_default();
+                        *unbonded = checked!(unbonded + amount)?;
                     }
                     PosAction::Withdraw(Withdraw { validator, source }) => {
                         let bond_id = BondId {
@@ -252,6 +298,27 @@ where
                         }
                         claimed_rewards.insert(bond_id);
                     }
+                    PosAction::AutoCompoundChange(AutoCompoundChange {
+                        validator,
+                        source,
+                        ..
+                    }) => {
+                        let bond_id = BondId {
+                            source: source.unwrap_or_else(|| validator.clone()),
+                            validator,
+                        };
+                        if !verifiers.contains(&bond_id.source) {
+                            tracing::info!(
+                                "Unauthorized PosAction::AutoCompoundChange"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "AutoCompoundChange",
+                                bond_id.source,
+                            )
+                            .into());
+                        }
+                        changed_auto_compound.insert(bond_id);
+                    }
                     PosAction::CommissionChange(validator) => {
                         if !verifiers.contains(&validator) {
                             tracing::info!(
@@ -265,6 +332,20 @@ where
                         }
                         changed_commission.insert(validator);
                     }
+                    PosAction::CommissionScheduleChange(validator) => {
+                        if !verifiers.contains(&validator) {
+                            tracing::info!(
+                                "Unauthorized \
+                                 PosAction::CommissionScheduleChange"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "CommissionScheduleChange",
+                                validator,
+                            )
+                            .into());
+                        }
+                        changed_commission_schedule.insert(validator);
+                    }
                     PosAction::MetadataChange(validator) => {
                         if !verifiers.contains(&validator) {
                             tracing::info!(
@@ -291,6 +372,58 @@ where
                         }
                         changed_consensus_key.insert(validator);
                     }
+                    PosAction::LiquidStakingChange(validator) => {
+                        if !verifiers.contains(&validator) {
+                            tracing::info!(
+                                "Unauthorized PosAction::LiquidStakingChange"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "LiquidStakingChange",
+                                validator,
+                            )
+                            .into());
+                        }
+                        changed_liquid_staking.insert(validator);
+                    }
+                    PosAction::MaxDelegationChange(validator) => {
+                        if !verifiers.contains(&validator) {
+                            tracing::info!(
+                                "Unauthorized PosAction::MaxDelegationChange"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "MaxDelegationChange",
+                                validator,
+                            )
+                            .into());
+                        }
+                        changed_max_delegation.insert(validator);
+                    }
+                    PosAction::ValidatorKeysChange(validator) => {
+                        if !verifiers.contains(&validator) {
+                            tracing::info!(
+                                "Unauthorized PosAction::ValidatorKeysChange"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "ValidatorKeysChange",
+                                validator,
+                            )
+                            .into());
+                        }
+                        changed_validator_keys.insert(validator);
+                    }
+                    PosAction::RewardRecipientChange(source) => {
+                        if !verifiers.contains(&source) {
+                            tracing::info!(
+                                "Unauthorized PosAction::RewardRecipientChange"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "RewardRecipientChange",
+                                source,
+                            )
+                            .into());
+                        }
+                        changed_reward_recipient.insert(source);
+                    }
                 },
                 _ => {
                     // Other actions are not relevant to PoS VP
@@ -306,11 +439,180 @@ where
                      governance proposal that has been accepted",
                 ));
             }
+            if let Some(validator) = is_commission_schedule_key(key) {
+                if !changed_commission_schedule.contains(validator) {
+                    return Err(Error::new_alloc(format!(
+                        "Commission schedule of validator {validator} changed \
+                         without a PosAction::CommissionScheduleChange"
+                    )));
+                }
+                Self::is_valid_commission_schedule_change(ctx, validator)?;
+            }
+            if let Some(validator) = is_validator_max_delegation_key(key) {
+                if !changed_max_delegation.contains(validator) {
+                    return Err(Error::new_alloc(format!(
+                        "Max delegation of validator {validator} changed \
+                         without a PosAction::MaxDelegationChange"
+                    )));
+                }
+            }
+            if let Some(validator) = is_validator_eth_cold_key_key(key)
+                .or_else(|| is_validator_eth_hot_key_key(key))
+            {
+                if !changed_validator_keys.contains(validator)
+                    && !became_validator.contains(validator)
+                {
+                    return Err(Error::new_alloc(format!(
+                        "Ethereum keys of validator {validator} changed \
+                         without a PosAction::ValidatorKeysChange"
+                    )));
+                }
+            }
+            if let Some(validator) = is_validator_liquid_staking_key(key) {
+                if !changed_liquid_staking.contains(validator) {
+                    return Err(Error::new_alloc(format!(
+                        "Liquid staking of validator {validator} changed \
+                         without a PosAction::LiquidStakingChange"
+                    )));
+                }
+            }
+            if let Some(token) = TokenKeys::is_any_minted_balance_key(key) {
+                if matches!(
+                    token,
+                    Address::Internal(InternalAddress::LiquidStakingToken(_))
+                ) {
+                    Self::is_valid_liquid_staking_supply_change(
+                        ctx, token, &bonds, &unbonds,
+                    )?;
+                }
+            }
+            if let Some(bond_id) = is_auto_compound_key(key) {
+                if !changed_auto_compound.contains(&bond_id) {
+                    return Err(Error::new_alloc(format!(
+                        "Auto-compounding of the bond {bond_id} changed \
+                         without a PosAction::AutoCompoundChange"
+                    )));
+                }
+            }
+            if let Some(source) = is_reward_recipient_key(key) {
+                if !changed_reward_recipient.contains(source) {
+                    return Err(Error::new_alloc(format!(
+                        "Reward recipient of {source} changed without a \
+                         PosAction::RewardRecipientChange"
+                    )));
+                }
+            }
             // TODO: validate changes keys against the accumulated changes
         }
         Ok(())
     }
 
+    /// Return `Ok` if the change of the supply of a liquid staking token is
+    /// matched by the bonds to and the unbonds from the pool of its validator.
+    /// The unbonded shares must all be burned and the minted shares must not
+    /// be worth more than the bonded tokens at the price of the shares before
+    /// the tx.
+    fn is_valid_liquid_staking_supply_change(
+        ctx: &'ctx CTX,
+        token: &Address,
+        bonds: &BTreeMap<BondId, token::Amount>,
+        unbonds: &BTreeMap<BondId, token::Amount>,
+    ) -> Result<()> {
+        let is_pool = |bond_id: &BondId| {
+            bond_id.source != bond_id.validator
+                && liquid_staking_token(&bond_id.validator) == *token
+        };
+        let current_epoch = ctx.get_block_epoch()?;
+
+        let mut pool_validator = None;
+        let mut bonded = token::Amount::zero();
+        for (bond_id, amount) in bonds.iter().filter(|(id, _)| is_pool(id)) {
+            pool_validator = Some(&bond_id.validator);
+            bonded = checked!(bonded + *amount)?;
+        }
+        // Only the unbonds of the holders without a bond of their own burn
+        // shares
+        let mut unbonded_shares = token::Amount::zero();
+        for (bond_id, amount) in unbonds.iter().filter(|(id, _)| is_pool(id)) {
+            let shares: token::Amount = ctx
+                .read_pre(&TokenKeys::balance_key(token, &bond_id.source))?
+                .unwrap_or_default();
+            if holds_only_shares::<_, Gov>(
+                &ctx.pre(),
+                &bond_id.source,
+                &bond_id.validator,
+                shares,
+                current_epoch,
+            )? {
+                unbonded_shares = checked!(unbonded_shares + *amount)?;
+            }
+        }
+
+        let minted_key = TokenKeys::minted_balance_key(token);
+        let pre: token::Amount = ctx.read_pre(&minted_key)?.unwrap_or_default();
+        let post: token::Amount =
+            ctx.read_post(&minted_key)?.unwrap_or_default();
+        // The shares minted for the bonds, given that all the unbonded shares
+        // are burned. When a tx both bonds and unbonds, any unbonded share
+        // that isn't burned is counted as minted.
+        let minted = checked!(post + unbonded_shares)?
+            .checked_sub(pre)
+            .ok_or_else(|| {
+                Error::new_alloc(format!(
+                    "Burned {} of liquid staking token {token}, but unbonded \
+                     {} shares from its pool",
+                    checked!(pre - post).unwrap_or_default().to_string_native(),
+                    unbonded_shares.to_string_native(),
+                ))
+            })?;
+        if minted.is_zero() {
+            return Ok(());
+        }
+        let Some(validator) = pool_validator else {
+            return Err(Error::new_alloc(format!(
+                "Liquid staking token {token} minted or not burned for {} \
+                 shares without a PosAction::Bond to its pool",
+                minted.to_string_native(),
+            )));
+        };
+        let worth = bond_shares_worth::<_, Gov>(
+            &ctx.pre(),
+            validator,
+            bonded,
+            pre,
+            current_epoch,
+        )?;
+        if minted > worth {
+            return Err(Error::new_alloc(format!(
+                "Minted {} of liquid staking token {token}, but the bonded {} \
+                 tokens are only worth {} shares",
+                minted.to_string_native(),
+                bonded.to_string_native(),
+                worth.to_string_native(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Return `Ok` if the change of a validator's commission schedule is valid
+    fn is_valid_commission_schedule_change(
+        ctx: &'ctx CTX,
+        validator: &Address,
+    ) -> Result<()> {
+        let handle = commission_schedules_handle();
+        let prior = handle.get(&ctx.pre(), validator)?;
+        let schedule = handle.get(&ctx.post(), validator)?.unwrap_or_default();
+        let current_epoch = ctx.get_block_epoch()?;
+        let params = read_pos_params::<_, Gov>(&ctx.pre())?;
+        validate_commission_schedule(
+            validator,
+            prior.as_ref(),
+            &schedule,
+            current_epoch,
+            params.pipeline_len,
+        )
+    }
+
     /// Return `Ok` if the changed parameters are valid
     fn is_valid_parameter_change(ctx: &'ctx CTX) -> Result<()> {
         let validation_errors: Vec<crate::parameters::ValidationError> =
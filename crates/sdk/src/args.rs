@@ -1,10 +1,12 @@
 //! Structures encapsulating SDK arguments
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration as StdDuration;
 
+use namada_account::{AccountPolicy, VestingSchedule};
 use namada_core::address::Address;
 use namada_core::chain::{BlockHeight, ChainId, Epoch};
 use namada_core::collections::HashMap;
@@ -16,10 +18,12 @@ use namada_core::masp::{MaspEpoch, PaymentAddress};
 use namada_core::time::DateTimeUtc;
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, UpgradeProposal,
 };
 use namada_ibc::IbcShieldingData;
 use namada_token::masp::utils::RetryStrategy;
+use namada_token::masp::{NoteConsolidation, NoteSelection};
 use namada_tx::data::GasLimit;
 use namada_tx::Memo;
 use serde::{Deserialize, Serialize};
@@ -335,6 +339,161 @@ impl TxTransparentTransfer {
     }
 }
 
+/// Token allowance approval arguments
+#[derive(Clone, Debug)]
+pub struct TxApprove<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The owner of the tokens
+    pub owner: C::Address,
+    /// The address allowed to transfer the owner's tokens
+    pub spender: C::Address,
+    /// The token of the allowance
+    pub token: C::Address,
+    /// The allowed amount. Zero revokes the allowance.
+    pub amount: InputAmount,
+    /// The maximum amount that can be transferred within an epoch
+    pub epoch_cap: Option<InputAmount>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxApprove<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxApprove {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxApprove<C> {
+    /// The owner of the tokens
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The address allowed to transfer the owner's tokens
+    pub fn spender(self, spender: C::Address) -> Self {
+        Self { spender, ..self }
+    }
+
+    /// The token of the allowance
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// The allowed amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// The maximum amount that can be transferred within an epoch
+    pub fn epoch_cap(self, epoch_cap: InputAmount) -> Self {
+        Self {
+            epoch_cap: Some(epoch_cap),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxApprove {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_approve(context, self).await
+    }
+}
+
+/// Transfer from an allowance arguments
+#[derive(Clone, Debug)]
+pub struct TxTransferFrom<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The address spending the allowance
+    pub spender: C::Address,
+    /// The owner of the tokens
+    pub owner: C::Address,
+    /// Transfer target address
+    pub target: C::Address,
+    /// Transferred token address
+    pub token: C::Address,
+    /// Transferred token amount
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxTransferFrom<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxTransferFrom {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxTransferFrom<C> {
+    /// The address spending the allowance
+    pub fn spender(self, spender: C::Address) -> Self {
+        Self { spender, ..self }
+    }
+
+    /// The owner of the tokens
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// Transfer target address
+    pub fn receiver(self, target: C::Address) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Transferred token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// Transferred token amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxTransferFrom {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_transfer_from(context, self).await
+    }
+}
+
 /// Shielded transfer-specific arguments
 #[derive(Clone, Debug)]
 pub struct TxShieldedTransferData<C: NamadaTypes = SdkTypes> {
@@ -360,6 +519,8 @@ pub struct TxShieldedTransfer<C: NamadaTypes = SdkTypes> {
     /// Generate an ephemeral signing key to be used only once to sign the
     /// wrapper tx
     pub disposable_signing_key: bool,
+    /// The strategy to select the notes that fund the transfer
+    pub note_selection: NoteSelection,
     /// Path to the TX WASM code file
     pub tx_code_path: PathBuf,
 }
@@ -457,6 +618,8 @@ pub struct TxUnshieldingTransfer<C: NamadaTypes = SdkTypes> {
     /// Generate an ephemeral signing key to be used only once to sign the
     /// wrapper tx
     pub disposable_signing_key: bool,
+    /// The strategy to select the notes that fund the transfer
+    pub note_selection: NoteSelection,
     /// Path to the TX WASM code file
     pub tx_code_path: PathBuf,
 }
@@ -471,6 +634,100 @@ impl TxUnshieldingTransfer {
     }
 }
 
+/// Shielded note consolidation transaction arguments
+#[derive(Clone, Debug)]
+pub struct ShieldedConsolidate<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The spending key whose notes are consolidated
+    pub source: C::SpendingKey,
+    /// The payment address that receives the consolidated notes
+    pub target: C::PaymentAddress,
+    /// Only consolidate the notes of this token
+    pub token: Option<C::Address>,
+    /// The maximum number of notes to merge in a single transaction
+    pub max_notes: Option<usize>,
+    /// Optional additional keys for gas payment
+    pub gas_spending_keys: Vec<C::SpendingKey>,
+    /// Generate an ephemeral signing key to be used only once to sign the
+    /// wrapper txs
+    pub disposable_signing_key: bool,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for ShieldedConsolidate<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        ShieldedConsolidate {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> ShieldedConsolidate<C> {
+    /// Only consolidate the notes of this token
+    pub fn token(self, token: C::Address) -> Self {
+        Self {
+            token: Some(token),
+            ..self
+        }
+    }
+
+    /// The maximum number of notes to merge in a single transaction
+    pub fn max_notes(self, max_notes: usize) -> Self {
+        Self {
+            max_notes: Some(max_notes),
+            ..self
+        }
+    }
+
+    /// Optional additional keys for gas payment
+    pub fn gas_spending_keys(
+        self,
+        gas_spending_keys: Vec<C::SpendingKey>,
+    ) -> Self {
+        Self {
+            gas_spending_keys,
+            ..self
+        }
+    }
+
+    /// Generate an ephemeral signing key to sign the wrapper txs
+    pub fn disposable_signing_key(self, disposable_signing_key: bool) -> Self {
+        Self {
+            disposable_signing_key,
+            ..self
+        }
+    }
+}
+
+impl ShieldedConsolidate {
+    /// Plan the chain of consolidations of this builder, to be built and
+    /// submitted one at a time, in order
+    pub async fn plan(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<Vec<NoteConsolidation>> {
+        tx::plan_shielded_consolidation(context, self).await
+    }
+
+    /// Build the transaction of the first of the given planned
+    /// consolidations, reserving the notes of the later ones
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+        consolidation: &NoteConsolidation,
+        later: &[NoteConsolidation],
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_shielded_consolidation(context, self, consolidation, later)
+            .await
+    }
+}
+
 /// IBC transfer transaction arguments
 #[derive(Clone, Debug)]
 pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
@@ -632,6 +889,10 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type parameter change
+    pub is_parameter_change: bool,
+    /// Flag if proposal is of type software upgrade
+    pub is_upgrade: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -673,6 +934,19 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type parameter change
+    pub fn is_parameter_change(self, is_parameter_change: bool) -> Self {
+        Self {
+            is_parameter_change,
+            ..self
+        }
+    }
+
+    /// Flag if proposal is of type software upgrade
+    pub fn is_upgrade(self, is_upgrade: bool) -> Self {
+        Self { is_upgrade, ..self }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -736,6 +1010,64 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_parameter_change {
+            let proposal = ParameterChangeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+                None,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_parameter_change_proposal(context, self, proposal).await
+        } else if self.is_upgrade {
+            let proposal = UpgradeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+                None,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_upgrade_proposal(context, self, proposal).await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -850,6 +1182,10 @@ pub struct TxInitAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account multisignature threshold
     pub threshold: Option<u8>,
+    /// Vesting schedules locking a part of the account's balances
+    pub vesting: Vec<VestingSchedule>,
+    /// The signing policy of the new account
+    pub policy: Option<AccountPolicy>,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxInitAccount<C> {
@@ -881,6 +1217,19 @@ impl<C: NamadaTypes> TxInitAccount<C> {
         }
     }
 
+    /// Vesting schedules to lock a part of the new account's balances
+    pub fn vesting(self, vesting: Vec<VestingSchedule>) -> Self {
+        Self { vesting, ..self }
+    }
+
+    /// A signing policy to associate with the new account
+    pub fn policy(self, policy: AccountPolicy) -> Self {
+        Self {
+            policy: Some(policy),
+            ..self
+        }
+    }
+
     /// Path to the VP WASM code file
     pub fn vp_code_path(self, vp_code_path: PathBuf) -> Self {
         Self {
@@ -1068,6 +1417,8 @@ pub struct TxUpdateAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account threshold
     pub threshold: Option<u8>,
+    /// The new signing policy of the account
+    pub policy: Option<AccountPolicy>,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxUpdateAccount<C> {
@@ -1119,6 +1470,14 @@ impl<C: NamadaTypes> TxUpdateAccount<C> {
             ..self
         }
     }
+
+    /// The new signing policy of the account
+    pub fn policy(self, policy: AccountPolicy) -> Self {
+        Self {
+            policy: Some(policy),
+            ..self
+        }
+    }
 }
 
 impl TxUpdateAccount {
@@ -1269,9 +1628,76 @@ impl<C: NamadaTypes> Unbond<C> {
     }
 }
 
-/// Redelegation arguments
+/// Instant unbond arguments
 #[derive(Clone, Debug)]
-pub struct Redelegate<C: NamadaTypes = SdkTypes> {
+pub struct InstantUnbond<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address
+    pub validator: C::Address,
+    /// Amount of tokens to unbond from a bond
+    pub amount: token::Amount,
+    /// Source address for unbonding from delegations. For unbonding from
+    /// self-bonds, the validator is also the source
+    pub source: Option<C::Address>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl InstantUnbond {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_instant_unbond(context, self).await
+    }
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for InstantUnbond<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        InstantUnbond {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> InstantUnbond<C> {
+    /// Validator address
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Amount of tokens to unbond from a bond
+    pub fn amount(self, amount: token::Amount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Source address for unbonding from delegations. For unbonding from
+    /// self-bonds, the validator is also the source
+    pub fn source(self, source: C::Address) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+/// Redelegation arguments
+#[derive(Clone, Debug)]
+pub struct Redelegate<C: NamadaTypes = SdkTypes> {
     /// Common tx arguments
     pub tx: Tx<C>,
     /// Source validator address
@@ -1578,6 +2004,131 @@ impl ClaimRewards {
     }
 }
 
+/// Automatic reward compounding change arguments
+#[derive(Clone, Debug)]
+pub struct AutoCompoundChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address
+    pub validator: C::Address,
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
+    pub source: Option<C::Address>,
+    /// Whether the rewards of the bond are compounded automatically
+    pub enabled: bool,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for AutoCompoundChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        AutoCompoundChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> AutoCompoundChange<C> {
+    /// Validator address
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Source address of the bond
+    pub fn source(self, source: C::Address) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Whether the rewards of the bond are compounded automatically
+    pub fn enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl AutoCompoundChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_auto_compound_change(context, self).await
+    }
+}
+
+/// Reward recipient change arguments
+#[derive(Clone, Debug)]
+pub struct RewardRecipientChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Source address of the bonds (a delegator or a self-bonded validator)
+    pub source: C::Address,
+    /// The recipient of the rewards, if they're redirected away from the
+    /// source. It can be a transparent address or a shielded payment address.
+    pub recipient: Option<C::TransferTarget>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for RewardRecipientChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        RewardRecipientChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> RewardRecipientChange<C> {
+    /// Source address of the bonds
+    pub fn source(self, source: C::Address) -> Self {
+        Self { source, ..self }
+    }
+
+    /// The recipient of the rewards
+    pub fn recipient(self, recipient: C::TransferTarget) -> Self {
+        Self {
+            recipient: Some(recipient),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl RewardRecipientChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_reward_recipient_change(context, self).await
+    }
+}
+
 /// Query asset conversions
 #[derive(Clone, Debug)]
 pub struct QueryConversions<C: NamadaTypes = SdkTypes> {
@@ -1774,6 +2325,283 @@ impl ConsensusKeyChange {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Validator protocol and Ethereum bridge keys change args
+pub struct ValidatorKeysChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address (should be self)
+    pub validator: C::Address,
+    /// New protocol key
+    pub protocol_key: Option<C::PublicKey>,
+    /// New Ethereum bridge cold key
+    pub eth_cold_key: Option<C::PublicKey>,
+    /// New Ethereum bridge hot key
+    pub eth_hot_key: Option<C::PublicKey>,
+    /// Generate a new protocol key, if none is given
+    pub gen_protocol_key: bool,
+    /// Generate a new Ethereum bridge cold key, if none is given
+    pub gen_eth_cold_key: bool,
+    /// Generate a new Ethereum bridge hot key, if none is given
+    pub gen_eth_hot_key: bool,
+    /// Don't encrypt the keypair
+    pub unsafe_dont_encrypt: bool,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for ValidatorKeysChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        ValidatorKeysChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> ValidatorKeysChange<C> {
+    /// Validator address (should be self)
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// New protocol key
+    pub fn protocol_key(self, protocol_key: C::PublicKey) -> Self {
+        Self {
+            protocol_key: Some(protocol_key),
+            ..self
+        }
+    }
+
+    /// New Ethereum bridge cold key
+    pub fn eth_cold_key(self, eth_cold_key: C::PublicKey) -> Self {
+        Self {
+            eth_cold_key: Some(eth_cold_key),
+            ..self
+        }
+    }
+
+    /// New Ethereum bridge hot key
+    pub fn eth_hot_key(self, eth_hot_key: C::PublicKey) -> Self {
+        Self {
+            eth_hot_key: Some(eth_hot_key),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl ValidatorKeysChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_change_validator_keys(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Liquid staking change args
+pub struct LiquidStakingChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address (should be self)
+    pub validator: C::Address,
+    /// Whether new delegations to the validator are liquid staked
+    pub enabled: bool,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for LiquidStakingChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        LiquidStakingChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> LiquidStakingChange<C> {
+    /// Validator address (should be self)
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Whether new delegations to the validator are liquid staked
+    pub fn enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl LiquidStakingChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_validator_liquid_staking_change(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Commission schedule change args
+pub struct CommissionScheduleChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address (should be self)
+    pub validator: C::Address,
+    /// Future-dated commission rate changes, keyed by the epoch from which
+    /// they take effect
+    pub changes: BTreeMap<Epoch, Dec>,
+    /// Commission tiers by delegation size, keyed by the minimum delegated
+    /// amount
+    pub tiers: BTreeMap<token::Amount, Dec>,
+    /// The epoch until which the validator commits not to raise its
+    /// commission rate
+    pub no_raise_until: Option<Epoch>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for CommissionScheduleChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        CommissionScheduleChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> CommissionScheduleChange<C> {
+    /// Validator address (should be self)
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Schedule a commission rate change from the given epoch
+    pub fn change(mut self, epoch: Epoch, rate: Dec) -> Self {
+        self.changes.insert(epoch, rate);
+        self
+    }
+
+    /// Charge the given commission rate on delegations of at least the given
+    /// amount
+    pub fn tier(mut self, min_amount: token::Amount, rate: Dec) -> Self {
+        self.tiers.insert(min_amount, rate);
+        self
+    }
+
+    /// Commit not to raise the commission rate until the given epoch
+    pub fn no_raise_until(self, epoch: Epoch) -> Self {
+        Self {
+            no_raise_until: Some(epoch),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl CommissionScheduleChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_validator_commission_schedule_change(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Max delegation change args
+pub struct MaxDelegationChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address (should be self)
+    pub validator: C::Address,
+    /// The maximum amount that may be delegated to the validator, if any
+    pub max_delegation: Option<token::Amount>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for MaxDelegationChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        MaxDelegationChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> MaxDelegationChange<C> {
+    /// Validator address (should be self)
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// The maximum amount that may be delegated to the validator, if any
+    pub fn max_delegation(self, max_delegation: Option<token::Amount>) -> Self {
+        Self {
+            max_delegation,
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl MaxDelegationChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_validator_max_delegation_change(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Commission rate change args
 pub struct MetaDataChange<C: NamadaTypes = SdkTypes> {
@@ -2180,6 +3008,15 @@ pub struct ShieldedSync<C: NamadaTypes = SdkTypes> {
     pub retry_strategy: RetryStrategy,
 }
 
+/// Query the history of the shielded txs of a viewing key
+#[derive(Clone, Debug)]
+pub struct ShieldedHistory<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The viewing key whose history to query
+    pub viewing_key: C::ViewingKey,
+}
+
 /// Query PoS commission rate
 #[derive(Clone, Debug)]
 pub struct QueryCommissionRate<C: NamadaTypes = SdkTypes> {
@@ -2209,6 +3046,15 @@ pub struct QuerySlashes<C: NamadaTypes = SdkTypes> {
     pub validator: Option<C::Address>,
 }
 
+/// Query the projected losses of a bond owner to the enqueued PoS slashes
+#[derive(Clone, Debug)]
+pub struct QuerySlashProjection<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of the bond owner
+    pub owner: C::Address,
+}
+
 /// Query PoS rewards
 #[derive(Clone, Debug)]
 pub struct QueryRewards<C: NamadaTypes = SdkTypes> {
@@ -6,6 +6,7 @@ use namada_core::dec::Dec;
 use namada_core::ethereum_events::EthAddress;
 use namada_core::{arith, storage};
 use namada_events::EventError;
+use namada_proof_of_stake::StakeLimitError;
 use namada_tx::Tx;
 use prost::EncodeError;
 use tendermint_rpc::Error as RpcError;
@@ -41,6 +42,9 @@ pub enum Error {
     /// Arithmetic error
     #[error("Arithmetic {0}")]
     Arith(#[from] arith::Error),
+    /// Stake limits errors
+    #[error("{0}")]
+    StakeLimit(#[from] StakeLimitError),
     /// Any Other errors that are uncategorized
     #[error("{0}")]
     Other(String),
@@ -114,6 +118,12 @@ pub enum TxSubmitError {
     /// Invalid commission rate set
     #[error("Invalid new commission rate, received {0}")]
     InvalidCommissionRate(Dec),
+    /// Commission rate change scheduled too early
+    #[error(
+        "Commission rate change scheduled for epoch {0} must be after the \
+         pipeline epoch {1}"
+    )]
+    CommissionChangeTooEarly(Epoch, Epoch),
     /// Invalid validator address
     #[error("The address {0} doesn't belong to any known validator account.")]
     InvalidValidatorAddress(Address),
@@ -187,6 +197,12 @@ pub enum TxSubmitError {
          unbonded. Amount to unbond is {1} and the total bonds is {2}."
     )]
     LowerBondThanUnbond(Address, String, String),
+    /// Instant unbonds are not enabled by governance
+    #[error("Instant unbonds are not enabled.")]
+    InstantUnbondDisabled,
+    /// Invalid recipient of redirected rewards
+    #[error("The target {0} cannot receive redirected rewards.")]
+    InvalidRewardRecipient(String),
     /// Balance is too low
     #[error(
         "The balance of the source {0} of token {1} is lower than the amount \
@@ -199,6 +215,11 @@ pub enum TxSubmitError {
          required for fees. Amount of the fees is {2} and the balance is {3}."
     )]
     BalanceTooLowForFees(Address, Address, String, String),
+    /// Allowance is too low for the transfer
+    #[error(
+        "The allowance of {1} on token {2} of {0} is too low for the transfer."
+    )]
+    AllowanceTooLow(Address, Address, Address),
     /// Token Address does not exist on chain
     #[error("The token address {0} doesn't exist on chain.")]
     TokenDoesNotExist(Address),
@@ -276,6 +297,12 @@ pub enum TxSubmitError {
     /// Account threshold is not set
     #[error("Account threshold is invalid.")]
     InvalidAccountThreshold,
+    /// Invalid vesting schedule
+    #[error("Invalid vesting schedule: {0}")]
+    InvalidVestingSchedule(String),
+    /// Invalid account signing policy
+    #[error("Invalid account policy: {0}")]
+    InvalidAccountPolicy(String),
     /// Not enough signature
     #[error("Account threshold is {0} but the valid signatures are {1}.")]
     MissingSigningKeys(u8, u8),
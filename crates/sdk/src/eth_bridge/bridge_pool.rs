@@ -4,6 +4,7 @@
 
 use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use borsh_ext::BorshSerializeExt;
@@ -41,7 +42,9 @@ use crate::queries::{
     TransferToEthereumStatus, RPC,
 };
 use crate::rpc::{query_storage_value, query_wasm_code_hash, validate_amount};
-use crate::signing::{aux_signing_data, validate_transparent_fee};
+use crate::signing::{
+    aux_signing_data, validate_transparent_fee, SigningAction,
+};
 use crate::tx::prepare_tx;
 use crate::{args, MaybeSync, Namada, SigningTxData};
 
@@ -62,7 +65,7 @@ pub async fn build_bridge_pool_tx(
     }: args::EthereumBridgePool,
 ) -> Result<(Tx, SigningTxData), Error> {
     let sender_ = sender.clone();
-    let (transfer, tx_code_hash, signing_data) = futures::try_join!(
+    let (transfer, tx_code_hash) = futures::try_join!(
         validate_bridge_pool_tx(
             context,
             tx_args.force,
@@ -76,17 +79,33 @@ pub async fn build_bridge_pool_tx(
             fee_token,
         ),
         query_wasm_code_hash(context, code_path.to_string_lossy()),
-        aux_signing_data(
-            context,
-            &tx_args,
-            // token owner
-            Some(sender_.clone()),
-            // tx signer
-            Some(sender_),
-            vec![],
-            false,
-        ),
     )?;
+    // The sender is debited the transferred amount and the gas fees, if it
+    // pays them
+    let mut debits =
+        BTreeMap::from([(transfer.token_address(), transfer.transfer.amount)]);
+    if transfer.gas_fee.payer == sender_ {
+        let debit = debits.entry(transfer.gas_fee.token.clone()).or_default();
+        *debit = debit
+            .checked_add(transfer.gas_fee.amount)
+            .ok_or_else(|| Error::Other("Overflow in the debit".into()))?;
+    }
+    let actions: Vec<_> = debits
+        .into_iter()
+        .map(|(token, amount)| SigningAction::Debit { token, amount })
+        .collect();
+    let signing_data = aux_signing_data(
+        context,
+        &tx_args,
+        // token owner
+        Some(sender_.clone()),
+        // tx signer
+        Some(sender_),
+        vec![],
+        false,
+        &actions,
+    )
+    .await?;
     let (fee_amount, _) =
         validate_transparent_fee(context, &tx_args, &signing_data.fee_payer)
             .await?;
@@ -66,13 +66,17 @@ use signing::SigningTxData;
 use token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tx::{
-    ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
-    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    ProcessTxResponse, TX_APPROVE_WASM, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+    TX_BRIDGE_POOL_WASM, TX_CHANGE_AUTO_COMPOUND_WASM,
+    TX_CHANGE_COMMISSION_SCHEDULE_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_LIQUID_STAKING_WASM,
+    TX_CHANGE_MAX_DELEGATION_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CHANGE_REWARD_RECIPIENT_WASM, TX_CHANGE_VALIDATOR_KEYS_WASM,
     TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_INSTANT_UNBOND_WASM,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_TRANSFER_FROM_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
     TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
@@ -166,6 +170,45 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make a TxApprove builder from the given minimum set of arguments
+    fn new_approve(
+        &self,
+        owner: Address,
+        spender: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxApprove {
+        args::TxApprove {
+            owner,
+            spender,
+            token,
+            amount,
+            epoch_cap: None,
+            tx_code_path: PathBuf::from(TX_APPROVE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxTransferFrom builder from the given minimum set of arguments
+    fn new_transfer_from(
+        &self,
+        spender: Address,
+        owner: Address,
+        target: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxTransferFrom {
+        args::TxTransferFrom {
+            spender,
+            owner,
+            target,
+            token,
+            amount,
+            tx_code_path: PathBuf::from(TX_TRANSFER_FROM_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a TxShieldedTransfer builder from the given minimum set of
     /// arguments
     fn new_shielded_transfer(
@@ -179,6 +222,7 @@ pub trait Namada: NamadaIo {
             gas_spending_keys,
             tx_code_path: PathBuf::from(TX_TRANSFER_WASM),
             disposable_signing_key,
+            note_selection: Default::default(),
             tx: self.tx_builder(),
         }
     }
@@ -212,6 +256,26 @@ pub trait Namada: NamadaIo {
             data,
             gas_spending_keys,
             disposable_signing_key,
+            note_selection: Default::default(),
+            tx_code_path: PathBuf::from(TX_TRANSFER_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a ShieldedConsolidate builder from the given minimum set of
+    /// arguments
+    fn new_shielded_consolidation(
+        &self,
+        source: ExtendedSpendingKey,
+        target: PaymentAddress,
+    ) -> args::ShieldedConsolidate {
+        args::ShieldedConsolidate {
+            source,
+            target,
+            token: None,
+            max_notes: None,
+            gas_spending_keys: vec![],
+            disposable_signing_key: false,
             tx_code_path: PathBuf::from(TX_TRANSFER_WASM),
             tx: self.tx_builder(),
         }
@@ -229,6 +293,8 @@ pub trait Namada: NamadaIo {
             tx_code_path: PathBuf::from(TX_INIT_ACCOUNT_WASM),
             public_keys,
             threshold,
+            vesting: vec![],
+            policy: None,
         }
     }
 
@@ -270,6 +336,21 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make an InstantUnbond builder from the given minimum set of arguments
+    fn new_instant_unbond(
+        &self,
+        validator: Address,
+        amount: token::Amount,
+    ) -> args::InstantUnbond {
+        args::InstantUnbond {
+            validator,
+            amount,
+            source: None,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_INSTANT_UNBOND_WASM),
+        }
+    }
+
     /// Make a Redelegation builder for the given minimum set of arguments
     fn new_redelegation(
         &self,
@@ -328,6 +409,8 @@ pub trait Namada: NamadaIo {
             proposal_data,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_parameter_change: false,
+            is_upgrade: false,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -345,6 +428,7 @@ pub trait Namada: NamadaIo {
             vp_code_path: None,
             public_keys,
             threshold: Some(threshold),
+            policy: None,
             tx_code_path: PathBuf::from(TX_UPDATE_ACCOUNT_WASM),
             tx: self.tx_builder(),
         }
@@ -381,6 +465,22 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make a CommissionScheduleChange builder from the given minimum set of
+    /// arguments
+    fn new_change_commission_schedule(
+        &self,
+        validator: Address,
+    ) -> args::CommissionScheduleChange {
+        args::CommissionScheduleChange {
+            validator,
+            changes: Default::default(),
+            tiers: Default::default(),
+            no_raise_until: None,
+            tx_code_path: PathBuf::from(TX_CHANGE_COMMISSION_SCHEDULE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make ConsensusKeyChange builder from the given minimum set of arguments
     fn new_change_consensus_key(
         &self,
@@ -396,6 +496,56 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make a ValidatorKeysChange builder from the given minimum set of
+    /// arguments
+    fn new_change_validator_keys(
+        &self,
+        validator: Address,
+    ) -> args::ValidatorKeysChange {
+        args::ValidatorKeysChange {
+            validator,
+            protocol_key: None,
+            eth_cold_key: None,
+            eth_hot_key: None,
+            gen_protocol_key: false,
+            gen_eth_cold_key: false,
+            gen_eth_hot_key: false,
+            unsafe_dont_encrypt: false,
+            tx_code_path: PathBuf::from(TX_CHANGE_VALIDATOR_KEYS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a LiquidStakingChange builder from the given minimum set of
+    /// arguments
+    fn new_change_liquid_staking(
+        &self,
+        validator: Address,
+        enabled: bool,
+    ) -> args::LiquidStakingChange {
+        args::LiquidStakingChange {
+            validator,
+            enabled,
+            tx_code_path: PathBuf::from(TX_CHANGE_LIQUID_STAKING_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a MaxDelegationChange builder from the given minimum set of
+    /// arguments
+    fn new_change_max_delegation(
+        &self,
+        validator: Address,
+        max_delegation: Option<token::Amount>,
+    ) -> args::MaxDelegationChange {
+        args::MaxDelegationChange {
+            validator,
+            max_delegation,
+            tx_code_path: PathBuf::from(TX_CHANGE_MAX_DELEGATION_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a CommissionRateChange builder from the given minimum set of
     /// arguments
     fn new_change_metadata(&self, validator: Address) -> args::MetaDataChange {
@@ -538,6 +688,37 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make an AutoCompoundChange builder from the given minimum set of
+    /// arguments
+    fn new_change_auto_compound(
+        &self,
+        validator: Address,
+        enabled: bool,
+    ) -> args::AutoCompoundChange {
+        args::AutoCompoundChange {
+            validator,
+            source: None,
+            enabled,
+            tx_code_path: PathBuf::from(TX_CHANGE_AUTO_COMPOUND_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a RewardRecipientChange builder from the given minimum set of
+    /// arguments
+    fn new_change_reward_recipient(
+        &self,
+        source: Address,
+        recipient: Option<TransferTarget>,
+    ) -> args::RewardRecipientChange {
+        args::RewardRecipientChange {
+            source,
+            recipient,
+            tx_code_path: PathBuf::from(TX_CHANGE_REWARD_RECIPIENT_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a Withdraw builder from the given minimum set of arguments
     fn new_add_erc20_transfer(
         &self,
@@ -10,7 +10,7 @@ use masp_primitives::transaction::Transaction;
 use namada_core::address::Address;
 use namada_core::chain::BlockHeight;
 use namada_core::masp::MaspEpoch;
-use namada_core::time::DurationSecs;
+use namada_core::time::{DateTimeUtc, DurationSecs};
 use namada_core::token::{Denomination, MaspDigitPos};
 use namada_events::extend::{
     IndexedMaspData, MaspDataRefs as MaspDataRefsAttr, MaspTxRef, MaspTxRefs,
@@ -25,15 +25,15 @@ pub use utilities::{IndexerMaspClient, LedgerMaspClient};
 
 use crate::error::{Error, QueryError};
 use crate::rpc::{
-    query_block, query_conversion, query_denom, query_masp_epoch,
-    query_max_block_time_estimate, query_native_token,
+    query_block, query_block_time, query_conversion, query_denom,
+    query_masp_epoch, query_max_block_time_estimate, query_native_token,
 };
 use crate::{token, MaybeSend, MaybeSync};
 
 /// Extract the relevant shield portions from a [`Tx`] MASP section or an IBC
 /// message, if any.
 #[allow(clippy::result_large_err)]
-fn extract_masp_tx(
+pub fn extract_masp_tx(
     tx: &Tx,
     masp_refs: &MaspTxRefs,
 ) -> Result<Vec<Transaction>, Error> {
@@ -211,6 +211,13 @@ impl<U: ShieldedUtils + MaybeSync + MaybeSend> ShieldedQueries<U>
         wrap_err!(query_block(client).await.map(|b| b.map(|h| h.height.0)))
     }
 
+    async fn query_block_time<C: Client + Sync>(
+        client: &C,
+        height: BlockHeight,
+    ) -> Result<DateTimeUtc, eyre::Report> {
+        wrap_err!(query_block_time(client, height).await)
+    }
+
     async fn query_max_block_time_estimate<C: Client + Sync>(
         client: &C,
     ) -> Result<DurationSecs, eyre::Report> {
@@ -10,8 +10,9 @@ use masp_primitives::transaction::Transaction as MaspTx;
 use namada_core::chain::BlockHeight;
 use namada_core::collections::HashMap;
 use namada_core::storage::TxIndex;
-use namada_events::extend::IndexedMaspData;
+use namada_events::extend::{IndexedMaspData, MaspTxRefs};
 use namada_io::Client;
+use namada_token::block_index::{CompactMaspTx, MaspBlockIndex};
 use namada_token::masp::utils::{
     IndexedNoteEntry, MaspClient, MaspClientCapabilities,
 };
@@ -20,14 +21,22 @@ use tokio::sync::Semaphore;
 
 use crate::error::{Error, QueryError};
 use crate::masp::{extract_masp_tx, get_indexed_masp_events_at_height};
+use crate::rpc::{query_masp_block_index, query_masp_block_notes};
 
 struct LedgerMaspClientInner<C> {
     client: C,
     semaphore: Semaphore,
+    /// Index of the blocks with MASP txs served by the node, to avoid
+    /// fetching the blocks with no MASP notes. Nodes that don't serve it
+    /// leave this empty.
+    block_index: init_once::InitOnce<Option<MaspBlockIndex>>,
 }
 
-/// An inefficient MASP client which simply uses a
-/// client to the blockchain to query it
+/// A MASP client which simply uses a client to the blockchain to query it.
+/// If the node serves an index of the blocks with MASP txs, it's used to
+/// skip the other blocks, otherwise every block of the range is fetched.
+/// The MASP txs of a block are located from the compact notes that the node
+/// keeps for the recent blocks, or else from the events of the block.
 pub struct LedgerMaspClient<C> {
     inner: Arc<LedgerMaspClientInner<C>>,
 }
@@ -48,6 +57,7 @@ impl<C> LedgerMaspClient<C> {
             inner: Arc::new(LedgerMaspClientInner {
                 client,
                 semaphore: Semaphore::new(max_concurrent_fetches),
+                block_index: init_once::InitOnce::new(),
             }),
         }
     }
@@ -66,21 +76,52 @@ impl<C: Client + Send + Sync> MaspClient for LedgerMaspClient<C> {
         from: BlockHeight,
         to: BlockHeight,
     ) -> Result<Vec<IndexedNoteEntry>, Error> {
+        let maybe_block_index = self
+            .inner
+            .block_index
+            .try_init_async(async {
+                let _permit = self.inner.semaphore.acquire().await.unwrap();
+                query_masp_block_index(&self.inner.client)
+                    .await
+                    .ok()
+                    .flatten()
+            })
+            .await
+            .and_then(Option::as_ref);
+
         // Fetch all the transactions we do not have yet
         let mut txs = vec![];
 
         for height in from.0..=to.0 {
-            let maybe_txs_results = async {
+            // Skip the blocks that the index excludes
+            if let Some(block_index) = maybe_block_index {
+                if !block_index.may_contain(height.into()) {
+                    continue;
+                }
+            }
+
+            // Only the nodes serving the block index keep the compact notes
+            let maybe_block_notes = if maybe_block_index.is_some() {
                 let _permit = self.inner.semaphore.acquire().await.unwrap();
 
-                get_indexed_masp_events_at_height(
-                    &self.inner.client,
-                    height.into(),
-                )
-                .await
+                query_masp_block_notes(&self.inner.client, height.into())
+                    .await?
+            } else {
+                None
             };
 
-            let txs_results = maybe_txs_results.await?;
+            let txs_results = match maybe_block_notes {
+                Some(block_notes) => indexed_masp_data(block_notes),
+                None => {
+                    let _permit = self.inner.semaphore.acquire().await.unwrap();
+
+                    get_indexed_masp_events_at_height(
+                        &self.inner.client,
+                        height.into(),
+                    )
+                    .await?
+                }
+            };
             if txs_results.is_empty() {
                 continue;
             };
@@ -686,6 +727,29 @@ impl MaspClient for IndexerMaspClient {
     }
 }
 
+// Group the references to the MASP txs in the compact notes of a block by the
+// txs that carry them
+fn indexed_masp_data(block_notes: Vec<CompactMaspTx>) -> Vec<IndexedMaspData> {
+    let mut masp_data: Vec<IndexedMaspData> = vec![];
+    for CompactMaspTx {
+        indexed_tx,
+        masp_ref,
+        ..
+    } in block_notes
+    {
+        match masp_data.last_mut() {
+            Some(data) if data.tx_index == indexed_tx.index => {
+                data.masp_refs.0.push(masp_ref);
+            }
+            _ => masp_data.push(IndexedMaspData {
+                tx_index: indexed_tx.index,
+                masp_refs: MaspTxRefs(vec![masp_ref]),
+            }),
+        }
+    }
+    masp_data
+}
+
 #[allow(clippy::result_large_err)]
 fn index_txs(
     txs: &mut Vec<(IndexedTx, MaspTx)>,
@@ -7,7 +7,7 @@ use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
-use namada_account::{Account, AccountPublicKeysMap};
+use namada_account::{Account, AccountPolicy, VestingSchedule};
 use namada_core::address::Address;
 use namada_core::arith::checked;
 use namada_core::chain::{BlockHeader, BlockHeight, Epoch};
@@ -22,6 +22,7 @@ use namada_core::uint::Uint;
 use namada_ibc::event::IbcEventType;
 use namada_state::{DBIter, LastBlock, StateRead, StorageHasher, DB};
 use namada_storage::{ResultExt, StorageRead};
+use namada_token::block_index::{CompactMaspTx, MaspBlockIndex};
 use namada_token::masp::MaspTokenRewardData;
 use namada_token::storage_key::masp_token_map_key;
 use namada_tx::data::DryRunResult;
@@ -101,6 +102,13 @@ router! {SHELL,
     // Conversion state access - read conversion
     ( "masp_reward_tokens" ) -> Vec<MaspTokenRewardData> = masp_reward_tokens,
 
+    // Index of the blocks with MASP txs up to the last committed block
+    ( "masp_block_index" ) -> Option<MaspBlockIndex> = masp_block_index,
+
+    // Compact note data of the MASP txs of a block, if still kept
+    ( "masp_block_notes" / [height: BlockHeight] )
+        -> Option<Vec<CompactMaspTx>> = masp_block_notes,
+
     // Block results access - read bit-vec
     ( "results" ) -> Vec<BlockResults> = read_results,
 
@@ -110,6 +118,12 @@ router! {SHELL,
     // Query account subspace
     ( "account" / [owner: Address] ) -> Option<Account> = account,
 
+    // Query the vesting schedules of an account
+    ( "account_vesting" / [owner: Address] ) -> Vec<VestingSchedule> = account_vesting,
+
+    // Query the signing policy of an account
+    ( "account_policy" / [owner: Address] ) -> AccountPolicy = account_policy,
+
     // Query public key revealad
     ( "revealed" / [owner: Address] ) -> bool = revealed,
 
@@ -157,6 +171,30 @@ where
     )
 }
 
+/// Read the node-local index of the blocks with MASP txs
+fn masp_block_index<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Option<MaspBlockIndex>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_token::block_index::read_block_index(ctx.state.db())
+}
+
+/// Read the compact note data of the MASP txs of a committed block, if they
+/// are still kept
+fn masp_block_notes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+) -> namada_storage::Result<Option<Vec<CompactMaspTx>>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_token::block_index::read_block_notes(ctx.state.db(), height)
+}
+
 /// Get the block header associated with the requested height
 fn block_header<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -450,26 +488,45 @@ where
         }
     };
 
-    if let Some(past_height_limit) = ctx.storage_read_past_height_limit {
-        if checked!(queried_height + past_height_limit)? < last_committed_height
-        {
-            return Err(namada_storage::Error::new(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!(
-                    "Cannot query more than {past_height_limit} blocks in the \
-                     past (configured via \
-                     `shell.storage_read_past_height_limit`)."
-                ),
-            )));
+    // Past values of any key can be read from the archive, if enabled
+    let archived_value = if queried_height < last_committed_height {
+        ctx.state
+            .db()
+            .read_archived_subspace_val(&storage_key, queried_height)
+            .into_storage_result()?
+    } else {
+        None
+    };
+    let value = match archived_value {
+        Some(value) => value,
+        None => {
+            if let Some(past_height_limit) = ctx.storage_read_past_height_limit
+            {
+                if checked!(queried_height + past_height_limit)?
+                    < last_committed_height
+                {
+                    return Err(namada_storage::Error::new(
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!(
+                                "Cannot query more than {past_height_limit} \
+                                 blocks in the past (configured via \
+                                 `shell.storage_read_past_height_limit`)."
+                            ),
+                        ),
+                    ));
+                }
+            }
+
+            ctx.state
+                .db_read_with_height(&storage_key, queried_height)
+                .into_storage_result()?
+                .0
         }
-    }
+    };
 
-    match ctx
-        .state
-        .db_read_with_height(&storage_key, queried_height)
-        .into_storage_result()?
-    {
-        (Some(value), _gas) => {
+    match value {
+        Some(value) => {
             let proof = if request.prove {
                 let proof = ctx
                     .state
@@ -486,7 +543,7 @@ where
                 height: queried_height,
             })
         }
-        (None, _gas) => {
+        None => {
             let proof = if request.prove {
                 let proof = ctx
                     .state
@@ -515,26 +572,52 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    require_latest_height(&ctx, request)?;
-
-    let iter = namada_storage::iter_prefix_bytes(ctx.state, &storage_key)?;
-    let data: namada_storage::Result<Vec<PrefixValue>> = iter
-        .map(|iter_result| {
-            let (key, value) = iter_result?;
-            Ok(PrefixValue { key, value })
-        })
-        .collect();
-    let data = data?;
+    let last_committed_height = ctx.state.in_mem().get_last_block_height();
     let queried_height = {
         let height: BlockHeight = request.height.into();
         let is_last_height_query = height.0 == 0;
 
         if hints::likely(is_last_height_query) {
-            ctx.state.in_mem().get_last_block_height()
+            last_committed_height
         } else {
             height
         }
     };
+
+    let data = if queried_height < last_committed_height {
+        // Past key-vals can only be read from the archive, if enabled
+        let Some(key_vals) = ctx
+            .state
+            .db()
+            .read_archived_subspace_prefix(Some(&storage_key), queried_height)
+            .into_storage_result()?
+        else {
+            return Err(namada_storage::Error::new_const(
+                "Past key-vals can only be queried from the archive, which is \
+                 not enabled on this node (configured via \
+                 `shell.archival_mode`) or doesn't go back to the queried \
+                 height",
+            ));
+        };
+        key_vals
+            .into_iter()
+            .map(|(key, value)| {
+                let key = storage::Key::parse(key).into_storage_result()?;
+                Ok(PrefixValue { key, value })
+            })
+            .collect::<namada_storage::Result<Vec<PrefixValue>>>()?
+    } else {
+        require_latest_height(&ctx, request)?;
+
+        let iter = namada_storage::iter_prefix_bytes(ctx.state, &storage_key)?;
+        let data: namada_storage::Result<Vec<PrefixValue>> = iter
+            .map(|iter_result| {
+                let (key, value) = iter_result?;
+                Ok(PrefixValue { key, value })
+            })
+            .collect();
+        data?
+    };
     let proof = if request.prove {
         let mut ops = vec![];
         for PrefixValue { key, value } in &data {
@@ -634,11 +717,12 @@ where
     let account_exists = namada_account::exists(ctx.state, &owner)?;
 
     if account_exists {
-        let public_keys = namada_account::public_keys(ctx.state, &owner)?;
+        let public_keys_map =
+            namada_account::public_keys_index_map(ctx.state, &owner)?;
         let threshold = namada_account::threshold(ctx.state, &owner)?;
 
         Ok(Some(Account {
-            public_keys_map: AccountPublicKeysMap::from_iter(public_keys),
+            public_keys_map,
             address: owner,
             threshold: threshold.unwrap_or(1),
         }))
@@ -647,6 +731,28 @@ where
     }
 }
 
+fn account_vesting<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<Vec<VestingSchedule>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_account::vesting_schedules(ctx.state, &owner)
+}
+
+fn account_policy<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<AccountPolicy>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_account::account_policy(ctx.state, &owner)
+}
+
 fn revealed<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     owner: Address,
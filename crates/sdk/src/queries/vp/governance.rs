@@ -2,7 +2,9 @@
 
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::storage::proposal::StorageProposal;
-use namada_governance::utils::{ProposalResult, Vote};
+use namada_governance::utils::{
+    ChoiceResult, ProposalResult, Vote, VotePower,
+};
 use namada_state::{DBIter, StorageHasher, DB};
 
 use crate::queries::types::RequestCtx;
@@ -13,6 +15,8 @@ router! {GOV,
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
     ( "parameters" ) -> GovernanceParameters = parameters,
     ( "stored_proposal_result" / [id: u64] ) -> Option<ProposalResult> = proposal_result,
+    ( "stored_proposal_result" / [id: u64] / "choice" ) -> Option<ChoiceResult> = proposal_choice_result,
+    ( "stored_proposal_result" / [id: u64] / "veto_power" ) -> VotePower = proposal_veto_power,
 }
 
 /// Query the provided proposal id
@@ -61,3 +65,28 @@ where
 {
     namada_governance::storage::get_proposal_result(ctx.state, id)
 }
+
+/// Get the result of the options of a governance proposal stored in storage
+fn proposal_choice_result<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<ChoiceResult>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proposal_choice_result(ctx.state, id)
+}
+
+/// Get the voting power of the veto votes on a governance proposal stored in
+/// storage
+fn proposal_veto_power<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<VotePower>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proposal_veto_power(ctx.state, id)
+}
@@ -7,36 +7,44 @@ use namada_core::address::Address;
 use namada_core::arith::{self, checked};
 use namada_core::chain::Epoch;
 use namada_core::collections::{HashMap, HashSet};
+use namada_core::dec::Dec;
 use namada_core::key::common;
 use namada_core::token;
-use namada_proof_of_stake::parameters::PosParams;
+use namada_proof_of_stake::instant_unbond::quote_instant_unbond;
+use namada_proof_of_stake::liquid_staking::query_share_price;
+use namada_proof_of_stake::parameters::{ExtraPosParams, PosParams};
+use namada_proof_of_stake::performance::read_validator_performance_history;
 use namada_proof_of_stake::queries::{
     find_delegation_validators, find_delegations,
 };
 use namada_proof_of_stake::slashing::{
-    find_all_enqueued_slashes, find_all_slashes,
+    find_all_enqueued_slashes, find_all_slashes, simulate_enqueued_slashes,
 };
+use namada_proof_of_stake::stake_limits::read_pipeline_stake;
 use namada_proof_of_stake::storage::{
-    bond_handle, read_all_validator_addresses,
+    bond_handle, commission_schedules_handle, read_all_validator_addresses,
     read_below_capacity_validator_set_addresses_with_stake,
-    read_consensus_validator_set_addresses_with_stake, read_pos_params,
-    read_total_active_stake, read_total_stake, read_validator_avatar,
-    read_validator_description, read_validator_discord_handle,
-    read_validator_email, read_validator_last_slash_epoch,
-    read_validator_max_commission_rate_change, read_validator_name,
-    read_validator_stake, read_validator_website, unbond_handle,
+    read_consensus_validator_set_addresses_with_stake, read_owned_pos_params,
+    read_pos_params, read_total_active_stake, read_total_stake,
+    read_validator_avatar, read_validator_description,
+    read_validator_discord_handle, read_validator_email,
+    read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
+    read_validator_max_delegation, read_validator_name, read_validator_stake,
+    read_validator_website, reward_recipients_handle, unbond_handle,
     validator_commission_rate_handle, validator_incoming_redelegations_handle,
-    validator_slashes_handle,
+    validator_performance_handle, validator_slashes_handle,
 };
 pub use namada_proof_of_stake::types::ValidatorStateInfo;
 use namada_proof_of_stake::types::{
     BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, CommissionPair,
-    Slash, ValidatorMetaData, WeightedValidator,
+    CommissionSchedule, InstantUnbondQuote, PipelineStake, RewardRecipient,
+    Slash, SlashProjection, ValidatorEpochPerformance, ValidatorMetaData,
+    WeightedValidator,
 };
 use namada_proof_of_stake::{bond_amount, query_reward_tokens};
 use namada_state::{DBIter, KeySeg, StorageHasher, DB};
 use namada_storage::collections::lazy_map;
-use namada_storage::OptionExt;
+use namada_storage::{OptionExt, ResultExt};
 
 use crate::governance;
 use crate::queries::types::RequestCtx;
@@ -60,6 +68,15 @@ router! {POS,
         ( "commission" / [validator: Address] / [epoch: opt Epoch] )
             -> CommissionPair = validator_commission,
 
+        ( "commission_schedule" / [validator: Address] )
+            -> Option<CommissionSchedule> = validator_commission_schedule,
+
+        ( "max_delegation" / [validator: Address] )
+            -> Option<token::Amount> = validator_max_delegation,
+
+        ( "pipeline_stake" / [validator: Address] )
+            -> PipelineStake = validator_pipeline_stake,
+
         ( "metadata" / [validator: Address] )
             -> Option<ValidatorMetaData> = validator_metadata,
 
@@ -71,6 +88,15 @@ router! {POS,
 
         ( "last_infraction_epoch" / [validator: Address] )
             -> Option<Epoch> = validator_last_infraction_epoch,
+
+        ( "liquid_share_price" / [validator: Address] )
+            -> Option<Dec> = validator_liquid_share_price,
+
+        ( "performance" / [validator: Address] / [epoch: opt Epoch] )
+            -> Option<ValidatorEpochPerformance> = validator_performance,
+
+        ( "performance_history" / [validator: Address] / [from_epoch: opt Epoch] )
+            -> BTreeMap<Epoch, ValidatorEpochPerformance> = validator_performance_history,
     },
 
     ( "validator_set" ) = {
@@ -83,6 +109,8 @@ router! {POS,
 
     ( "pos_params") -> PosParams = pos_params,
 
+    ( "extra_pos_params") -> ExtraPosParams = extra_pos_params,
+
     ( "total_stake" / [epoch: opt Epoch] )
         -> token::Amount = total_stake,
 
@@ -104,6 +132,9 @@ router! {POS,
     ( "rewards" / [validator: Address] / [source: opt Address] )
         -> token::Amount = rewards,
 
+    ( "reward_recipient" / [source: Address] )
+        -> Option<RewardRecipient> = reward_recipient,
+
     ( "bond_with_slashing" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
         -> token::Amount = bond_with_slashing,
 
@@ -128,6 +159,11 @@ router! {POS,
 
     ( "all_slashes" ) -> HashMap<Address, Vec<Slash>> = slashes,
 
+    ( "slash_projection" / [owner: Address] ) -> SlashProjection = slash_projection,
+
+    ( "instant_unbond_quote" / [source: Address] / [validator: Address] / [amount: token::DenominatedAmount] )
+        -> InstantUnbondQuote = instant_unbond_quote,
+
     ( "is_delegator" / [addr: Address ] / [epoch: opt Epoch] ) -> bool = is_delegator,
 
     ( "validator_by_tm_addr" / [tm_addr: String] )
@@ -193,6 +229,17 @@ where
     read_pos_params::<_, governance::Store<_>>(ctx.state)
 }
 
+/// Get the PoS parameters stored apart from the others
+fn extra_pos_params<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<ExtraPosParams>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(read_owned_pos_params(ctx.state)?.extra())
+}
+
 /// Find if the given address belongs to a validator account.
 fn is_validator<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -273,6 +320,48 @@ where
     })
 }
 
+/// Get the validator commission schedule
+fn validator_commission_schedule<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<CommissionSchedule>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    commission_schedules_handle().get(ctx.state, &validator)
+}
+
+/// Get the maximum amount that may be delegated to a validator, if any
+fn validator_max_delegation<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_validator_max_delegation(ctx.state, &validator)
+}
+
+/// Get the stake of a validator at the pipeline epoch that is subject to the
+/// stake limits
+fn validator_pipeline_stake<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<PipelineStake>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    read_pipeline_stake::<_, governance::Store<_>>(
+        ctx.state,
+        &validator,
+        current_epoch,
+    )
+}
+
 /// Get the validator metadata
 fn validator_metadata<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -580,6 +669,69 @@ where
     )
 }
 
+/// The recipient that a bond source redirected its rewards to, if any
+fn reward_recipient<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    source: Address,
+) -> namada_storage::Result<Option<RewardRecipient>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    reward_recipients_handle().get(ctx.state, &source)
+}
+
+/// Get a validator's performance in the given epoch or in the last epoch
+/// whose rewards have been distributed when `None`. Returns `None` when the
+/// validator wasn't in the consensus set in the epoch or when the epoch is no
+/// longer in its performance history.
+fn validator_performance<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+    epoch: Option<Epoch>,
+) -> namada_storage::Result<Option<ValidatorEpochPerformance>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let Some(epoch) = epoch.or_else(|| ctx.state.in_mem().last_epoch.prev())
+    else {
+        return Ok(None);
+    };
+    validator_performance_handle(&validator).get(ctx.state, &epoch)
+}
+
+/// Get a validator's performance in the epochs of its history, starting from
+/// the given epoch, if any
+fn validator_performance_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+    from_epoch: Option<Epoch>,
+) -> namada_storage::Result<BTreeMap<Epoch, ValidatorEpochPerformance>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_validator_performance_history(ctx.state, &validator, from_epoch)
+}
+
+/// Price of a share of a validator's liquid staking pool in the staking token
+fn validator_liquid_share_price<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<Dec>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    query_share_price::<_, governance::Store<_>, crate::token::Store<_>>(
+        ctx.state,
+        &validator,
+        current_epoch,
+    )
+}
+
 fn bonds_and_unbonds<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     source: Option<Address>,
@@ -660,6 +812,55 @@ where
     find_all_enqueued_slashes(ctx.state, current_epoch)
 }
 
+/// Projected token losses of a bond owner's bonds and unbonds to the enqueued
+/// slashes, before they are processed
+fn slash_projection<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<SlashProjection>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    // The simulated slashes are only written to a temporary write log that
+    // gets dropped with the query
+    let mut state = ctx.state.with_temp_write_log();
+    simulate_enqueued_slashes::<_, governance::Store<_>>(
+        &mut state,
+        &owner,
+        current_epoch,
+    )
+}
+
+/// Quote of the penalty and the released amount of an instant unbond of the
+/// given amount, in the native token's denomination
+fn instant_unbond_quote<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    source: Address,
+    validator: Address,
+    amount: token::DenominatedAmount,
+) -> namada_storage::Result<InstantUnbondQuote>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let amount = amount
+        .scale(token::NATIVE_MAX_DECIMAL_PLACES)
+        .into_storage_result()?;
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    // The instant unbond is only applied to a temporary write log that gets
+    // dropped with the query
+    let mut state = ctx.state.with_temp_write_log();
+    quote_instant_unbond::<_, governance::Store<_>>(
+        &mut state,
+        Some(&source),
+        &validator,
+        amount,
+        current_epoch,
+    )
+}
+
 /// Native validator address by looking up the Tendermint address
 fn validator_by_tm_addr<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -5,7 +5,8 @@ use namada_core::token;
 use namada_proof_of_stake::rewards::estimate_staking_reward_rate;
 use namada_state::{DBIter, StorageHasher, DB};
 use namada_token::{
-    get_effective_total_native_supply, read_denom, read_total_supply, Dec,
+    get_effective_total_native_supply, read_allowance, read_denom,
+    read_total_supply, Allowance, Dec,
 };
 
 use crate::queries::RequestCtx;
@@ -15,6 +16,7 @@ router! {TOKEN,
     ( "total_supply" / [token: Address] ) -> token::Amount = total_supply,
     ( "effective_native_supply" ) -> token::Amount = effective_native_supply,
     ( "staking_rewards_rate" ) -> Dec = staking_rewards_rate,
+    ( "allowance" / [token: Address] / [owner: Address] / [spender: Address] ) -> Option<Allowance> = allowance,
 }
 
 /// Get the number of decimal places (in base 10) for a
@@ -42,6 +44,20 @@ where
     read_total_supply(ctx.state, &token)
 }
 
+/// Get the allowance given by an owner to a spender on a token
+fn allowance<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+    owner: Address,
+    spender: Address,
+) -> namada_storage::Result<Option<Allowance>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_allowance(ctx.state, &token, &owner, &spender)
+}
+
 /// Get the effective total supply of the native token
 fn effective_native_supply<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
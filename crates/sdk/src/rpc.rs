@@ -10,7 +10,7 @@ use borsh::BorshDeserialize;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
-use namada_account::Account;
+use namada_account::{Account, AccountPolicy, VestingSchedule};
 use namada_core::address::{Address, InternalAddress};
 use namada_core::arith::checked;
 use namada_core::chain::{BlockHeight, Epoch};
@@ -20,7 +20,7 @@ use namada_core::ibc::IbcTokenHash;
 use namada_core::key::common;
 use namada_core::masp::MaspEpoch;
 use namada_core::storage::{BlockResults, Key, PrefixValue};
-use namada_core::time::DurationSecs;
+use namada_core::time::{DateTimeUtc, DurationSecs};
 use namada_core::token::{
     Amount, DenominatedAmount, Denomination, MaspDigitPos,
 };
@@ -32,7 +32,8 @@ use namada_governance::pgf::parameters::PgfParameters;
 use namada_governance::pgf::storage::steward::StewardDetail;
 use namada_governance::storage::proposal::StorageProposal;
 use namada_governance::utils::{
-    compute_proposal_result, ProposalResult, ProposalVotes, Vote,
+    compute_proposal_result, ChoiceResult, ProposalResult, ProposalVotes, Vote,
+    VotePower,
 };
 use namada_ibc::storage::{
     ibc_trace_key, ibc_trace_key_prefix, is_ibc_trace_key,
@@ -41,12 +42,14 @@ use namada_io::{display_line, edisplay_line, Client, Io};
 use namada_parameters::{storage as params_storage, EpochDuration};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
-    BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData,
-    WeightedValidator,
+    BondsAndUnbondsDetails, CommissionPair, CommissionSchedule,
+    InstantUnbondQuote, PipelineStake, RewardRecipient, SlashProjection,
+    ValidatorEpochPerformance, ValidatorMetaData, WeightedValidator,
 };
 use namada_state::LastBlock;
+use namada_token::block_index::{CompactMaspTx, MaspBlockIndex};
 use namada_token::masp::MaspTokenRewardData;
-use namada_token::Dec;
+use namada_token::{Allowance, Dec};
 use namada_tx::data::{BatchedTxResult, DryRunResult, ResultCode, TxResult};
 use namada_tx::event::{Batch as BatchAttr, Code as CodeAttr};
 use serde::Serialize;
@@ -184,6 +187,19 @@ pub async fn query_block<C: namada_io::Client + Sync>(
     convert_response::<C, _>(RPC.shell().last_block(client).await)
 }
 
+/// Query the time of the block at the given height.
+pub async fn query_block_time<C: namada_io::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<DateTimeUtc, error::Error> {
+    let response = convert_response::<C, _>(client.block(height.0).await)?;
+    DateTimeUtc::try_from(response.block.header.time).map_err(|err| {
+        Error::from(QueryError::General(format!(
+            "Invalid time of block {height}: {err}"
+        )))
+    })
+}
+
 /// A helper to unwrap client's response. Will shut down process on error.
 fn unwrap_client_response<C: namada_io::Client, T>(
     response: Result<T, C::Error>,
@@ -229,6 +245,21 @@ pub async fn get_token_total_supply<C: namada_io::Client + Sync>(
     convert_response::<C, _>(RPC.vp().token().total_supply(client, token).await)
 }
 
+/// Query the allowance given by an owner to a spender on a token
+pub async fn query_allowance<C: namada_io::Client + Sync>(
+    client: &C,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> Result<Option<Allowance>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .token()
+            .allowance(client, token, owner, spender)
+            .await,
+    )
+}
+
 /// Query the effective total supply of the native token
 pub async fn get_effective_native_supply<C: Client + Sync>(
     client: &C,
@@ -392,6 +423,25 @@ pub async fn query_masp_reward_tokens<C: namada_io::Client + Sync>(
     convert_response::<C, _>(RPC.shell().masp_reward_tokens(client).await)
 }
 
+/// Query the node-local index of the blocks with MASP txs, if the node
+/// maintains one
+pub async fn query_masp_block_index<C: namada_io::Client + Sync>(
+    client: &C,
+) -> Result<Option<MaspBlockIndex>, Error> {
+    convert_response::<C, _>(RPC.shell().masp_block_index(client).await)
+}
+
+/// Query the compact note data of the MASP txs of a block, if the node still
+/// keeps them
+pub async fn query_masp_block_notes<C: namada_io::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<Option<Vec<CompactMaspTx>>, Error> {
+    convert_response::<C, _>(
+        RPC.shell().masp_block_notes(client, &height).await,
+    )
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -738,7 +788,14 @@ impl TxResponse {
 pub async fn get_pos_params<C: namada_io::Client + Sync>(
     client: &C,
 ) -> Result<PosParams, error::Error> {
-    convert_response::<C, _>(RPC.vp().pos().pos_params(client).await)
+    let mut params: PosParams =
+        convert_response::<C, _>(RPC.vp().pos().pos_params(client).await)?;
+    // The extra parameters aren't part of the encoded parameters
+    let extra = convert_response::<C, _>(
+        RPC.vp().pos().extra_pos_params(client).await,
+    )?;
+    params.owned.set_extra(extra);
+    Ok(params)
 }
 
 /// Get all validators in the given epoch
@@ -873,6 +930,125 @@ pub async fn query_commission_rate<C: namada_io::Client + Sync>(
     )
 }
 
+/// Query and return validator's commission schedule, if any
+pub async fn query_commission_schedule<C: namada_io::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<CommissionSchedule>, Error> {
+    convert_response::<C, Option<CommissionSchedule>>(
+        RPC.vp()
+            .pos()
+            .validator_commission_schedule(client, validator)
+            .await,
+    )
+}
+
+/// Query and return the maximum amount that may be delegated to a validator,
+/// if any
+pub async fn query_validator_max_delegation<C: namada_io::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<token::Amount>, Error> {
+    convert_response::<C, Option<token::Amount>>(
+        RPC.vp()
+            .pos()
+            .validator_max_delegation(client, validator)
+            .await,
+    )
+}
+
+/// Query and return the stake of a validator at the pipeline epoch that is
+/// subject to the stake limits
+pub async fn query_validator_pipeline_stake<C: namada_io::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<PipelineStake, Error> {
+    convert_response::<C, PipelineStake>(
+        RPC.vp()
+            .pos()
+            .validator_pipeline_stake(client, validator)
+            .await,
+    )
+}
+
+/// Query and return validator's performance in the given epoch or in the last
+/// epoch whose rewards have been distributed when `None`
+pub async fn query_validator_performance<C: namada_io::Client + Sync>(
+    client: &C,
+    validator: &Address,
+    epoch: Option<Epoch>,
+) -> Result<Option<ValidatorEpochPerformance>, Error> {
+    convert_response::<C, Option<ValidatorEpochPerformance>>(
+        RPC.vp()
+            .pos()
+            .validator_performance(client, validator, &epoch)
+            .await,
+    )
+}
+
+/// Query and return validator's performance history, starting from the given
+/// epoch, if any
+pub async fn query_validator_performance_history<
+    C: namada_io::Client + Sync,
+>(
+    client: &C,
+    validator: &Address,
+    from_epoch: Option<Epoch>,
+) -> Result<BTreeMap<Epoch, ValidatorEpochPerformance>, Error> {
+    convert_response::<C, BTreeMap<Epoch, ValidatorEpochPerformance>>(
+        RPC.vp()
+            .pos()
+            .validator_performance_history(client, validator, &from_epoch)
+            .await,
+    )
+}
+
+/// Query and return the projected token losses of a bond owner's bonds and
+/// unbonds to the enqueued slashes, before they are processed
+pub async fn query_slash_projection<C: namada_io::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<SlashProjection, Error> {
+    convert_response::<C, SlashProjection>(
+        RPC.vp().pos().slash_projection(client, owner).await,
+    )
+}
+
+/// Query and return a quote of the penalty for instantly unbonding the given
+/// amount of a bond and of the amount that would be released
+pub async fn query_instant_unbond_quote<C: namada_io::Client + Sync>(
+    client: &C,
+    source: &Address,
+    validator: &Address,
+    amount: token::Amount,
+) -> Result<InstantUnbondQuote, Error> {
+    convert_response::<C, InstantUnbondQuote>(
+        RPC.vp()
+            .pos()
+            .instant_unbond_quote(
+                client,
+                source,
+                validator,
+                &amount.native_denominated(),
+            )
+            .await,
+    )
+}
+
+/// Query and return the price of a share of a validator's liquid staking pool
+/// in the staking token, if any shares have been minted
+pub async fn query_liquid_share_price<C: namada_io::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<Dec>, Error> {
+    convert_response::<C, Option<Dec>>(
+        RPC.vp()
+            .pos()
+            .validator_liquid_share_price(client, validator)
+            .await,
+    )
+}
+
 /// Query and return validator's metadata, including the commission rate and max
 /// commission rate change
 pub async fn query_metadata<C: namada_io::Client + Sync>(
@@ -919,6 +1095,16 @@ pub async fn query_bond<C: namada_io::Client + Sync>(
     )
 }
 
+/// Query the recipient that a bond source redirected its rewards to, if any
+pub async fn query_reward_recipient<C: namada_io::Client + Sync>(
+    client: &C,
+    source: &Address,
+) -> Result<Option<RewardRecipient>, error::Error> {
+    convert_response::<C, Option<RewardRecipient>>(
+        RPC.vp().pos().reward_recipient(client, source).await,
+    )
+}
+
 /// Query a validator's bonds for a given epoch
 pub async fn query_last_infraction_epoch<C: namada_io::Client + Sync>(
     client: &C,
@@ -942,6 +1128,26 @@ pub async fn get_account_info<C: namada_io::Client + Sync>(
     )
 }
 
+/// Query the vesting schedules of an account
+pub async fn get_account_vesting_schedules<C: namada_io::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<Vec<VestingSchedule>, error::Error> {
+    convert_response::<C, Vec<VestingSchedule>>(
+        RPC.shell().account_vesting(client, owner).await,
+    )
+}
+
+/// Query the signing policy of an account
+pub async fn get_account_policy<C: namada_io::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<AccountPolicy, error::Error> {
+    convert_response::<C, AccountPolicy>(
+        RPC.shell().account_policy(client, owner).await,
+    )
+}
+
 /// Query if the public_key is revealed
 pub async fn is_public_key_revealed<C: namada_io::Client + Sync>(
     client: &C,
@@ -988,7 +1194,24 @@ pub async fn query_proposal_result<C: namada_io::Client + Sync>(
     )?;
 
     let proposal_result = match stored_proposal_result {
-        Some(proposal_result) => proposal_result,
+        Some(mut proposal_result) => {
+            // The veto power and the result of the options aren't part of the
+            // encoded result
+            proposal_result.total_veto_power = convert_response::<C, VotePower>(
+                RPC.vp()
+                    .gov()
+                    .proposal_veto_power(client, &proposal_id)
+                    .await,
+            )?;
+            proposal_result.choice_result =
+                convert_response::<C, Option<ChoiceResult>>(
+                    RPC.vp()
+                        .gov()
+                        .proposal_choice_result(client, &proposal_id)
+                        .await,
+                )?;
+            proposal_result
+        }
         None => {
             let tally_epoch = current_epoch;
 
@@ -1005,7 +1228,10 @@ pub async fn query_proposal_result<C: namada_io::Client + Sync>(
                     .await
                     .unwrap_or_default();
 
-            let mut proposal_votes = ProposalVotes::default();
+            let mut proposal_votes = ProposalVotes {
+                voting_mode: proposal.voting_mode.clone(),
+                ..ProposalVotes::default()
+            };
 
             for vote in votes {
                 match vote.is_validator() {
@@ -1045,10 +1271,13 @@ pub async fn query_proposal_result<C: namada_io::Client + Sync>(
                     }
                 }
             }
+            let veto_threshold =
+                query_governance_parameters(client).await.veto_threshold;
             compute_proposal_result(
                 proposal_votes,
                 total_active_voting_power,
                 tally_type,
+                veto_threshold,
             )?
         }
     };
@@ -13,7 +13,9 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::components::sapling::fees::{
     InputView, OutputView,
 };
-use namada_account::{AccountPublicKeysMap, InitAccount, UpdateAccount};
+use namada_account::{
+    AccountAction, AccountPublicKeysMap, InitAccount, UpdateAccount,
+};
 use namada_core::address::{Address, ImplicitAddress, InternalAddress, MASP};
 use namada_core::arith::checked;
 use namada_core::collections::{HashMap, HashSet};
@@ -75,6 +77,27 @@ pub struct SigningTxData {
     pub fee_payer: common::PublicKey,
 }
 
+/// An effect of a tx on the account of its owner that may require a specific
+/// signature threshold under the account's signing policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum SigningAction {
+    /// An action with its own threshold
+    Action(AccountAction),
+    /// A debit of some amount of a token
+    Debit {
+        /// The debited token
+        token: Address,
+        /// The debited amount
+        amount: Amount,
+    },
+}
+
+impl From<AccountAction> for SigningAction {
+    fn from(action: AccountAction) -> Self {
+        Self::Action(action)
+    }
+}
+
 /// Find the public key for the given address and try to load the keypair
 /// for it from the wallet.
 ///
@@ -233,7 +256,7 @@ where
     }
 
     // Then try to sign the raw header with private keys in the software wallet
-    if let Some(account_public_keys_map) = signing_data.account_public_keys_map
+    if let Some(account_public_keys_map) = &signing_data.account_public_keys_map
     {
         let mut wallet = wallet.write().await;
         let mut signing_tx_keypairs = vec![];
@@ -255,7 +278,7 @@ where
         if !signing_tx_keypairs.is_empty() {
             tx.sign_raw(
                 signing_tx_keypairs,
-                account_public_keys_map,
+                account_public_keys_map.clone(),
                 signing_data.owner,
             );
         }
@@ -314,15 +337,26 @@ where
             .await?;
         }
     }
-    // Then make sure that the number of public keys used exceeds the threshold
-    let used_pubkeys_len = used_pubkeys
-        .len()
-        .try_into()
-        .expect("Public keys associated with account exceed 127");
-    if used_pubkeys_len < signing_data.threshold {
+    // Then make sure that the weight of the public keys used meets the
+    // threshold
+    let used_weight: u32 = match &signing_data.account_public_keys_map {
+        Some(account_public_keys_map) => account_public_keys_map.total_weight(
+            &used_pubkeys
+                .iter()
+                .filter_map(|pk| {
+                    account_public_keys_map.get_index_from_public_key(pk)
+                })
+                .collect::<Vec<_>>(),
+        ),
+        None => used_pubkeys
+            .len()
+            .try_into()
+            .expect("Public keys associated with account exceed 127"),
+    };
+    if used_weight < signing_data.threshold.into() {
         Err(Error::from(TxSubmitError::MissingSigningKeys(
             signing_data.threshold,
-            used_pubkeys_len,
+            used_weight.try_into().unwrap_or(u8::MAX),
         )))
     } else {
         Ok(())
@@ -330,7 +364,10 @@ where
 }
 
 /// Return the necessary data regarding an account to be able to generate a
-/// signature section
+/// signature section. The threshold is the one required by the owner's signing
+/// policy for the given actions of the tx on its account, or the account's
+/// threshold if there are none.
+#[allow(clippy::too_many_arguments)]
 pub async fn aux_signing_data(
     context: &impl Namada,
     args: &args::Tx<SdkTypes>,
@@ -338,6 +375,7 @@ pub async fn aux_signing_data(
     default_signer: Option<Address>,
     extra_public_keys: Vec<common::PublicKey>,
     disposable_signing_key: bool,
+    actions: &[SigningAction],
 ) -> Result<SigningTxData, Error> {
     let mut public_keys =
         tx_signers(context, args, default_signer.clone()).await?;
@@ -348,16 +386,23 @@ pub async fn aux_signing_data(
             let account =
                 rpc::get_account_info(context.client(), owner).await?;
             if let Some(account) = account {
-                (Some(account.clone().public_keys_map), account.threshold)
+                let threshold = policy_threshold(
+                    context,
+                    owner,
+                    account.threshold,
+                    actions,
+                )
+                .await?;
+                (Some(account.clone().public_keys_map), threshold)
             } else {
                 return Err(Error::from(TxSubmitError::InvalidAccount(
                     owner.encode(),
                 )));
             }
         }
-        Some(Address::Implicit(_)) => (
+        Some(owner @ Address::Implicit(_)) => (
             Some(AccountPublicKeysMap::from_iter(public_keys.clone())),
-            1u8,
+            policy_threshold(context, owner, 1, actions).await?,
         ),
         Some(owner @ Address::Internal(internal)) => match internal {
             InternalAddress::Masp => (None, 0u8),
@@ -398,6 +443,32 @@ pub async fn aux_signing_data(
     })
 }
 
+/// The signature threshold required by the signing policy of an account to
+/// authorize all the given actions, given the account's `threshold`
+async fn policy_threshold(
+    context: &impl Namada,
+    owner: &Address,
+    threshold: u8,
+    actions: &[SigningAction],
+) -> Result<u8, Error> {
+    if actions.is_empty() {
+        return Ok(threshold);
+    }
+    let policy = rpc::get_account_policy(context.client(), owner).await?;
+    Ok(actions
+        .iter()
+        .map(|action| match action {
+            SigningAction::Action(action) => {
+                policy.action_threshold(*action, threshold)
+            }
+            SigningAction::Debit { token, amount } => {
+                policy.debit_threshold(token, *amount, threshold)
+            }
+        })
+        .max()
+        .unwrap_or(threshold))
+}
+
 /// Information about the post-fee balance of the tx's source. Used to correctly
 /// handle balance validation in the inner tx
 pub struct TxSourcePostBalance {
@@ -804,6 +875,9 @@ impl<'a> Display for LedgerProposalVote<'a> {
             ProposalVote::Yay => write!(f, "yay"),
             ProposalVote::Nay => write!(f, "nay"),
             ProposalVote::Abstain => write!(f, "abstain"),
+            ProposalVote::Veto => write!(f, "veto"),
+            ProposalVote::Choice(choice) => write!(f, "{choice}"),
+            ProposalVote::Split(split) => write!(f, "{split}"),
         }
     }
 }
@@ -844,6 +918,23 @@ fn proposal_type_to_ledger_vector(
                 }
             }
         }
+        ProposalType::ParameterChange(changes) => {
+            output.push("Proposal type : Parameter Change".to_string());
+            for change in changes {
+                output.push(format!("{} : {}", change.name(), change.value()));
+            }
+        }
+        ProposalType::Upgrade(upgrade) => {
+            output.push("Proposal type : Upgrade".to_string());
+            output.push(format!("Upgrade height : {}", upgrade.height));
+            output.push(format!("Version : {}", upgrade.version));
+            for binary_hash in &upgrade.binary_hashes {
+                output.push(format!("Binary hash : {}", binary_hash));
+            }
+            if let Some(migration_hash) = &upgrade.migration_hash {
+                output.push(format!("Migration hash : {}", migration_hash));
+            }
+        }
         ProposalType::PGFPayment(actions) => {
             output.push("Proposal type : PGF Payment".to_string());
             for action in actions {
@@ -18,7 +18,9 @@ use masp_primitives::transaction::components::transparent::fees::{
 };
 use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::transaction::{builder, Transaction as MaspTransaction};
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::{
+    AccountAction, AccountPolicy, InitAccount, UpdateAccount,
+};
 use namada_core::address::{Address, IBC, MASP};
 use namada_core::arith::checked;
 use namada_core::chain::Epoch;
@@ -43,8 +45,10 @@ use namada_core::masp::{
 };
 use namada_core::storage;
 use namada_core::time::DateTimeUtc;
+use namada_gas::Gas;
 use namada_governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, OnChainProposal, ParameterChangeProposal,
+    PgfFundingProposal, PgfStewardProposal, UpgradeProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
 use namada_governance::storage::proposal::{
@@ -55,20 +59,30 @@ use namada_ibc::storage::channel_key;
 use namada_ibc::trace::is_nft_trace;
 use namada_ibc::{MsgNftTransfer, MsgTransfer};
 use namada_io::{display_line, edisplay_line, Client, Io};
+use namada_parameters::storage as params_storage;
+use namada_proof_of_stake::liquid_staking::liquid_staking_token;
 use namada_proof_of_stake::parameters::{
     PosParams, MAX_VALIDATOR_METADATA_LEN,
 };
-use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
+use namada_proof_of_stake::stake_limits::{
+    check_min_self_bond, check_stake_limits,
+};
+use namada_proof_of_stake::types::{
+    CommissionPair, PipelineStake, ValidatorState,
+};
 use namada_token as token;
 use namada_token::masp::shielded_wallet::ShieldedApi;
 use namada_token::masp::TransferErr::Build;
 use namada_token::masp::{
-    MaspDataLog, MaspFeeData, MaspTransferData, ShieldedTransfer,
+    MaspDataLog, MaspFeeData, MaspTransferData, NoteConsolidation,
+    NoteSelection, NoteSelectionStrategy, PreferredNotes, ShieldedTransfer,
 };
 use namada_token::storage_key::balance_key;
 use namada_token::DenominatedAmount;
 use namada_tx::data::pgf::UpdateStewardCommission;
-use namada_tx::data::pos::{BecomeValidator, ConsensusKeyChange};
+use namada_tx::data::pos::{
+    BecomeValidator, ConsensusKeyChange, RewardRecipient,
+};
 use namada_tx::data::{
     compute_inner_tx_hash, pos, BatchedTxResult, DryRunResult, ResultCode,
 };
@@ -77,7 +91,7 @@ use num_traits::Zero;
 use rand_core::{OsRng, RngCore};
 
 use crate::args::{
-    SdkTypes, TxShieldedTransferData, TxShieldingTransferData,
+    InputAmount, SdkTypes, TxShieldedTransferData, TxShieldingTransferData,
     TxTransparentTransferData, TxUnshieldingTransferData,
 };
 use crate::control_flow::time;
@@ -87,7 +101,7 @@ use crate::rpc::{
     InnerTxResult, TxBroadcastData, TxResponse,
 };
 use crate::signing::{
-    self, validate_fee, validate_transparent_fee, SigningTxData,
+    self, validate_fee, validate_transparent_fee, SigningAction, SigningTxData,
 };
 use crate::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use crate::tendermint_rpc::error::Error as RpcError;
@@ -114,6 +128,10 @@ pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
 /// Transparent transfer transaction WASM path
 pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
+/// Token allowance approval transaction WASM path
+pub const TX_APPROVE_WASM: &str = "tx_approve.wasm";
+/// Transfer from an allowance transaction WASM path
+pub const TX_TRANSFER_FROM_WASM: &str = "tx_transfer_from.wasm";
 /// IBC transaction WASM path
 pub const TX_IBC_WASM: &str = "tx_ibc.wasm";
 /// User validity predicate WASM path
@@ -122,10 +140,17 @@ pub const VP_USER_WASM: &str = "vp_user.wasm";
 pub const TX_BOND_WASM: &str = "tx_bond.wasm";
 /// Unbond WASM path
 pub const TX_UNBOND_WASM: &str = "tx_unbond.wasm";
+/// Instant unbond WASM path
+pub const TX_INSTANT_UNBOND_WASM: &str = "tx_instant_unbond.wasm";
 /// Withdraw WASM path
 pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 /// Claim-rewards WASM path
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
+/// Change automatic reward compounding WASM path
+pub const TX_CHANGE_AUTO_COMPOUND_WASM: &str = "tx_change_auto_compound.wasm";
+/// Change reward recipient WASM path
+pub const TX_CHANGE_REWARD_RECIPIENT_WASM: &str =
+    "tx_change_reward_recipient.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
 /// Change commission WASM path
@@ -133,8 +158,17 @@ pub const TX_CHANGE_COMMISSION_WASM: &str =
     "tx_change_validator_commission.wasm";
 /// Change consensus key WASM path
 pub const TX_CHANGE_CONSENSUS_KEY_WASM: &str = "tx_change_consensus_key.wasm";
+/// Change validator protocol and Ethereum keys WASM path
+pub const TX_CHANGE_VALIDATOR_KEYS_WASM: &str = "tx_change_validator_keys.wasm";
+/// Change validator commission schedule WASM path
+pub const TX_CHANGE_COMMISSION_SCHEDULE_WASM: &str =
+    "tx_change_commission_schedule.wasm";
 /// Change validator metadata WASM path
 pub const TX_CHANGE_METADATA_WASM: &str = "tx_change_validator_metadata.wasm";
+/// Change validator liquid staking WASM path
+pub const TX_CHANGE_LIQUID_STAKING_WASM: &str = "tx_change_liquid_staking.wasm";
+/// Change validator max delegation WASM path
+pub const TX_CHANGE_MAX_DELEGATION_WASM: &str = "tx_change_max_delegation.wasm";
 /// Resign steward WASM path
 pub const TX_RESIGN_STEWARD: &str = "tx_resign_steward.wasm";
 /// Update steward commission WASM path
@@ -150,6 +184,11 @@ const IBC_REFUND_ALIAS_PREFIX: &str = "ibc-refund-target";
 /// and `/applied` ABCI query endpoints.
 const DEFAULT_NAMADA_EVENTS_MAX_WAIT_TIME_SECONDS: u64 = 60;
 
+/// The percentage of the gas limit of a note consolidation tx that the
+/// verification of its MASP bundle may use, leaving the rest to the execution
+/// of the tx.
+const CONSOLIDATION_MASP_GAS_PERCENTAGE: u64 = 75;
+
 /// Capture the result of running a transaction
 #[derive(Debug)]
 pub enum ProcessTxResponse {
@@ -319,6 +358,7 @@ pub async fn build_reveal_pk(
         Some(public_key.into()),
         vec![],
         false,
+        &[],
     )
     .await?;
     let (fee_amount, _) =
@@ -576,6 +616,7 @@ pub async fn build_change_consensus_key(
         None,
         vec![consensus_key.clone()],
         false,
+        &[],
     )
     .await?;
 
@@ -614,6 +655,7 @@ pub async fn build_validator_commission_change(
         default_signer,
         vec![],
         false,
+        &[AccountAction::ValidatorManagement.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -733,6 +775,317 @@ pub async fn build_validator_commission_change(
     .map(|tx| (tx, signing_data))
 }
 
+/// Submit validator commission schedule change
+pub async fn build_validator_commission_schedule_change(
+    context: &impl Namada,
+    args::CommissionScheduleChange {
+        tx: tx_args,
+        validator,
+        changes,
+        tiers,
+        no_raise_until,
+        tx_code_path,
+    }: &args::CommissionScheduleChange,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(validator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(validator.clone()),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::ValidatorManagement.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    if !rpc::is_validator(context.client(), validator).await? {
+        edisplay_line!(
+            context.io(),
+            "The given address {validator} is not a validator."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidValidatorAddress(
+                validator.clone(),
+            )));
+        }
+    }
+
+    for rate in changes.values().chain(tiers.values()) {
+        if rate.is_negative() || *rate > Dec::one() {
+            edisplay_line!(
+                context.io(),
+                "Commission rate {rate} is outside of the allowed range of \
+                 values between 0.0 and 1.0."
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::InvalidCommissionRate(
+                    *rate,
+                )));
+            }
+        }
+    }
+
+    let epoch = rpc::query_epoch(context.client()).await?;
+    let params: PosParams = rpc::get_pos_params(context.client()).await?;
+    let pipeline_epoch = epoch.unchecked_add(params.pipeline_len);
+    if let Some(first_epoch) = changes.keys().next() {
+        if *first_epoch <= pipeline_epoch {
+            edisplay_line!(
+                context.io(),
+                "Commission rate changes must be scheduled after the pipeline \
+                 epoch {pipeline_epoch}, received a change for epoch \
+                 {first_epoch}."
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::CommissionChangeTooEarly(
+                        *first_epoch,
+                        pipeline_epoch,
+                    ),
+                ));
+            }
+        }
+    }
+
+    let data = pos::CommissionScheduleChange {
+        validator: validator.clone(),
+        changes: changes.clone(),
+        tiers: tiers.clone(),
+        no_raise_until: *no_raise_until,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit validator liquid staking change
+pub async fn build_validator_liquid_staking_change(
+    context: &impl Namada,
+    args::LiquidStakingChange {
+        tx: tx_args,
+        validator,
+        enabled,
+        tx_code_path,
+    }: &args::LiquidStakingChange,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(validator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(validator.clone()),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::ValidatorManagement.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    if !rpc::is_validator(context.client(), validator).await? {
+        edisplay_line!(
+            context.io(),
+            "The given address {validator} is not a validator."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidValidatorAddress(
+                validator.clone(),
+            )));
+        }
+    }
+
+    let data = pos::LiquidStakingChange {
+        validator: validator.clone(),
+        enabled: *enabled,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit validator protocol and Ethereum bridge keys change
+pub async fn build_change_validator_keys(
+    context: &impl Namada,
+    args::ValidatorKeysChange {
+        tx: tx_args,
+        validator,
+        protocol_key,
+        eth_cold_key,
+        eth_hot_key,
+        gen_protocol_key: _,
+        gen_eth_cold_key: _,
+        gen_eth_hot_key: _,
+        unsafe_dont_encrypt: _,
+        tx_code_path,
+    }: &args::ValidatorKeysChange,
+) -> Result<(Tx, SigningTxData)> {
+    let new_pks: Vec<_> = [protocol_key, eth_cold_key, eth_hot_key]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    if new_pks.is_empty() {
+        edisplay_line!(
+            context.io(),
+            "At least one of the validator keys must be changed."
+        );
+        return Err(Error::Other(
+            "No validator key to change is present".to_string(),
+        ));
+    }
+
+    if !rpc::is_validator(context.client(), validator).await? {
+        edisplay_line!(
+            context.io(),
+            "The given address {validator} is not a validator."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidValidatorAddress(
+                validator.clone(),
+            )));
+        }
+    }
+
+    let to_eth_key = |pk: &Option<common::PublicKey>, name: &str| {
+        pk.as_ref()
+            .map(key::secp256k1::PublicKey::try_from_pk)
+            .transpose()
+            .map_err(|_| {
+                edisplay_line!(
+                    context.io(),
+                    "The Ethereum {name} key can only be secp256k1."
+                );
+                Error::Other(format!("Invalid Ethereum {name} key"))
+            })
+    };
+    let data = pos::ValidatorKeysChange {
+        validator: validator.clone(),
+        protocol_key: protocol_key.clone(),
+        eth_cold_key: to_eth_key(eth_cold_key, "cold")?,
+        eth_hot_key: to_eth_key(eth_hot_key, "hot")?,
+    };
+
+    // Sign with the validator's keys and all the new keys to verify ownership
+    let account = if let Some(account) =
+        rpc::get_account_info(context.client(), validator).await?
+    {
+        account
+    } else {
+        edisplay_line!(
+            context.io(),
+            "Unable to query account keys for address {validator}."
+        );
+        return Err(Error::Other("Invalid address".to_string()));
+    };
+    let mut all_pks = account.get_all_public_keys();
+    all_pks.extend(new_pks);
+
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        None,
+        None,
+        all_pks,
+        false,
+        &[],
+    )
+    .await?;
+
+    let (fee_amount, _updated_balance) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit validator max delegation change
+pub async fn build_validator_max_delegation_change(
+    context: &impl Namada,
+    args::MaxDelegationChange {
+        tx: tx_args,
+        validator,
+        max_delegation,
+        tx_code_path,
+    }: &args::MaxDelegationChange,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(validator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(validator.clone()),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::ValidatorManagement.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    if !rpc::is_validator(context.client(), validator).await? {
+        edisplay_line!(
+            context.io(),
+            "The given address {validator} is not a validator."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidValidatorAddress(
+                validator.clone(),
+            )));
+        }
+    }
+
+    let data = pos::MaxDelegationChange {
+        validator: validator.clone(),
+        max_delegation: *max_delegation,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit validator metadata change
 pub async fn build_validator_metadata_change(
     context: &impl Namada,
@@ -757,6 +1110,7 @@ pub async fn build_validator_metadata_change(
         default_signer,
         vec![],
         false,
+        &[AccountAction::ValidatorManagement.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -983,6 +1337,7 @@ pub async fn build_update_steward_commission(
         default_signer,
         vec![],
         false,
+        &[AccountAction::Pgf.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1052,6 +1407,7 @@ pub async fn build_resign_steward(
         default_signer,
         vec![],
         false,
+        &[AccountAction::Pgf.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1101,6 +1457,7 @@ pub async fn build_unjail_validator(
         default_signer,
         vec![],
         false,
+        &[AccountAction::ValidatorManagement.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1206,6 +1563,7 @@ pub async fn build_deactivate_validator(
         default_signer,
         vec![],
         false,
+        &[AccountAction::ValidatorManagement.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1282,6 +1640,7 @@ pub async fn build_reactivate_validator(
         default_signer,
         vec![],
         false,
+        &[AccountAction::ValidatorManagement.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1495,6 +1854,16 @@ pub async fn build_redelegation(
         );
     }
 
+    stake_limits_or_err(
+        &params,
+        &dest_validator,
+        *redel_amount,
+        true,
+        tx_args.force,
+        context,
+    )
+    .await?;
+
     let default_address = owner.clone();
     let default_signer = Some(default_address.clone());
     let signing_data = signing::aux_signing_data(
@@ -1504,6 +1873,7 @@ pub async fn build_redelegation(
         default_signer,
         vec![],
         false,
+        &[AccountAction::Redelegate.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1549,6 +1919,7 @@ pub async fn build_withdraw(
         default_signer,
         vec![],
         false,
+        &[AccountAction::Withdraw.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1637,6 +2008,7 @@ pub async fn build_claim_rewards(
         default_signer,
         vec![],
         false,
+        &[AccountAction::ClaimRewards.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1671,19 +2043,155 @@ pub async fn build_claim_rewards(
     .map(|tx| (tx, signing_data))
 }
 
-/// Submit a transaction to unbond
-pub async fn build_unbond(
+/// Submit a transaction to opt in or out of automatic reward compounding
+pub async fn build_auto_compound_change(
     context: &impl Namada,
-    args::Unbond {
+    args::AutoCompoundChange {
         tx: tx_args,
         validator,
-        amount,
         source,
+        enabled,
         tx_code_path,
-    }: &args::Unbond,
-) -> Result<(Tx, SigningTxData, Option<(Epoch, token::Amount)>)> {
-    // Require a positive amount of tokens to be bonded
-    if amount.is_zero() {
+    }: &args::AutoCompoundChange,
+) -> Result<(Tx, SigningTxData)> {
+    let default_address = source.clone().unwrap_or(validator.clone());
+    let default_signer = Some(default_address.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(default_address),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::ClaimRewards.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    // Check that the validator address is actually a validator
+    let validator =
+        known_validator_or_err(validator.clone(), tx_args.force, context)
+            .await?;
+
+    // Check that the source address exists on chain
+    let source = match source.clone() {
+        Some(source) => source_exists_or_err(source, tx_args.force, context)
+            .await
+            .map(Some),
+        None => Ok(source.clone()),
+    }?;
+
+    let data = pos::AutoCompoundChange {
+        validator,
+        source,
+        enabled: *enabled,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit a transaction to redirect the rewards of a source's bonds
+pub async fn build_reward_recipient_change(
+    context: &impl Namada,
+    args::RewardRecipientChange {
+        tx: tx_args,
+        source,
+        recipient,
+        tx_code_path,
+    }: &args::RewardRecipientChange,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(source.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(source.clone()),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::Debit.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    // Check that the source address exists on chain
+    let source =
+        source_exists_or_err(source.clone(), tx_args.force, context).await?;
+
+    // Rewards can only be redirected to non-internal transparent addresses
+    // or to shielded payment addresses
+    let recipient = match recipient {
+        Some(TransferTarget::Address(internal @ Address::Internal(_))) => {
+            edisplay_line!(
+                context.io(),
+                "The rewards cannot be redirected to an internal address."
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::InvalidRewardRecipient(internal.to_string()),
+                ));
+            }
+            Some(RewardRecipient::Transparent(internal.clone()))
+        }
+        Some(TransferTarget::Address(address)) => {
+            Some(RewardRecipient::Transparent(address.clone()))
+        }
+        Some(TransferTarget::PaymentAddress(payment_address)) => {
+            Some(RewardRecipient::Shielded(*payment_address))
+        }
+        Some(TransferTarget::Ibc(receiver)) => {
+            edisplay_line!(
+                context.io(),
+                "The rewards cannot be redirected to an IBC receiver."
+            );
+            return Err(Error::from(TxSubmitError::InvalidRewardRecipient(
+                receiver.clone(),
+            )));
+        }
+        None => None,
+    };
+
+    let data = pos::RewardRecipientChange { source, recipient };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit a transaction to unbond
+pub async fn build_unbond(
+    context: &impl Namada,
+    args::Unbond {
+        tx: tx_args,
+        validator,
+        amount,
+        source,
+        tx_code_path,
+    }: &args::Unbond,
+) -> Result<(Tx, SigningTxData, Option<(Epoch, token::Amount)>)> {
+    // Require a positive amount of tokens to be bonded
+    if amount.is_zero() {
         edisplay_line!(
             context.io(),
             "The requested bond amount is 0. A positive amount must be \
@@ -1742,6 +2250,7 @@ pub async fn build_unbond(
         default_signer,
         vec![],
         false,
+        &[AccountAction::Unbond.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -1751,9 +2260,19 @@ pub async fn build_unbond(
     // Check the source's current bond amount
     let bond_source = source.clone().unwrap_or_else(|| validator.clone());
 
-    let bond_amount =
+    let mut bond_amount =
         rpc::query_bond(context.client(), &bond_source, &validator, None)
             .await?;
+    // Without a bond of its own, the source unbonds its liquid staking shares
+    if bond_amount.is_zero() && bond_source != validator {
+        bond_amount = rpc::get_token_balance(
+            context.client(),
+            &liquid_staking_token(&validator),
+            &bond_source,
+            None,
+        )
+        .await?;
+    }
     display_line!(
         context.io(),
         "Bond amount available for unbonding: {} NAM",
@@ -1778,6 +2297,12 @@ pub async fn build_unbond(
         }
     }
 
+    // A validator cannot unbond its own stake below the minimum self-bond
+    if bond_source == validator {
+        min_self_bond_or_err(&validator, *amount, tx_args.force, context)
+            .await?;
+    }
+
     // Query the unbonds before submitting the tx
     let unbonds = rpc::query_unbond_with_slashing(
         context.client(),
@@ -1811,6 +2336,129 @@ pub async fn build_unbond(
     Ok((tx, signing_data, latest_withdrawal_pre))
 }
 
+/// Submit a transaction to instantly unbond
+pub async fn build_instant_unbond(
+    context: &impl Namada,
+    args::InstantUnbond {
+        tx: tx_args,
+        validator,
+        amount,
+        source,
+        tx_code_path,
+    }: &args::InstantUnbond,
+) -> Result<(Tx, SigningTxData)> {
+    // Require a positive amount of tokens to be unbonded
+    if amount.is_zero() {
+        edisplay_line!(
+            context.io(),
+            "The requested unbond amount is 0. A positive amount must be \
+             requested."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::BondIsZero));
+        }
+    }
+
+    // The validator must actually be a validator
+    let validator =
+        known_validator_or_err(validator.clone(), tx_args.force, context)
+            .await?;
+
+    // Check that the source address exists on chain
+    let source = match source.clone() {
+        Some(source) => source_exists_or_err(source, tx_args.force, context)
+            .await
+            .map(Some),
+        None => Ok(source.clone()),
+    }?;
+
+    // Check that instant unbonds are enabled
+    let params = rpc::get_pos_params(context.client()).await?;
+    if params.instant_unbond_penalty.is_none() {
+        edisplay_line!(
+            context.io(),
+            "Instant unbonds are not enabled by governance."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InstantUnbondDisabled));
+        }
+    }
+
+    let default_address = source.clone().unwrap_or(validator.clone());
+    let default_signer = Some(default_address.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(default_address),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::Unbond.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    // Quote the penalty before submitting the tx
+    let bond_source = source.clone().unwrap_or_else(|| validator.clone());
+    match rpc::query_instant_unbond_quote(
+        context.client(),
+        &bond_source,
+        &validator,
+        *amount,
+    )
+    .await
+    {
+        Ok(quote) => display_line!(
+            context.io(),
+            "Instantly unbonding {} NAM releases {} NAM for a penalty of {} \
+             NAM and holds back a reserve of {} NAM that may still be \
+             slashed. Both are held until epoch {} to cover any slashes for \
+             earlier infractions. The rest of the reserve is then returned \
+             and the rest of the penalty is {}.",
+            quote.amount.to_string_native(),
+            quote.released.to_string_native(),
+            quote.penalty.to_string_native(),
+            quote.reserve.to_string_native(),
+            quote.maturity_epoch,
+            if params.instant_unbond_penalty_to_pgf {
+                "sent to PGF"
+            } else {
+                "burned"
+            }
+        ),
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to quote the instant unbond: {}",
+                err
+            );
+            if !tx_args.force {
+                return Err(err);
+            }
+        }
+    }
+
+    let data = pos::Unbond {
+        validator,
+        amount: *amount,
+        source,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Query the unbonds post-tx
 pub async fn query_unbonds(
     context: &impl Namada,
@@ -1969,6 +2617,17 @@ pub async fn build_bond(
         }
     }
 
+    let is_delegation = source.as_ref().is_some_and(|src| src != &validator);
+    stake_limits_or_err(
+        &params,
+        &validator,
+        *amount,
+        is_delegation,
+        tx_args.force,
+        context,
+    )
+    .await?;
+
     let default_address = source.clone().unwrap_or(validator.clone());
     let default_signer = Some(default_address.clone());
     let signing_data = signing::aux_signing_data(
@@ -1978,6 +2637,13 @@ pub async fn build_bond(
         default_signer,
         vec![],
         false,
+        &[
+            AccountAction::Bond.into(),
+            SigningAction::Debit {
+                token: context.native_token(),
+                amount: *amount,
+            },
+        ],
     )
     .await?;
     let (fee_amount, updated_balance) =
@@ -2024,6 +2690,19 @@ pub async fn build_bond(
     .map(|tx| (tx, signing_data))
 }
 
+/// The actions of a governance proposal on the account of its author, for its
+/// signing policy. The author is debited the proposal's deposit.
+async fn proposal_signing_actions(context: &impl Namada) -> Vec<SigningAction> {
+    let params = rpc::query_governance_parameters(context.client()).await;
+    vec![
+        AccountAction::Governance.into(),
+        SigningAction::Debit {
+            token: context.native_token(),
+            amount: params.min_proposal_fund,
+        },
+    ]
+}
+
 /// Build a default proposal governance
 pub async fn build_default_proposal(
     context: &impl Namada,
@@ -2032,6 +2711,8 @@ pub async fn build_default_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -2044,6 +2725,7 @@ pub async fn build_default_proposal(
         default_signer,
         vec![],
         false,
+        &proposal_signing_actions(context).await,
     )
     .await?;
     let (fee_amount, _updated_balance) =
@@ -2099,6 +2781,7 @@ pub async fn build_vote_proposal(
         default_signer.clone(),
         vec![],
         false,
+        &[AccountAction::Governance.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -2117,6 +2800,21 @@ pub async fn build_vote_proposal(
         )));
     };
 
+    // Split votes must add up to 100% and choice votes must refer to the
+    // options of the proposal
+    if !proposal.voting_mode.is_valid_vote(&proposal_vote) {
+        edisplay_line!(
+            context.io(),
+            "The vote {} is not valid for proposal {} with voting mode {}.",
+            proposal_vote,
+            proposal_id,
+            proposal.voting_mode
+        );
+        if !tx.force {
+            return Err(Error::from(TxSubmitError::InvalidProposalVote));
+        }
+    }
+
     let is_validator =
         rpc::is_validator(context.client(), voter_address).await?;
 
@@ -2400,9 +3098,16 @@ pub async fn build_become_validator(
     all_pks.push(eth_hot_key.clone().unwrap());
     all_pks.push(protocol_key.clone().unwrap().clone());
 
-    let signing_data =
-        signing::aux_signing_data(context, tx_args, None, None, all_pks, false)
-            .await?;
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        None,
+        None,
+        all_pks,
+        false,
+        &[],
+    )
+    .await?;
 
     let (fee_amount, _updated_balance) =
         validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
@@ -2421,17 +3126,120 @@ pub async fn build_become_validator(
     .map(|tx| (tx, signing_data))
 }
 
-/// Build a pgf funding proposal governance
-pub async fn build_pgf_funding_proposal(
+/// Build a pgf funding proposal governance
+pub async fn build_pgf_funding_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: PgfFundingProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+        vec![],
+        false,
+        &proposal_signing_actions(context).await,
+    )
+    .await?;
+    let (fee_amount, _updated_balance) =
+        validate_transparent_fee(context, tx, &signing_data.fee_payer).await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a pgf funding proposal governance
+pub async fn build_pgf_stewards_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: PgfStewardProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+        vec![],
+        false,
+        &proposal_signing_actions(context).await,
+    )
+    .await?;
+    let (fee_amount, _updated_balance) =
+        validate_transparent_fee(context, tx, &signing_data.fee_payer).await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a parameter change proposal governance
+pub async fn build_parameter_change_proposal(
     context: &impl Namada,
     args::InitProposal {
         tx,
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
         tx_code_path,
     }: &args::InitProposal,
-    proposal: PgfFundingProposal,
+    proposal: ParameterChangeProposal,
 ) -> Result<(Tx, SigningTxData)> {
     let default_signer = Some(proposal.proposal.author.clone());
     let signing_data = signing::aux_signing_data(
@@ -2441,6 +3249,7 @@ pub async fn build_pgf_funding_proposal(
         default_signer,
         vec![],
         false,
+        &proposal_signing_actions(context).await,
     )
     .await?;
     let (fee_amount, _updated_balance) =
@@ -2455,6 +3264,7 @@ pub async fn build_pgf_funding_proposal(
         data.content = extra_section_hash;
         Ok(())
     };
+
     build(
         context,
         tx,
@@ -2468,17 +3278,19 @@ pub async fn build_pgf_funding_proposal(
     .map(|tx| (tx, signing_data))
 }
 
-/// Build a pgf funding proposal governance
-pub async fn build_pgf_stewards_proposal(
+/// Build a software upgrade proposal governance
+pub async fn build_upgrade_proposal(
     context: &impl Namada,
     args::InitProposal {
         tx,
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
         tx_code_path,
     }: &args::InitProposal,
-    proposal: PgfStewardProposal,
+    proposal: UpgradeProposal,
 ) -> Result<(Tx, SigningTxData)> {
     let default_signer = Some(proposal.proposal.author.clone());
     let signing_data = signing::aux_signing_data(
@@ -2488,6 +3300,7 @@ pub async fn build_pgf_stewards_proposal(
         default_signer,
         vec![],
         false,
+        &proposal_signing_actions(context).await,
     )
     .await?;
     let (fee_amount, _updated_balance) =
@@ -2532,7 +3345,17 @@ pub async fn build_ibc_transfer(
     let refund_target =
         get_refund_target(context, &args.source, &args.refund_target).await?;
 
+    // validate the amount given
+    let validated_amount =
+        validate_amount(context, args.amount, &args.token, args.tx.force)
+            .await
+            .expect("expected to validate amount");
+
     let source = args.source.effective_address();
+    let actions = [SigningAction::Debit {
+        token: args.token.clone(),
+        amount: validated_amount.amount(),
+    }];
     let signing_data = signing::aux_signing_data(
         context,
         &args.tx,
@@ -2540,6 +3363,7 @@ pub async fn build_ibc_transfer(
         Some(source.clone()),
         vec![],
         args.disposable_signing_key,
+        &actions,
     )
     .await?;
     let (fee_per_gas_unit, updated_balance) =
@@ -2560,12 +3384,6 @@ pub async fn build_ibc_transfer(
         source_exists_or_err(source.clone(), args.tx.force, context).await?;
     // We cannot check the receiver
 
-    // validate the amount given
-    let validated_amount =
-        validate_amount(context, args.amount, &args.token, args.tx.force)
-            .await
-            .expect("expected to validate amount");
-
     // If source is transparent check the balance (MASP balance is checked when
     // constructing the shielded part)
     if let Some(updated_balance) = updated_balance {
@@ -2629,6 +3447,7 @@ pub async fn build_ibc_transfer(
         masp_fee_data,
         !(args.tx.dry_run || args.tx.dry_run_wrapper),
         args.tx.expiration.to_datetime(),
+        &NoteSelection::default(),
     )
     .await?;
     let shielded_tx_epoch = shielded_parts.as_ref().map(|trans| trans.0.epoch);
@@ -2939,6 +3758,23 @@ pub async fn build_transparent_transfer<N: Namada>(
             // argument
             None
         };
+        // The single source is debited the transferred amount
+        let actions = match args.data.as_slice() {
+            [TxTransparentTransferData { token, amount, .. }] => {
+                let amount = validate_amount(
+                    context,
+                    amount.to_owned(),
+                    token,
+                    args.tx.force,
+                )
+                .await?;
+                vec![SigningAction::Debit {
+                    token: token.clone(),
+                    amount: amount.amount(),
+                }]
+            }
+            _ => vec![],
+        };
 
         let signing_data = signing::aux_signing_data(
             context,
@@ -2947,6 +3783,7 @@ pub async fn build_transparent_transfer<N: Namada>(
             source,
             vec![],
             false,
+            &actions,
         )
         .await?;
 
@@ -3026,10 +3863,179 @@ pub async fn build_transparent_transfer<N: Namada>(
     Ok((tx, signing_data))
 }
 
+/// Build a token allowance approval
+pub async fn build_approve(
+    context: &impl Namada,
+    args::TxApprove {
+        tx: tx_args,
+        owner,
+        spender,
+        token,
+        amount,
+        epoch_cap,
+        tx_code_path,
+    }: &args::TxApprove,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+        vec![],
+        false,
+        &[AccountAction::Debit.into()],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    source_exists_or_err(owner.clone(), tx_args.force, context).await?;
+    target_exists_or_err(spender.clone(), tx_args.force, context).await?;
+
+    let amount =
+        validate_amount(context, amount.to_owned(), token, tx_args.force)
+            .await?
+            .amount();
+    let epoch_cap = match epoch_cap {
+        Some(epoch_cap) => Some(
+            validate_amount(
+                context,
+                epoch_cap.to_owned(),
+                token,
+                tx_args.force,
+            )
+            .await?
+            .amount(),
+        ),
+        None => None,
+    };
+
+    let data = token::Approve {
+        owner: owner.clone(),
+        spender: spender.clone(),
+        token: token.clone(),
+        amount,
+        epoch_cap,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a transfer from an allowance
+pub async fn build_transfer_from(
+    context: &impl Namada,
+    args::TxTransferFrom {
+        tx: tx_args,
+        spender,
+        owner,
+        target,
+        token,
+        amount,
+        tx_code_path,
+    }: &args::TxTransferFrom,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(spender.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(spender.clone()),
+        default_signer,
+        vec![],
+        false,
+        &[],
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    source_exists_or_err(owner.clone(), tx_args.force, context).await?;
+    target_exists_or_err(target.clone(), tx_args.force, context).await?;
+
+    let amount =
+        validate_amount(context, amount.to_owned(), token, tx_args.force)
+            .await?;
+
+    let allowance =
+        rpc::query_allowance(context.client(), token, owner, spender)
+            .await?
+            .unwrap_or_default();
+    let epoch = rpc::query_epoch(context.client()).await?;
+    if amount.amount() > allowance.available_at(epoch) {
+        edisplay_line!(
+            context.io(),
+            "The allowance of {spender} on {token} of {owner} is too low to \
+             transfer {amount}."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::AllowanceTooLow(
+                owner.clone(),
+                spender.clone(),
+                token.clone(),
+            )));
+        }
+    }
+    check_balance_too_low_err(
+        token,
+        owner,
+        amount.amount(),
+        CheckBalance::Query(balance_key(token, owner)),
+        tx_args.force,
+        context,
+    )
+    .await?;
+
+    let data = token::TransferFrom {
+        spender: spender.clone(),
+        owner: owner.clone(),
+        target: target.clone(),
+        token: token.clone(),
+        amount: amount.amount(),
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Build a shielded transfer
 pub async fn build_shielded_transfer<N: Namada>(
     context: &N,
     args: &mut args::TxShieldedTransfer,
+) -> Result<(Tx, SigningTxData)> {
+    build_shielded_transfer_with(context, args, &args.note_selection, false)
+        .await
+}
+
+// Build a shielded transfer funded by the notes chosen with the given note
+// selection strategy. If requested, the part of the fees paid from the
+// shielded pool is deducted from the transfers of the fee token, so that the
+// notes that cover them also cover the fees.
+async fn build_shielded_transfer_with<N: Namada>(
+    context: &N,
+    args: &args::TxShieldedTransfer,
+    note_selection: &impl NoteSelectionStrategy,
+    fees_from_amount: bool,
 ) -> Result<(Tx, SigningTxData)> {
     let signing_data = signing::aux_signing_data(
         context,
@@ -3038,11 +4044,25 @@ pub async fn build_shielded_transfer<N: Namada>(
         Some(MASP),
         vec![],
         args.disposable_signing_key,
+        &[],
     )
     .await?;
 
     // Shielded fee payment
     let fee_per_gas_unit = validate_fee(context, &args.tx).await?;
+    let masp_fee_data = get_masp_fee_payment_amount(
+        context,
+        &args.tx,
+        fee_per_gas_unit,
+        &signing_data.fee_payer,
+        args.gas_spending_keys.clone(),
+    )
+    .await?;
+    // The fees to deduct from the first transfer of the fee token, if any
+    let mut fees_to_deduct = masp_fee_data
+        .as_ref()
+        .filter(|_| fees_from_amount)
+        .map(|fee_data| (fee_data.token.clone(), fee_data.amount));
 
     let mut transfer_data = vec![];
     for TxShieldedTransferData {
@@ -3053,9 +4073,20 @@ pub async fn build_shielded_transfer<N: Namada>(
     } in &args.data
     {
         // Validate the amount given
-        let validated_amount =
+        let mut validated_amount =
             validate_amount(context, amount.to_owned(), token, args.tx.force)
                 .await?;
+        if let Some((_, fees)) =
+            fees_to_deduct.take_if(|(fee_token, _)| fee_token == token)
+        {
+            validated_amount =
+                validated_amount.checked_sub(fees).ok_or_else(|| {
+                    Error::Other(format!(
+                        "The amount {validated_amount} of token {token} \
+                         doesn't cover the fees of {fees}"
+                    ))
+                })?;
+        }
 
         transfer_data.push(MaspTransferData {
             source: TransferSource::ExtendedSpendingKey(source.to_owned()),
@@ -3069,14 +4100,6 @@ pub async fn build_shielded_transfer<N: Namada>(
     let mut data = token::Transfer::default();
 
     // Add masp fee payment if necessary
-    let masp_fee_data = get_masp_fee_payment_amount(
-        context,
-        &args.tx,
-        fee_per_gas_unit,
-        &signing_data.fee_payer,
-        args.gas_spending_keys.clone(),
-    )
-    .await?;
     if let Some(fee_data) = &masp_fee_data {
         data = data
             .transfer(
@@ -3094,6 +4117,7 @@ pub async fn build_shielded_transfer<N: Namada>(
         masp_fee_data,
         !(args.tx.dry_run || args.tx.dry_run_wrapper),
         args.tx.expiration.to_datetime(),
+        note_selection,
     )
     .await?
     .expect("Shielded transfer must have shielded parts");
@@ -3140,6 +4164,89 @@ pub async fn build_shielded_transfer<N: Namada>(
     Ok((tx, signing_data))
 }
 
+/// Plan the chain of self-transfers that consolidate the unspent notes of a
+/// spending key into one note per token. The notes of a token are split into
+/// batches so that the verification of each MASP bundle stays within the gas
+/// limit of the tx, the maximum block gas and the MASP fee payment gas limit.
+/// The self-transfers must be built with [`build_shielded_consolidation`] and
+/// submitted one at a time, in order.
+pub async fn plan_shielded_consolidation<N: Namada>(
+    context: &N,
+    args: &args::ShieldedConsolidate,
+) -> Result<Vec<NoteConsolidation>> {
+    let max_block_gas: u64 = rpc::query_storage_value(
+        context.client(),
+        &params_storage::get_max_block_gas_key(),
+    )
+    .await?;
+    let masp_fee_payment_gas_limit: u64 = rpc::query_storage_value(
+        context.client(),
+        &params_storage::get_masp_fee_payment_gas_limit_key(),
+    )
+    .await?;
+    let gas_scale: u64 = rpc::query_storage_value(
+        context.client(),
+        &params_storage::get_gas_scale_key(),
+    )
+    .await?;
+    let gas_limit = u64::from(args.tx.gas_limit)
+        .min(max_block_gas)
+        .min(masp_fee_payment_gas_limit);
+    let masp_gas_limit =
+        checked!(gas_limit * CONSOLIDATION_MASP_GAS_PERCENTAGE / 100)?;
+    let gas_budget = Gas::from_whole_units(masp_gas_limit.into(), gas_scale)
+        .ok_or_else(|| Error::Other("Gas overflow".to_string()))?;
+
+    let mut shielded = context.shielded_mut().await;
+    let _ = shielded.load().await;
+    shielded
+        .plan_note_consolidation(
+            context,
+            args.source,
+            args.token.as_ref(),
+            &gas_budget,
+            args.max_notes,
+        )
+        .await
+        .map_err(|e| Error::Other(e.to_string()))
+}
+
+/// Build the self-transfer of the first of the given planned consolidations.
+/// The notes of the later ones are only used to pay the fees of the tx if no
+/// other notes can. If the fees are paid from the shielded pool in the token
+/// of the consolidation, they are taken out of the merged note. The tx must
+/// be submitted before building the next one, so that the notes that it
+/// spends are known to be spent.
+pub async fn build_shielded_consolidation<N: Namada>(
+    context: &N,
+    args: &args::ShieldedConsolidate,
+    consolidation: &NoteConsolidation,
+    later: &[NoteConsolidation],
+) -> Result<(Tx, SigningTxData)> {
+    let transfer = args::TxShieldedTransfer {
+        tx: args.tx.clone(),
+        data: vec![TxShieldedTransferData {
+            source: args.source,
+            target: args.target,
+            token: consolidation.token.clone(),
+            amount: InputAmount::Validated(consolidation.amount),
+        }],
+        gas_spending_keys: args.gas_spending_keys.clone(),
+        disposable_signing_key: args.disposable_signing_key,
+        note_selection: NoteSelection::default(),
+        tx_code_path: args.tx_code_path.clone(),
+    };
+    let note_selection = PreferredNotes {
+        preferred: consolidation.notes.clone(),
+        reserved: later
+            .iter()
+            .flat_map(|later| later.notes.iter().copied())
+            .collect(),
+    };
+    build_shielded_transfer_with(context, &transfer, &note_selection, true)
+        .await
+}
+
 // Check if the transaction will need to pay fees via the masp and extract the
 // right masp data
 async fn get_masp_fee_payment_amount<N: Namada>(
@@ -3186,6 +4293,27 @@ pub async fn build_shielding_transfer<N: Namada>(
         // argument
         None
     };
+    // The single source authorizes the MASP tx and is debited the shielded
+    // amount
+    let actions = match args.data.as_slice() {
+        [TxShieldingTransferData { token, amount, .. }] => {
+            let amount = validate_amount(
+                context,
+                amount.to_owned(),
+                token,
+                args.tx.force,
+            )
+            .await?;
+            vec![
+                AccountAction::Masp.into(),
+                SigningAction::Debit {
+                    token: token.clone(),
+                    amount: amount.amount(),
+                },
+            ]
+        }
+        _ => vec![],
+    };
     let signing_data = signing::aux_signing_data(
         context,
         &args.tx,
@@ -3193,6 +4321,7 @@ pub async fn build_shielding_transfer<N: Namada>(
         source,
         vec![],
         false,
+        &actions,
     )
     .await?;
 
@@ -3261,6 +4390,7 @@ pub async fn build_shielding_transfer<N: Namada>(
         None,
         !(args.tx.dry_run || args.tx.dry_run_wrapper),
         args.tx.expiration.to_datetime(),
+        &NoteSelection::default(),
     )
     .await?
     .expect("Shielding transfer must have shielded parts");
@@ -3320,6 +4450,7 @@ pub async fn build_unshielding_transfer<N: Namada>(
         Some(MASP),
         vec![],
         args.disposable_signing_key,
+        &[],
     )
     .await?;
 
@@ -3383,6 +4514,7 @@ pub async fn build_unshielding_transfer<N: Namada>(
         masp_fee_data,
         !(args.tx.dry_run || args.tx.dry_run_wrapper),
         args.tx.expiration.to_datetime(),
+        &args.note_selection,
     )
     .await?
     .expect("Shielding transfer must have shielded parts");
@@ -3436,6 +4568,7 @@ async fn construct_shielded_parts<N: Namada>(
     fee_data: Option<MaspFeeData>,
     update_ctx: bool,
     expiration: Option<DateTimeUtc>,
+    note_selection: &impl NoteSelectionStrategy,
 ) -> Result<Option<(ShieldedTransfer, HashSet<AssetData>)>> {
     // Precompute asset types to increase chances of success in decoding
     let token_map = context.wallet().await.get_addresses();
@@ -3449,7 +4582,12 @@ async fn construct_shielded_parts<N: Namada>(
 
         shielded
             .gen_shielded_transfer(
-                context, data, fee_data, expiration, update_ctx,
+                context,
+                data,
+                fee_data,
+                expiration,
+                update_ctx,
+                note_selection,
             )
             .await
     };
@@ -3510,21 +4648,34 @@ pub async fn build_init_account(
         tx_code_path,
         public_keys,
         threshold,
+        vesting,
+        policy,
     }: &args::TxInitAccount,
 ) -> Result<(Tx, SigningTxData)> {
-    let signing_data =
-        signing::aux_signing_data(context, tx_args, None, None, vec![], false)
-            .await?;
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        None,
+        None,
+        vec![],
+        false,
+        &[],
+    )
+    .await?;
     let (fee_amount, _) =
         validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
             .await?;
 
     let vp_code_hash = query_wasm_code_hash_buf(context, vp_code_path).await?;
 
+    let total_weight =
+        policy.as_ref().map_or(public_keys.len() as u32, |policy| {
+            policy.total_weight(public_keys.len())
+        });
     let threshold = match threshold {
         Some(threshold) => {
             let threshold = *threshold;
-            if (threshold > 0 && public_keys.len() as u8 >= threshold)
+            if (threshold > 0 && total_weight >= u32::from(threshold))
                 || tx_args.force
             {
                 threshold
@@ -3554,11 +4705,40 @@ pub async fn build_init_account(
         }
     };
 
+    let mut vested_tokens = HashSet::new();
+    for schedule in vesting {
+        if let Err(err) = schedule.validate() {
+            return Err(Error::from(TxSubmitError::InvalidVestingSchedule(
+                err.to_string(),
+            )));
+        }
+        if !vested_tokens.insert(&schedule.token) {
+            return Err(Error::from(TxSubmitError::InvalidVestingSchedule(
+                format!(
+                    "More than one vesting schedule was given for token {}",
+                    schedule.token
+                ),
+            )));
+        }
+    }
+
+    if let Some(policy) = policy {
+        validate_account_policy(
+            context,
+            tx_args,
+            policy,
+            public_keys.len(),
+            threshold,
+        )?;
+    }
+
     let data = InitAccount {
         public_keys: public_keys.clone(),
         // We will add the hash inside the add_code_hash function
         vp_code_hash: Hash::zero(),
         threshold,
+        vesting: vesting.clone(),
+        policy: policy.clone(),
     };
 
     let add_code_hash = |tx: &mut Tx, data: &mut InitAccount| {
@@ -3592,6 +4772,7 @@ pub async fn build_update_account(
         addr,
         public_keys,
         threshold,
+        policy,
     }: &args::TxUpdateAccount,
 ) -> Result<(Tx, SigningTxData)> {
     let default_signer = Some(addr.clone());
@@ -3602,6 +4783,7 @@ pub async fn build_update_account(
         default_signer,
         vec![],
         false,
+        &[AccountAction::UpdateAccount.into()],
     )
     .await?;
     let (fee_amount, _) =
@@ -3618,19 +4800,28 @@ pub async fn build_update_account(
         )));
     };
 
+    // The number and total weight of the account's public keys once the
+    // update is applied
+    let num_public_keys = if public_keys.is_empty() {
+        account.get_all_public_keys().len()
+    } else {
+        public_keys.len()
+    };
+    let total_weight = match policy {
+        Some(policy) => policy.total_weight(num_public_keys),
+        None if public_keys.is_empty() => account
+            .public_keys_map
+            .total_weight(account.public_keys_map.idx_to_pk.keys()),
+        None => num_public_keys as u32,
+    };
+
     let threshold = if let Some(threshold) = threshold {
         let threshold = *threshold;
 
         let invalid_threshold = threshold.is_zero();
-        let invalid_threshold_updated =
-            !public_keys.is_empty() && public_keys.len() < threshold as usize;
-        let invalid_threshold_current = public_keys.is_empty()
-            && account.get_all_public_keys().len() < threshold as usize;
-
-        if invalid_threshold
-            || invalid_threshold_updated
-            || invalid_threshold_current
-        {
+        let invalid_threshold_weight = total_weight < u32::from(threshold);
+
+        if invalid_threshold || invalid_threshold_weight {
             edisplay_line!(
                 context.io(),
                 "Invalid account threshold: either the provided threshold is \
@@ -3645,8 +4836,8 @@ pub async fn build_update_account(
 
         Some(threshold)
     } else {
-        let invalid_too_few_pks = !public_keys.is_empty()
-            && public_keys.len() < account.threshold as usize;
+        let invalid_too_few_pks = (!public_keys.is_empty() || policy.is_some())
+            && total_weight < u32::from(account.threshold);
 
         if invalid_too_few_pks {
             return Err(Error::from(TxSubmitError::InvalidAccountThreshold));
@@ -3655,6 +4846,16 @@ pub async fn build_update_account(
         None
     };
 
+    if let Some(policy) = policy {
+        validate_account_policy(
+            context,
+            tx_args,
+            policy,
+            num_public_keys,
+            threshold.unwrap_or(account.threshold),
+        )?;
+    }
+
     let vp_code_hash = match vp_code_path {
         Some(code_path) => {
             let vp_hash = query_wasm_code_hash_buf(context, code_path).await?;
@@ -3682,6 +4883,7 @@ pub async fn build_update_account(
         vp_code_hash: extra_section_hash,
         public_keys: public_keys.clone(),
         threshold,
+        policy: policy.clone(),
     };
 
     let add_code_hash = |tx: &mut Tx, data: &mut UpdateAccount| {
@@ -3709,6 +4911,26 @@ pub async fn build_update_account(
     .map(|tx| (tx, signing_data))
 }
 
+/// Check that the signing policy of an account is consistent with its public
+/// keys and threshold
+fn validate_account_policy(
+    context: &impl Namada,
+    tx_args: &args::Tx,
+    policy: &AccountPolicy,
+    num_public_keys: usize,
+    threshold: u8,
+) -> Result<()> {
+    if let Err(err) = policy.validate(num_public_keys, threshold) {
+        edisplay_line!(context.io(), "Invalid account policy: {err}");
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidAccountPolicy(
+                err.to_string(),
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Submit a custom transaction
 pub async fn build_custom(
     context: &impl Namada,
@@ -3729,6 +4951,7 @@ pub async fn build_custom(
         default_signer,
         vec![],
         *disposable_signing_key,
+        &[],
     )
     .await?;
     let fee_amount = validate_fee(context, tx_args).await?;
@@ -3816,6 +5039,7 @@ pub async fn gen_ibc_shielding_transfer<N: Namada>(
                 None,
                 args.expiration.to_datetime(),
                 true,
+                &NoteSelection::default(),
             )
             .await
             .map_err(|err| TxSubmitError::MaspError(err.to_string()))?
@@ -3913,6 +5137,94 @@ async fn known_validator_or_err(
     }
 }
 
+/// Check that bonding or redelegating the `amount` to the `validator` keeps it
+/// within its stake limits and, for delegations, keeps its self-bond above
+/// the minimum, otherwise returns an error, force forces the stake through
+/// even if it exceeds the limits. The protocol checks the limits against the
+/// exact stake once the tx is applied.
+async fn stake_limits_or_err(
+    params: &PosParams,
+    validator: &Address,
+    amount: token::Amount,
+    is_delegation: bool,
+    force: bool,
+    context: &impl Namada,
+) -> Result<()> {
+    let max_delegation = if is_delegation {
+        rpc::query_validator_max_delegation(context.client(), validator).await?
+    } else {
+        None
+    };
+    let has_min_self_bond = is_delegation
+        && (params.min_self_bond.is_some()
+            || params.min_self_bond_ratio.is_some());
+    if max_delegation.is_none()
+        && params.max_validator_stake_share.is_none()
+        && !has_min_self_bond
+    {
+        return Ok(());
+    }
+    let stake =
+        rpc::query_validator_pipeline_stake(context.client(), validator)
+            .await?
+            .with_bond(amount, is_delegation)?;
+    let self_bond =
+        stake.stake.checked_sub(stake.delegated).unwrap_or_default();
+    if let Err(err) = check_stake_limits(
+        params,
+        validator,
+        max_delegation,
+        is_delegation,
+        &stake,
+    )
+    .and_then(|()| {
+        if has_min_self_bond {
+            check_min_self_bond(params, validator, self_bond, stake.stake)
+        } else {
+            Ok(())
+        }
+    }) {
+        edisplay_line!(context.io(), "{err}");
+        if !force {
+            return Err(Error::from(err));
+        }
+    }
+    Ok(())
+}
+
+/// Check that unbonding the `amount` from the self-bond of the `validator`
+/// keeps it above the minimum self-bond, otherwise returns an error, force
+/// forces the unbond through. The protocol only checks it for the validators
+/// that remain in the consensus or below-capacity sets after the unbond.
+async fn min_self_bond_or_err(
+    validator: &Address,
+    amount: token::Amount,
+    force: bool,
+    context: &impl Namada,
+) -> Result<()> {
+    let params = rpc::get_pos_params(context.client()).await?;
+    if params.min_self_bond.is_none() && params.min_self_bond_ratio.is_none() {
+        return Ok(());
+    }
+    let PipelineStake {
+        stake, delegated, ..
+    } = rpc::query_validator_pipeline_stake(context.client(), validator)
+        .await?;
+    let stake = stake.checked_sub(amount).unwrap_or_default();
+    if stake < params.validator_stake_threshold {
+        return Ok(());
+    }
+    let self_bond = stake.checked_sub(delegated).unwrap_or_default();
+    if let Err(err) = check_min_self_bond(&params, validator, self_bond, stake)
+    {
+        edisplay_line!(context.io(), "{err}");
+        if !force {
+            return Err(Error::from(err));
+        }
+    }
+    Ok(())
+}
+
 /// general pattern for checking if an address exists on the chain, or
 /// throwing an error if it's not forced. Takes a generic error
 /// message and the error type.
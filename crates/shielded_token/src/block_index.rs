@@ -0,0 +1,422 @@
+//! The index of the blocks with MASP txs and the compact data of their notes.
+//!
+//! These are maintained by each node in its local DB, outside of the
+//! merklized storage, so that the shielded sync clients talking directly to a
+//! node can skip the blocks without MASP txs and trial-decrypt the notes of
+//! the others without fetching the full blocks.
+//!
+//! The index covers a contiguous range of committed blocks, from the first
+//! one indexed by the node. It starts over if a block is not indexed, e.g.
+//! when the node stops between committing a block and indexing it. The
+//! heights of the blocks are split in chunks of [`BLOCK_INDEX_CHUNK_LEN`]
+//! blocks, each with a bitmap of its blocks with MASP txs. The compact notes
+//! are only kept for the last [`BLOCK_NOTES_RETENTION`] blocks.
+
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use masp_primitives::transaction::Transaction;
+use namada_core::borsh::BorshSerializeExt;
+use namada_core::chain::BlockHeight;
+use namada_core::decode;
+use namada_events::extend::MaspTxRef;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_state::DB;
+use namada_tx::IndexedTx;
+
+use crate::{Error, Result};
+
+/// The number of blocks covered by each bitmap of the block index
+pub const BLOCK_INDEX_CHUNK_LEN: u64 = 10_000;
+/// The number of the last blocks whose compact notes are kept
+pub const BLOCK_NOTES_RETENTION: u64 = 100_000;
+
+/// The length in bytes of the bitmap of a chunk of the block index
+#[allow(clippy::cast_possible_truncation)]
+const CHUNK_BITMAP_LEN: usize = BLOCK_INDEX_CHUNK_LEN.div_ceil(8) as usize;
+
+const START_HEIGHT_KEY: &str = "start_height";
+const HEIGHT_KEY: &str = "height";
+const CHUNKS_KEY_PREFIX: &str = "chunks/";
+const NOTES_KEY_PREFIX: &str = "notes/";
+
+/// The compact data of a shielded output, enough to trial-decrypt its note
+/// and to append its commitment to the note commitment tree
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct CompactMaspOutput {
+    /// The note commitment
+    pub cmu: [u8; 32],
+    /// The ephemeral public key used to encrypt the note
+    pub ephemeral_key: [u8; 32],
+    /// The encrypted note plaintext
+    pub enc_ciphertext: Vec<u8>,
+}
+
+/// The compact data of the shielded outputs of a MASP tx
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct CompactMaspTx {
+    /// The position of the MASP tx on chain
+    pub indexed_tx: IndexedTx,
+    /// The reference to the MASP tx in the tx that carries it
+    pub masp_ref: MaspTxRef,
+    /// The shielded outputs of the MASP tx, in the order in which their notes
+    /// are appended to the note commitment tree
+    pub outputs: Vec<CompactMaspOutput>,
+}
+
+impl CompactMaspTx {
+    /// Extract the compact data of the shielded outputs of a MASP tx
+    pub fn new(
+        indexed_tx: IndexedTx,
+        masp_ref: MaspTxRef,
+        transaction: &Transaction,
+    ) -> Self {
+        let outputs = transaction
+            .sapling_bundle()
+            .map_or(&vec![], |bundle| &bundle.shielded_outputs)
+            .iter()
+            .map(|output| CompactMaspOutput {
+                cmu: output.cmu.to_bytes(),
+                ephemeral_key: output.ephemeral_key.0,
+                enc_ciphertext: output.enc_ciphertext.to_vec(),
+            })
+            .collect();
+        Self {
+            indexed_tx,
+            masp_ref,
+            outputs,
+        }
+    }
+}
+
+/// The index of the blocks with MASP txs in a range of block heights
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct MaspBlockIndex {
+    /// The height of the first block covered by the index
+    pub start_height: BlockHeight,
+    /// The height of the last block covered by the index
+    pub height: BlockHeight,
+    /// The bitmaps of the blocks with MASP txs of the chunks of blocks, by
+    /// chunk. The chunks without MASP txs have no bitmap.
+    pub chunks: BTreeMap<u64, Vec<u8>>,
+}
+
+impl MaspBlockIndex {
+    /// Check if the block at the given height may contain MASP txs. This is
+    /// exact for the blocks that the index covers, the others always may.
+    pub fn may_contain(&self, height: BlockHeight) -> bool {
+        if height < self.start_height || height > self.height {
+            return true;
+        }
+        let (byte, mask) = chunk_bitmap_position(height);
+        self.chunks
+            .get(&block_index_chunk(height))
+            .and_then(|bitmap| bitmap.get(byte))
+            .is_some_and(|bits| bits & mask != 0)
+    }
+}
+
+/// Get the chunk of blocks of the block index that contains the given height
+pub fn block_index_chunk(height: BlockHeight) -> u64 {
+    height.0 / BLOCK_INDEX_CHUNK_LEN
+}
+
+/// Get the position of the bit of a block in the bitmap of its chunk, as the
+/// index of its byte and the mask of the bit in that byte
+// The offset of a block in its chunk is below the chunk length - cannot
+// overflow or truncate
+#[allow(clippy::arithmetic_side_effects, clippy::cast_possible_truncation)]
+fn chunk_bitmap_position(height: BlockHeight) -> (usize, u8) {
+    let offset = height.0 % BLOCK_INDEX_CHUNK_LEN;
+    ((offset / 8) as usize, 1 << (offset % 8))
+}
+
+fn chunk_key(chunk: u64) -> String {
+    format!("{CHUNKS_KEY_PREFIX}{chunk:020}")
+}
+
+fn notes_key(height: BlockHeight) -> String {
+    format!("{NOTES_KEY_PREFIX}{:020}", height.0)
+}
+
+fn read_index_val<D, T>(db: &D, key: &str) -> Result<Option<T>>
+where
+    D: DB,
+    T: BorshDeserialize,
+{
+    let val = db.read_masp_index_val(key)?.map(decode).transpose()?;
+    Ok(val)
+}
+
+/// Update the block index and the compact notes with a committed block,
+/// given the compact data of its MASP txs in the order in which they were
+/// applied. Must be called for every block, otherwise the index starts over
+/// from the next block that is indexed.
+pub fn index_block<D>(
+    db: &mut D,
+    height: BlockHeight,
+    txs: Vec<CompactMaspTx>,
+) -> Result<()>
+where
+    D: DB,
+{
+    let mut changes = vec![];
+
+    // The index only covers contiguous blocks, so it starts over if the last
+    // indexed block is not the previous one
+    let last_height = read_index_val::<D, BlockHeight>(db, HEIGHT_KEY)?;
+    let is_contiguous = last_height
+        .is_some_and(|last_height| last_height.next_height() == height);
+    if !is_contiguous {
+        for (key, _) in db.read_masp_index_prefix("")? {
+            changes.push((key, None));
+        }
+        changes.push((
+            START_HEIGHT_KEY.to_owned(),
+            Some(height.serialize_to_vec()),
+        ));
+    }
+
+    if !txs.is_empty() {
+        let chunk_key = chunk_key(block_index_chunk(height));
+        let bitmap = if is_contiguous {
+            db.read_masp_index_val(&chunk_key)?
+        } else {
+            None
+        };
+        let mut bitmap = bitmap.unwrap_or_else(|| vec![0; CHUNK_BITMAP_LEN]);
+        let (byte, mask) = chunk_bitmap_position(height);
+        let bits = bitmap.get_mut(byte).ok_or_else(|| {
+            Error::new_const("Invalid bitmap in the MASP block index")
+        })?;
+        *bits |= mask;
+        changes.push((chunk_key, Some(bitmap)));
+        changes.push((notes_key(height), Some(txs.serialize_to_vec())));
+    }
+
+    // Prune the compact notes that are not kept anymore
+    if let Some(pruned_height) = height.0.checked_sub(BLOCK_NOTES_RETENTION) {
+        changes.push((notes_key(BlockHeight(pruned_height)), None));
+    }
+
+    changes.push((HEIGHT_KEY.to_owned(), Some(height.serialize_to_vec())));
+    db.write_masp_index(changes)?;
+    Ok(())
+}
+
+/// Read the compact data of the MASP txs of a block, if they are kept
+pub fn read_block_notes<D>(
+    db: &D,
+    height: BlockHeight,
+) -> Result<Option<Vec<CompactMaspTx>>>
+where
+    D: DB,
+{
+    let (Some(start_height), Some(last_height)) = (
+        read_index_val::<D, BlockHeight>(db, START_HEIGHT_KEY)?,
+        read_index_val::<D, BlockHeight>(db, HEIGHT_KEY)?,
+    ) else {
+        return Ok(None);
+    };
+    let is_kept = start_height <= height
+        && height <= last_height
+        && height.0.saturating_add(BLOCK_NOTES_RETENTION) > last_height.0;
+    if !is_kept {
+        return Ok(None);
+    }
+    Ok(Some(
+        read_index_val(db, &notes_key(height))?.unwrap_or_default(),
+    ))
+}
+
+/// Read the index of the blocks with MASP txs, if there is one
+pub fn read_block_index<D>(db: &D) -> Result<Option<MaspBlockIndex>>
+where
+    D: DB,
+{
+    let (Some(start_height), Some(height)) = (
+        read_index_val(db, START_HEIGHT_KEY)?,
+        read_index_val(db, HEIGHT_KEY)?,
+    ) else {
+        return Ok(None);
+    };
+    let chunks = db
+        .read_masp_index_prefix(CHUNKS_KEY_PREFIX)?
+        .into_iter()
+        .filter_map(|(key, bitmap)| {
+            let chunk = key.strip_prefix(CHUNKS_KEY_PREFIX)?.parse().ok()?;
+            Some((chunk, bitmap))
+        })
+        .collect();
+    Ok(Some(MaspBlockIndex {
+        start_height,
+        height,
+        chunks,
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::arithmetic_side_effects)]
+mod tests {
+    use namada_core::hash::Hash;
+    use namada_core::storage::TxIndex;
+    use namada_state::mockdb::MockDB;
+
+    use super::*;
+
+    fn compact_tx(height: u64, index: u32) -> CompactMaspTx {
+        CompactMaspTx {
+            indexed_tx: IndexedTx {
+                height: BlockHeight(height),
+                index: TxIndex(index),
+                batch_index: Some(0),
+            },
+            masp_ref: MaspTxRef::IbcData(Hash::zero()),
+            outputs: vec![CompactMaspOutput {
+                cmu: [1; 32],
+                ephemeral_key: [2; 32],
+                enc_ciphertext: vec![3; 16],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_block_notes_and_index() {
+        let mut db = MockDB::default();
+        let start = BLOCK_INDEX_CHUNK_LEN - 2;
+        let last = 2 * BLOCK_INDEX_CHUNK_LEN + 1;
+        let with_txs = [start, start + 1, BLOCK_INDEX_CHUNK_LEN + 5, last];
+
+        assert!(read_block_index(&db).unwrap().is_none());
+        for height in start..=last {
+            let txs = if with_txs.contains(&height) {
+                vec![compact_tx(height, 0), compact_tx(height, 2)]
+            } else {
+                vec![]
+            };
+            index_block(&mut db, BlockHeight(height), txs).unwrap();
+        }
+
+        // The txs of a block are kept in the order in which they're applied
+        assert_eq!(
+            read_block_notes(&db, BlockHeight(start)).unwrap(),
+            Some(vec![compact_tx(start, 0), compact_tx(start, 2)])
+        );
+        assert_eq!(
+            read_block_notes(&db, BlockHeight(start + 2)).unwrap(),
+            Some(vec![])
+        );
+        // The blocks before the first indexed one are not covered
+        assert!(read_block_notes(&db, BlockHeight(start - 1))
+            .unwrap()
+            .is_none());
+
+        // Only the chunks with MASP txs have a bitmap
+        let index = read_block_index(&db).unwrap().unwrap();
+        assert_eq!(index.start_height, BlockHeight(start));
+        assert_eq!(index.height, BlockHeight(last));
+        assert_eq!(
+            index.chunks.keys().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        for height in start..=last {
+            assert_eq!(
+                index.may_contain(BlockHeight(height)),
+                with_txs.contains(&height)
+            );
+        }
+        // The blocks that the index doesn't cover may contain MASP txs
+        assert!(index.may_contain(BlockHeight(start - 1)));
+        assert!(index.may_contain(BlockHeight(last + 1)));
+
+        // The index survives a borsh round-trip
+        let decoded =
+            MaspBlockIndex::try_from_slice(&index.serialize_to_vec()).unwrap();
+        assert_eq!(decoded, index);
+    }
+
+    #[test]
+    fn test_block_notes_pruning() {
+        let mut db = MockDB::default();
+        let last = BLOCK_NOTES_RETENTION + 1;
+
+        index_block(&mut db, BlockHeight(1), vec![compact_tx(1, 0)]).unwrap();
+        index_block(&mut db, BlockHeight(2), vec![compact_tx(2, 0)]).unwrap();
+        for height in 3..=last {
+            index_block(&mut db, BlockHeight(height), vec![]).unwrap();
+        }
+
+        // The notes of the blocks out of the retention window are pruned
+        assert!(db
+            .read_masp_index_val(&notes_key(BlockHeight(1)))
+            .unwrap()
+            .is_none());
+        assert!(read_block_notes(&db, BlockHeight(1)).unwrap().is_none());
+        assert_eq!(
+            read_block_notes(&db, BlockHeight(2)).unwrap(),
+            Some(vec![compact_tx(2, 0)])
+        );
+
+        // The index still covers the blocks with pruned notes
+        let index = read_block_index(&db).unwrap().unwrap();
+        assert!(index.may_contain(BlockHeight(1)));
+        assert!(!index.may_contain(BlockHeight(3)));
+    }
+
+    #[test]
+    fn test_block_index_restart() {
+        let mut db = MockDB::default();
+
+        index_block(&mut db, BlockHeight(1), vec![]).unwrap();
+        index_block(&mut db, BlockHeight(2), vec![compact_tx(2, 0)]).unwrap();
+        // A missed block makes the index start over
+        index_block(&mut db, BlockHeight(4), vec![compact_tx(4, 0)]).unwrap();
+
+        let index = read_block_index(&db).unwrap().unwrap();
+        assert_eq!(index.start_height, BlockHeight(4));
+        assert_eq!(index.height, BlockHeight(4));
+        assert!(index.may_contain(BlockHeight(4)));
+        assert!(read_block_notes(&db, BlockHeight(2)).unwrap().is_none());
+        assert!(db
+            .read_masp_index_val(&notes_key(BlockHeight(2)))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            read_block_notes(&db, BlockHeight(4)).unwrap(),
+            Some(vec![compact_tx(4, 0)])
+        );
+
+        // So does a rollback to a lower height
+        index_block(&mut db, BlockHeight(3), vec![]).unwrap();
+        let index = read_block_index(&db).unwrap().unwrap();
+        assert_eq!(index.start_height, BlockHeight(3));
+        assert!(index.chunks.is_empty());
+        assert!(!index.may_contain(BlockHeight(3)));
+    }
+}
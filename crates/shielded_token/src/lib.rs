@@ -17,6 +17,7 @@
     clippy::print_stderr
 )]
 
+pub mod block_index;
 pub mod conversion;
 
 #[cfg(feature = "masp")]
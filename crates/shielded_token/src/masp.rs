@@ -2,12 +2,16 @@
 #![allow(clippy::arithmetic_side_effects)]
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_sign_loss)]
+#[cfg(feature = "std")]
+pub mod encrypted_fs;
+pub mod note_selection;
+pub mod shielded_history;
 mod shielded_sync;
 pub mod shielded_wallet;
 #[cfg(test)]
 mod test_utils;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -41,6 +45,9 @@ use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
 use namada_tx::IndexedTx;
+pub use note_selection::{
+    NoteCandidate, NoteSelection, NoteSelectionStrategy, PreferredNotes,
+};
 use rand_core::{CryptoRng, RngCore};
 pub use shielded_wallet::ShieldedWallet;
 use smooth_operator::checked;
@@ -121,6 +128,18 @@ pub struct MaspDataLog {
     pub amount: token::DenominatedAmount,
 }
 
+/// A batch of unspent notes of a token to be merged into a single note by a
+/// self-transfer
+#[derive(Debug, Clone)]
+pub struct NoteConsolidation {
+    /// The token of the notes
+    pub token: Address,
+    /// The positions of the notes in the commitment tree
+    pub notes: BTreeSet<usize>,
+    /// The total value of the notes, including their pending rewards
+    pub amount: token::DenominatedAmount,
+}
+
 #[allow(missing_docs)]
 pub struct MaspTxReorderedData {
     source_data: HashMap<MaspSourceTransferData, token::DenominatedAmount>,
@@ -211,6 +230,15 @@ pub trait ShieldedUtils:
         ctx: &ShieldedWallet<U>,
     ) -> std::io::Result<()>;
 
+    /// Load the notes of the given ShieldedContext that `load` may leave out
+    /// because they are all spent, for the stores that load them lazily
+    async fn load_spent_notes<U: ShieldedUtils + MaybeSend>(
+        &self,
+        _ctx: &mut ShieldedWallet<U>,
+    ) -> std::io::Result<()> {
+        Ok(())
+    }
+
     /// Save a cache of data as part of shielded sync if that
     /// process gets interrupted.
     async fn cache_save(&self, _cache: &DispatcherCache)
@@ -1009,11 +1037,11 @@ pub mod fs {
     };
 
     /// Shielded context file name
-    const FILE_NAME: &str = "shielded.dat";
+    pub(crate) const FILE_NAME: &str = "shielded.dat";
     const TMP_FILE_PREFIX: &str = "shielded.tmp";
-    const SPECULATIVE_FILE_NAME: &str = "speculative_shielded.dat";
+    pub(crate) const SPECULATIVE_FILE_NAME: &str = "speculative_shielded.dat";
     const SPECULATIVE_TMP_FILE_PREFIX: &str = "speculative_shielded.tmp";
-    const CACHE_FILE_NAME: &str = "shielded_sync.cache";
+    pub(crate) const CACHE_FILE_NAME: &str = "shielded_sync.cache";
     const CACHE_FILE_TMP_PREFIX: &str = "shielded_sync.cache.tmp";
 
     #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
@@ -1029,28 +1057,7 @@ pub mod fs {
         pub fn new(context_dir: PathBuf) -> ShieldedWallet<Self> {
             // Make sure that MASP parameters are downloaded to enable MASP
             // transaction building and verification later on
-            let params_dir = get_params_dir();
-            let spend_path = params_dir.join(SPEND_NAME);
-            let convert_path = params_dir.join(CONVERT_NAME);
-            let output_path = params_dir.join(OUTPUT_NAME);
-            if !(spend_path.exists()
-                && convert_path.exists()
-                && output_path.exists())
-            {
-                #[allow(clippy::print_stdout)]
-                {
-                    println!("MASP parameters not present, downloading...");
-                }
-                masp_proofs::download_masp_parameters(None)
-                    .expect("MASP parameters not present or downloadable");
-                #[allow(clippy::print_stdout)]
-                {
-                    println!(
-                        "MASP parameter download complete, resuming \
-                         execution..."
-                    );
-                }
-            }
+            download_masp_params_if_missing();
             // Finally initialize a shielded context with the supplied directory
 
             let sync_status =
@@ -1081,7 +1088,7 @@ pub mod fs {
         ///
         /// N.B. This only truly works if each process uses
         /// to a *unique* tmp file name.
-        fn atomic_file_write(
+        pub(crate) fn atomic_file_write(
             &self,
             tmp_file_name: impl AsRef<std::path::Path>,
             file_name: impl AsRef<std::path::Path>,
@@ -1116,6 +1123,32 @@ pub mod fs {
         }
     }
 
+    /// Download the MASP parameters if they are not present in the
+    /// parameters directory
+    pub(crate) fn download_masp_params_if_missing() {
+        let params_dir = get_params_dir();
+        let spend_path = params_dir.join(SPEND_NAME);
+        let convert_path = params_dir.join(CONVERT_NAME);
+        let output_path = params_dir.join(OUTPUT_NAME);
+        if !(spend_path.exists()
+            && convert_path.exists()
+            && output_path.exists())
+        {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("MASP parameters not present, downloading...");
+            }
+            masp_proofs::download_masp_parameters(None)
+                .expect("MASP parameters not present or downloadable");
+            #[allow(clippy::print_stdout)]
+            {
+                println!(
+                    "MASP parameter download complete, resuming execution..."
+                );
+            }
+        }
+    }
+
     impl Default for FsShieldedUtils {
         fn default() -> Self {
             Self {
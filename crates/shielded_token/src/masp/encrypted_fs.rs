@@ -0,0 +1,889 @@
+//! An implementation of [`ShieldedUtils`] for standard filesystems that keeps
+//! the shielded context encrypted at rest and persists it incrementally.
+//!
+//! The context is split in two parts. The decrypted notes, with their memos,
+//! diversifiers, viewing keys and nullifiers, never change once they are
+//! found, so they are appended to immutable note segments that are listed
+//! with the note positions they contain in a manifest. The rest of the
+//! context (the commitment tree, the witnesses, the spent notes, etc.) is
+//! stored field by field in state parts, which are listed by the state file.
+//! Saving the context only writes the notes that are not in any segment yet,
+//! the state parts that changed since they were last read or written, the
+//! manifest and the state file. Loading it only reads the segments with
+//! unspent notes, the others are read on demand by
+//! [`ShieldedUtils::load_spent_notes`]. The segments and the state parts are
+//! named after their content and decrypted at most once per process.
+//!
+//! All the files are encrypted with a key derived from a password and a
+//! random salt, which is stored next to them.
+//!
+//! This store is meant for the SDK users that can ask for a password; the
+//! CLI keeps using the plaintext [`FsShieldedUtils`]. An existing plaintext
+//! context can be moved to it with
+//! [`EncryptedFsShieldedUtils::migrate_from_fs`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use masp_primitives::asset_type::AssetType;
+use masp_primitives::memo::MemoBytes;
+use masp_primitives::merkle_tree::CommitmentTree;
+use masp_primitives::sapling::{
+    Diversifier, Node, Note, Nullifier, ViewingKey,
+};
+use masp_proofs::prover::LocalTxProver;
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
+use namada_core::collections::{HashMap, HashSet};
+use namada_core::hash::Hash;
+use namada_core::masp::{AssetData, MaspTxId};
+use namada_core::token::DenominatedAmount;
+use namada_io::{MaybeSend, MaybeSync};
+use namada_tx::IndexedTx;
+use orion::{aead, kdf};
+use zeroize::Zeroizing;
+
+use crate::masp::fs::{self, download_masp_params_if_missing, FsShieldedUtils};
+use crate::masp::shielded_history::ShieldedTxRecord;
+use crate::masp::{
+    ContextSyncStatus, DispatcherCache, NoteIndex, ShieldedUtils,
+    ShieldedWallet, WitnessMap,
+};
+
+/// The file name of the encryption key salt
+const SALT_FILE_NAME: &str = "shielded.salt";
+/// The note segments manifest file name
+const MANIFEST_FILE_NAME: &str = "shielded_manifest.dat";
+const MANIFEST_TMP_FILE_PREFIX: &str = "shielded_manifest.tmp";
+/// The confirmed state file name
+const STATE_FILE_NAME: &str = "shielded_state.dat";
+const STATE_TMP_FILE_PREFIX: &str = "shielded_state.tmp";
+/// The speculative state file name
+const SPECULATIVE_STATE_FILE_NAME: &str = "speculative_shielded_state.dat";
+const SPECULATIVE_STATE_TMP_FILE_PREFIX: &str =
+    "speculative_shielded_state.tmp";
+/// The prefix of the state part file names
+const STATE_PART_FILE_PREFIX: &str = "shielded_state_part_";
+const STATE_PART_TMP_FILE_PREFIX: &str = "shielded_state_part.tmp";
+const NOTES_TMP_FILE_PREFIX: &str = "shielded_notes.tmp";
+const CACHE_FILE_NAME: &str = "shielded_sync.cache";
+const CACHE_FILE_TMP_PREFIX: &str = "shielded_sync.cache.tmp";
+
+/// The number of iterations of the key derivation function
+const KDF_ITERATIONS: u32 = 3;
+/// The memory (in KiB) used by the key derivation function
+const KDF_MEMORY: u32 = 1 << 17;
+/// The length of the encryption key
+const KEY_LEN: u32 = 32;
+
+/// A note of the shielded context, as stored in a note segment
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+struct NoteEntry {
+    note_pos: usize,
+    note: Note,
+    memo: MemoBytes,
+    diversifier: Diversifier,
+    vk: ViewingKey,
+    nullifier: Nullifier,
+}
+
+/// The list of the note segments, with the note positions they contain
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct Manifest {
+    segments: Vec<(String, BTreeSet<usize>)>,
+}
+
+/// The list of the state parts, in the order of the fields of [`State`]
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct StateManifest {
+    parts: Vec<String>,
+}
+
+/// The part of the shielded context that is not stored in note segments.
+/// Each field is stored in its own state part, so the concatenation of the
+/// parts is the encoding of the state. Must be kept in sync with
+/// [`EncryptedFsShieldedUtils::save_state`].
+#[derive(BorshDeserialize)]
+struct State {
+    tree: CommitmentTree<Node>,
+    vk_heights: BTreeMap<ViewingKey, Option<IndexedTx>>,
+    pos_map: HashMap<ViewingKey, BTreeSet<usize>>,
+    witness_map: WitnessMap,
+    spents: HashSet<usize>,
+    asset_types: HashMap<AssetType, AssetData>,
+    note_index: NoteIndex,
+    tx_records: BTreeMap<IndexedTx, ShieldedTxRecord>,
+    tx_fees: HashMap<MaspTxId, (Address, DenominatedAmount)>,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, Default)]
+/// An implementation of ShieldedUtils for standard filesystems that encrypts
+/// the shielded context and only writes its changed parts
+pub struct EncryptedFsShieldedUtils {
+    #[borsh(skip)]
+    fs: FsShieldedUtils,
+    /// The encryption key, absent if the utils were not constructed with a
+    /// password
+    #[borsh(skip)]
+    key: Option<Arc<kdf::SecretKey>>,
+    /// The note segments decrypted so far
+    #[borsh(skip)]
+    segments: Arc<Mutex<HashMap<String, Arc<Vec<NoteEntry>>>>>,
+    /// The files of the state parts read or written so far, by the hash of
+    /// their decrypted content
+    #[borsh(skip)]
+    state_parts: Arc<Mutex<HashMap<Hash, String>>>,
+}
+
+impl EncryptedFsShieldedUtils {
+    /// Initialize a shielded transaction context that identifies notes
+    /// decryptable by any viewing key in the given set and stores them in
+    /// the given directory, encrypted with the given password
+    pub fn new(
+        context_dir: PathBuf,
+        password: Zeroizing<String>,
+    ) -> std::io::Result<ShieldedWallet<Self>> {
+        // Make sure that MASP parameters are downloaded to enable MASP
+        // transaction building and verification later on
+        download_masp_params_if_missing();
+        let utils = Self::with_password(context_dir, password)?;
+        Ok(utils.into_wallet())
+    }
+
+    /// Move the plaintext shielded context of [`FsShieldedUtils`] in the
+    /// directory of this store, if any, to this store and initialize a
+    /// shielded transaction context with it. The plaintext files are removed
+    /// once their content is stored encrypted.
+    pub async fn migrate_from_fs(
+        &self,
+    ) -> std::io::Result<ShieldedWallet<Self>> {
+        // Migrate the confirmed state first, as saving it removes the
+        // speculative one
+        for (sync_status, file_name) in [
+            (ContextSyncStatus::Confirmed, fs::FILE_NAME),
+            (ContextSyncStatus::Speculative, fs::SPECULATIVE_FILE_NAME),
+        ] {
+            let mut plaintext = ShieldedWallet {
+                utils: self.fs.clone(),
+                sync_status,
+                ..Default::default()
+            };
+            match plaintext.load().await {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+            let ShieldedWallet {
+                utils: _,
+                tree,
+                vk_heights,
+                pos_map,
+                nf_map,
+                note_map,
+                memo_map,
+                div_map,
+                witness_map,
+                spents,
+                asset_types,
+                vk_map,
+                note_index,
+                sync_status,
+                tx_records,
+                tx_fees,
+            } = plaintext;
+            let ctx = ShieldedWallet {
+                utils: self.clone(),
+                tree,
+                vk_heights,
+                pos_map,
+                nf_map,
+                note_map,
+                memo_map,
+                div_map,
+                witness_map,
+                spents,
+                asset_types,
+                vk_map,
+                note_index,
+                sync_status,
+                tx_records,
+                tx_fees,
+            };
+            ctx.save().await?;
+            std::fs::remove_file(self.fs.context_dir.join(file_name))?;
+        }
+        // The plaintext cache can't be decrypted, it's rebuilt by the next
+        // sync instead
+        match std::fs::remove_file(
+            self.fs.context_dir.join(fs::CACHE_FILE_NAME),
+        ) {
+            Err(err) if err.kind() != ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        Ok(self.clone().into_wallet())
+    }
+
+    /// Initialize a shielded transaction context with these utils
+    fn into_wallet(self) -> ShieldedWallet<Self> {
+        let sync_status = if self
+            .fs
+            .context_dir
+            .join(SPECULATIVE_STATE_FILE_NAME)
+            .exists()
+        {
+            // Load speculative state
+            ContextSyncStatus::Speculative
+        } else {
+            ContextSyncStatus::Confirmed
+        };
+        ShieldedWallet {
+            utils: self,
+            sync_status,
+            ..Default::default()
+        }
+    }
+
+    /// Derive the encryption key of the store in the given directory from
+    /// the given password. Fails if the password doesn't match the one that
+    /// the store was created with.
+    pub fn with_password(
+        context_dir: PathBuf,
+        password: Zeroizing<String>,
+    ) -> std::io::Result<Self> {
+        if password.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Empty password provided",
+            ));
+        }
+        std::fs::create_dir_all(&context_dir)?;
+        let salt_path = context_dir.join(SALT_FILE_NAME);
+        let salt = match std::fs::read(&salt_path) {
+            Ok(raw_salt) => kdf::Salt::from_slice(&raw_salt).map_err(|_| {
+                Error::new(ErrorKind::InvalidData, "Invalid encryption salt")
+            })?,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let salt = kdf::Salt::default();
+                std::fs::write(&salt_path, salt.as_ref())?;
+                salt
+            }
+            Err(err) => return Err(err),
+        };
+        let key = kdf::Password::from_slice(password.as_bytes())
+            .and_then(|password| {
+                kdf::derive_key(
+                    &password,
+                    &salt,
+                    KDF_ITERATIONS,
+                    KDF_MEMORY,
+                    KEY_LEN,
+                )
+            })
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::Other,
+                    "Unable to derive the encryption key",
+                )
+            })?;
+        let utils = Self {
+            fs: FsShieldedUtils { context_dir },
+            key: Some(Arc::new(key)),
+            segments: Default::default(),
+            state_parts: Default::default(),
+        };
+        // Check the password against the existing store, if any
+        utils.read_manifest()?;
+        Ok(utils)
+    }
+
+    fn key(&self) -> std::io::Result<&kdf::SecretKey> {
+        self.key.as_deref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::PermissionDenied,
+                "The shielded context store was opened without a password",
+            )
+        })
+    }
+
+    fn encrypt(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        aead::seal(self.key()?, data)
+            .map_err(|_| Error::new(ErrorKind::Other, "Unable to encrypt data"))
+    }
+
+    /// Encrypt and write the given data to a file
+    fn write_encrypted(
+        &self,
+        tmp_file_prefix: &str,
+        file_name: &str,
+        data: &impl BorshSerialize,
+    ) -> std::io::Result<()> {
+        let cipher = self.encrypt(&data.serialize_to_vec())?;
+        self.write_cipher(tmp_file_prefix, file_name, &cipher)
+    }
+
+    fn write_cipher(
+        &self,
+        tmp_file_prefix: &str,
+        file_name: &str,
+        cipher: &[u8],
+    ) -> std::io::Result<()> {
+        let tmp_file_name = {
+            let t = tempfile::Builder::new()
+                .prefix(tmp_file_prefix)
+                .tempfile()?;
+            t.path().file_name().unwrap().to_owned()
+        };
+        self.fs.atomic_file_write(tmp_file_name, file_name, cipher)
+    }
+
+    /// Read and decrypt the content of a file
+    fn read_decrypted(&self, file_name: &str) -> std::io::Result<Vec<u8>> {
+        let mut file = File::open(self.fs.context_dir.join(file_name))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        let cipher = Vec::<u8>::try_from_slice(&bytes)?;
+        aead::open(self.key()?, &cipher).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Unable to decrypt {file_name}, the password may be wrong"
+                ),
+            )
+        })
+    }
+
+    /// Read and decrypt the data of a file
+    fn read_encrypted<T: BorshDeserialize>(
+        &self,
+        file_name: &str,
+    ) -> std::io::Result<T> {
+        T::try_from_slice(&self.read_decrypted(file_name)?)
+    }
+
+    /// Read the manifest, which is empty until the first notes are stored
+    fn read_manifest(&self) -> std::io::Result<Manifest> {
+        match self.read_encrypted(MANIFEST_FILE_NAME) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                Ok(Manifest::default())
+            }
+            res => res,
+        }
+    }
+
+    /// Get the notes of a segment, reading it only if it's not cached
+    fn read_segment(
+        &self,
+        segment: &str,
+    ) -> std::io::Result<Arc<Vec<NoteEntry>>> {
+        if let Some(notes) = self.segments.lock().unwrap().get(segment) {
+            return Ok(notes.clone());
+        }
+        let notes = Arc::new(self.read_encrypted::<Vec<NoteEntry>>(segment)?);
+        self.segments
+            .lock()
+            .unwrap()
+            .insert(segment.to_string(), notes.clone());
+        Ok(notes)
+    }
+
+    /// Add the notes of the segments that match the given predicate on the
+    /// context and their note positions to the context
+    fn load_segments<U: ShieldedUtils>(
+        &self,
+        ctx: &mut ShieldedWallet<U>,
+        predicate: impl Fn(&ShieldedWallet<U>, &BTreeSet<usize>) -> bool,
+    ) -> std::io::Result<()> {
+        for (segment, positions) in self.read_manifest()?.segments {
+            if !predicate(ctx, &positions) {
+                continue;
+            }
+            for entry in self.read_segment(&segment)?.iter() {
+                ctx.note_map.insert(entry.note_pos, entry.note);
+                ctx.memo_map.insert(entry.note_pos, entry.memo.clone());
+                ctx.div_map.insert(entry.note_pos, entry.diversifier);
+                ctx.vk_map.insert(entry.note_pos, entry.vk);
+                ctx.nf_map.insert(entry.nullifier, entry.note_pos);
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the notes of the context that are not in any segment yet to a
+    /// new segment
+    fn save_new_notes<U: ShieldedUtils>(
+        &self,
+        ctx: &ShieldedWallet<U>,
+    ) -> std::io::Result<()> {
+        let mut manifest = self.read_manifest()?;
+        let stored: HashSet<usize> = manifest
+            .segments
+            .iter()
+            .flat_map(|(_, positions)| positions.iter().copied())
+            .collect();
+        let nullifiers: HashMap<usize, Nullifier> = ctx
+            .nf_map
+            .iter()
+            .filter(|(_, note_pos)| !stored.contains(note_pos))
+            .map(|(nullifier, note_pos)| (*note_pos, *nullifier))
+            .collect();
+        let missing = |note_pos: &usize| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Incomplete data of note {note_pos}"),
+            )
+        };
+        let mut notes = ctx
+            .note_map
+            .iter()
+            .filter(|(note_pos, _)| !stored.contains(note_pos))
+            .map(|(note_pos, note)| {
+                Ok(NoteEntry {
+                    note_pos: *note_pos,
+                    note: *note,
+                    memo: ctx
+                        .memo_map
+                        .get(note_pos)
+                        .cloned()
+                        .unwrap_or_else(MemoBytes::empty),
+                    diversifier: *ctx
+                        .div_map
+                        .get(note_pos)
+                        .ok_or_else(|| missing(note_pos))?,
+                    vk: *ctx
+                        .vk_map
+                        .get(note_pos)
+                        .ok_or_else(|| missing(note_pos))?,
+                    nullifier: *nullifiers
+                        .get(note_pos)
+                        .ok_or_else(|| missing(note_pos))?,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        if notes.is_empty() {
+            return Ok(());
+        }
+        notes.sort_by_key(|entry| entry.note_pos);
+
+        let cipher = self.encrypt(&notes.serialize_to_vec())?;
+        // Segments are named after their content, so that they can never be
+        // overwritten with different notes
+        let segment = format!("shielded_notes_{}.dat", Hash::sha256(&cipher));
+        self.write_cipher(NOTES_TMP_FILE_PREFIX, &segment, &cipher)?;
+        let positions = notes.iter().map(|entry| entry.note_pos).collect();
+        self.segments
+            .lock()
+            .unwrap()
+            .insert(segment.clone(), Arc::new(notes));
+        manifest.segments.push((segment, positions));
+        self.write_encrypted(
+            MANIFEST_TMP_FILE_PREFIX,
+            MANIFEST_FILE_NAME,
+            &manifest,
+        )
+    }
+
+    /// Read the state listed in the given state file
+    fn read_state(&self, file_name: &str) -> std::io::Result<State> {
+        let manifest: StateManifest = self.read_encrypted(file_name)?;
+        let mut bytes = vec![];
+        for part in manifest.parts {
+            let data = self.read_decrypted(&part)?;
+            self.state_parts
+                .lock()
+                .unwrap()
+                .insert(Hash::sha256(&data), part);
+            bytes.extend(data);
+        }
+        State::try_from_slice(&bytes)
+    }
+
+    /// Write the state parts of the context that aren't stored yet and list
+    /// all of them in the given state file
+    fn save_state<U: ShieldedUtils>(
+        &self,
+        ctx: &ShieldedWallet<U>,
+        tmp_file_prefix: &str,
+        file_name: &str,
+    ) -> std::io::Result<()> {
+        let parts = [
+            ctx.tree.serialize_to_vec(),
+            ctx.vk_heights.serialize_to_vec(),
+            ctx.pos_map.serialize_to_vec(),
+            ctx.witness_map.serialize_to_vec(),
+            ctx.spents.serialize_to_vec(),
+            ctx.asset_types.serialize_to_vec(),
+            ctx.note_index.serialize_to_vec(),
+            ctx.tx_records.serialize_to_vec(),
+            ctx.tx_fees.serialize_to_vec(),
+        ];
+        let mut manifest = StateManifest::default();
+        for data in parts {
+            let data_hash = Hash::sha256(&data);
+            let stored =
+                self.state_parts.lock().unwrap().get(&data_hash).cloned();
+            let part = match stored {
+                Some(part) if self.fs.context_dir.join(&part).exists() => part,
+                _ => {
+                    let cipher = self.encrypt(&data)?;
+                    // Parts are named after their content, like segments
+                    let part = format!(
+                        "{STATE_PART_FILE_PREFIX}{}.dat",
+                        Hash::sha256(&cipher)
+                    );
+                    self.write_cipher(
+                        STATE_PART_TMP_FILE_PREFIX,
+                        &part,
+                        &cipher,
+                    )?;
+                    self.state_parts
+                        .lock()
+                        .unwrap()
+                        .insert(data_hash, part.clone());
+                    part
+                }
+            };
+            manifest.parts.push(part);
+        }
+        self.write_encrypted(tmp_file_prefix, file_name, &manifest)
+    }
+
+    /// Remove the state parts that are not listed in any state file
+    fn remove_unused_state_parts(&self) -> std::io::Result<()> {
+        let mut used = HashSet::new();
+        for file_name in [STATE_FILE_NAME, SPECULATIVE_STATE_FILE_NAME] {
+            match self.read_encrypted::<StateManifest>(file_name) {
+                Ok(manifest) => used.extend(manifest.parts),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        for entry in std::fs::read_dir(&self.fs.context_dir)? {
+            let file_name = entry?.file_name().to_string_lossy().into_owned();
+            if file_name.starts_with(STATE_PART_FILE_PREFIX)
+                && !used.contains(&file_name)
+            {
+                let _ =
+                    std::fs::remove_file(self.fs.context_dir.join(file_name));
+            }
+        }
+        self.state_parts
+            .lock()
+            .unwrap()
+            .retain(|_, part| used.contains(part));
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl ShieldedUtils for EncryptedFsShieldedUtils {
+    fn local_tx_prover(&self) -> LocalTxProver {
+        self.fs.local_tx_prover()
+    }
+
+    /// Try to load the last saved shielded context from the given context
+    /// directory. If this fails, then leave the current context unchanged.
+    async fn load<U: ShieldedUtils + MaybeSend>(
+        &self,
+        ctx: &mut ShieldedWallet<U>,
+        force_confirmed: bool,
+    ) -> std::io::Result<()> {
+        let (file_name, sync_status) = if force_confirmed {
+            (STATE_FILE_NAME, ContextSyncStatus::Confirmed)
+        } else {
+            match ctx.sync_status {
+                ContextSyncStatus::Confirmed => {
+                    (STATE_FILE_NAME, ContextSyncStatus::Confirmed)
+                }
+                ContextSyncStatus::Speculative => (
+                    SPECULATIVE_STATE_FILE_NAME,
+                    ContextSyncStatus::Speculative,
+                ),
+            }
+        };
+        let State {
+            tree,
+            vk_heights,
+            pos_map,
+            witness_map,
+            spents,
+            asset_types,
+            note_index,
+            tx_records,
+            tx_fees,
+        } = self.read_state(file_name)?;
+        let mut loaded = ShieldedWallet {
+            utils: ctx.utils.clone(),
+            tree,
+            vk_heights,
+            pos_map,
+            witness_map,
+            spents,
+            asset_types,
+            note_index,
+            sync_status,
+            tx_records,
+            tx_fees,
+            ..Default::default()
+        };
+        // The segments with only spent notes are left out until needed
+        self.load_segments(&mut loaded, |ctx, positions| {
+            !positions.iter().all(|pos| ctx.spents.contains(pos))
+        })?;
+        *ctx = loaded;
+        Ok(())
+    }
+
+    async fn load_spent_notes<U: ShieldedUtils + MaybeSend>(
+        &self,
+        ctx: &mut ShieldedWallet<U>,
+    ) -> std::io::Result<()> {
+        self.load_segments(ctx, |ctx, positions| {
+            !positions.iter().all(|pos| ctx.note_map.contains_key(pos))
+        })
+    }
+
+    /// Save the new notes and the state of this shielded context into its
+    /// associated context directory. At the same time, delete the
+    /// speculative state if the saved one is confirmed.
+    async fn save<U: ShieldedUtils + MaybeSync>(
+        &self,
+        ctx: &ShieldedWallet<U>,
+    ) -> std::io::Result<()> {
+        self.save_new_notes(ctx)?;
+
+        let (tmp_file_prefix, file_name) = match ctx.sync_status {
+            ContextSyncStatus::Confirmed => {
+                (STATE_TMP_FILE_PREFIX, STATE_FILE_NAME)
+            }
+            ContextSyncStatus::Speculative => (
+                SPECULATIVE_STATE_TMP_FILE_PREFIX,
+                SPECULATIVE_STATE_FILE_NAME,
+            ),
+        };
+        self.save_state(ctx, tmp_file_prefix, file_name)?;
+
+        // Remove the speculative state if present since it's overruled by
+        // the confirmed one we just saved
+        if let ContextSyncStatus::Confirmed = ctx.sync_status {
+            let _ = std::fs::remove_file(
+                self.fs.context_dir.join(SPECULATIVE_STATE_FILE_NAME),
+            );
+        }
+
+        self.remove_unused_state_parts()
+    }
+
+    async fn cache_save(&self, cache: &DispatcherCache) -> std::io::Result<()> {
+        self.write_encrypted(CACHE_FILE_TMP_PREFIX, CACHE_FILE_NAME, cache)
+    }
+
+    async fn cache_load(&self) -> std::io::Result<DispatcherCache> {
+        self.read_encrypted(CACHE_FILE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use namada_core::chain::BlockHeight;
+    use namada_core::storage::TxIndex;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::masp::shielded_sync::trial_decrypt;
+    use crate::masp::test_utils::{arbitrary_masp_tx, arbitrary_vk};
+
+    fn password() -> Zeroizing<String> {
+        Zeroizing::new("password".to_string())
+    }
+
+    /// Add the notes of an arbitrary MASP tx that the given viewing key can
+    /// decrypt to the context
+    fn add_tx_notes(
+        ctx: &mut ShieldedWallet<EncryptedFsShieldedUtils>,
+        vk: &ViewingKey,
+        index: u32,
+    ) {
+        let indexed_tx = IndexedTx {
+            height: BlockHeight(1),
+            index: TxIndex(index),
+            batch_index: None,
+        };
+        let tx = arbitrary_masp_tx();
+        ctx.update_witness_map(indexed_tx, &tx).unwrap();
+        let first_note_pos = ctx.note_index[&indexed_tx];
+        let ControlFlow::Continue(decrypted) = trial_decrypt(tx, *vk, || false)
+        else {
+            panic!("Test failed")
+        };
+        for (note_pos_offset, (note, pa, memo)) in decrypted {
+            ctx.save_decrypted_shielded_outputs(
+                vk,
+                first_note_pos + note_pos_offset,
+                note,
+                pa,
+                memo,
+            )
+            .unwrap();
+        }
+    }
+
+    fn files_with_prefix(
+        dir: &std::path::Path,
+        prefix: &str,
+    ) -> BTreeSet<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                entry.unwrap().file_name().to_string_lossy().into_owned()
+            })
+            .filter(|file_name| file_name.starts_with(prefix))
+            .collect()
+    }
+
+    /// Test that the notes are appended to new segments only when they are
+    /// not stored yet, that only the changed state parts are written and
+    /// that the context is restored from the store
+    #[tokio::test]
+    async fn test_incremental_save_and_load() {
+        let temp_dir = tempdir().unwrap();
+        let utils = EncryptedFsShieldedUtils::with_password(
+            temp_dir.path().to_path_buf(),
+            password(),
+        )
+        .unwrap();
+        let vk = arbitrary_vk();
+        let mut ctx = ShieldedWallet {
+            utils,
+            ..Default::default()
+        };
+        ctx.vk_heights.insert(vk, None);
+
+        let mut first_notes = vec![];
+        for index in 0..2 {
+            add_tx_notes(&mut ctx, &vk, index);
+            if index == 0 {
+                first_notes.extend(ctx.note_map.keys().copied());
+            }
+            ctx.save().await.unwrap();
+            assert_eq!(
+                files_with_prefix(temp_dir.path(), "shielded_notes_").len(),
+                index as usize + 1
+            );
+        }
+        // Saving again without changes doesn't write anything new
+        let parts = files_with_prefix(temp_dir.path(), STATE_PART_FILE_PREFIX);
+        ctx.save().await.unwrap();
+        assert_eq!(
+            files_with_prefix(temp_dir.path(), STATE_PART_FILE_PREFIX),
+            parts
+        );
+        // Saving again without new notes doesn't add a segment and only
+        // writes the changed state part
+        ctx.spents.extend(first_notes.iter().copied());
+        ctx.save().await.unwrap();
+        assert_eq!(
+            files_with_prefix(temp_dir.path(), "shielded_notes_").len(),
+            2
+        );
+        let new_parts =
+            files_with_prefix(temp_dir.path(), STATE_PART_FILE_PREFIX);
+        assert_eq!(new_parts.difference(&parts).count(), 1);
+
+        let utils = EncryptedFsShieldedUtils::with_password(
+            temp_dir.path().to_path_buf(),
+            password(),
+        )
+        .unwrap();
+        let mut loaded = ShieldedWallet {
+            utils,
+            ..Default::default()
+        };
+        loaded.load().await.unwrap();
+        // The segment with only spent notes is left out
+        assert!(first_notes
+            .iter()
+            .all(|note_pos| !loaded.note_map.contains_key(note_pos)));
+        loaded.load_spent_notes().await.unwrap();
+        assert_eq!(loaded.note_map.len(), ctx.note_map.len());
+        assert_eq!(loaded.vk_heights, ctx.vk_heights);
+        assert_eq!(loaded.pos_map, ctx.pos_map);
+        assert_eq!(loaded.nf_map, ctx.nf_map);
+        assert_eq!(loaded.vk_map, ctx.vk_map);
+        assert_eq!(loaded.div_map, ctx.div_map);
+        assert_eq!(loaded.note_index, ctx.note_index);
+        assert_eq!(loaded.spents, ctx.spents);
+        assert_eq!(loaded.tree.size(), ctx.tree.size());
+
+        // Saving the loaded context reuses all its state parts
+        loaded.save().await.unwrap();
+        assert_eq!(
+            files_with_prefix(temp_dir.path(), STATE_PART_FILE_PREFIX),
+            new_parts
+        );
+    }
+
+    /// Test that a plaintext context is moved to the encrypted store
+    #[tokio::test]
+    async fn test_migrate_from_fs() {
+        let temp_dir = tempdir().unwrap();
+        let vk = arbitrary_vk();
+        let mut plaintext = ShieldedWallet {
+            utils: FsShieldedUtils {
+                context_dir: temp_dir.path().to_path_buf(),
+            },
+            ..Default::default()
+        };
+        plaintext.vk_heights.insert(vk, None);
+        plaintext.save().await.unwrap();
+        assert!(temp_dir.path().join(fs::FILE_NAME).exists());
+
+        let mut ctx = EncryptedFsShieldedUtils::with_password(
+            temp_dir.path().to_path_buf(),
+            password(),
+        )
+        .unwrap()
+        .migrate_from_fs()
+        .await
+        .unwrap();
+        assert!(!temp_dir.path().join(fs::FILE_NAME).exists());
+        assert!(matches!(ctx.sync_status, ContextSyncStatus::Confirmed));
+        ctx.load().await.unwrap();
+        assert_eq!(ctx.vk_heights, plaintext.vk_heights);
+    }
+
+    /// Test that the store can't be opened with a wrong password
+    #[tokio::test]
+    async fn test_wrong_password() {
+        let temp_dir = tempdir().unwrap();
+        let utils = EncryptedFsShieldedUtils::with_password(
+            temp_dir.path().to_path_buf(),
+            password(),
+        )
+        .unwrap();
+        let vk = arbitrary_vk();
+        let mut ctx = ShieldedWallet {
+            utils,
+            ..Default::default()
+        };
+        ctx.vk_heights.insert(vk, None);
+        add_tx_notes(&mut ctx, &vk, 0);
+        ctx.save().await.unwrap();
+
+        let err = EncryptedFsShieldedUtils::with_password(
+            temp_dir.path().to_path_buf(),
+            Zeroizing::new("wrong password".to_string()),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // The store can't be used without a password either
+        let mut ctx = ShieldedWallet::<EncryptedFsShieldedUtils>::default();
+        assert!(ctx.load().await.is_err());
+    }
+}
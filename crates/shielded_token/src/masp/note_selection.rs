@@ -0,0 +1,333 @@
+//! Strategies to select the notes that fund a shielded transfer.
+
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use masp_primitives::transaction::components::I128Sum;
+use rand::seq::SliceRandom;
+use rand_core::RngCore;
+
+/// An unspent note that may fund a shielded transfer
+#[derive(Debug, Clone)]
+pub struct NoteCandidate {
+    /// The position of the note in the commitment tree
+    pub note_pos: usize,
+    /// The value of the note, converted to the asset types of the epoch in
+    /// which the transfer is built. Zero if the strategy doesn't need the
+    /// values of the notes.
+    pub normed_value: I128Sum,
+}
+
+/// A strategy to select the notes that fund a shielded transfer.
+///
+/// The candidate notes are tried in the order returned by the strategy and
+/// each of them is added to the transfer only if it brings the inputs closer
+/// to the target, until the target is met.
+pub trait NoteSelectionStrategy {
+    /// Check if the strategy orders the notes by their values. Otherwise, the
+    /// notes are not valued in the epoch of the transfer before ordering them,
+    /// which saves the conversion queries.
+    fn needs_values(&self) -> bool {
+        true
+    }
+
+    /// Order the candidate notes by preference to meet the given target
+    fn order_notes(
+        &self,
+        candidates: Vec<NoteCandidate>,
+        target: &I128Sum,
+        rng: &mut dyn RngCore,
+    ) -> Vec<NoteCandidate>;
+}
+
+/// The built-in note selection strategies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NoteSelection {
+    /// Spend the oldest notes first
+    #[default]
+    OldestFirst,
+    /// Spend as few notes as possible. The smallest note that meets the
+    /// target on its own is preferred, otherwise the notes that contribute
+    /// the most to the target are spent first.
+    FewestNotes,
+    /// Spend the notes in a random order, so that the notes spent together
+    /// cannot be linked by their age
+    Random,
+    /// Spend the oldest notes first, but try last the notes whose value
+    /// exactly matches the target, so that the transfer produces a change
+    /// note whenever the other notes can fund it
+    AvoidExactMatch,
+}
+
+impl NoteSelectionStrategy for NoteSelection {
+    fn needs_values(&self) -> bool {
+        match self {
+            Self::OldestFirst | Self::Random => false,
+            Self::FewestNotes | Self::AvoidExactMatch => true,
+        }
+    }
+
+    fn order_notes(
+        &self,
+        mut candidates: Vec<NoteCandidate>,
+        target: &I128Sum,
+        rng: &mut dyn RngCore,
+    ) -> Vec<NoteCandidate> {
+        match self {
+            Self::OldestFirst => {
+                candidates.sort_by_key(|candidate| candidate.note_pos);
+                candidates
+            }
+            Self::FewestNotes => {
+                candidates.sort_by_key(|candidate| candidate.note_pos);
+                let (mut covering, mut partial): (Vec<_>, Vec<_>) =
+                    candidates.into_iter().partition(|candidate| {
+                        covers(&candidate.normed_value, target)
+                    });
+                covering.sort_by_key(|candidate| {
+                    total_in(&candidate.normed_value, target)
+                });
+                partial.sort_by_key(|candidate| {
+                    Reverse(coverage(&candidate.normed_value, target))
+                });
+                covering.extend(partial);
+                covering
+            }
+            Self::Random => {
+                candidates.shuffle(rng);
+                candidates
+            }
+            Self::AvoidExactMatch => {
+                candidates.sort_by_key(|candidate| {
+                    (
+                        is_exact_match(&candidate.normed_value, target),
+                        candidate.note_pos,
+                    )
+                });
+                candidates
+            }
+        }
+    }
+}
+
+/// Spend the preferred notes first, then the other notes and finally the
+/// reserved ones, oldest first in each group. Used to spend specific notes
+/// (e.g. to consolidate them) while paying the fees from other notes, keeping
+/// the reserved notes for later transfers whenever possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreferredNotes {
+    /// The notes to spend first
+    pub preferred: BTreeSet<usize>,
+    /// The notes to spend last
+    pub reserved: BTreeSet<usize>,
+}
+
+impl NoteSelectionStrategy for PreferredNotes {
+    fn needs_values(&self) -> bool {
+        false
+    }
+
+    fn order_notes(
+        &self,
+        mut candidates: Vec<NoteCandidate>,
+        _target: &I128Sum,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<NoteCandidate> {
+        candidates.sort_by_key(|candidate| {
+            let group = if self.preferred.contains(&candidate.note_pos) {
+                0
+            } else if self.reserved.contains(&candidate.note_pos) {
+                2
+            } else {
+                1
+            };
+            (group, candidate.note_pos)
+        });
+        candidates
+    }
+}
+
+impl fmt::Display for NoteSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OldestFirst => write!(f, "oldest-first"),
+            Self::FewestNotes => write!(f, "fewest-notes"),
+            Self::Random => write!(f, "random"),
+            Self::AvoidExactMatch => write!(f, "avoid-exact-match"),
+        }
+    }
+}
+
+impl FromStr for NoteSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "oldest-first" => Ok(Self::OldestFirst),
+            "fewest-notes" => Ok(Self::FewestNotes),
+            "random" => Ok(Self::Random),
+            "avoid-exact-match" => Ok(Self::AvoidExactMatch),
+            raw => Err(format!(
+                "Unexpected note selection strategy \"{raw}\". Valid options \
+                 are \"oldest-first\", \"fewest-notes\", \"random\" or \
+                 \"avoid-exact-match\"."
+            )),
+        }
+    }
+}
+
+/// Check if the value meets every component of the target
+fn covers(value: &I128Sum, target: &I128Sum) -> bool {
+    target
+        .components()
+        .all(|(asset_type, amount)| value[asset_type] >= *amount)
+}
+
+/// Check if the value is exactly the target in the asset types of the target
+fn is_exact_match(value: &I128Sum, target: &I128Sum) -> bool {
+    target
+        .components()
+        .all(|(asset_type, amount)| value[asset_type] == *amount)
+}
+
+/// The part of the target that the value contributes to
+fn coverage(value: &I128Sum, target: &I128Sum) -> i128 {
+    target
+        .components()
+        .filter(|(_, amount)| **amount > 0)
+        .map(|(asset_type, amount)| value[asset_type].clamp(0, *amount))
+        .fold(0, i128::saturating_add)
+}
+
+/// The value in the asset types of the target
+fn total_in(value: &I128Sum, target: &I128Sum) -> i128 {
+    target
+        .components()
+        .map(|(asset_type, _)| value[asset_type].max(0))
+        .fold(0, i128::saturating_add)
+}
+
+#[cfg(test)]
+mod tests {
+    use masp_primitives::asset_type::AssetType;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn candidates(
+        asset_type: AssetType,
+        values: &[i128],
+    ) -> Vec<NoteCandidate> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(note_pos, value)| NoteCandidate {
+                note_pos,
+                normed_value: I128Sum::from_pair(asset_type, *value),
+            })
+            .collect()
+    }
+
+    fn order(
+        strategy: NoteSelection,
+        values: &[i128],
+        target: i128,
+    ) -> Vec<usize> {
+        let asset_type = AssetType::new(b"token").unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        strategy
+            .order_notes(
+                candidates(asset_type, values),
+                &I128Sum::from_pair(asset_type, target),
+                &mut rng,
+            )
+            .into_iter()
+            .map(|candidate| candidate.note_pos)
+            .collect()
+    }
+
+    #[test]
+    fn test_oldest_first() {
+        assert_eq!(
+            order(NoteSelection::OldestFirst, &[5, 1, 20, 10], 10),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_fewest_notes() {
+        // The smallest notes that meet the target on their own come first
+        assert_eq!(
+            order(NoteSelection::FewestNotes, &[5, 1, 20, 10, 7], 10),
+            vec![3, 2, 4, 0, 1]
+        );
+        // Without such notes, the largest ones come first
+        assert_eq!(
+            order(NoteSelection::FewestNotes, &[5, 1, 8, 3], 10),
+            vec![2, 0, 3, 1]
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        let mut ordered =
+            order(NoteSelection::Random, &[5, 1, 20, 10, 7, 3, 2, 9], 10);
+        assert_ne!(ordered, (0..8).collect::<Vec<_>>());
+        ordered.sort();
+        assert_eq!(ordered, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_avoid_exact_match() {
+        assert_eq!(
+            order(NoteSelection::AvoidExactMatch, &[10, 5, 20, 10, 7], 10),
+            vec![1, 2, 4, 0, 3]
+        );
+    }
+
+    #[test]
+    fn test_preferred_notes() {
+        let asset_type = AssetType::new(b"token").unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let strategy = PreferredNotes {
+            preferred: [1, 3].into_iter().collect(),
+            reserved: [0].into_iter().collect(),
+        };
+        let ordered: Vec<_> = strategy
+            .order_notes(
+                candidates(asset_type, &[5, 1, 20, 10, 7]),
+                &I128Sum::from_pair(asset_type, 11),
+                &mut rng,
+            )
+            .into_iter()
+            .map(|candidate| candidate.note_pos)
+            .collect();
+        assert_eq!(ordered, vec![1, 3, 2, 4, 0]);
+    }
+
+    #[test]
+    fn test_needs_values() {
+        // Only the strategies comparing the notes with the target value them
+        assert!(!NoteSelection::OldestFirst.needs_values());
+        assert!(!NoteSelection::Random.needs_values());
+        assert!(NoteSelection::FewestNotes.needs_values());
+        assert!(NoteSelection::AvoidExactMatch.needs_values());
+        assert!(!PreferredNotes::default().needs_values());
+    }
+
+    #[test]
+    fn test_parse_note_selection() {
+        for strategy in [
+            NoteSelection::OldestFirst,
+            NoteSelection::FewestNotes,
+            NoteSelection::Random,
+            NoteSelection::AvoidExactMatch,
+        ] {
+            assert_eq!(strategy.to_string().parse(), Ok(strategy));
+        }
+        assert!("largest-first".parse::<NoteSelection>().is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! The history of the shielded txs of a viewing key, reconstructed from the
+//! records kept by the shielded wallet while syncing.
+use std::collections::BTreeMap;
+
+use eyre::eyre;
+use masp_primitives::memo::MemoBytes;
+use masp_primitives::sapling::ViewingKey;
+use masp_primitives::transaction::components::I128Sum;
+use masp_primitives::transaction::TransparentAddress;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::chain::BlockHeight;
+use namada_core::masp::MaspTxId;
+use namada_core::time::DateTimeUtc;
+use namada_io::client::Client;
+use namada_io::{MaybeSend, MaybeSync};
+use namada_tx::IndexedTx;
+
+use crate::masp::shielded_wallet::ShieldedApi;
+use crate::masp::{MaspAmount, ShieldedUtils};
+
+/// The record of a shielded tx that spent or produced notes of the viewing
+/// keys in a shielded context.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ShieldedTxRecord {
+    /// The id of the MASP tx
+    pub txid: MaspTxId,
+    /// The position of the first output note of the tx
+    pub first_note_pos: usize,
+    /// The number of shielded outputs of the tx
+    pub num_outputs: usize,
+    /// The number of shielded spends of the tx
+    pub num_spends: usize,
+    /// The positions of the spent notes of the viewing keys in the context
+    pub spent_notes: Vec<usize>,
+    /// The value moved by the tx from the shielded pool to the transparent
+    /// pool
+    pub value_balance: I128Sum,
+    /// The addresses of the transparent inputs of the tx
+    pub transparent_sources: Vec<TransparentAddress>,
+    /// The addresses of the transparent outputs of the tx
+    pub transparent_targets: Vec<TransparentAddress>,
+}
+
+/// A note received or spent by a viewing key in a shielded tx
+#[derive(Debug, Clone)]
+pub struct ShieldedHistoryNote {
+    /// The position of the note in the commitment tree
+    pub note_pos: usize,
+    /// The amount of the note
+    pub amount: MaspAmount,
+    /// The memo attached to the note
+    pub memo: MemoBytes,
+}
+
+/// The effect of a shielded tx on a viewing key
+#[derive(Debug, Clone)]
+pub struct ShieldedHistoryEntry {
+    /// The position of the tx on chain
+    pub indexed_tx: IndexedTx,
+    /// The id of the MASP tx
+    pub txid: MaspTxId,
+    /// The time of the block that included the tx
+    pub timestamp: DateTimeUtc,
+    /// The notes received in a tx that spent no notes of the viewing key
+    pub incoming: Vec<ShieldedHistoryNote>,
+    /// The notes received back in a tx that spent notes of the viewing key
+    pub change: Vec<ShieldedHistoryNote>,
+    /// The notes of the viewing key spent by the tx
+    pub spends: Vec<ShieldedHistoryNote>,
+    /// The fee paid from the spent notes, if the tx was built by this
+    /// context
+    pub fee: Option<MaspAmount>,
+    /// The amount gained by converting the spent notes (i.e. the MASP
+    /// rewards), if all the notes of the tx belong to the viewing key
+    pub conversion: Option<MaspAmount>,
+    /// The net change of the shielded balance of the viewing key
+    pub net: MaspAmount,
+    /// The addresses of the transparent inputs of the tx
+    pub transparent_sources: Vec<TransparentAddress>,
+    /// The addresses of the transparent outputs of the tx
+    pub transparent_targets: Vec<TransparentAddress>,
+}
+
+/// The history of the shielded txs of the viewing keys in a shielded wallet.
+/// This cannot be overridden downstream.
+pub trait ShieldedHistory<U: ShieldedUtils + MaybeSend + MaybeSync>:
+    ShieldedApi<U>
+{
+    /// Reconstruct the history of the given viewing key from the shielded txs
+    /// recorded by the context, ordered by their position on chain. Only the
+    /// txs scanned by the context are covered, so it should be synced first.
+    #[allow(async_fn_in_trait)]
+    async fn shielded_history<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        vk: &ViewingKey,
+    ) -> Result<Vec<ShieldedHistoryEntry>, eyre::Error> {
+        let Some(vk_notes) = self.pos_map.get(vk).cloned() else {
+            return Err(eyre!(
+                "The viewing key is not in the shielded context"
+            ));
+        };
+        // The history also covers the spent notes
+        self.load_spent_notes()
+            .await
+            .map_err(|err| eyre!("Unable to load the spent notes: {err}"))?;
+        let records: Vec<_> = self
+            .tx_records
+            .iter()
+            .map(|(indexed_tx, record)| (*indexed_tx, record.clone()))
+            .collect();
+        let mut block_times = BTreeMap::<BlockHeight, DateTimeUtc>::new();
+        let mut history = vec![];
+
+        for (indexed_tx, record) in records {
+            let received: Vec<usize> = (record.first_note_pos
+                ..record.first_note_pos + record.num_outputs)
+                .filter(|note_pos| vk_notes.contains(note_pos))
+                .collect();
+            let spent: Vec<usize> = record
+                .spent_notes
+                .iter()
+                .copied()
+                .filter(|note_pos| vk_notes.contains(note_pos))
+                .collect();
+            if received.is_empty() && spent.is_empty() {
+                continue;
+            }
+
+            let (received_notes, received_sum) =
+                history_notes::<U, _, _>(self, client, &received).await?;
+            let (spends, spent_sum) =
+                history_notes::<U, _, _>(self, client, &spent).await?;
+            let (incoming, change) = if spent.is_empty() {
+                (received_notes, vec![])
+            } else {
+                (vec![], received_notes)
+            };
+            let fee = if spent.is_empty() {
+                None
+            } else {
+                self.tx_fees.get(&record.txid).map(|(token, amount)| {
+                    MaspAmount::from_pair(
+                        (None, token.clone()),
+                        amount.amount().change(),
+                    )
+                })
+            };
+            // The notes of a tx balance out with its conversions and its value
+            // balance, so if all of them are known the conversions follow
+            let conversion = if !spent.is_empty()
+                && spent.len() == record.num_spends
+                && received.len() == record.num_outputs
+            {
+                let converted = received_sum.clone() + record.value_balance
+                    - spent_sum.clone();
+                if converted.is_zero() {
+                    None
+                } else {
+                    Some(
+                        decode_amount::<U, _, _>(self, client, converted)
+                            .await?,
+                    )
+                }
+            } else {
+                None
+            };
+            let net = decode_amount::<U, _, _>(
+                self,
+                client,
+                received_sum - spent_sum,
+            )
+            .await?;
+
+            let timestamp = match block_times.get(&indexed_tx.height) {
+                Some(timestamp) => *timestamp,
+                None => {
+                    let timestamp =
+                        Self::query_block_time(client, indexed_tx.height)
+                            .await?;
+                    block_times.insert(indexed_tx.height, timestamp);
+                    timestamp
+                }
+            };
+
+            history.push(ShieldedHistoryEntry {
+                indexed_tx,
+                txid: record.txid,
+                timestamp,
+                incoming,
+                change,
+                spends,
+                fee,
+                conversion,
+                net,
+                transparent_sources: record.transparent_sources,
+                transparent_targets: record.transparent_targets,
+            });
+        }
+        Ok(history)
+    }
+}
+
+impl<U: ShieldedUtils + MaybeSend + MaybeSync, T: ShieldedApi<U>>
+    ShieldedHistory<U> for T
+{
+}
+
+/// Collect the notes at the given positions with their total amount
+async fn history_notes<U, T, C>(
+    ctx: &mut T,
+    client: &C,
+    note_positions: &[usize],
+) -> Result<(Vec<ShieldedHistoryNote>, I128Sum), eyre::Error>
+where
+    U: ShieldedUtils + MaybeSend + MaybeSync,
+    T: ShieldedApi<U> + ?Sized,
+    C: Client + Sync,
+{
+    let mut notes = vec![];
+    let mut total = I128Sum::zero();
+    for note_pos in note_positions {
+        let note = ctx
+            .note_map
+            .get(note_pos)
+            .ok_or_else(|| eyre!("Unable to get note {note_pos}"))?;
+        let value =
+            I128Sum::from_nonnegative(note.asset_type, i128::from(note.value))
+                .map_err(|()| {
+                    eyre!("found note with invalid value or asset type")
+                })?;
+        let memo = ctx
+            .memo_map
+            .get(note_pos)
+            .cloned()
+            .unwrap_or_else(MemoBytes::empty);
+        total += value.clone();
+        notes.push(ShieldedHistoryNote {
+            note_pos: *note_pos,
+            amount: decode_amount::<U, _, _>(ctx, client, value).await?,
+            memo,
+        });
+    }
+    Ok((notes, total))
+}
+
+/// Decode an amount, failing if any of its asset types is unknown
+async fn decode_amount<U, T, C>(
+    ctx: &mut T,
+    client: &C,
+    amount: I128Sum,
+) -> Result<MaspAmount, eyre::Error>
+where
+    U: ShieldedUtils + MaybeSend + MaybeSync,
+    T: ShieldedApi<U> + ?Sized,
+    C: Client + Sync,
+{
+    let (decoded, undecoded) = ctx.decode_combine_sum(client, amount).await;
+    if let Some((asset_type, _)) = undecoded.components().next() {
+        return Err(eyre!("Unable to decode asset type {asset_type}"));
+    }
+    Ok(decoded)
+}
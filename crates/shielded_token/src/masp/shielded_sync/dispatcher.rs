@@ -429,6 +429,7 @@ where
                     self.config.applied_tracker.increment_by(1);
                 }
             }
+            self.ctx.save_shielded_tx_record(indexed_tx, &stx_batch);
             self.ctx.save_shielded_spends(&stx_batch);
             std::mem::swap(&mut vk_heights, &mut self.ctx.vk_heights);
         }
@@ -940,6 +941,65 @@ mod dispatcher_tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn test_applying_cache_records_relevant_txs() {
+        let (client, _) = TestingMaspClient::new(BlockHeight::first());
+        let (_sender, shutdown_sig) = shutdown_signal();
+        let config = ShieldedSyncConfig::builder()
+            .client(client)
+            .fetched_tracker(DevNullProgressBar)
+            .scanned_tracker(DevNullProgressBar)
+            .applied_tracker(DevNullProgressBar)
+            .shutdown_signal(shutdown_sig)
+            .build();
+        let temp_dir = tempdir().unwrap();
+        let utils = FsShieldedUtils {
+            context_dir: temp_dir.path().to_path_buf(),
+        };
+        MaspLocalTaskEnv::new(4)
+            .expect("Test failed")
+            .run(|s| async {
+                let mut dispatcher = config.dispatcher(s, &utils).await;
+                dispatcher.ctx.vk_heights =
+                    BTreeMap::from([(arbitrary_vk(), None)]);
+                // only the output of the first tx belongs to the viewing key
+                dispatcher.ctx.vk_map.insert(0, arbitrary_vk());
+                for h in 0u64..2 {
+                    let itx = IndexedTx {
+                        height: h.into(),
+                        index: Default::default(),
+                        batch_index: None,
+                    };
+                    dispatcher.cache.fetched.insert((itx, arbitrary_masp_tx()));
+                    dispatcher.ctx.note_index.insert(itx, h as usize);
+                }
+
+                dispatcher
+                    .apply_cache_to_shielded_context(&InitialState {
+                        last_witnessed_tx: None,
+                        start_height: Default::default(),
+                        last_query_height: 1.into(),
+                    })
+                    .expect("Test failed");
+                let first_tx = IndexedTx {
+                    height: 0.into(),
+                    index: Default::default(),
+                    batch_index: None,
+                };
+                assert_eq!(
+                    dispatcher.ctx.tx_records.keys().collect::<Vec<_>>(),
+                    vec![&first_tx]
+                );
+                let record = &dispatcher.ctx.tx_records[&first_tx];
+                assert_eq!(record.first_note_pos, 0);
+                assert_eq!(record.num_outputs, 1);
+                assert!(record.spent_notes.is_empty());
+                assert_eq!(record.transparent_sources.len(), 1);
+                assert!(record.transparent_targets.is_empty());
+            })
+            .await;
+    }
+
     #[tokio::test]
     async fn test_async_counter_on_async_interrupt() {
         MaspLocalTaskEnv::new(1)
@@ -32,38 +32,42 @@ use namada_core::chain::BlockHeight;
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::control_flow;
 use namada_core::masp::{
-    encode_asset_type, AssetData, MaspEpoch, TransferSource, TransferTarget,
+    encode_asset_type, AssetData, MaspEpoch, MaspTxId, TransferSource,
+    TransferTarget,
 };
 use namada_core::task_env::TaskEnvironment;
 use namada_core::time::{DateTimeUtc, DurationSecs};
 use namada_core::token::{
     Amount, Change, DenominatedAmount, Denomination, MaspDigitPos,
 };
+use namada_gas::Gas;
 use namada_io::client::Client;
 use namada_io::{
-    display_line, edisplay_line, Io, MaybeSend, MaybeSync, NamadaIo,
+    display_line, edisplay_line, Io, MaybeSend, MaybeSync, NamadaIo, NullIo,
     ProgressBar,
 };
 use namada_tx::IndexedTx;
 use namada_wallet::{DatedKeypair, DatedSpendingKey};
 use rand::prelude::StdRng;
-use rand_core::{OsRng, SeedableRng};
+use rand_core::{CryptoRng, OsRng, RngCore, SeedableRng};
 
+use crate::masp::shielded_history::ShieldedTxRecord;
 use crate::masp::utils::MaspClient;
 use crate::masp::{
     cloned_pair, is_amount_required, to_viewing_key, Changes,
     ContextSyncStatus, Conversions, MaspAmount, MaspDataLog, MaspFeeData,
     MaspSourceTransferData, MaspTargetTransferData, MaspTransferData,
-    MaspTxReorderedData, NoteIndex, ShieldedSyncConfig, ShieldedTransfer,
-    ShieldedUtils, SpentNotesTracker, TransferErr, WalletMap, WitnessMap,
-    NETWORK,
+    MaspTxReorderedData, NoteCandidate, NoteConsolidation, NoteIndex,
+    NoteSelectionStrategy, ShieldedSyncConfig, ShieldedTransfer, ShieldedUtils,
+    SpentNotesTracker, TransferErr, WalletMap, WitnessMap, NETWORK,
 };
 #[cfg(any(test, feature = "testing"))]
 use crate::masp::{testing, ENV_VAR_MASP_TEST_SEED};
+use crate::validation::estimate_verify_shielded_tx_gas;
 
 /// Represents the current state of the shielded pool from the perspective of
 /// the chosen viewing keys.
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[derive(BorshSerialize, Debug)]
 pub struct ShieldedWallet<U: ShieldedUtils> {
     /// Location where this shielded context is saved
     #[borsh(skip)]
@@ -95,6 +99,61 @@ pub struct ShieldedWallet<U: ShieldedUtils> {
     pub note_index: NoteIndex,
     /// The sync state of the context
     pub sync_status: ContextSyncStatus,
+    /// The records of the shielded txs that spent or produced notes of the
+    /// viewing keys in the context
+    pub tx_records: BTreeMap<IndexedTx, ShieldedTxRecord>,
+    /// Maps the shielded txs built by this context to the fees they pay. It's
+    /// kept in the confirmed state, as these fees are only known locally.
+    pub tx_fees: HashMap<MaspTxId, (Address, DenominatedAmount)>,
+}
+
+// The shielded history (`tx_records` and `tx_fees`) is missing from the
+// contexts saved before it was added, so it's read from the rest of the input
+// when there is any left. This requires a context to be the last item of its
+// encoding, as it's the case for the saved contexts.
+impl<U: ShieldedUtils> BorshDeserialize for ShieldedWallet<U> {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        let tree = BorshDeserialize::deserialize_reader(reader)?;
+        let vk_heights = BorshDeserialize::deserialize_reader(reader)?;
+        let pos_map = BorshDeserialize::deserialize_reader(reader)?;
+        let nf_map = BorshDeserialize::deserialize_reader(reader)?;
+        let note_map = BorshDeserialize::deserialize_reader(reader)?;
+        let memo_map = BorshDeserialize::deserialize_reader(reader)?;
+        let div_map = BorshDeserialize::deserialize_reader(reader)?;
+        let witness_map = BorshDeserialize::deserialize_reader(reader)?;
+        let spents = BorshDeserialize::deserialize_reader(reader)?;
+        let asset_types = BorshDeserialize::deserialize_reader(reader)?;
+        let vk_map = BorshDeserialize::deserialize_reader(reader)?;
+        let note_index = BorshDeserialize::deserialize_reader(reader)?;
+        let sync_status = BorshDeserialize::deserialize_reader(reader)?;
+        let mut history = vec![];
+        reader.read_to_end(&mut history)?;
+        let (tx_records, tx_fees) = if history.is_empty() {
+            Default::default()
+        } else {
+            BorshDeserialize::try_from_slice(&history)?
+        };
+        Ok(Self {
+            utils: U::default(),
+            tree,
+            vk_heights,
+            pos_map,
+            nf_map,
+            note_map,
+            memo_map,
+            div_map,
+            witness_map,
+            spents,
+            asset_types,
+            vk_map,
+            note_index,
+            sync_status,
+            tx_records,
+            tx_fees,
+        })
+    }
 }
 
 /// Default implementation to ease construction of TxContexts. Derive cannot be
@@ -116,6 +175,8 @@ impl<U: ShieldedUtils + Default> Default for ShieldedWallet<U> {
             asset_types: HashMap::default(),
             vk_map: HashMap::default(),
             sync_status: ContextSyncStatus::Confirmed,
+            tx_records: BTreeMap::default(),
+            tx_fees: HashMap::default(),
         }
     }
 }
@@ -143,6 +204,13 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedWallet<U> {
         self.utils.save(self).await
     }
 
+    /// Load the spent notes that the store of this shielded context may have
+    /// left out when loading it. Must be called before accessing the data of
+    /// spent notes.
+    pub async fn load_spent_notes(&mut self) -> std::io::Result<()> {
+        self.utils.clone().load_spent_notes(self).await
+    }
+
     /// Update the merkle tree of witnesses the first time we
     /// scan new MASP transactions.
     pub(crate) fn update_witness_map(
@@ -267,6 +335,67 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedWallet<U> {
         }
     }
 
+    /// Record the given shielded tx if it spends or produces any notes of the
+    /// viewing keys in the context. Must be called after the tx outputs are
+    /// saved and before its spends are.
+    pub fn save_shielded_tx_record(
+        &mut self,
+        indexed_tx: IndexedTx,
+        transaction: &Transaction,
+    ) {
+        let Some(first_note_pos) = self.note_index.get(&indexed_tx).copied()
+        else {
+            return;
+        };
+        let (spent_notes, num_spends, num_outputs) = match transaction
+            .sapling_bundle()
+        {
+            Some(bundle) => (
+                bundle
+                    .shielded_spends
+                    .iter()
+                    .filter_map(|ss| self.nf_map.get(&ss.nullifier).copied())
+                    .collect::<Vec<_>>(),
+                bundle.shielded_spends.len(),
+                bundle.shielded_outputs.len(),
+            ),
+            None => (vec![], 0, 0),
+        };
+        let has_outputs = (first_note_pos..first_note_pos + num_outputs)
+            .any(|note_pos| self.vk_map.contains_key(&note_pos));
+        if spent_notes.is_empty() && !has_outputs {
+            return;
+        }
+
+        let mut transparent_sources = vec![];
+        let mut transparent_targets = vec![];
+        if let Some(bundle) = transaction.transparent_bundle() {
+            for vin in &bundle.vin {
+                if !transparent_sources.contains(&vin.address) {
+                    transparent_sources.push(vin.address);
+                }
+            }
+            for vout in &bundle.vout {
+                if !transparent_targets.contains(&vout.address) {
+                    transparent_targets.push(vout.address);
+                }
+            }
+        }
+        self.tx_records.insert(
+            indexed_tx,
+            ShieldedTxRecord {
+                txid: transaction.txid().into(),
+                first_note_pos,
+                num_outputs,
+                num_spends,
+                spent_notes,
+                value_balance: transaction.sapling_value_balance(),
+                transparent_sources,
+                transparent_targets,
+            },
+        );
+    }
+
     /// Compute the total unspent notes associated with the viewing key in the
     /// context. If the key is not in the context, then we do not know the
     /// balance and hence we return None.
@@ -355,6 +484,31 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedWallet<U> {
         Ok(())
     }
 
+    /// Record the fee paid by a newly generated transaction. The speculative
+    /// state is overruled by the confirmed one at the next sync, so the fee
+    /// is also saved to the confirmed state, if there is one yet.
+    async fn save_tx_fee(
+        &mut self,
+        txid: MaspTxId,
+        fee: (Address, DenominatedAmount),
+    ) -> Result<(), eyre::Error> {
+        self.tx_fees.insert(txid, fee.clone());
+        let mut confirmed = ShieldedWallet {
+            utils: self.utils.clone(),
+            ..Default::default()
+        };
+        match confirmed.load_confirmed().await {
+            Ok(()) => {
+                confirmed.tx_fees.insert(txid, fee);
+                // Saving the confirmed state removes the speculative one,
+                // which is saved again right after this
+                confirmed.save().await.map_err(|e| eyre!(e.to_string()))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(eyre!(err.to_string())),
+        }
+    }
+
     /// Updates the internal state with the data of the newly generated
     /// transaction. More specifically invalidate the spent notes, but do not
     /// cache the newly produced output descriptions and therefore the merkle
@@ -411,6 +565,13 @@ pub trait ShieldedQueries<U: ShieldedUtils + MaybeSend + MaybeSync>:
         client: &C,
     ) -> Result<Option<u64>, eyre::Error>;
 
+    /// Get the time of the block at the given height
+    #[allow(async_fn_in_trait)]
+    async fn query_block_time<C: Client + Sync>(
+        client: &C,
+        height: BlockHeight,
+    ) -> Result<DateTimeUtc, eyre::Error>;
+
     /// Get the upper limit on the time to make a new block
     #[allow(async_fn_in_trait)]
     async fn query_max_block_time_estimate<C: Client + Sync>(
@@ -680,7 +841,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
     }
 
     /// Collect enough unspent notes in this context to exceed the given amount
-    /// of the specified asset type. Return the total value accumulated plus
+    /// of the specified asset type, trying them in the order given by the
+    /// note selection strategy. Return the total value accumulated plus
     /// notes and the corresponding diversifiers/merkle paths that were used to
     /// achieve the total value. Updates the changes map.
     #[allow(clippy::too_many_arguments)]
@@ -694,6 +856,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         target: I128Sum,
         target_epoch: MaspEpoch,
         changes: &mut Changes,
+        note_selection: &impl NoteSelectionStrategy,
+        rng: &mut (impl RngCore + CryptoRng),
     ) -> Result<
         (
             I128Sum,
@@ -703,8 +867,6 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         eyre::Error,
     > {
         let vk = &to_viewing_key(&sk.into()).vk;
-        // TODO: we should try to use the smallest notes possible to fund the
-        // transaction to allow people to fetch less often
         // Establish connection with which to do exchange rate queries
         let mut conversions = BTreeMap::new();
         let mut val_acc = I128Sum::zero();
@@ -713,6 +875,12 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
 
         // Retrieve the notes that can be spent by this key
         if let Some(avail_notes) = self.pos_map.get(vk).cloned() {
+            // Value the candidate notes in the target epoch if the strategy
+            // compares them with the target. The conversions queried here
+            // are only used for pricing.
+            let needs_values = note_selection.needs_values();
+            let mut pricing_convs = BTreeMap::new();
+            let mut candidates = vec![];
             for note_idx in &avail_notes {
                 // Skip spend notes already used in this transaction
                 if spent_notes
@@ -721,16 +889,48 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
                 {
                     continue;
                 }
-                // No more transaction inputs are required once we have met
-                // the target amount
-                if normed_val_acc >= target {
-                    break;
-                }
                 // Spent notes from the shielded context (i.e. from previous
                 // transactions) cannot contribute a new transaction's pool
                 if self.spents.contains(note_idx) {
                     continue;
                 }
+                let normed_value = if needs_values {
+                    let note =
+                        self.note_map.get(note_idx).ok_or_else(|| {
+                            eyre!("Unable to get note {note_idx}")
+                        })?;
+                    let pre_contr = I128Sum::from_pair(
+                        note.asset_type,
+                        i128::from(note.value),
+                    );
+                    let (_, normed_value, convs) = self
+                        .compute_exchanged_amount(
+                            context.client(),
+                            &NullIo,
+                            pre_contr,
+                            target_epoch,
+                            pricing_convs,
+                        )
+                        .await?;
+                    pricing_convs = convs;
+                    normed_value
+                } else {
+                    I128Sum::zero()
+                };
+                candidates.push(NoteCandidate {
+                    note_pos: *note_idx,
+                    normed_value,
+                });
+            }
+            let candidates =
+                note_selection.order_notes(candidates, &target, rng);
+
+            for note_idx in candidates.iter().map(|note| &note.note_pos) {
+                // No more transaction inputs are required once we have met
+                // the target amount
+                if normed_val_acc >= target {
+                    break;
+                }
                 // Get note, merkle path, diversifier associated with this ID
                 let note = *self
                     .note_map
@@ -802,6 +1002,119 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         Ok((val_acc, notes, conversions))
     }
 
+    /// Plan the consolidation of the unspent notes of the given spending key
+    /// into a single note per token, optionally only for the given token. The
+    /// notes of a token are split into batches whose self-transfers stay
+    /// within the given gas budget for the verification of their MASP
+    /// bundles and, if given, the maximum number of notes. Batches of a
+    /// single note are skipped.
+    #[allow(async_fn_in_trait)]
+    async fn plan_note_consolidation(
+        &mut self,
+        context: &impl NamadaIo,
+        sk: namada_core::masp::ExtendedSpendingKey,
+        token: Option<&Address>,
+        gas_budget: &Gas,
+        max_notes: Option<usize>,
+    ) -> Result<Vec<NoteConsolidation>, eyre::Error> {
+        let vk = &to_viewing_key(&sk.into()).vk;
+        let Some(avail_notes) = self.pos_map.get(vk).cloned() else {
+            return Err(eyre!(
+                "The spending key is not in the shielded context"
+            ));
+        };
+        let epoch = Self::query_masp_epoch(context.client()).await?;
+
+        // Group the unspent notes by token, along with the asset types that
+        // need a conversion to the current epoch
+        let mut token_notes =
+            BTreeMap::<Address, Vec<(usize, Option<AssetType>)>>::new();
+        for note_pos in avail_notes {
+            if self.spents.contains(&note_pos) {
+                continue;
+            }
+            let asset_type = self
+                .note_map
+                .get(&note_pos)
+                .ok_or_else(|| eyre!("Unable to get note {note_pos}"))?
+                .asset_type;
+            let decoded = self
+                .decode_asset_type(context.client(), asset_type)
+                .await
+                .ok_or_else(|| {
+                    eyre!("Unable to decode asset type {asset_type}")
+                })?;
+            if token.is_some_and(|token| *token != decoded.token) {
+                continue;
+            }
+            let conversion = decoded
+                .epoch
+                .is_some_and(|note_epoch| note_epoch < epoch)
+                .then_some(asset_type);
+            token_notes
+                .entry(decoded.token)
+                .or_default()
+                .push((note_pos, conversion));
+        }
+
+        let fits = |spends: usize, converts: usize| {
+            max_notes.map_or(true, |max_notes| spends <= max_notes)
+                && estimate_verify_shielded_tx_gas(
+                    spends,
+                    converts,
+                    CONSOLIDATION_OUTPUTS,
+                )
+                .is_ok_and(|gas| gas <= *gas_budget)
+        };
+        let mut consolidations = vec![];
+        for (token, notes) in token_notes {
+            let Some(denom) = Self::query_denom(context.client(), &token).await
+            else {
+                return Err(eyre!("denomination for token {token}"));
+            };
+            for notes in batch_notes(notes, &fits) {
+                if notes.len() < 2 {
+                    continue;
+                }
+                let mut value = I128Sum::zero();
+                for note_pos in &notes {
+                    let note =
+                        self.note_map.get(note_pos).ok_or_else(|| {
+                            eyre!("Unable to get note {note_pos}")
+                        })?;
+                    value += I128Sum::from_pair(
+                        note.asset_type,
+                        i128::from(note.value),
+                    );
+                }
+                // The merged note holds the value of the notes in the current
+                // epoch, while their rewards are returned as change
+                let (_, normed_value, _) = self
+                    .compute_exchanged_amount(
+                        context.client(),
+                        &NullIo,
+                        value,
+                        epoch,
+                        BTreeMap::new(),
+                    )
+                    .await?;
+                let amount = self
+                    .convert_masp_amount_to_namada(
+                        context.client(),
+                        denom,
+                        normed_value,
+                    )
+                    .await?;
+                consolidations.push(NoteConsolidation {
+                    token: token.clone(),
+                    notes,
+                    amount,
+                });
+            }
+        }
+        Ok(consolidations)
+    }
+
     /// Convert an amount whose units are AssetTypes to one whose units are
     /// Addresses that they decode to. All asset types not corresponding to
     /// the given epoch are ignored.
@@ -899,6 +1212,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
     /// UTXOs are sometimes used to make transactions balanced, but it is
     /// understood that transparent account changes are effected only by the
     /// amounts and signatures specified by the containing Transfer object.
+    /// The notes funding the transfer are chosen with the given note selection
+    /// strategy.
     #[allow(async_fn_in_trait)]
     async fn gen_shielded_transfer(
         &mut self,
@@ -907,6 +1222,7 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         fee_data: Option<MaspFeeData>,
         expiration: Option<DateTimeUtc>,
         update_ctx: bool,
+        note_selection: &impl NoteSelectionStrategy,
     ) -> Result<Option<ShieldedTransfer>, TransferErr> {
         // Determine epoch in which to submit potential shielded transaction
         let epoch = Self::query_masp_epoch(context.client())
@@ -1018,6 +1334,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
                 &mut notes_tracker,
                 &mut changes,
                 *token == native_token,
+                note_selection,
+                &mut rng,
             )
             .await?;
         }
@@ -1045,6 +1363,9 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         }
 
         // Collect the fees if needed
+        let fee = fee_data
+            .as_ref()
+            .map(|fee_data| (fee_data.token.clone(), fee_data.amount));
         if let Some(MaspFeeData {
             sources,
             target,
@@ -1064,6 +1385,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
                 &mut denoms,
                 &mut notes_tracker,
                 &mut changes,
+                note_selection,
+                &mut rng,
             )
             .await?;
         }
@@ -1087,6 +1410,11 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
             .map_err(|error| TransferErr::Build { error, data: None })?;
 
         if update_ctx {
+            if let Some(fee) = fee {
+                self.save_tx_fee(masp_tx.txid().into(), fee)
+                    .await
+                    .map_err(|e| TransferErr::General(e.to_string()))?;
+            }
             self.pre_cache_transaction(&masp_tx)
                 .await
                 .map_err(|e| TransferErr::General(e.to_string()))?;
@@ -1196,6 +1524,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         notes_tracker: &mut SpentNotesTracker,
         changes: &mut Changes,
         is_native_token: bool,
+        note_selection: &impl NoteSelectionStrategy,
+        rng: &mut (impl RngCore + CryptoRng),
     ) -> Result<Option<I128Sum>, TransferErr> {
         // We want to fund our transaction solely from supplied spending key
         let spending_key = source.spending_key();
@@ -1238,6 +1568,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
                     I128Sum::from_sum(masp_amount),
                     epoch,
                     changes,
+                    note_selection,
+                    rng,
                 )
                 .await
                 .map_err(|e| TransferErr::General(e.to_string()))?;
@@ -1467,6 +1799,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
         denoms: &mut HashMap<Address, Denomination>,
         notes_tracker: &mut SpentNotesTracker,
         changes: &mut Changes,
+        note_selection: &impl NoteSelectionStrategy,
+        rng: &mut (impl RngCore + CryptoRng),
     ) -> Result<(), TransferErr> {
         if denoms.get(token).is_none() {
             if let Some(denom) =
@@ -1636,6 +1970,8 @@ pub trait ShieldedApi<U: ShieldedUtils + MaybeSend + MaybeSync>:
                             notes_tracker,
                             changes,
                             *token == native_token,
+                            note_selection,
+                            rng,
                         )
                         .await
                         .map_err(|e| TransferErr::General(e.to_string()))?
@@ -1821,3 +2157,97 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync, T: ShieldedQueries<U>>
     ShieldedApi<U> for T
 {
 }
+
+/// The number of outputs assumed for a note consolidation: up to one per
+/// digit position for the merged note and as many for the change, which
+/// includes the rewards of the converted notes
+const CONSOLIDATION_OUTPUTS: usize = 8;
+
+/// Split the notes into consecutive batches such that each batch fits the
+/// given predicate on its number of notes and distinct conversions. A note
+/// that doesn't fit on its own gets a batch of its own.
+fn batch_notes(
+    notes: Vec<(usize, Option<AssetType>)>,
+    fits: impl Fn(usize, usize) -> bool,
+) -> Vec<BTreeSet<usize>> {
+    let mut batches = vec![];
+    let mut batch = BTreeSet::new();
+    let mut conversions = BTreeSet::new();
+    for (note_pos, conversion) in notes {
+        let converts = conversions.len()
+            + usize::from(
+                conversion.is_some_and(|conv| !conversions.contains(&conv)),
+            );
+        if !batch.is_empty() && !fits(batch.len() + 1, converts) {
+            batches.push(std::mem::take(&mut batch));
+            conversions.clear();
+        }
+        batch.insert(note_pos);
+        conversions.extend(conversion);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::borsh::BorshSerializeExt;
+
+    use super::*;
+    use crate::masp::fs::FsShieldedUtils;
+
+    #[test]
+    fn test_decode_context_without_history() {
+        let mut ctx = ShieldedWallet::<FsShieldedUtils>::default();
+        ctx.tx_fees.insert(
+            MaspTxId::from(masp_primitives::transaction::TxId::from_bytes(
+                [1; 32],
+            )),
+            (
+                namada_core::address::testing::nam(),
+                DenominatedAmount::native(1.into()),
+            ),
+        );
+        let bytes = ctx.serialize_to_vec();
+        let decoded =
+            ShieldedWallet::<FsShieldedUtils>::try_from_slice(&bytes).unwrap();
+        assert_eq!(decoded.tx_fees, ctx.tx_fees);
+
+        // A context saved before the shielded history was added decodes with
+        // an empty history
+        let history = (&ctx.tx_records, &ctx.tx_fees).serialize_to_vec();
+        let legacy_bytes = &bytes[..bytes.len() - history.len()];
+        let decoded =
+            ShieldedWallet::<FsShieldedUtils>::try_from_slice(legacy_bytes)
+                .unwrap();
+        assert!(decoded.tx_records.is_empty());
+        assert!(decoded.tx_fees.is_empty());
+    }
+
+    #[test]
+    fn test_batch_notes() {
+        let asset_type = |name: &str| AssetType::new(name.as_bytes()).unwrap();
+        let notes = vec![
+            (0, None),
+            (1, Some(asset_type("a"))),
+            (2, Some(asset_type("a"))),
+            (3, Some(asset_type("b"))),
+            (4, None),
+            (5, Some(asset_type("c"))),
+        ];
+        // At most 3 notes and 1 conversion per batch
+        let batches =
+            batch_notes(notes, |spends, converts| spends <= 3 && converts <= 1);
+        let expected: Vec<BTreeSet<usize>> = vec![
+            [0, 1, 2].into_iter().collect(),
+            [3, 4].into_iter().collect(),
+            [5].into_iter().collect(),
+        ];
+        assert_eq!(batches, expected);
+        // A note that never fits still gets a batch of its own
+        let batches = batch_notes(vec![(0, None), (1, None)], |_, _| false);
+        assert_eq!(batches.len(), 2);
+    }
+}
@@ -1,5 +1,6 @@
 //! MASP verification wrappers.
 
+use std::cell::Cell;
 use std::env;
 use std::ops::Deref;
 use std::path::PathBuf;
@@ -160,8 +161,9 @@ where
     #[cfg(feature = "testing")]
     let mut ctx = testing::MockBatchValidator::default();
 
+    let counts = BundleCounts::from(sapling_bundle);
     // Charge gas before check bundle
-    charge_masp_check_bundle_gas(sapling_bundle, &consume_verify_gas)?;
+    charge_masp_check_bundle_gas(&counts, &consume_verify_gas)?;
 
     if !ctx.check_bundle(sapling_bundle.to_owned(), sighash.as_ref().to_owned())
     {
@@ -171,7 +173,7 @@ where
     tracing::debug!("passed check bundle");
 
     // Charge gas before final validation
-    charge_masp_validate_gas(sapling_bundle, consume_verify_gas)?;
+    charge_masp_validate_gas(&counts, consume_verify_gas)?;
     if !ctx.validate(spend_vk, convert_vk, output_vk, OsRng) {
         return Err(Error::new_const("Invalid proofs or signatures"));
     }
@@ -211,10 +213,53 @@ pub fn partial_deauthorize(
     ))
 }
 
+/// Estimate the gas charged by [`verify_shielded_tx`] to verify a sapling
+/// bundle with the given numbers of spend, convert and output descriptions.
+pub fn estimate_verify_shielded_tx_gas(
+    spends: usize,
+    converts: usize,
+    outputs: usize,
+) -> Result<Gas> {
+    let counts = BundleCounts {
+        spends,
+        converts,
+        outputs,
+    };
+    let total = Cell::new(0_u64);
+    let consume_verify_gas = |gas: Gas| {
+        let sum = total
+            .get()
+            .checked_add(gas.into())
+            .ok_or_else(|| Error::new_const("Gas overflow"))?;
+        total.set(sum);
+        Ok(())
+    };
+    charge_masp_check_bundle_gas(&counts, &consume_verify_gas)?;
+    charge_masp_validate_gas(&counts, consume_verify_gas)?;
+    Ok(total.get().into())
+}
+
+/// The numbers of descriptions of a sapling bundle that are charged for
+struct BundleCounts {
+    spends: usize,
+    converts: usize,
+    outputs: usize,
+}
+
+impl From<&SaplingBundle<SaplingAuthorized>> for BundleCounts {
+    fn from(sapling_bundle: &SaplingBundle<SaplingAuthorized>) -> Self {
+        Self {
+            spends: sapling_bundle.shielded_spends.len(),
+            converts: sapling_bundle.shielded_converts.len(),
+            outputs: sapling_bundle.shielded_outputs.len(),
+        }
+    }
+}
+
 // Charge gas for the final validation, taking advtange of concurrency for
 // proofs verification but not for signatures
 fn charge_masp_validate_gas<F>(
-    sapling_bundle: &SaplingBundle<SaplingAuthorized>,
+    counts: &BundleCounts,
     consume_verify_gas: F,
 ) -> Result<()>
 where
@@ -224,8 +269,7 @@ where
     consume_verify_gas(
         checked!(
             // Add one for the binding signature
-            ((sapling_bundle.shielded_spends.len() as u64) + 1)
-                * namada_gas::MASP_VERIFY_SIG_GAS
+            ((counts.spends as u64) + 1) * namada_gas::MASP_VERIFY_SIG_GAS
         )?
         .into(),
     )?;
@@ -233,9 +277,7 @@ where
     // If at least one note is present charge the fixed costs. Then charge the
     // variable cost for every other note, amortized on the fixed expected
     // number of cores
-    if let Some(remaining_notes) =
-        sapling_bundle.shielded_spends.len().checked_sub(1)
-    {
+    if let Some(remaining_notes) = counts.spends.checked_sub(1) {
         consume_verify_gas(namada_gas::MASP_FIXED_SPEND_GAS.into())?;
         consume_verify_gas(
             checked!(
@@ -245,9 +287,7 @@ where
         )?;
     }
 
-    if let Some(remaining_notes) =
-        sapling_bundle.shielded_converts.len().checked_sub(1)
-    {
+    if let Some(remaining_notes) = counts.converts.checked_sub(1) {
         consume_verify_gas(namada_gas::MASP_FIXED_CONVERT_GAS.into())?;
         consume_verify_gas(
             checked!(
@@ -257,9 +297,7 @@ where
         )?;
     }
 
-    if let Some(remaining_notes) =
-        sapling_bundle.shielded_outputs.len().checked_sub(1)
-    {
+    if let Some(remaining_notes) = counts.outputs.checked_sub(1) {
         consume_verify_gas(namada_gas::MASP_FIXED_OUTPUT_GAS.into())?;
         consume_verify_gas(
             checked!(
@@ -274,34 +312,27 @@ where
 
 // Charge gas for the check_bundle operation which does not leverage concurrency
 fn charge_masp_check_bundle_gas<F>(
-    sapling_bundle: &SaplingBundle<SaplingAuthorized>,
+    counts: &BundleCounts,
     consume_verify_gas: F,
 ) -> Result<()>
 where
     F: Fn(Gas) -> Result<()>,
 {
     consume_verify_gas(
-        checked!(
-            (sapling_bundle.shielded_spends.len() as u64)
-                * namada_gas::MASP_SPEND_CHECK_GAS
-        )?
-        .into(),
+        checked!((counts.spends as u64) * namada_gas::MASP_SPEND_CHECK_GAS)?
+            .into(),
     )?;
 
     consume_verify_gas(
         checked!(
-            (sapling_bundle.shielded_converts.len() as u64)
-                * namada_gas::MASP_CONVERT_CHECK_GAS
+            (counts.converts as u64) * namada_gas::MASP_CONVERT_CHECK_GAS
         )?
         .into(),
     )?;
 
     consume_verify_gas(
-        checked!(
-            (sapling_bundle.shielded_outputs.len() as u64)
-                * namada_gas::MASP_OUTPUT_CHECK_GAS
-        )?
-        .into(),
+        checked!((counts.outputs as u64) * namada_gas::MASP_OUTPUT_CHECK_GAS)?
+            .into(),
     )
 }
 
@@ -41,6 +41,8 @@ pub enum Error {
     SizeDiffOverflow,
     #[error("Value length overflowed")]
     ValueLenOverflow,
+    #[error("No savepoint to rollback or release")]
+    NoSavepoint,
 }
 
 impl From<Error> for crate::Error {
@@ -109,6 +111,23 @@ pub(crate) struct BatchedTxWriteLog {
     write_log: HashMap<storage::Key, StorageModification>,
 }
 
+/// A savepoint of the write log of a transaction. Instead of a copy of the
+/// write log, this only records what's needed to undo the modifications and
+/// events that follow it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Savepoint {
+    // The generator of established addresses when the savepoint was taken
+    address_gen: Option<EstablishedAddressGen>,
+    // The storage modifications of the keys changed since the savepoint, as
+    // they were before their first change
+    write_log: HashMap<storage::Key, Option<StorageModification>>,
+    // The temporary values of the keys changed since the savepoint, as they
+    // were before their first change
+    tx_temp_log: HashMap<storage::Key, Option<Vec<u8>>>,
+    // The events emitted since the savepoint
+    events: Vec<Event>,
+}
+
 /// Log of events in the write log.
 #[derive(Debug, Clone)]
 pub(crate) struct WriteLogEvents {
@@ -147,6 +166,10 @@ pub struct WriteLog {
     pub(crate) batch_write_log: Vec<BatchedTxWriteLog>,
     // The write log of the current active transaction
     pub(crate) tx_write_log: TxWriteLog,
+    /// The savepoints of the current transaction's write log taken by
+    /// [`WriteLog::push_savepoint`], with the innermost one last. These are
+    /// cleared when the transaction is committed or dropped.
+    pub(crate) savepoints: Vec<Savepoint>,
     /// Storage modifications for the replay protection storage, cannot be
     /// managed in the normal write log because we need to commit them
     /// sometimes even on batch failure
@@ -176,6 +199,7 @@ impl Default for WriteLog {
             block_write_log: HashMap::with_capacity(100_000),
             batch_write_log: Vec::with_capacity(5),
             tx_write_log: Default::default(),
+            savepoints: Vec::new(),
             replay_protection: HashSet::with_capacity(1_000),
         }
     }
@@ -326,6 +350,7 @@ impl WriteLog {
             None => len_signed,
         };
 
+        self.record_write(key);
         self.tx_write_log
             .write_log
             .insert(key.clone(), StorageModification::Write { value });
@@ -406,6 +431,7 @@ impl WriteLog {
             None => len_signed,
         };
 
+        self.record_temp_write(key);
         self.tx_write_log.tx_temp_log.insert(key.clone(), value);
 
         // Temp writes are not propagated to db so just charge the cost of
@@ -438,6 +464,7 @@ impl WriteLog {
             None => 0,
         };
 
+        self.record_write(key);
         self.tx_write_log
             .write_log
             .insert(key.clone(), StorageModification::Delete);
@@ -495,6 +522,7 @@ impl WriteLog {
             .expect("Cannot overflow")) as u64)
             .checked_mul(STORAGE_WRITE_GAS_PER_BYTE)
             .expect("Canno overflow");
+        self.record_write(&key);
         self.tx_write_log
             .write_log
             .insert(key, StorageModification::InitAccount { vp_code_hash });
@@ -514,12 +542,20 @@ impl WriteLog {
                     .tree
                     .insert(&event_type, HashSet::new());
             }
-            self.tx_write_log
+            let is_new = self
+                .tx_write_log
                 .events
                 .tree
                 .get_mut(&event_type)
                 .unwrap()
-                .insert(event);
+                .insert(event.clone());
+            // Events already emitted before the savepoint must survive its
+            // rollback
+            if is_new {
+                if let Some(savepoint) = self.savepoints.last_mut() {
+                    savepoint.events.push(event);
+                }
+            }
         }
         gas_cost.map(|gas| gas.into())
     }
@@ -620,6 +656,7 @@ impl WriteLog {
     /// accepted by all the triggered validity predicates. Starts a new
     /// transaction write log.
     pub fn commit_tx_to_batch(&mut self) {
+        self.savepoints.clear();
         let tx_write_log = std::mem::take(&mut self.tx_write_log);
         let batched_log = BatchedTxWriteLog {
             address_gen: tx_write_log.address_gen,
@@ -633,9 +670,114 @@ impl WriteLog {
     /// declined by any of the triggered validity predicates. Starts a new
     /// transaction write log and clears the temp write log.
     pub fn drop_tx(&mut self) {
+        self.savepoints.clear();
         self.tx_write_log = Default::default();
     }
 
+    /// Take a savepoint of the current transaction's write log, including its
+    /// temporary writes, events and address generator, that can be later
+    /// restored with [`WriteLog::rollback_savepoint`] or discarded with
+    /// [`WriteLog::release_savepoint`]. Savepoints can be nested. Returns the
+    /// number of active savepoints.
+    ///
+    /// The protocol writes to the block write log are not covered.
+    pub fn push_savepoint(&mut self) -> usize {
+        self.savepoints.push(Savepoint {
+            address_gen: self.tx_write_log.address_gen.clone(),
+            ..Default::default()
+        });
+        self.savepoints.len()
+    }
+
+    /// Discard all the modifications and events of the current transaction
+    /// since the innermost savepoint and remove it.
+    /// Fails with [`Error::NoSavepoint`] if there's no active savepoint.
+    pub fn rollback_savepoint(&mut self) -> Result<()> {
+        let savepoint = self.savepoints.pop().ok_or(Error::NoSavepoint)?;
+        self.tx_write_log.address_gen = savepoint.address_gen;
+        for (key, modification) in savepoint.write_log {
+            match modification {
+                Some(modification) => {
+                    self.tx_write_log.write_log.insert(key, modification);
+                }
+                None => {
+                    self.tx_write_log.write_log.remove(&key);
+                }
+            }
+        }
+        for (key, value) in savepoint.tx_temp_log {
+            match value {
+                Some(value) => {
+                    self.tx_write_log.tx_temp_log.insert(key, value);
+                }
+                None => {
+                    self.tx_write_log.tx_temp_log.remove(&key);
+                }
+            }
+        }
+        for event in savepoint.events {
+            let event_type = event.kind().to_string();
+            if let Some(events) =
+                self.tx_write_log.events.tree.get_mut(&event_type)
+            {
+                events.remove(&event);
+                if events.is_empty() {
+                    self.tx_write_log.events.tree.remove(&event_type);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the innermost savepoint keeping all the modifications and
+    /// events of the current transaction since it was taken. These can still
+    /// be discarded by rolling back an outer savepoint.
+    /// Fails with [`Error::NoSavepoint`] if there's no active savepoint.
+    pub fn release_savepoint(&mut self) -> Result<()> {
+        let savepoint = self.savepoints.pop().ok_or(Error::NoSavepoint)?;
+        if let Some(outer) = self.savepoints.last_mut() {
+            for (key, modification) in savepoint.write_log {
+                outer.write_log.entry(key).or_insert(modification);
+            }
+            for (key, value) in savepoint.tx_temp_log {
+                outer.tx_temp_log.entry(key).or_insert(value);
+            }
+            outer.events.extend(savepoint.events);
+        }
+        Ok(())
+    }
+
+    /// Get the number of active savepoints of the current transaction
+    pub fn savepoints_depth(&self) -> usize {
+        self.savepoints.len()
+    }
+
+    // Record the storage modification of the given key before it's changed
+    // in the innermost savepoint, unless it's already recorded
+    fn record_write(&mut self, key: &storage::Key) {
+        if let Some(savepoint) = self.savepoints.last_mut() {
+            if !savepoint.write_log.contains_key(key) {
+                savepoint.write_log.insert(
+                    key.clone(),
+                    self.tx_write_log.write_log.get(key).cloned(),
+                );
+            }
+        }
+    }
+
+    // Record the temporary value of the given key before it's changed in the
+    // innermost savepoint, unless it's already recorded
+    fn record_temp_write(&mut self, key: &storage::Key) {
+        if let Some(savepoint) = self.savepoints.last_mut() {
+            if !savepoint.tx_temp_log.contains_key(key) {
+                savepoint.tx_temp_log.insert(
+                    key.clone(),
+                    self.tx_write_log.tx_temp_log.get(key).cloned(),
+                );
+            }
+        }
+    }
+
     /// Commit the current tx and the entire batch to the block log.
     pub fn commit_batch_and_current_tx(&mut self) {
         self.commit_tx_to_batch();
@@ -767,6 +909,7 @@ impl WriteLog {
 mod tests {
     use assert_matches::assert_matches;
     use namada_core::address;
+    use namada_events::EventLevel;
     use pretty_assertions::assert_eq;
     use proptest::prelude::*;
 
@@ -1142,6 +1285,74 @@ mod tests {
         ));
     }
 
+    // Test that nested savepoints only discard the modifications and events
+    // done after them
+    #[test]
+    fn test_savepoints() {
+        let mut write_log = WriteLog::default();
+
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let key3 =
+            storage::Key::parse("key3").expect("cannot parse the key string");
+        let val1 = "val1".as_bytes().to_vec();
+        let val2 = "val2".as_bytes().to_vec();
+        let event = Event::new(EventType::new("test"), EventLevel::Tx);
+
+        assert_matches!(
+            write_log.rollback_savepoint(),
+            Err(Error::NoSavepoint)
+        );
+        assert_matches!(write_log.release_savepoint(), Err(Error::NoSavepoint));
+
+        let _ = write_log.write(&key1, val1.clone()).unwrap();
+        assert_eq!(write_log.push_savepoint(), 1);
+        let _ = write_log.write(&key1, val2.clone()).unwrap();
+        let _ = write_log.write(&key2, val2.clone()).unwrap();
+        assert_eq!(write_log.push_savepoint(), 2);
+        let _ = write_log.delete(&key2).unwrap();
+        let _ = write_log.write_temp(&key3, val2.clone()).unwrap();
+        let _ = write_log.emit_event(event.clone()).unwrap();
+
+        // Rolling back the inner savepoint discards only its changes
+        write_log.rollback_savepoint().unwrap();
+        assert_eq!(write_log.savepoints_depth(), 1);
+        assert_eq!(
+            write_log.read(&key2).unwrap().0,
+            Some(&StorageModification::Write {
+                value: val2.clone()
+            })
+        );
+        assert!(write_log.read_temp(&key3).unwrap().0.is_none());
+        assert_eq!(write_log.get_events().count(), 0);
+
+        // Releasing a savepoint keeps its changes, but they are still
+        // discarded when an outer savepoint is rolled back
+        assert_eq!(write_log.push_savepoint(), 2);
+        let _ = write_log.emit_event(event).unwrap();
+        write_log.release_savepoint().unwrap();
+        assert_eq!(write_log.get_events().count(), 1);
+        write_log.rollback_savepoint().unwrap();
+        assert_eq!(write_log.savepoints_depth(), 0);
+        assert_eq!(write_log.get_events().count(), 0);
+        assert_eq!(
+            write_log.read(&key1).unwrap().0,
+            Some(&StorageModification::Write { value: val1 })
+        );
+        assert!(write_log.read(&key2).unwrap().0.is_none());
+
+        // Savepoints don't outlive the transaction
+        write_log.push_savepoint();
+        write_log.commit_tx_to_batch();
+        assert_eq!(write_log.savepoints_depth(), 0);
+        assert_matches!(
+            write_log.rollback_savepoint(),
+            Err(Error::NoSavepoint)
+        );
+    }
+
     prop_compose! {
         fn arb_verifiers_changed_key_tx_all_key()
             (verifiers_from_tx in testing::arb_verifiers_from_tx())
@@ -288,6 +288,67 @@ pub trait DB: Debug {
         height: BlockHeight,
     ) -> Result<()>;
 
+    /// Start keeping the values of all the account subspace keys at every
+    /// block height from the last committed height onwards. Has no effect if
+    /// the archive is already enabled.
+    fn enable_archive(&mut self) -> Result<()>;
+
+    /// Stop keeping the values of the account subspace keys at every block
+    /// height and delete the archive. Has no effect if the archive is not
+    /// enabled.
+    fn disable_archive(&mut self) -> Result<()>;
+
+    /// Read the lowest block height from which the archived values can be
+    /// read, if the archive is enabled.
+    fn read_archive_start_height(&self) -> Result<Option<BlockHeight>>;
+
+    /// Read the archived value of an account subspace key at the given
+    /// height. Unlike [`DB::read_subspace_val_with_height`], this works for
+    /// any key. Returns `None` if the height is not covered by the archive.
+    fn read_archived_subspace_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+    ) -> Result<Option<Option<Vec<u8>>>>;
+
+    /// Read the archived account subspace key-vals with the given prefix at
+    /// the given height, ordered by the storage keys. Returns `None` if the
+    /// height is not covered by the archive.
+    fn read_archived_subspace_prefix(
+        &self,
+        prefix: Option<&Key>,
+        height: BlockHeight,
+    ) -> Result<Option<Vec<(String, Vec<u8>)>>>;
+
+    /// Prune the archived values that are not needed to read the archive at
+    /// the given height or above, which becomes the lowest height that can be
+    /// read from it. At most `max_entries` archived values are visited per
+    /// call, and the following calls with the same height resume from there.
+    /// Returns `true` once the pruning is done.
+    fn prune_archive(
+        &mut self,
+        height: BlockHeight,
+        max_entries: usize,
+    ) -> Result<bool>;
+
+    /// Read a value of the node-local index of the blocks with MASP txs,
+    /// which is kept outside of the account subspaces.
+    fn read_masp_index_val(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Read the key-vals of the node-local index of the blocks with MASP txs
+    /// with the given prefix, ordered by their keys.
+    fn read_masp_index_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>>;
+
+    /// Atomically write the given changes to the node-local index of the
+    /// blocks with MASP txs. A `None` value deletes the key.
+    fn write_masp_index(
+        &mut self,
+        changes: Vec<(String, Option<Vec<u8>>)>,
+    ) -> Result<()>;
+
     /// Overwrite a new value in storage, taking into
     /// account values stored at a previous height
     fn overwrite_entry(
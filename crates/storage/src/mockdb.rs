@@ -48,6 +48,10 @@ const ADDRESS_GEN_KEY_SEGMENT: &str = "address_gen";
 const OLD_DIFF_PREFIX: &str = "old";
 const NEW_DIFF_PREFIX: &str = "new";
 
+/// The prefix of the keys of the index of the blocks with MASP txs, which
+/// RocksDB keeps in its own column family
+const MASP_INDEX_PREFIX: &str = "masp_index/";
+
 /// An in-memory DB for testing.
 #[derive(Debug, Default)]
 pub struct MockDB(
@@ -88,6 +92,10 @@ impl MockDB {
     }
 }
 
+fn masp_index_key(key: &str) -> String {
+    format!("{MASP_INDEX_PREFIX}{key}")
+}
+
 /// Source to restore a [`MockDB`] from.
 ///
 /// Since this enum has no variants, you can't
@@ -628,6 +636,79 @@ impl DB for MockDB {
         Ok(())
     }
 
+    fn enable_archive(&mut self) -> Result<()> {
+        // No-op - MockDB doesn't support archive
+        Ok(())
+    }
+
+    fn disable_archive(&mut self) -> Result<()> {
+        // No-op - MockDB doesn't support archive
+        Ok(())
+    }
+
+    fn read_archive_start_height(&self) -> Result<Option<BlockHeight>> {
+        Ok(None)
+    }
+
+    fn read_archived_subspace_val(
+        &self,
+        _key: &Key,
+        _height: BlockHeight,
+    ) -> Result<Option<Option<Vec<u8>>>> {
+        Ok(None)
+    }
+
+    fn read_archived_subspace_prefix(
+        &self,
+        _prefix: Option<&Key>,
+        _height: BlockHeight,
+    ) -> Result<Option<Vec<(String, Vec<u8>)>>> {
+        Ok(None)
+    }
+
+    fn prune_archive(
+        &mut self,
+        _height: BlockHeight,
+        _max_entries: usize,
+    ) -> Result<bool> {
+        // No-op - MockDB doesn't support archive
+        Ok(true)
+    }
+
+    fn read_masp_index_val(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.0.borrow().get(&masp_index_key(key)).cloned())
+    }
+
+    fn read_masp_index_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let prefix = masp_index_key(prefix);
+        Ok(self
+            .0
+            .borrow()
+            .range(prefix.clone()..)
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, val)| {
+                (key[MASP_INDEX_PREFIX.len()..].to_owned(), val.clone())
+            })
+            .collect())
+    }
+
+    fn write_masp_index(
+        &mut self,
+        changes: Vec<(String, Option<Vec<u8>>)>,
+    ) -> Result<()> {
+        let mut db = self.0.borrow_mut();
+        for (key, val) in changes {
+            match val {
+                Some(val) => db.insert(masp_index_key(&key), val),
+                None => db.remove(&masp_index_key(&key)),
+            };
+        }
+        Ok(())
+    }
+
     fn overwrite_entry(
         &self,
         _batch: &mut Self::WriteBatch,
@@ -7,6 +7,7 @@ use masp_primitives::transaction::TransparentAddress;
 use namada_core::address::Address;
 use namada_core::borsh::BorshDeserialize;
 use namada_core::masp::TAddrData;
+use namada_core::parameter_change::IbcParameterChange;
 use namada_core::{masp_primitives, storage, token};
 pub use namada_storage::Result;
 
@@ -26,6 +27,15 @@ pub trait Read<S> {
     ) -> Result<ChangedBalances>;
 }
 
+/// Abstract IBC storage write interface
+pub trait Write<S>: Read<S> {
+    /// Apply changes of the IBC parameters proposed by governance
+    fn change_params(
+        storage: &mut S,
+        changes: &[IbcParameterChange],
+    ) -> Result<()>;
+}
+
 /// Balances changed by a transaction
 #[derive(Default, Debug, Clone)]
 pub struct ChangedBalances {
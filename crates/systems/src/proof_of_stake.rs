@@ -2,6 +2,7 @@
 
 use namada_core::address::Address;
 use namada_core::chain::Epoch;
+use namada_core::parameter_change::PosParameterChange;
 use namada_core::token;
 pub use namada_storage::Result;
 
@@ -64,3 +65,14 @@ pub trait Read<S> {
     where
         Gov: governance::Read<S>;
 }
+
+/// Abstract PoS storage write interface
+pub trait Write<S>: Read<S> {
+    /// Apply changes of the PoS parameters proposed by governance. Returns
+    /// `false` and leaves the storage untouched if the changed parameters are
+    /// not valid.
+    fn change_params(
+        storage: &mut S,
+        changes: &[PosParameterChange],
+    ) -> Result<bool>;
+}
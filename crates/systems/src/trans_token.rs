@@ -58,6 +58,10 @@ pub trait Read<S> {
         token: &Address,
         owner: &Address,
     ) -> Result<token::Amount>;
+
+    /// Read the total supply of a given token.
+    fn read_total_supply(storage: &S, token: &Address)
+        -> Result<token::Amount>;
 }
 
 /// Abstract token storage write interface
@@ -318,6 +318,63 @@ impl TransparentTransfersRef<'_> {
     }
 }
 
+/// Arguments to approve an allowance for a spender to transfer an owner's
+/// tokens
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct Approve {
+    /// Owner of the tokens
+    pub owner: Address,
+    /// Spender allowed to transfer the tokens
+    pub spender: Address,
+    /// Token of the allowance
+    pub token: Address,
+    /// The amount that the spender can transfer. Zero revokes the allowance.
+    pub amount: Amount,
+    /// The maximum amount that the spender can transfer within an epoch
+    pub epoch_cap: Option<Amount>,
+}
+
+/// Arguments to transfer an owner's tokens by spending an allowance
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct TransferFrom {
+    /// Spender of the allowance
+    pub spender: Address,
+    /// Owner of the tokens
+    pub owner: Address,
+    /// Target of the transfer
+    pub target: Address,
+    /// Token to transfer
+    pub token: Address,
+    /// The amount to transfer
+    pub amount: Amount,
+}
+
 #[cfg(all(any(test, feature = "testing"), feature = "masp"))]
 /// Testing helpers and strategies for tokens
 pub mod testing {
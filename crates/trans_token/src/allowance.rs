@@ -0,0 +1,129 @@
+//! Allowances that let a spender transfer tokens on behalf of their owner
+
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::chain::Epoch;
+use namada_core::token::Amount;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+
+/// An allowance given by an owner to a spender to transfer some of its tokens
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct Allowance {
+    /// The remaining amount that the spender can transfer
+    pub amount: Amount,
+    /// The maximum amount that the spender can transfer within an epoch
+    pub epoch_cap: Option<Amount>,
+    /// The last epoch in which the spender transferred from the allowance
+    pub last_epoch: Epoch,
+    /// The amount transferred in the `last_epoch`
+    pub spent_in_last_epoch: Amount,
+}
+
+impl Allowance {
+    /// Create a new allowance with the given amount and epoch cap
+    pub fn new(amount: Amount, epoch_cap: Option<Amount>) -> Self {
+        Self {
+            amount,
+            epoch_cap,
+            ..Self::default()
+        }
+    }
+
+    /// Replace the amount and epoch cap of the allowance. The amount already
+    /// spent in the last epoch still counts towards the new cap.
+    pub fn reapprove(&self, amount: Amount, epoch_cap: Option<Amount>) -> Self {
+        Self {
+            amount,
+            epoch_cap,
+            ..*self
+        }
+    }
+
+    /// The amount spent from the allowance within the given epoch
+    pub fn spent_at(&self, epoch: Epoch) -> Amount {
+        if self.last_epoch == epoch {
+            self.spent_in_last_epoch
+        } else {
+            Amount::zero()
+        }
+    }
+
+    /// The amount that the spender can still transfer within the given epoch
+    pub fn available_at(&self, epoch: Epoch) -> Amount {
+        match self.epoch_cap {
+            Some(cap) => std::cmp::min(
+                self.amount,
+                cap.checked_sub(self.spent_at(epoch)).unwrap_or_default(),
+            ),
+            None => self.amount,
+        }
+    }
+
+    /// Spend the given amount from the allowance within the given epoch.
+    /// Returns `None` if the amount is not available.
+    pub fn spend(&self, amount: Amount, epoch: Epoch) -> Option<Self> {
+        if amount > self.available_at(epoch) {
+            return None;
+        }
+        Some(Self {
+            amount: self.amount.checked_sub(amount)?,
+            epoch_cap: self.epoch_cap,
+            last_epoch: epoch,
+            spent_in_last_epoch: self.spent_at(epoch).checked_add(amount)?,
+        })
+    }
+
+    /// If `post` is the result of spending from this allowance within the
+    /// given epoch, returns the spent amount.
+    pub fn spent_to(&self, post: &Self, epoch: Epoch) -> Option<Amount> {
+        let spent = self
+            .amount
+            .checked_sub(post.amount)
+            .filter(|spent| !spent.is_zero())?;
+        (self.spend(spent, epoch).as_ref() == Some(post)).then_some(spent)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_allowance_epoch_cap() {
+        let allowance = Allowance::new(
+            Amount::native_whole(100),
+            Some(Amount::native_whole(30)),
+        );
+        let epoch = Epoch(5);
+
+        let spent = allowance.spend(Amount::native_whole(20), epoch).unwrap();
+        assert_eq!(spent.amount, Amount::native_whole(80));
+        assert_eq!(spent.available_at(epoch), Amount::native_whole(10));
+        assert_eq!(
+            allowance.spent_to(&spent, epoch),
+            Some(Amount::native_whole(20))
+        );
+        // The cap is reached for this epoch
+        assert!(spent.spend(Amount::native_whole(11), epoch).is_none());
+        // The cap resets in the next epoch
+        assert_eq!(spent.available_at(epoch.next()), Amount::native_whole(30));
+        // Changes that don't record the spent amount are not spends
+        let unrecorded = Allowance {
+            amount: Amount::native_whole(80),
+            ..allowance
+        };
+        assert_eq!(allowance.spent_to(&unrecorded, epoch), None);
+    }
+}
@@ -17,6 +17,7 @@
     clippy::print_stderr
 )]
 
+mod allowance;
 pub mod event;
 mod storage;
 pub mod storage_key;
@@ -26,6 +27,7 @@ pub mod vp;
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
 
+pub use allowance::*;
 use event::{TokenEvent, TokenOperation};
 use namada_core::address::Address;
 use namada_core::token;
@@ -98,6 +100,13 @@ where
     ) -> Result<token::Amount> {
         storage::read_balance(storage, token, owner)
     }
+
+    fn read_total_supply(
+        storage: &S,
+        token: &Address,
+    ) -> Result<token::Amount> {
+        storage::read_total_supply(storage, token)
+    }
 }
 
 impl<S> Write<S> for Store<S>
@@ -1,10 +1,11 @@
 use namada_core::address::{Address, InternalAddress};
+use namada_core::chain::Epoch;
 use namada_core::hints;
 pub use namada_core::storage::Key;
 use namada_core::token::{self, Amount, AmountError, DenominatedAmount};
 
 use crate::storage_key::*;
-use crate::{Error, Result, ResultExt, StorageRead, StorageWrite};
+use crate::{Allowance, Error, Result, ResultExt, StorageRead, StorageWrite};
 
 /// Initialize parameters for the token in storage during the genesis block.
 pub fn write_params<S>(storage: &mut S, address: &Address) -> Result<()>
@@ -185,6 +186,11 @@ where
         Address::Internal(InternalAddress::IbcToken(_)) => {
             return Ok(Some(0u8.into()));
         }
+        Address::Internal(InternalAddress::LiquidStakingToken(_)) => {
+            // NB: liquid staking tokens are denominated like the native
+            // token they are backed by
+            return Ok(Some(token::NATIVE_MAX_DECIMAL_PLACES.into()));
+        }
         token => (denom_key(token), false),
     };
     storage.read(&key).map(|opt_denom| {
@@ -261,6 +267,71 @@ where
     }
 }
 
+/// Read the allowance given by an owner to a spender.
+pub fn read_allowance<S>(
+    storage: &S,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> Result<Option<Allowance>>
+where
+    S: StorageRead,
+{
+    storage.read(&allowance_key(token, owner, spender))
+}
+
+/// Set the amount and epoch cap of the allowance given by an owner to a
+/// spender. A zero amount revokes the allowance.
+pub fn approve<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+    amount: token::Amount,
+    epoch_cap: Option<token::Amount>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = allowance_key(token, owner, spender);
+    if amount.is_zero() {
+        return storage.delete(&key);
+    }
+    let allowance = match read_allowance(storage, token, owner, spender)? {
+        Some(allowance) => allowance.reapprove(amount, epoch_cap),
+        None => Allowance::new(amount, epoch_cap),
+    };
+    storage.write(&key, allowance)
+}
+
+/// Spend an amount from the allowance given by an owner to a spender within
+/// the given epoch.
+pub fn spend_allowance<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+    amount: token::Amount,
+    epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let allowance = read_allowance(storage, token, owner, spender)?
+        .ok_or_else(|| {
+            Error::new_alloc(format!(
+                "{owner} has not given an allowance of {token} to {spender}"
+            ))
+        })?;
+    let allowance = allowance.spend(amount, epoch).ok_or_else(|| {
+        Error::new_alloc(format!(
+            "The allowance of {token} given by {owner} to {spender} is \
+             insufficient"
+        ))
+    })?;
+    storage.write(&allowance_key(token, owner, spender), allowance)
+}
+
 /// Mint `amount` of `token` as `minter` to `dest`.
 pub fn mint_tokens<S>(
     storage: &mut S,
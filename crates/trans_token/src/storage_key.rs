@@ -13,6 +13,8 @@ pub const MINTER_STORAGE_KEY: &str = "minter";
 pub const MINTED_STORAGE_KEY: &str = "minted";
 /// Key segment for token parameters
 pub const PARAMETERS_STORAGE_KEY: &str = "parameters";
+/// Key segment for allowances
+pub const ALLOWANCE_STORAGE_KEY: &str = "allowance";
 
 /// Gets the key for the given token address, error with the given
 /// message to expect if the key is not in the address
@@ -55,6 +57,30 @@ pub fn parameter_prefix(token_addr: &Address) -> storage::Key {
     .expect("Cannot obtain a storage key")
 }
 
+/// Obtain a storage key prefix for all the allowances of a token.
+pub fn allowance_prefix(token_addr: &Address) -> storage::Key {
+    storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    )
+    .push(&token_addr.to_db_key())
+    .expect("Cannot obtain a storage key")
+    .push(&ALLOWANCE_STORAGE_KEY.to_owned())
+    .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key for the allowance given by an owner to a spender.
+pub fn allowance_key(
+    token_addr: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> storage::Key {
+    allowance_prefix(token_addr)
+        .push(&owner.to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&spender.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Obtain a storage key for the multitoken minter.
 pub fn minter_key(token_addr: &Address) -> storage::Key {
     storage::Key::from(
@@ -192,3 +218,22 @@ pub fn is_any_minted_balance_key(key: &storage::Key) -> Option<&Address> {
         _ => None,
     }
 }
+
+/// Check if the given storage key is an allowance key for an unspecified
+/// token. If it is, return the token, owner and spender addresses.
+pub fn is_any_allowance_key(key: &storage::Key) -> Option<[&Address; 3]> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(allowance),
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::AddressSeg(spender),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && allowance == ALLOWANCE_STORAGE_KEY =>
+        {
+            Some([token, owner, spender])
+        }
+        _ => None,
+    }
+}
@@ -229,6 +229,46 @@ where
     Ok(())
 }
 
+/// Set the allowance given by the owner to the spender and insert the owner
+/// as a verifier.
+pub fn approve<ENV>(
+    env: &mut ENV,
+    owner: &Address,
+    spender: &Address,
+    token: &Address,
+    amount: Amount,
+    epoch_cap: Option<Amount>,
+) -> Result<()>
+where
+    ENV: TxEnv,
+{
+    // The allowance must be authorized by the owner
+    env.insert_verifier(owner)?;
+    crate::approve(env, token, owner, spender, amount, epoch_cap)
+}
+
+/// Transfer transparent token from the owner to the target, spending the
+/// allowance given by the owner to the spender. Inserts the verifiers
+/// expected by the VP and emits an event.
+pub fn transfer_from<ENV>(
+    env: &mut ENV,
+    spender: &Address,
+    owner: &Address,
+    target: &Address,
+    token: &Address,
+    amount: Amount,
+    event_desc: Cow<'static, str>,
+) -> Result<()>
+where
+    ENV: TxEnv + EmitEvents,
+{
+    // The spending must be authorized by the spender
+    env.insert_verifier(spender)?;
+    let epoch = env.get_block_epoch()?;
+    crate::spend_allowance(env, token, owner, spender, amount, epoch)?;
+    transfer(env, owner, target, token, amount, event_desc)
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::BTreeMap;
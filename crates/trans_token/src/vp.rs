@@ -16,10 +16,10 @@ use namada_tx::BatchedTxRef;
 use namada_vp_env::{Error, Result, VpEnv};
 
 use crate::storage_key::{
-    is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
-    is_any_token_parameter_key, minter_key,
+    is_any_allowance_key, is_any_minted_balance_key, is_any_minter_key,
+    is_any_token_balance_key, is_any_token_parameter_key, minter_key,
 };
-use crate::StorageRead;
+use crate::{Allowance, StorageRead};
 
 /// The owner of some balance change.
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -133,6 +133,7 @@ where
                                 "Native token deposit isn't allowed",
                             ));
                         }
+                        Self::is_vesting_respected(ctx, token, owner, post)?;
                         let diff = pre
                             .checked_sub(post)
                             .expect("Underflow shouldn't happen here");
@@ -177,6 +178,12 @@ where
                 Self::is_valid_minter(ctx, token, verifiers)?;
             } else if let Some(token) = is_any_minter_key(key) {
                 Self::is_valid_minter(ctx, token, verifiers)?;
+            } else if let Some([_token, owner, spender]) =
+                is_any_allowance_key(key)
+            {
+                Self::is_valid_allowance_change(
+                    ctx, key, owner, spender, verifiers,
+                )?;
             } else if is_any_token_parameter_key(key).is_some() {
                 return Self::is_valid_parameter(ctx, tx_data);
             } else if key.segments.first()
@@ -240,6 +247,56 @@ where
         })
     }
 
+    /// Check that a debited balance is not lower than the amount still locked
+    /// by the vesting schedule of its owner
+    pub fn is_vesting_respected(
+        ctx: &'ctx CTX,
+        token: &Address,
+        owner: &Address,
+        post: Amount,
+    ) -> Result<()> {
+        let epoch = ctx.get_block_epoch()?;
+        let locked =
+            namada_account::locked_amount(&ctx.pre(), owner, token, epoch)?;
+        (post >= locked).ok_or_else(|| {
+            Error::new_alloc(format!(
+                "The balance of {owner} cannot go below the amount of {token} \
+                 locked by its vesting schedule"
+            ))
+        })
+    }
+
+    /// Check that an allowance is either spent within its limits by its
+    /// spender or otherwise changed by its owner
+    pub fn is_valid_allowance_change(
+        ctx: &'ctx CTX,
+        key: &Key,
+        owner: &Address,
+        spender: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        let pre: Option<Allowance> = ctx.read_pre(key)?;
+        let post: Option<Allowance> = ctx.read_post(key)?;
+        let epoch = ctx.get_block_epoch()?;
+        let is_spend = pre
+            .zip(post)
+            .and_then(|(pre, post)| pre.spent_to(&post, epoch))
+            .is_some();
+        if is_spend {
+            verifiers.contains(spender).ok_or_else(|| {
+                Error::new_alloc(format!(
+                    "The vp of the spender {spender} has not been triggered"
+                ))
+            })
+        } else {
+            verifiers.contains(owner).ok_or_else(|| {
+                Error::new_alloc(format!(
+                    "The vp of the owner {owner} has not been triggered"
+                ))
+            })
+        }
+    }
+
     /// Return the minter if the minter is valid and the minter VP exists
     pub fn is_valid_minter(
         ctx: &'ctx CTX,
@@ -264,6 +321,21 @@ where
                     )),
                 }
             }
+            Address::Internal(InternalAddress::LiquidStakingToken(_)) => {
+                // Check if the minter is set
+                let minter_key = minter_key(token);
+                match ctx.read_post::<Address>(&minter_key)? {
+                    Some(minter) if minter == POS => {
+                        verifiers.contains(&minter).ok_or_else(|| {
+                            Error::new_const("The PoS VP was not triggered")
+                        })
+                    }
+                    _ => Err(Error::new_const(
+                        "Only the PoS account is able to mint liquid staking \
+                         tokens",
+                    )),
+                }
+            }
             _ => Err(Error::new_alloc(format!(
                 "Attempted to mint non-IBC token {token}"
             ))),
@@ -350,9 +422,11 @@ mod tests {
 
     use assert_matches::assert_matches;
     use namada_core::address::testing::{
-        established_address_1, established_address_2, nam,
+        established_address_1, established_address_2, established_address_3,
+        nam,
     };
     use namada_core::borsh::BorshSerializeExt;
+    use namada_core::chain::Epoch;
     use namada_core::key::testing::keypair_1;
     use namada_gas::{TxGasMeter, VpGasMeter};
     use namada_ibc::trace::ibc_token;
@@ -369,7 +443,7 @@ mod tests {
     use namada_vp::native_vp::{self, CtxPreStorageRead};
 
     use super::*;
-    use crate::storage_key::{balance_key, minted_balance_key};
+    use crate::storage_key::{allowance_key, balance_key, minted_balance_key};
 
     const ADDRESS: Address = Address::Internal(InternalAddress::Multitoken);
 
@@ -524,6 +598,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transfer_of_vested_balance() {
+        let mut state = init_state();
+        let src = established_address_1();
+        let dest = established_address_2();
+
+        // lock 95 of the source's balance of 100
+        namada_account::init_vesting_schedules(
+            &mut state,
+            &src,
+            &[namada_account::VestingSchedule {
+                token: nam(),
+                amount: Amount::native_whole(95),
+                kind: namada_account::VestingKind::Cliff { unlock: Epoch(100) },
+            }],
+        )
+        .unwrap();
+        state.commit_tx_batch();
+
+        // transfer 10
+        let keys_changed = transfer(&mut state, &src, &dest);
+
+        let tx_index = TxIndex::default();
+        let BatchedTx { tx, cmt } = dummy_tx(&state);
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new(u64::MAX),
+        ));
+        let (vp_vp_cache, _vp_cache_dir) = vp_cache();
+        let mut verifiers = BTreeSet::new();
+        verifiers.insert(src);
+        verifiers.insert(dest);
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &state,
+            &tx,
+            &cmt,
+            &tx_index,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_vp_cache,
+        );
+
+        assert_matches!(
+            MultitokenVp::validate_tx(
+                &ctx,
+                &tx.batch_ref_tx(&cmt),
+                &keys_changed,
+                &verifiers
+            ),
+            Err(_)
+        );
+    }
+
+    #[test]
+    fn test_transfer_from_allowance() {
+        let mut state = init_state();
+        let src = established_address_1();
+        let dest = established_address_2();
+        let spender = established_address_3();
+
+        // the source allows the spender to transfer 50
+        let allowance_key = allowance_key(&nam(), &src, &spender);
+        let allowance = Allowance::new(Amount::native_whole(50), None);
+        state
+            .db_write(&allowance_key, allowance.serialize_to_vec())
+            .expect("write failed");
+
+        // the spender transfers 10
+        let mut keys_changed = transfer(&mut state, &src, &dest);
+        let epoch = state.in_mem().block.epoch;
+        let allowance =
+            allowance.spend(Amount::native_whole(10), epoch).unwrap();
+        let _ = state
+            .write_log_mut()
+            .write(&allowance_key, allowance.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(allowance_key);
+
+        let tx_index = TxIndex::default();
+        let BatchedTx { tx, cmt } = dummy_tx(&state);
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new(u64::MAX),
+        ));
+        let (vp_vp_cache, _vp_cache_dir) = vp_cache();
+        let mut verifiers = BTreeSet::new();
+        verifiers.insert(src);
+        verifiers.insert(dest);
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &state,
+            &tx,
+            &cmt,
+            &tx_index,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_vp_cache.clone(),
+        );
+
+        // the spender must verify the spending
+        assert_matches!(
+            MultitokenVp::validate_tx(
+                &ctx,
+                &tx.batch_ref_tx(&cmt),
+                &keys_changed,
+                &verifiers
+            ),
+            Err(_)
+        );
+
+        verifiers.insert(spender);
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &state,
+            &tx,
+            &cmt,
+            &tx_index,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_vp_cache,
+        );
+        assert!(
+            MultitokenVp::validate_tx(
+                &ctx,
+                &tx.batch_ref_tx(&cmt),
+                &keys_changed,
+                &verifiers
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn test_valid_mint() {
         let mut state = init_state();
@@ -15,7 +15,7 @@ use namada_core::storage::KeySeg;
 use namada_core::{address, storage};
 
 pub use crate::data::pos::{
-    Bond, ClaimRewards, Redelegation, Unbond, Withdraw,
+    AutoCompoundChange, Bond, ClaimRewards, Redelegation, Unbond, Withdraw,
 };
 
 /// Actions applied from txs.
@@ -42,12 +42,19 @@ pub enum PosAction {
     Unjail(Address),
     Bond(Bond),
     Unbond(Unbond),
+    InstantUnbond(Unbond),
     Withdraw(Withdraw),
     Redelegation(Redelegation),
     ClaimRewards(ClaimRewards),
+    AutoCompoundChange(AutoCompoundChange),
+    RewardRecipientChange(Address),
     CommissionChange(Address),
+    CommissionScheduleChange(Address),
     MetadataChange(Address),
     ConsensusKeyChange(Address),
+    LiquidStakingChange(Address),
+    MaxDelegationChange(Address),
+    ValidatorKeysChange(Address),
 }
 
 /// Gov tx actions.
@@ -1,9 +1,13 @@
 //! Types used for PoS system transactions
 
+use std::collections::BTreeMap;
+
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::chain::Epoch;
 use namada_core::dec::Dec;
 use namada_core::key::{common, secp256k1};
+use namada_core::masp::PaymentAddress;
 use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -130,6 +134,73 @@ pub struct ClaimRewards {
     pub source: Option<Address>,
 }
 
+/// An opt-in or opt-out of the automatic compounding of a bond's rewards.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct AutoCompoundChange {
+    /// Validator address
+    pub validator: Address,
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
+    pub source: Option<Address>,
+    /// Whether the rewards of the bond are compounded automatically
+    pub enabled: bool,
+}
+
+/// The recipient of the redirected rewards of a bond source.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub enum RewardRecipient {
+    /// The rewards are paid out to a transparent address
+    Transparent(Address),
+    /// The rewards are paid out to the source and shielded to a payment
+    /// address by the client that claims them
+    Shielded(PaymentAddress),
+}
+
+/// A redirection of the rewards of all the bonds of a source to a recipient.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct RewardRecipientChange {
+    /// Source address of the bonds (a delegator or a self-bonded validator)
+    pub source: Address,
+    /// The recipient of the rewards, if they're redirected away from the
+    /// source
+    pub recipient: Option<RewardRecipient>,
+}
+
 /// A redelegation of bonded tokens from one validator to another.
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
@@ -178,6 +249,35 @@ pub struct CommissionChange {
     pub new_rate: Dec,
 }
 
+/// A change to the validator's commission schedule.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct CommissionScheduleChange {
+    /// Validator address
+    pub validator: Address,
+    /// Future-dated commission rate changes, keyed by the epoch from which
+    /// they take effect
+    pub changes: BTreeMap<Epoch, Dec>,
+    /// Commission tiers by delegation size, keyed by the minimum delegated
+    /// amount
+    pub tiers: BTreeMap<token::Amount, Dec>,
+    /// The epoch until which the validator commits not to raise its
+    /// commission rate
+    pub no_raise_until: Option<Epoch>,
+}
+
 /// A change to the validator metadata.
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
@@ -234,6 +334,77 @@ pub struct ConsensusKeyChange {
     pub consensus_key: common::PublicKey,
 }
 
+/// A change to the validator's liquid staking.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct LiquidStakingChange {
+    /// Validator address
+    pub validator: Address,
+    /// Whether new delegations to the validator are liquid staked
+    pub enabled: bool,
+}
+
+/// A change to the maximum amount that may be delegated to a validator.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct MaxDelegationChange {
+    /// Validator address
+    pub validator: Address,
+    /// The maximum amount that may be delegated to the validator, if any
+    pub max_delegation: Option<token::Amount>,
+}
+
+/// A rotation of the validator's protocol and Ethereum bridge keys. The keys
+/// that are not given are left unchanged.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct ValidatorKeysChange {
+    /// Validator address
+    pub validator: Address,
+    /// The new protocol key
+    pub protocol_key: Option<common::PublicKey>,
+    /// The new Ethereum bridge cold key
+    pub eth_cold_key: Option<secp256k1::PublicKey>,
+    /// The new Ethereum bridge hot key
+    pub eth_hot_key: Option<secp256k1::PublicKey>,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for proof-of-stake
 pub mod tests {
@@ -324,6 +495,23 @@ pub mod tests {
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary validator keys change
+        pub fn arb_validator_keys_change()(
+            validator in arb_non_internal_address(),
+            protocol_key in option::of(arb_common_pk()),
+            eth_cold_key in option::of(arb_pk::<secp256k1::SigScheme>()),
+            eth_hot_key in option::of(arb_pk::<secp256k1::SigScheme>()),
+        ) -> ValidatorKeysChange {
+            ValidatorKeysChange {
+                validator,
+                protocol_key,
+                eth_cold_key,
+                eth_hot_key,
+            }
+        }
+    }
+
     prop_compose! {
         /// Generate a validator initialization
         pub fn arb_become_validator()(
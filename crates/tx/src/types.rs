@@ -1484,7 +1484,9 @@ impl Tx {
                         witnesses.push(signatures);
                     }
                     // Short-circuit these checks if the threshold is exceeded
-                    if verified_pks.len() >= threshold.into() {
+                    if public_keys_index_map.total_weight(&verified_pks)
+                        >= threshold.into()
+                    {
                         return Ok(witnesses);
                     }
                 }
@@ -1492,7 +1494,7 @@ impl Tx {
         }
         Err(VerifySigError::InvalidSectionSignature(format!(
             "signature threshold not met: ({} < {})",
-            verified_pks.len(),
+            public_keys_index_map.total_weight(&verified_pks),
             threshold
         )))
     }
@@ -16,5 +16,10 @@ pub fn init_account(
         owner,
         &data.public_keys,
         data.threshold,
-    )
+    )?;
+    namada_account::init_vesting_schedules(ctx, owner, &data.vesting)?;
+    if let Some(policy) = &data.policy {
+        namada_account::write_account_policy(ctx, owner, policy)?;
+    }
+    Ok(())
 }
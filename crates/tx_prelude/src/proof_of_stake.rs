@@ -2,21 +2,31 @@
 
 use namada_core::dec::Dec;
 use namada_core::key;
+use namada_proof_of_stake::commission::change_commission_schedule;
+use namada_proof_of_stake::instant_unbond::instant_unbond_tokens;
+use namada_proof_of_stake::liquid_staking::change_liquid_staking;
 pub use namada_proof_of_stake::parameters::PosParams;
 pub use namada_proof_of_stake::queries::find_delegation_validators;
+use namada_proof_of_stake::stake_limits::change_max_delegation;
 use namada_proof_of_stake::storage::read_pos_params;
-use namada_proof_of_stake::types::{ResultSlashing, ValidatorMetaData};
+use namada_proof_of_stake::types::{
+    CommissionSchedule, InstantUnbondQuote, ResultSlashing, ValidatorMetaData,
+};
 use namada_proof_of_stake::{
-    become_validator, bond_tokens, change_consensus_key,
-    change_validator_commission_rate, change_validator_metadata,
-    claim_reward_tokens, deactivate_validator, reactivate_validator,
-    redelegate_tokens, unbond_tokens, unjail_validator, withdraw_tokens,
+    become_validator, bond_tokens, change_auto_compound, change_consensus_key,
+    change_reward_recipient, change_validator_commission_rate,
+    change_validator_keys, change_validator_metadata, claim_reward_tokens,
+    deactivate_validator, reactivate_validator, redelegate_tokens,
+    unbond_tokens, unjail_validator, withdraw_tokens,
 };
 pub use namada_proof_of_stake::{parameters, storage, storage_key, types};
 use namada_tx::action::{
-    Action, ClaimRewards, PosAction, Redelegation, Unbond, Withdraw, Write,
+    Action, AutoCompoundChange, ClaimRewards, PosAction, Redelegation, Unbond,
+    Withdraw, Write,
+};
+use namada_tx::data::pos::{
+    BecomeValidator, Bond, RewardRecipient, ValidatorKeysChange,
 };
-use namada_tx::data::pos::{BecomeValidator, Bond};
 
 use super::*;
 use crate::token;
@@ -72,7 +82,7 @@ impl Ctx {
         })))?;
 
         let current_epoch = self.get_block_epoch()?;
-        unbond_tokens::<_, governance::Store<_>>(
+        unbond_tokens::<_, governance::Store<_>, token::Store<_>>(
             self,
             source,
             validator,
@@ -82,6 +92,36 @@ impl Ctx {
         )
     }
 
+    /// Instantly unbond self-bonded tokens from a validator when `source` is
+    /// `None` or equal to the `validator` address, or delegated tokens from
+    /// the `source` to the `validator`. The unbonded tokens are released
+    /// right away, less the instant unbond penalty.
+    pub fn instant_unbond_tokens(
+        &mut self,
+        source: Option<&Address>,
+        validator: &Address,
+        amount: token::Amount,
+    ) -> Result<InstantUnbondQuote> {
+        // The tx must be authorized by the source address
+        let verifier = source.as_ref().unwrap_or(&validator);
+        self.insert_verifier(verifier)?;
+
+        self.push_action(Action::Pos(PosAction::InstantUnbond(Unbond {
+            validator: validator.clone(),
+            amount,
+            source: source.cloned(),
+        })))?;
+
+        let current_epoch = self.get_block_epoch()?;
+        instant_unbond_tokens::<_, governance::Store<_>, token::Store<_>>(
+            self,
+            source,
+            validator,
+            amount,
+            current_epoch,
+        )
+    }
+
     /// Withdraw unbonded tokens from a self-bond to a validator when
     /// `source` is `None` or equal to the `validator` address, or withdraw
     /// unbonded tokens delegated to the `validator` to the `source`.
@@ -130,6 +170,70 @@ impl Ctx {
         )
     }
 
+    /// Rotate the protocol and Ethereum bridge keys of a validator.
+    pub fn change_validator_keys(
+        &mut self,
+        ValidatorKeysChange {
+            validator,
+            protocol_key,
+            eth_cold_key,
+            eth_hot_key,
+        }: ValidatorKeysChange,
+    ) -> TxResult {
+        let eth_cold_key = eth_cold_key.map(key::common::PublicKey::Secp256k1);
+        let eth_hot_key = eth_hot_key.map(key::common::PublicKey::Secp256k1);
+
+        // The tx must be authorized by the source address
+        self.insert_verifier(&validator)?;
+
+        self.push_action(Action::Pos(PosAction::ValidatorKeysChange(
+            validator.clone(),
+        )))?;
+
+        let current_epoch = self.get_block_epoch()?;
+        change_validator_keys::<_, governance::Store<_>>(
+            self,
+            &validator,
+            protocol_key.as_ref(),
+            eth_cold_key.as_ref(),
+            eth_hot_key.as_ref(),
+            current_epoch,
+        )
+    }
+
+    /// Enable or disable liquid staking of the new delegations to a
+    /// validator.
+    pub fn change_validator_liquid_staking(
+        &mut self,
+        validator: &Address,
+        enabled: bool,
+    ) -> TxResult {
+        // The tx must be authorized by the source address
+        self.insert_verifier(validator)?;
+
+        self.push_action(Action::Pos(PosAction::LiquidStakingChange(
+            validator.clone(),
+        )))?;
+
+        change_liquid_staking::<_, token::Store<_>>(self, validator, enabled)
+    }
+
+    /// Set or remove the maximum amount that may be delegated to a validator.
+    pub fn change_validator_max_delegation(
+        &mut self,
+        validator: &Address,
+        max_delegation: Option<token::Amount>,
+    ) -> TxResult {
+        // The tx must be authorized by the source address
+        self.insert_verifier(validator)?;
+
+        self.push_action(Action::Pos(PosAction::MaxDelegationChange(
+            validator.clone(),
+        )))?;
+
+        change_max_delegation(self, validator, max_delegation)
+    }
+
     /// Change validator commission rate.
     pub fn change_validator_commission_rate(
         &mut self,
@@ -152,6 +256,28 @@ impl Ctx {
         )
     }
 
+    /// Publish a validator's commission schedule, replacing its current one.
+    pub fn change_validator_commission_schedule(
+        &mut self,
+        validator: &Address,
+        schedule: CommissionSchedule,
+    ) -> TxResult {
+        // The tx must be authorized by the source address
+        self.insert_verifier(validator)?;
+
+        self.push_action(Action::Pos(PosAction::CommissionScheduleChange(
+            validator.clone(),
+        )))?;
+
+        let current_epoch = self.get_block_epoch()?;
+        change_commission_schedule::<_, governance::Store<_>>(
+            self,
+            validator,
+            schedule,
+            current_epoch,
+        )
+    }
+
     /// Unjail a jailed validator and re-enter the validator sets.
     pub fn unjail_validator(&mut self, validator: &Address) -> TxResult {
         // The tx must be authorized by the source address
@@ -220,6 +346,46 @@ impl Ctx {
         )
     }
 
+    /// Opt in or out of the automatic compounding of a bond's rewards at
+    /// every new epoch
+    pub fn change_auto_compound(
+        &mut self,
+        source: Option<&Address>,
+        validator: &Address,
+        enabled: bool,
+    ) -> TxResult {
+        // The tx must be authorized by the source address
+        let verifier = source.as_ref().unwrap_or(&validator);
+        self.insert_verifier(verifier)?;
+
+        self.push_action(Action::Pos(PosAction::AutoCompoundChange(
+            AutoCompoundChange {
+                validator: validator.clone(),
+                source: source.cloned(),
+                enabled,
+            },
+        )))?;
+
+        change_auto_compound(self, source, validator, enabled)
+    }
+
+    /// Redirect the rewards of all the bonds of the `source` to the
+    /// `recipient`, or back to the `source` when `None`
+    pub fn change_reward_recipient(
+        &mut self,
+        source: &Address,
+        recipient: Option<&RewardRecipient>,
+    ) -> TxResult {
+        // The tx must be authorized by the source address
+        self.insert_verifier(source)?;
+
+        self.push_action(Action::Pos(PosAction::RewardRecipientChange(
+            source.clone(),
+        )))?;
+
+        change_reward_recipient(self, source, recipient)
+    }
+
     /// Attempt to initialize a validator account. On success, returns the
     /// initialized validator account's address.
     pub fn become_validator(
@@ -6,7 +6,8 @@ pub use namada_token::testing;
 pub use namada_token::tx::apply_shielded_transfer;
 use namada_token::TransparentTransfersRef;
 pub use namada_token::{
-    storage_key, utils, Amount, DenominatedAmount, Store, Transfer,
+    storage_key, utils, Amount, Approve, DenominatedAmount, Store, Transfer,
+    TransferFrom,
 };
 use namada_tx::BatchedTx;
 use namada_tx_env::Address;
@@ -27,6 +28,34 @@ pub fn transfer(
     namada_token::tx::transfer(ctx, src, dest, token, amount, EVENT_DESC.into())
 }
 
+/// Set the allowance given by the owner to the spender and insert the owner
+/// as a verifier.
+pub fn approve(ctx: &mut Ctx, data: &Approve) -> TxResult {
+    namada_token::tx::approve(
+        ctx,
+        &data.owner,
+        &data.spender,
+        &data.token,
+        data.amount,
+        data.epoch_cap,
+    )
+}
+
+/// Transfer transparent token from the owner to the target by spending the
+/// allowance given to the spender. Inserts the verifiers expected by the VP
+/// and emits an event.
+pub fn transfer_from(ctx: &mut Ctx, data: &TransferFrom) -> TxResult {
+    namada_token::tx::transfer_from(
+        ctx,
+        &data.spender,
+        &data.owner,
+        &data.target,
+        &data.token,
+        data.amount,
+        EVENT_DESC.into(),
+    )
+}
+
 /// Transparent and shielded token transfers that can be used in a transaction.
 pub fn multi_transfer(
     ctx: &mut Ctx,
@@ -81,15 +81,78 @@ pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpEnvResult<bool> {
     ctx.has_key_pre(&proposal_execution_key).into_vp_error()
 }
 
-/// Verify section signatures
+/// Checks that the balance of a token debited from an account is not lower
+/// than the amount still locked by the account's vesting schedule
+pub fn verify_vested_balance(
+    ctx: &Ctx,
+    owner: &Address,
+    token: &Address,
+    post: token::Amount,
+) -> VpResult {
+    let epoch = ctx.get_block_epoch().into_vp_error()?;
+    let locked = account::locked_amount(&ctx.pre(), owner, token, epoch)
+        .into_vp_error()?;
+    if post < locked {
+        return Err(VpError::Erased(format!(
+            "The balance of {owner} cannot go below the amount of {token} \
+             locked by its vesting schedule"
+        )));
+    }
+    Ok(())
+}
+
+/// If the change of the given allowance key spends from the allowance,
+/// returns the spent amount
+pub fn allowance_spend(
+    ctx: &Ctx,
+    key: &storage::Key,
+) -> VpResult<Option<token::Amount>> {
+    let pre: Option<token::Allowance> = ctx.read_pre(key).into_vp_error()?;
+    let post: Option<token::Allowance> = ctx.read_post(key).into_vp_error()?;
+    let epoch = ctx.get_block_epoch().into_vp_error()?;
+    Ok(pre
+        .zip(post)
+        .and_then(|(pre, post)| pre.spent_to(&post, epoch)))
+}
+
+/// Sum up the amounts of a token spent in the tx from the allowances given by
+/// the owner
+pub fn spent_allowances(
+    ctx: &Ctx,
+    keys_changed: &BTreeSet<storage::Key>,
+    token: &Address,
+    owner: &Address,
+) -> VpResult<token::Amount> {
+    let mut spent = token::Amount::zero();
+    for key in keys_changed {
+        if let Some([key_token, key_owner, _spender]) =
+            token::storage_key::is_any_allowance_key(key)
+        {
+            if key_token == token && key_owner == owner {
+                if let Some(amount) = allowance_spend(ctx, key)? {
+                    spent = spent.checked_add(amount).ok_or_else(|| {
+                        VpError::Erased(
+                            "Overflowed in allowance check".to_string(),
+                        )
+                    })?;
+                }
+            }
+        }
+    }
+    Ok(spent)
+}
+
+/// Verify section signatures against the given threshold
 #[cold]
 #[inline(never)]
-fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
+fn verify_signatures(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+    threshold: u8,
+) -> VpResult {
     let public_keys_index_map =
         account::public_keys_index_map(&ctx.pre(), owner).into_vp_error()?;
-    let threshold = account::threshold(&ctx.pre(), owner)
-        .into_vp_error()?
-        .unwrap_or(1);
 
     // Serialize parameters
     let public_keys_map = public_keys_index_map.serialize_to_vec();
@@ -114,18 +177,19 @@ fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
 #[derive(Default)]
 #[repr(transparent)]
 pub struct VerifySigGadget {
-    has_validated_sig: bool,
+    verified_threshold: Option<u8>,
 }
 
 impl VerifySigGadget {
     /// Create a new [`VerifySigGadget`].
     pub const fn new() -> Self {
         Self {
-            has_validated_sig: false,
+            verified_threshold: None,
         }
     }
 
-    /// Verify a tx signature, only paying the cost of this operation once.
+    /// Verify a tx signature against the account's threshold, only paying
+    /// the cost of this operation once.
     #[inline(always)]
     pub fn verify_signatures(
         &mut self,
@@ -133,11 +197,10 @@ impl VerifySigGadget {
         tx_data: &Tx,
         owner: &Address,
     ) -> VpResult {
-        if !self.has_validated_sig {
-            verify_signatures(ctx, tx_data, owner)?;
-            self.has_validated_sig = true;
-        }
-        Ok(())
+        let threshold = account::threshold(&ctx.pre(), owner)
+            .into_vp_error()?
+            .unwrap_or(1);
+        self.verify_signatures_with_threshold(ctx, tx_data, owner, threshold)
     }
 
     /// Identical to [`Self::verify_signatures`], but execute a predicate before
@@ -156,6 +219,60 @@ impl VerifySigGadget {
         }
         Ok(())
     }
+
+    /// Verify a tx signature against the threshold of the given action on
+    /// the account.
+    #[inline(always)]
+    pub fn verify_signatures_for(
+        &mut self,
+        action: account::AccountAction,
+        ctx: &Ctx,
+        tx_data: &Tx,
+        owner: &Address,
+    ) -> VpResult {
+        let threshold = account::action_threshold(&ctx.pre(), owner, action)
+            .into_vp_error()?;
+        self.verify_signatures_with_threshold(ctx, tx_data, owner, threshold)
+    }
+
+    /// Identical to [`Self::verify_signatures_for`], but execute a predicate
+    /// before validating a sig. If the predicate returns false, we do not
+    /// check tx signatures.
+    #[inline(always)]
+    pub fn verify_signatures_for_when<F: FnOnce() -> bool>(
+        &mut self,
+        predicate: F,
+        action: account::AccountAction,
+        ctx: &Ctx,
+        tx_data: &Tx,
+        owner: &Address,
+    ) -> VpResult {
+        if predicate() {
+            self.verify_signatures_for(action, ctx, tx_data, owner)?;
+        }
+        Ok(())
+    }
+
+    /// Verify a tx signature against the given threshold. The signatures are
+    /// only verified again if the threshold is higher than the ones that were
+    /// previously met.
+    #[inline(always)]
+    pub fn verify_signatures_with_threshold(
+        &mut self,
+        ctx: &Ctx,
+        tx_data: &Tx,
+        owner: &Address,
+        threshold: u8,
+    ) -> VpResult {
+        if self
+            .verified_threshold
+            .map_or(true, |verified| verified < threshold)
+        {
+            verify_signatures(ctx, tx_data, owner, threshold)?;
+            self.verified_threshold = Some(threshold);
+        }
+        Ok(())
+    }
 }
 
 /// Format and log a string in a debug build.
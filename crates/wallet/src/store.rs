@@ -58,6 +58,43 @@ pub struct ValidatorData {
     pub address: Address,
     /// special keys for a validator
     pub keys: ValidatorKeys,
+    /// Keys that the validator rotated in, which replace the `keys` once they
+    /// become active at the pipeline epoch of their change
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_keys: Vec<ValidatorKeys>,
+}
+
+impl ValidatorData {
+    /// Add keys that the validator rotated in
+    pub fn add_pending_keys(&mut self, keys: ValidatorKeys) {
+        self.pending_keys.push(keys);
+    }
+
+    /// Switch to the latest pending keys whose public keys match the given
+    /// protocol and Ethereum hot keys that are active on chain. The pending
+    /// keys that were rotated in before them are dropped. Returns `true` if
+    /// the keys were switched.
+    pub fn activate_pending_keys(
+        &mut self,
+        protocol_pk: &common::PublicKey,
+        eth_hot_pk: &common::PublicKey,
+    ) -> bool {
+        let is_active = |keys: &ValidatorKeys| {
+            keys.protocol_keypair.ref_to() == *protocol_pk
+                && keys.eth_bridge_keypair.ref_to() == *eth_hot_pk
+        };
+        match self.pending_keys.iter().rposition(is_active) {
+            Some(index) => {
+                self.keys = self
+                    .pending_keys
+                    .drain(..=index)
+                    .next_back()
+                    .expect("The activated keys must be pending");
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 /// A Storage area for keys and addresses
@@ -271,7 +308,11 @@ impl Store {
         address: Address,
         keys: ValidatorKeys,
     ) {
-        self.validator_data = Some(ValidatorData { address, keys });
+        self.validator_data = Some(ValidatorData {
+            address,
+            keys,
+            pending_keys: Vec::new(),
+        });
     }
 
     /// Returns a reference to the validator data, if it exists.
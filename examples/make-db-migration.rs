@@ -3,6 +3,8 @@ use std::collections::BTreeMap;
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_macros::BorshDeserializer;
 use namada_sdk::address::Address;
+use namada_sdk::dec::Dec;
+use namada_sdk::governance::storage::keys::get_veto_threshold_key;
 use namada_sdk::masp_primitives::asset_type::AssetType;
 use namada_sdk::masp_primitives::merkle_tree::FrozenCommitmentTree;
 use namada_sdk::masp_primitives::sapling;
@@ -72,6 +74,23 @@ fn example() {
         .unwrap();
 }
 
+/// Set the governance veto threshold of a chain initialized before it was
+/// introduced
+#[allow(dead_code)]
+fn veto_threshold_migration() {
+    let updates = [migrations::DbUpdateType::Add {
+        key: get_veto_threshold_key(),
+        cf: DbColFam::SUBSPACE,
+        value: Dec::one_third().into(),
+        force: false,
+    }];
+    let changes = migrations::DbChanges {
+        changes: updates.into_iter().collect(),
+    };
+    std::fs::write("migrations.json", serde_json::to_string(&changes).unwrap())
+        .unwrap();
+}
+
 fn main() {
     example()
 }
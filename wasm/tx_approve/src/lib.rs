@@ -0,0 +1,13 @@
+//! A tx to approve an allowance for a spender to transfer an owner's tokens.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let approve = token::Approve::try_from_slice(&data[..])
+        .wrap_err("Failed to decode token::Approve tx data")?;
+    debug_log!("apply_tx called with approve: {:#?}", approve);
+
+    token::approve(ctx, &approve).wrap_err("Failed to approve allowance")
+}
@@ -0,0 +1,18 @@
+//! A tx for a user to opt in or out of the automatic compounding of their
+//! bond's PoS rewards at every new epoch.
+
+use namada_tx_prelude::transaction::pos::AutoCompoundChange;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let AutoCompoundChange {
+        validator,
+        source,
+        enabled,
+    } = transaction::pos::AutoCompoundChange::try_from_slice(&data[..])
+        .wrap_err("Failed to decode AutoCompoundChange value")?;
+    ctx.change_auto_compound(source.as_ref(), &validator, enabled)
+        .wrap_err("Failed to change the automatic compounding of rewards")
+}
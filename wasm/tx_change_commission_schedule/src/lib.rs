@@ -0,0 +1,24 @@
+//! A tx for a validator to publish a commission schedule for PoS rewards.
+
+use namada_tx_prelude::proof_of_stake::types::CommissionSchedule;
+use namada_tx_prelude::transaction::pos::CommissionScheduleChange;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let CommissionScheduleChange {
+        validator,
+        changes,
+        tiers,
+        no_raise_until,
+    } = transaction::pos::CommissionScheduleChange::try_from_slice(&data[..])
+        .wrap_err("Failed to decode CommissionScheduleChange value")?;
+    let schedule = CommissionSchedule {
+        changes,
+        tiers,
+        no_raise_until,
+    };
+    ctx.change_validator_commission_schedule(&validator, schedule)
+        .wrap_err("Failed to change validator's commission schedule")
+}
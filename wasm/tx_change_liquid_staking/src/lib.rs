@@ -0,0 +1,15 @@
+//! A tx for a validator to enable or disable liquid staking of the new
+//! delegations to them.
+
+use namada_tx_prelude::transaction::pos::LiquidStakingChange;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let LiquidStakingChange { validator, enabled } =
+        transaction::pos::LiquidStakingChange::try_from_slice(&data[..])
+            .wrap_err("Failed to decode LiquidStakingChange value")?;
+    ctx.change_validator_liquid_staking(&validator, enabled)
+        .wrap_err("Failed to change validator's liquid staking")
+}
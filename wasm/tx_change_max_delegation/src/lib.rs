@@ -0,0 +1,17 @@
+//! A tx for a validator to set or remove the maximum amount that may be
+//! delegated to them.
+
+use namada_tx_prelude::transaction::pos::MaxDelegationChange;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let MaxDelegationChange {
+        validator,
+        max_delegation,
+    } = transaction::pos::MaxDelegationChange::try_from_slice(&data[..])
+        .wrap_err("Failed to decode MaxDelegationChange value")?;
+    ctx.change_validator_max_delegation(&validator, max_delegation)
+        .wrap_err("Failed to change validator's max delegation")
+}
@@ -0,0 +1,15 @@
+//! A tx for a bond source to redirect its PoS rewards to a different address,
+//! or back to itself.
+
+use namada_tx_prelude::transaction::pos::RewardRecipientChange;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let RewardRecipientChange { source, recipient } =
+        transaction::pos::RewardRecipientChange::try_from_slice(&data[..])
+            .wrap_err("Failed to decode RewardRecipientChange value")?;
+    ctx.change_reward_recipient(&source, recipient.as_ref())
+        .wrap_err("Failed to change the reward recipient")
+}
@@ -0,0 +1,39 @@
+//! A tx for a validator to rotate their protocol and Ethereum bridge keys.
+
+use booleans::ResultBoolExt;
+use namada_tx_prelude::transaction::pos::ValidatorKeysChange;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let keys_change = ValidatorKeysChange::try_from_slice(&data[..])
+        .wrap_err("Failed to decode ValidatorKeysChange value")?;
+
+    // Check that the tx has been signed with all the new keys
+    let new_pks = keys_change
+        .protocol_key
+        .clone()
+        .into_iter()
+        .chain(
+            keys_change
+                .eth_cold_key
+                .clone()
+                .map(key::common::PublicKey::Secp256k1),
+        )
+        .chain(
+            keys_change
+                .eth_hot_key
+                .clone()
+                .map(key::common::PublicKey::Secp256k1),
+        )
+        .collect();
+    verify_signatures_of_pks(&tx_data.tx, new_pks).true_or_else(|| {
+        const ERR_MSG: &str = "Keys ownership signature verification failed";
+        debug_log!("{ERR_MSG}");
+        Error::new_const(ERR_MSG)
+    })?;
+
+    ctx.change_validator_keys(keys_change)
+        .wrap_err("Failed to change validator keys")
+}
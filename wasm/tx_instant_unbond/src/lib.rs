@@ -0,0 +1,30 @@
+//! A tx for a PoS instant unbond that removes staked tokens from a self-bond
+//! or a delegation and releases them right away, less the instant unbond
+//! penalty.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let unbond = transaction::pos::Unbond::try_from_slice(&data[..])
+        .wrap_err("Failed to decode Unbond tx data")?;
+
+    let quote = ctx
+        .instant_unbond_tokens(
+            unbond.source.as_ref(),
+            &unbond.validator,
+            unbond.amount,
+        )
+        .wrap_err("Failed to instantly unbond tokens")?;
+
+    debug_log!(
+        "Instantly unbonded {} from {}, released {} for a penalty of {}",
+        unbond.amount,
+        unbond.validator,
+        quote.released,
+        quote.penalty
+    );
+
+    Ok(())
+}
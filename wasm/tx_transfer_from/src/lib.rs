@@ -0,0 +1,15 @@
+//! A tx to transfer an owner's tokens by spending the allowance it gave to a
+//! spender.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let transfer = token::TransferFrom::try_from_slice(&data[..])
+        .wrap_err("Failed to decode token::TransferFrom tx data")?;
+    debug_log!("apply_tx called with transfer from: {:#?}", transfer);
+
+    token::transfer_from(ctx, &transfer)
+        .wrap_err("Failed to transfer from allowance")
+}
@@ -55,5 +55,27 @@ fn apply_tx(ctx: &mut Ctx, batched_tx: BatchedTx) -> TxResult {
         }
     }
 
+    if let Some(policy) = &tx_data.policy {
+        account::write_account_policy(ctx, owner, policy)
+            .wrap_err("Failed to update the account's signing policy")?;
+    } else {
+        if !tx_data.public_keys.is_empty() {
+            // The weights were assigned to the previous public keys
+            account::clear_key_weights(ctx, owner).wrap_err(
+                "Failed to reset the weights of the account's keys",
+            )?;
+        }
+        // The remaining policy must stay reachable with the updated keys and
+        // threshold
+        let policy = account::account_policy(ctx, owner)
+            .wrap_err("Failed to read the account's signing policy")?;
+        if !policy.is_empty() {
+            account::validate_account_policy(ctx, owner, &policy).wrap_err(
+                "The account's signing policy is invalid for its updated keys \
+                 and threshold",
+            )?;
+        }
+    }
+
     Ok(())
 }
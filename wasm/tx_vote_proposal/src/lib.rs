@@ -1,5 +1,6 @@
 //! A tx to vote on a proposal
 
+use booleans::BoolResultUnitExt;
 use namada_tx_prelude::action::{Action, GovAction, Write};
 use namada_tx_prelude::proof_of_stake::find_delegation_validators;
 use namada_tx_prelude::*;
@@ -10,6 +11,13 @@ fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
     let tx_data = governance::VoteProposalData::try_from_slice(&data[..])
         .wrap_err("Failed to decode VoteProposalData value")?;
 
+    // The shares of a split vote must add up to the voting power of the voter
+    tx_data.vote.is_well_formed().ok_or_else(|| {
+        const ERR_MSG: &str = "Split vote percentages must add up to 100";
+        debug_log!("{ERR_MSG}");
+        Error::new_const(ERR_MSG)
+    })?;
+
     // The tx must be authorized by the source address
     ctx.insert_verifier(&tx_data.voter)?;
 
@@ -61,8 +61,13 @@ fn validate_tx(
                 | PosAction::ReactivateValidator(source)
                 | PosAction::Unjail(source)
                 | PosAction::CommissionChange(source)
+                | PosAction::CommissionScheduleChange(source)
                 | PosAction::MetadataChange(source)
                 | PosAction::ConsensusKeyChange(source)
+                | PosAction::LiquidStakingChange(source)
+                | PosAction::MaxDelegationChange(source)
+                | PosAction::ValidatorKeysChange(source)
+                | PosAction::RewardRecipientChange(source)
                 | PosAction::Redelegation(Redelegation {
                     owner: source, ..
                 }) => gadget.verify_signatures_when(
@@ -77,9 +82,16 @@ fn validate_tx(
                 | PosAction::Unbond(Unbond {
                     source, validator, ..
                 })
+                | PosAction::InstantUnbond(Unbond {
+                    source, validator, ..
+                })
                 | PosAction::Withdraw(Withdraw { source, validator })
-                | PosAction::ClaimRewards(ClaimRewards { validator, source }) =>
-                {
+                | PosAction::ClaimRewards(ClaimRewards { validator, source })
+                | PosAction::AutoCompoundChange(AutoCompoundChange {
+                    validator,
+                    source,
+                    ..
+                }) => {
                     let source = source.unwrap_or(validator);
                     gadget.verify_signatures_when(
                         || source == addr,
@@ -151,7 +163,7 @@ fn validate_tx(
                 }
                 Ok(())
             }
-            KeyType::TokenBalance { owner, .. } => {
+            KeyType::TokenBalance { token, owner } => {
                 if owner == &addr {
                     let pre: token::Amount =
                         ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
@@ -159,13 +171,22 @@ fn validate_tx(
                         ctx.read_post(key).into_vp_error()?.unwrap_or_default();
                     let change =
                         post.change().checked_sub(pre.change()).unwrap();
-                    gadget.verify_signatures_when(
-                        // NB: debit has to signed, credit doesn't
-                        || change.is_negative(),
-                        ctx,
-                        &tx,
-                        &addr,
-                    )?;
+                    if change.is_negative() {
+                        // NB: debit has to signed, credit doesn't, unless it's
+                        // covered by the allowances spent in this tx, which
+                        // are authorized by their spenders
+                        let debited = pre.checked_sub(post).unwrap_or_default();
+                        let spent =
+                            spent_allowances(ctx, &keys_changed, token, &addr)?;
+                        gadget.verify_signatures_when(
+                            || debited > spent,
+                            ctx,
+                            &tx,
+                            &addr,
+                        )?;
+                        // NB: debits must leave the vested amount untouched
+                        verify_vested_balance(ctx, &addr, token, post)?;
+                    }
                     let sign = if change.non_negative() { "" } else { "-" };
                     debug_log!("token key: {key}, change: {sign}{change:?}");
                 } else {
@@ -180,6 +201,33 @@ fn validate_tx(
                 }
                 Ok(())
             }
+            KeyType::Vesting(owner) => {
+                // Vesting schedules can only be set on account creation
+                (owner != &addr).ok_or_else(|| {
+                    VpError::Erased(format!(
+                        "The vesting schedules of {addr} cannot be modified"
+                    ))
+                })
+            }
+            KeyType::Allowance { owner, spender } => {
+                if allowance_spend(ctx, key)?.is_some() {
+                    // NB: spending an allowance has to be signed by the spender
+                    gadget.verify_signatures_when(
+                        || spender == &addr,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )
+                } else {
+                    // NB: any other change has to be signed by the owner
+                    gadget.verify_signatures_when(
+                        || owner == &addr,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )
+                }
+            }
             KeyType::TokenMinted => {
                 verifiers.contains(&address::MULTITOKEN).ok_or_else(|| {
                     VpError::Erased(
@@ -213,8 +261,15 @@ enum KeyType<'a> {
     /// Public key - written once revealed
     Pk(&'a Address),
     TokenBalance {
+        token: &'a Address,
         owner: &'a Address,
     },
+    /// Vesting schedules - only written on account creation
+    Vesting(&'a Address),
+    Allowance {
+        owner: &'a Address,
+        spender: &'a Address,
+    },
     TokenMinted,
     TokenMinter(&'a Address),
     Masp,
@@ -226,10 +281,16 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
     fn from(key: &'a storage::Key) -> KeyType<'a> {
         if let Some(address) = account::is_pks_key(key) {
             Self::Pk(address)
-        } else if let Some([_, owner]) =
+        } else if let Some([token, owner]) =
             token::storage_key::is_any_token_balance_key(key)
         {
-            Self::TokenBalance { owner }
+            Self::TokenBalance { token, owner }
+        } else if let Some(owner) = account::is_vesting_key(key) {
+            Self::Vesting(owner)
+        } else if let Some([_token, owner, spender]) =
+            token::storage_key::is_any_allowance_key(key)
+        {
+            Self::Allowance { owner, spender }
         } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
             Self::TokenMinted
         } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
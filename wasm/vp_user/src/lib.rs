@@ -9,9 +9,13 @@
 //! For validator a tx to change a validator's commission rate or metadata
 //! requires a valid signature(s) only from the validator.
 //!
+//! The account's signing policy can require a different threshold for specific
+//! actions and for debits of a token over a limit.
+//!
 //! Any other storage key changes are allowed only with a valid signature.
 
 use booleans::BoolResultUnitExt;
+use namada_vp_prelude::account::AccountAction;
 use namada_vp_prelude::tx::action::*;
 use namada_vp_prelude::*;
 
@@ -60,28 +64,84 @@ fn validate_tx(
                 | PosAction::ReactivateValidator(source)
                 | PosAction::Unjail(source)
                 | PosAction::CommissionChange(source)
+                | PosAction::CommissionScheduleChange(source)
                 | PosAction::MetadataChange(source)
                 | PosAction::ConsensusKeyChange(source)
-                | PosAction::Redelegation(Redelegation {
+                | PosAction::LiquidStakingChange(source)
+                | PosAction::MaxDelegationChange(source)
+                | PosAction::ValidatorKeysChange(source) => gadget
+                    .verify_signatures_for_when(
+                        || source == addr,
+                        AccountAction::ValidatorManagement,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )?,
+                PosAction::Redelegation(Redelegation {
                     owner: source, ..
-                }) => gadget.verify_signatures_when(
+                }) => gadget.verify_signatures_for_when(
                     || source == addr,
+                    AccountAction::Redelegate,
                     ctx,
                     &tx,
                     &addr,
                 )?,
+                // Redirecting the rewards moves them away from the account
+                PosAction::RewardRecipientChange(source) => gadget
+                    .verify_signatures_for_when(
+                        || source == addr,
+                        AccountAction::Debit,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )?,
                 PosAction::Bond(Bond {
                     source, validator, ..
-                })
-                | PosAction::Unbond(Unbond {
+                }) => {
+                    let source = source.unwrap_or(validator);
+                    gadget.verify_signatures_for_when(
+                        || source == addr,
+                        AccountAction::Bond,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )?
+                }
+                PosAction::Unbond(Unbond {
                     source, validator, ..
                 })
-                | PosAction::Withdraw(Withdraw { source, validator })
-                | PosAction::ClaimRewards(ClaimRewards { validator, source }) =>
-                {
+                | PosAction::InstantUnbond(Unbond {
+                    source, validator, ..
+                }) => {
                     let source = source.unwrap_or(validator);
-                    gadget.verify_signatures_when(
+                    gadget.verify_signatures_for_when(
+                        || source == addr,
+                        AccountAction::Unbond,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )?
+                }
+                PosAction::Withdraw(Withdraw { source, validator }) => {
+                    let source = source.unwrap_or(validator);
+                    gadget.verify_signatures_for_when(
+                        || source == addr,
+                        AccountAction::Withdraw,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )?
+                }
+                PosAction::ClaimRewards(ClaimRewards { validator, source })
+                | PosAction::AutoCompoundChange(AutoCompoundChange {
+                    validator,
+                    source,
+                    ..
+                }) => {
+                    let source = source.unwrap_or(validator);
+                    gadget.verify_signatures_for_when(
                         || source == addr,
+                        AccountAction::ClaimRewards,
                         ctx,
                         &tx,
                         &addr,
@@ -91,18 +151,31 @@ fn validate_tx(
             Action::Gov(
                 GovAction::InitProposal { author: source }
                 | GovAction::VoteProposal { voter: source, .. },
-            )
-            | Action::Pgf(
+            ) => gadget.verify_signatures_for_when(
+                || source == addr,
+                AccountAction::Governance,
+                ctx,
+                &tx,
+                &addr,
+            )?,
+            Action::Pgf(
                 PgfAction::ResignSteward(source)
                 | PgfAction::UpdateStewardCommission(source),
-            ) => gadget.verify_signatures_when(
+            ) => gadget.verify_signatures_for_when(
                 || source == addr,
+                AccountAction::Pgf,
                 ctx,
                 &tx,
                 &addr,
             )?,
             Action::Masp(MaspAction::MaspAuthorizer(source)) => gadget
-                .verify_signatures_when(|| source == addr, ctx, &tx, &addr)?,
+                .verify_signatures_for_when(
+                    || source == addr,
+                    AccountAction::Masp,
+                    ctx,
+                    &tx,
+                    &addr,
+                )?,
             Action::Masp(MaspAction::MaspSectionRef(_)) => (),
             Action::IbcShielding => (),
         }
@@ -111,7 +184,7 @@ fn validate_tx(
     keys_changed.iter().try_for_each(|key| {
         let key_type: KeyType = key.into();
         let mut validate_change = || match key_type {
-            KeyType::TokenBalance { owner, .. } => {
+            KeyType::TokenBalance { token, owner } => {
                 if owner == &addr {
                     let pre: token::Amount =
                         ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
@@ -119,13 +192,30 @@ fn validate_tx(
                         ctx.read_post(key).into_vp_error()?.unwrap_or_default();
                     let change =
                         post.change().checked_sub(pre.change()).unwrap();
-                    gadget.verify_signatures_when(
-                        // NB: debit has to signed, credit doesn't
-                        || change.is_negative(),
-                        ctx,
-                        &tx,
-                        &addr,
-                    )?;
+                    // NB: debit has to signed, credit doesn't
+                    if change.is_negative() {
+                        let debited = pre.checked_sub(post).unwrap_or_default();
+                        // NB: debits covered by the allowances spent in this
+                        // tx are authorized by their spenders
+                        let spent =
+                            spent_allowances(ctx, &keys_changed, token, &addr)?;
+                        if debited > spent {
+                            // NB: debits over the account's limit for the
+                            // token may require a higher threshold
+                            let threshold = account::debit_threshold(
+                                &ctx.pre(),
+                                &addr,
+                                token,
+                                debited,
+                            )
+                            .into_vp_error()?;
+                            gadget.verify_signatures_with_threshold(
+                                ctx, &tx, &addr, threshold,
+                            )?;
+                        }
+                        // NB: debits must leave the vested amount untouched
+                        verify_vested_balance(ctx, &addr, token, post)?;
+                    }
                     let sign = if change.non_negative() { "" } else { "-" };
                     debug_log!("token key: {key}, change: {sign}{change:?}");
                 } else {
@@ -140,6 +230,34 @@ fn validate_tx(
                 }
                 Ok(())
             }
+            KeyType::Vesting(owner) => {
+                // Vesting schedules can only be set on account creation
+                (owner != &addr).ok_or_else(|| {
+                    VpError::Erased(format!(
+                        "The vesting schedules of {addr} cannot be modified"
+                    ))
+                })
+            }
+            KeyType::Allowance { owner, spender } => {
+                if allowance_spend(ctx, key)?.is_some() {
+                    // NB: spending an allowance has to be signed by the spender
+                    gadget.verify_signatures_when(
+                        || spender == &addr,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )
+                } else {
+                    // NB: any other change has to be signed by the owner
+                    gadget.verify_signatures_for_when(
+                        || owner == &addr,
+                        AccountAction::Debit,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )
+                }
+            }
             KeyType::TokenMinted => {
                 verifiers.contains(&address::MULTITOKEN).ok_or_else(|| {
                     VpError::Erased(
@@ -158,13 +276,26 @@ fn validate_tx(
             KeyType::Vp(owner) => {
                 let vp_overwritten: bool =
                     ctx.has_key_post(key).into_vp_error()?;
-                gadget.verify_signatures_when(
+                gadget.verify_signatures_for_when(
                     || owner == &addr && vp_overwritten,
+                    AccountAction::UpdateAccount,
                     ctx,
                     &tx,
                     &addr,
                 )
             }
+            KeyType::AccountConfig(owner) => {
+                if owner == &addr {
+                    gadget.verify_signatures_for(
+                        AccountAction::UpdateAccount,
+                        ctx,
+                        &tx,
+                        &addr,
+                    )
+                } else {
+                    gadget.verify_signatures(ctx, &tx, &addr)
+                }
+            }
             KeyType::Masp | KeyType::Ibc => Ok(()),
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -180,7 +311,16 @@ fn validate_tx(
 }
 
 enum KeyType<'a> {
-    TokenBalance { owner: &'a Address },
+    TokenBalance {
+        token: &'a Address,
+        owner: &'a Address,
+    },
+    Vesting(&'a Address),
+    AccountConfig(&'a Address),
+    Allowance {
+        owner: &'a Address,
+        spender: &'a Address,
+    },
     TokenMinted,
     TokenMinter(&'a Address),
     Vp(&'a Address),
@@ -191,10 +331,21 @@ enum KeyType<'a> {
 
 impl<'a> From<&'a storage::Key> for KeyType<'a> {
     fn from(key: &'a storage::Key) -> KeyType<'a> {
-        if let Some([_, owner]) =
+        if let Some([token, owner]) =
             token::storage_key::is_any_token_balance_key(key)
         {
-            Self::TokenBalance { owner }
+            Self::TokenBalance { token, owner }
+        } else if let Some(owner) = account::is_vesting_key(key) {
+            Self::Vesting(owner)
+        } else if let Some(owner) = account::is_pks_key(key)
+            .or_else(|| account::is_threshold_key(key))
+            .or_else(|| account::is_policy_key(key))
+        {
+            Self::AccountConfig(owner)
+        } else if let Some([_token, owner, spender]) =
+            token::storage_key::is_any_allowance_key(key)
+        {
+            Self::Allowance { owner, spender }
         } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
             Self::TokenMinted
         } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
@@ -469,6 +620,251 @@ mod tests {
         );
     }
 
+    /// Test that a signed debit transfer of a balance that is still locked by a
+    /// vesting schedule is rejected.
+    #[test]
+    fn test_signed_debit_of_vested_balance_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+        // Lock half of the balance until a future epoch
+        account::init_vesting_schedules(
+            &mut tx_env.state,
+            &vp_owner,
+            &[account::VestingSchedule {
+                token: token.clone(),
+                amount: amount.checked_div_u64(2).unwrap(),
+                kind: account::VestingKind::Cliff { unlock: Epoch(100) },
+            }],
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Try to transfer the whole balance, including the locked part
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .is_err()
+        );
+    }
+
+    /// Test that a debit over the account's limit for the token is rejected
+    /// without enough signatures for the limit's threshold.
+    #[test]
+    fn test_signed_debit_over_limit_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let other_public_key = key::testing::keypair_2().ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(
+            &vp_owner,
+            vec![public_key.clone(), other_public_key.clone()],
+            1,
+        );
+        // Require both signatures to debit more than half of the balance
+        account::write_account_policy(
+            &mut tx_env.state,
+            &vp_owner,
+            &account::AccountPolicy {
+                debit_limits: [(
+                    token.clone(),
+                    account::DebitLimit {
+                        amount: amount.checked_div_u64(2).unwrap(),
+                        threshold: 2,
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Transfer the whole balance
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map =
+            AccountPublicKeysMap::from_iter(vec![public_key, other_public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .is_err()
+        );
+    }
+
+    /// Test that a signature of a single key whose weight meets the threshold
+    /// authorizes a debit.
+    #[test]
+    fn test_signed_debit_with_weighted_key_accepted() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let other_public_key = key::testing::keypair_2().ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(
+            &vp_owner,
+            vec![public_key.clone(), other_public_key.clone()],
+            2,
+        );
+        // The first key is enough to meet the threshold on its own
+        account::write_account_policy(
+            &mut tx_env.state,
+            &vp_owner,
+            &account::AccountPolicy {
+                key_weights: [(0, 2)].into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map =
+            AccountPublicKeysMap::from_iter(vec![public_key, other_public_key])
+                .with_weights([(0, 2)]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .is_ok()
+        );
+    }
+
     /// Test that a non-validator PoS action that must be authorized is rejected
     /// without a valid signature.
     #[test]